@@ -8,8 +8,11 @@ use vrrb_core::claim::Claim;
 use vrrb_core::transactions::{TransactionDigest, TransactionKind};
 
 use crate::{
-    error::BlockError, header::BlockHeader, Block, BlockHash, Certificate, ConsolidatedClaims,
-    ConsolidatedTxns,
+    error::BlockError,
+    fees::BlockFees,
+    header::BlockHeader,
+    slashing::{evidence_root, SlashingEvidence},
+    Block, BlockHash, Certificate, ConsolidatedClaims, ConsolidatedTxns,
 };
 
 pub struct MineArgs<'a> {
@@ -37,6 +40,13 @@ pub struct ConvergenceBlock {
     pub claims: ConsolidatedClaims,
     pub hash: BlockHash,
     pub certificate: Option<Certificate>,
+    /// Evidence of misbehaving quorum members to be slashed once the block
+    /// is applied
+    #[serde(default)]
+    pub slashing_evidence: Vec<SlashingEvidence>,
+    /// Totals of the transaction fees collected by the block
+    #[serde(default)]
+    pub fees: BlockFees,
 }
 
 impl ConvergenceBlock {
@@ -51,6 +61,19 @@ impl ConvergenceBlock {
         }))
     }
 
+    /// Checks that the header commits to the slashing evidence carried by
+    /// the block, so that the evidence can't be swapped without changing the
+    /// block hash
+    pub fn verify_slashing_evidence_root(&self) -> Result<(), BlockError> {
+        if self.header.slashing_evidence_root != evidence_root(&self.slashing_evidence) {
+            return Err(BlockError::Other(
+                "slashing evidence does not match the root in the header".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+
     pub fn txn_id_set(&self) -> LinkedHashSet<&TransactionDigest> {
         self.txns.iter().flat_map(|(_, set)| set).collect()
     }
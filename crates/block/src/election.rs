@@ -0,0 +1,185 @@
+use ethereum_types::U256;
+use patriecia::{KeyHash, RootHash, SparseMerkleProof};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use thiserror::Error;
+use utils::hash_data;
+use vrrb_core::claim::{Claim, Eligibility};
+use vrrb_vrf::{vrng::VRNG, vvrf::VVRF};
+
+use crate::header::BlockHeader;
+
+/// Lower bound of the range next block seeds are drawn from
+pub const NEXT_BLOCK_SEED_MIN: u64 = u32::MAX as u64;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ElectionError {
+    #[error("block header does not carry an election proof")]
+    MissingProof,
+
+    #[error("winning claim {0} is not the claim of the block's miner")]
+    ClaimMismatch(U256),
+
+    #[error("invalid VRF proof: {0}")]
+    InvalidVrfProof(String),
+
+    #[error("next block seed was not derived from the VRF output")]
+    SeedMismatch,
+
+    #[error("block seed or VRF message does not follow from the previous block")]
+    PreviousBlockMismatch,
+
+    #[error("invalid claim inclusion proof: {0}")]
+    InvalidClaimProof(String),
+
+    #[error("claim {0} is not eligible to mine")]
+    IneligibleClaim(U256),
+
+    #[error("claim {0} did not win the miner election")]
+    NotElected(U256),
+}
+
+/// Merkle proof that a claim is part of the claims trie the miner election
+/// ran against. Both fields are bincode encoded so the header stays hashable.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Hash, PartialEq, Eq)]
+pub struct ClaimInclusionProof {
+    /// Encoded `SparseMerkleProof` of the claim
+    pub proof: Vec<u8>,
+
+    /// Encoded claim stored in the trie under the winning claim hash
+    pub value: Vec<u8>,
+}
+
+impl ClaimInclusionProof {
+    /// Checks the proof against `claims_root` and returns the claim stored
+    /// under `claim_hash`
+    pub fn verify(&self, claim_hash: &U256, claims_root: &str) -> Result<Claim, ElectionError> {
+        let root_hash = hex::decode(claims_root.trim_start_matches("0x"))
+            .ok()
+            .and_then(|bytes| <[u8; 32]>::try_from(bytes.as_slice()).ok())
+            .map(RootHash)
+            .ok_or_else(|| ElectionError::InvalidClaimProof("invalid claims root".into()))?;
+
+        let proof: SparseMerkleProof<Sha256> = bincode::deserialize(&self.proof)
+            .map_err(|err| ElectionError::InvalidClaimProof(err.to_string()))?;
+
+        let key = bincode::serialize(claim_hash)
+            .map_err(|err| ElectionError::InvalidClaimProof(err.to_string()))?;
+
+        proof
+            .verify(root_hash, KeyHash::with::<Sha256>(&key), Some(&self.value))
+            .map_err(|err| ElectionError::InvalidClaimProof(err.to_string()))?;
+
+        bincode::deserialize(&self.value)
+            .map_err(|err| ElectionError::InvalidClaimProof(err.to_string()))
+    }
+}
+
+/// Proof that the miner of a block won the election of its round, carried by
+/// the block header
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Hash, PartialEq, Eq)]
+pub struct MinerElectionProof {
+    /// Hash of the claim with the lowest pointer sum for the block seed
+    pub winning_claim_hash: U256,
+
+    /// Message the miner evaluated its VRF on
+    pub vrf_message: Vec<u8>,
+
+    /// ECVRF proof over `vrf_message`, the next block seed is drawn from its
+    /// output
+    pub vrf_proof: Vec<u8>,
+
+    /// Inclusion of the winning claim in the claims trie, set by miners with
+    /// access to the state
+    pub claim_inclusion: ClaimInclusionProof,
+}
+
+impl MinerElectionProof {
+    pub fn new(winning_claim_hash: U256, vrf: &VVRF) -> Self {
+        Self {
+            winning_claim_hash,
+            vrf_message: vrf.get_message(),
+            vrf_proof: vrf.get_proof().to_vec(),
+            claim_inclusion: ClaimInclusionProof::default(),
+        }
+    }
+}
+
+/// Message the miner of the block following `last_header` evaluates its VRF
+/// on. It only depends on the previous block, so a miner cannot grind through
+/// next block seeds by varying the contents of its own block.
+pub fn election_message(last_header: &BlockHeader) -> Vec<u8> {
+    hex::encode(hash_data!(last_header.hash(), last_header.next_block_seed))
+        .as_bytes()
+        .to_vec()
+}
+
+/// Returns the hash of the claim winning the miner election for
+/// `block_seed`: the claim eligible to mine with the lowest pointer sum, ties
+/// going to the lowest claim hash
+pub fn elect_miner<'a>(
+    claims: impl IntoIterator<Item = &'a Claim>,
+    block_seed: u64,
+) -> Option<U256> {
+    claims
+        .into_iter()
+        .filter(|claim| claim.eligibility == Eligibility::Miner)
+        .min_by_key(|claim| (claim.get_election_result(block_seed), claim.hash))
+        .map(|claim| claim.hash)
+}
+
+/// Checks that the miner of the block behind `header` won the miner election
+/// of the round following `last_header`. `claims_root` is the root of the
+/// claims trie the election ran against and `claims` the claims stored in it,
+/// the winner is recomputed from them.
+pub fn verify_miner_election<'a>(
+    header: &BlockHeader,
+    last_header: &BlockHeader,
+    claims_root: &str,
+    claims: impl IntoIterator<Item = &'a Claim>,
+) -> Result<(), ElectionError> {
+    let proof = &header.election_proof;
+
+    if proof.vrf_proof.is_empty() {
+        return Err(ElectionError::MissingProof);
+    }
+
+    if proof.winning_claim_hash != header.miner_claim.hash {
+        return Err(ElectionError::ClaimMismatch(proof.winning_claim_hash));
+    }
+
+    if header.block_seed != last_header.next_block_seed
+        || proof.vrf_message != election_message(last_header)
+    {
+        return Err(ElectionError::PreviousBlockMismatch);
+    }
+
+    let mut vrf = VVRF::from_proof(
+        &header.miner_claim.public_key.serialize(),
+        &proof.vrf_message,
+        &proof.vrf_proof,
+    )
+    .map_err(|err| ElectionError::InvalidVrfProof(err.to_string()))?;
+
+    if vrf.generate_u64_in_range(NEXT_BLOCK_SEED_MIN, u64::MAX) != header.next_block_seed {
+        return Err(ElectionError::SeedMismatch);
+    }
+
+    let claim = proof
+        .claim_inclusion
+        .verify(&proof.winning_claim_hash, claims_root)?;
+
+    if claim.public_key != header.miner_claim.public_key {
+        return Err(ElectionError::ClaimMismatch(proof.winning_claim_hash));
+    }
+
+    if claim.eligibility != Eligibility::Miner {
+        return Err(ElectionError::IneligibleClaim(proof.winning_claim_hash));
+    }
+
+    if elect_miner(claims, header.block_seed) != Some(proof.winning_claim_hash) {
+        return Err(ElectionError::NotElected(proof.winning_claim_hash));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use serde::{Deserialize, Serialize};
+use vrrb_core::transactions::{FeeSplit, Transaction};
+
+use crate::{ConsolidatedTxns, ProposalBlock};
+
+/// Transaction fees collected by a convergence block and how they were split
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct BlockFees {
+    /// Percentage of every fee burned when the block is applied
+    pub burn_rate: u8,
+    /// Sum of the fees paid by the transactions of the block
+    pub collected: u128,
+    /// Part of the fees credited to validators
+    pub validators: u128,
+    /// Part of the fees credited to proposers
+    pub proposers: u128,
+    /// Part of the fees burned
+    pub burned: u128,
+}
+
+impl BlockFees {
+    /// Totals the fees of the transactions of `proposals` included by a
+    /// convergence block, burning `burn_rate` percent of each fee
+    pub fn new(txns: &ConsolidatedTxns, proposals: &[ProposalBlock], burn_rate: u8) -> Self {
+        let mut fees = Self {
+            burn_rate,
+            ..Default::default()
+        };
+
+        for proposal in proposals {
+            if let Some(txn_set) = txns.get(&proposal.hash) {
+                proposal
+                    .txns
+                    .iter()
+                    .filter(|(digest, _)| txn_set.contains(*digest))
+                    .for_each(|(_, txn)| fees.record(FeeSplit::new(txn, burn_rate)));
+            }
+        }
+
+        fees
+    }
+
+    /// Splits the fee of `txn` at the block's burn rate
+    pub fn split<T: Transaction>(&self, txn: &T) -> FeeSplit {
+        FeeSplit::new(txn, self.burn_rate)
+    }
+
+    /// Adds the fee paid by a transaction to the totals
+    pub fn record(&mut self, split: FeeSplit) {
+        self.collected = self.collected.saturating_add(split.fee);
+        self.validators = self.validators.saturating_add(split.validators);
+        self.proposers = self.proposers.saturating_add(split.proposer);
+        self.burned = self.burned.saturating_add(split.burned);
+    }
+}
@@ -1,4 +1,4 @@
-use primitives::Address;
+use primitives::{Address, ChainParameters};
 #[cfg(mainnet)]
 use reward::reward::GENESIS_REWARD;
 use ritelinked::LinkedHashMap;
@@ -17,6 +17,9 @@ pub struct GenesisBlock {
     pub header: BlockHeader,
     pub genesis_rewards: GenesisRewards,
     pub claims: ClaimList,
+    /// Protocol parameters every node of the chain follows
+    #[serde(default)]
+    pub chain_parameters: ChainParameters,
     pub hash: BlockHash,
     pub certificate: Option<Certificate>,
 }
@@ -1,7 +1,7 @@
 use std::fmt::Debug;
 // FEATURE TAG(S): Block Structure, Rewards
 use chrono;
-use primitives::{Epoch, SecretKey};
+use primitives::{epoch_of, Epoch, Round, SecretKey};
 use reward::reward::Reward;
 use secp256k1::{
     hashes::{sha256 as s256, Hash},
@@ -12,7 +12,12 @@ use utils::{create_payload, hash_data, payload::digest_data_to_bytes};
 use vrrb_core::claim::Claim;
 use vrrb_vrf::{vrng::VRNG, vvrf::VVRF};
 
-use crate::{block::Block, InnerBlock, NextEpochAdjustment};
+use crate::{
+    block::Block,
+    election::{election_message, MinerElectionProof, NEXT_BLOCK_SEED_MIN},
+    slashing::evidence_root,
+    BlockHash, InnerBlock, NextEpochAdjustment,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Hash, PartialEq, Eq)]
 pub struct BlockHeader {
@@ -31,6 +36,24 @@ pub struct BlockHeader {
     pub block_reward: Reward,
     pub next_block_reward: Reward,
     pub miner_signature: primitives::Signature,
+    #[serde(default)]
+    pub state_roots: StateRoots,
+    #[serde(default)]
+    pub election_proof: MinerElectionProof,
+    /// Root of the slashing evidence included in the block, see
+    /// `slashing::evidence_root`
+    #[serde(default)]
+    pub slashing_evidence_root: String,
+}
+
+/// Hex encoded roots of the `VrrbDb` tries as they were when the block was
+/// mined. Merkle proofs generated against that state can be checked with
+/// nothing but the header.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Hash, PartialEq, Eq)]
+pub struct StateRoots {
+    pub state_root_hash: String,
+    pub transactions_root_hash: String,
+    pub claims_root_hash: String,
 }
 
 pub fn genesis_default_ref_hashes() -> Vec<String> {
@@ -60,7 +83,7 @@ pub fn genesis_block_header_hashed_payload(
 
     let mut vrf = VVRF::new(&message, secret_key.secret_bytes().as_ref());
 
-    let next_block_seed = vrf.generate_u64_in_range(u32::MAX as u64, u64::MAX);
+    let next_block_seed = vrf.generate_u64_in_range(NEXT_BLOCK_SEED_MIN, u64::MAX);
 
     let timestamp = chrono::Utc::now().timestamp();
     let txn_hash = hex::encode(hash_data!("Genesis_Txn_Hash".to_string()));
@@ -112,7 +135,7 @@ impl BlockHeader {
 
         let mut vrf = VVRF::new(&message, secret_key.secret_bytes().as_ref());
 
-        let next_block_seed = vrf.generate_u64_in_range(u32::MAX as u64, u64::MAX);
+        let next_block_seed = vrf.generate_u64_in_range(NEXT_BLOCK_SEED_MIN, u64::MAX);
 
         let timestamp = chrono::Utc::now().timestamp();
         let txn_hash = hex::encode(hash_data!("Genesis_Txn_Hash".to_string()));
@@ -120,6 +143,7 @@ impl BlockHeader {
         let next_block_reward = Reward::default();
 
         let miner_signature = secret_key.sign_ecdsa(payload);
+        let election_proof = MinerElectionProof::new(miner_claim.hash, &vrf);
 
         BlockHeader {
             ref_hashes,
@@ -135,6 +159,9 @@ impl BlockHeader {
             block_reward,
             next_block_reward,
             miner_signature,
+            state_roots: StateRoots::default(),
+            election_proof,
+            slashing_evidence_root: evidence_root(&[]),
         }
     }
 
@@ -145,6 +172,7 @@ impl BlockHeader {
         secret_key: SecretKey,
         txn_hash: String,
         claim_list_hash: String,
+        epoch_length: Round,
         adjustment_next_epoch: NextEpochAdjustment,
     ) -> Option<BlockHeader> {
         // Get the last block
@@ -162,39 +190,42 @@ impl BlockHeader {
         // Get block height
         let block_height = last_block.get_header().block_height + 1;
 
-        // get the message; TODO: replace ref_hashes with
-        // last_block.certificate
-        let message = {
-            let hash = hex::encode(hash_data!(last_block.get_hash(), ref_hashes));
-            hash.as_bytes().to_vec()
-        };
+        // The VRF message is bound to the previous block only, see
+        // `election_message`
+        let message = election_message(&last_block.get_header());
 
         let sk_bytes = &secret_key.secret_bytes();
 
         // Generate next_block_seed
         let mut vrf = VVRF::new(&message, sk_bytes);
-        let next_block_seed = vrf.generate_u64_in_range(u32::MAX as u64, u64::MAX);
+        let next_block_seed = vrf.generate_u64_in_range(NEXT_BLOCK_SEED_MIN, u64::MAX);
 
         // generate timestamp
         let timestamp = chrono::Utc::now().timestamp();
 
-        // Get current block reward, which is last_block.next_block_reward
+        // Get the round of the current block, which is last_block.round + 1
+        let round = last_block.get_header().round + 1;
+
+        // Get current epoch, which follows from the round so every node agrees
+        // on where epochs start
+        let last_epoch = last_block.get_header().epoch;
+        let epoch = epoch_of(round, epoch_length);
+
+        // Get current block reward, which is last_block.next_block_reward,
+        // adjusted if this block opens a new epoch
         let mut block_reward = last_block.get_next_block_reward();
+        if epoch > last_epoch {
+            block_reward = block_reward.advance_epoch(adjustment_next_epoch, epoch_length);
+        }
         block_reward.current_block = block_height;
 
         // Create the next block reward, which is a clone of the current
-        // reward, unless there's an epoch change
-        let next_block_reward = block_reward.generate_next_reward(adjustment_next_epoch);
+        // reward
+        let next_block_reward = block_reward.generate_next_reward();
 
         // Append the miner to the current block reward
         block_reward.miner = Some(miner_claim.address.to_string());
 
-        // Get current epoch which is the same as last epoch unless it's an
-        // epoch change block.
-        let epoch = last_block.get_header().epoch;
-        // Get the reward for current block which is last_block.round + 1
-        let round = last_block.get_header().round + 1;
-
         let payload = create_payload!(
             ref_hashes,
             round,
@@ -211,6 +242,7 @@ impl BlockHeader {
         );
 
         let miner_signature = secret_key.sign_ecdsa(payload);
+        let election_proof = MinerElectionProof::new(miner_claim.hash, &vrf);
 
         let block_header = BlockHeader {
             ref_hashes,
@@ -226,6 +258,9 @@ impl BlockHeader {
             block_reward,
             next_block_reward,
             miner_signature,
+            state_roots: StateRoots::default(),
+            election_proof,
+            slashing_evidence_root: evidence_root(&[]),
         };
 
         Some(block_header)
@@ -247,6 +282,29 @@ impl BlockHeader {
         )
     }
 
+    /// Returns the hash of the convergence block built on top of this header
+    pub fn hash(&self) -> BlockHash {
+        let block_hash = hash_data!(
+            self.ref_hashes,
+            self.round,
+            self.block_seed,
+            self.next_block_seed,
+            self.block_height,
+            self.timestamp,
+            self.txn_hash,
+            self.miner_claim,
+            self.claim_list_hash,
+            self.block_reward,
+            self.next_block_reward,
+            self.miner_signature,
+            self.state_roots,
+            self.election_proof,
+            self.slashing_evidence_root
+        );
+
+        format!("{block_hash:x}")
+    }
+
     pub fn as_bytes(&self) -> Vec<u8> {
         self.to_string().as_bytes().to_vec()
     }
@@ -1,9 +1,12 @@
 pub mod block;
 pub mod convergence_block;
+pub mod election;
 pub mod error;
+pub mod fees;
 pub mod genesis;
 pub mod header;
 pub mod proposal_block;
+pub mod slashing;
 mod types;
 
 pub use crate::{block::*, convergence_block::*, genesis::*, proposal_block::*, types::*};
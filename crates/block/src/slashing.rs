@@ -0,0 +1,269 @@
+use primitives::{Address, NodeId, PublicKey, SecretKey, Signature};
+use secp256k1::Message;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use utils::hash_data;
+use vrrb_core::{
+    staking::{Stake, StakeUpdate},
+    transactions::TransactionDigest,
+};
+
+use crate::header::BlockHeader;
+
+/// Percentage of stake slashed from a farmer that voted both for and against
+/// a transaction
+pub const DOUBLE_VOTE_SLASH_PERCENTAGE: u8 = 10;
+
+/// Percentage of stake slashed from a harvester that signed two different
+/// convergence blocks in the same round
+pub const CONFLICTING_BLOCK_SIGNATURES_SLASH_PERCENTAGE: u8 = 20;
+
+pub type SlashingEvidenceId = String;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error, Serialize, Deserialize)]
+pub enum SlashingError {
+    #[error("evidence does not show conflicting messages: {0}")]
+    NoConflict(String),
+
+    #[error("invalid signature from {0}")]
+    InvalidSignature(NodeId),
+
+    #[error("invalid penalty: {0}")]
+    InvalidPenalty(String),
+}
+
+/// A farmer's signed verdict on a transaction
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SignedVote {
+    pub txn_id: TransactionDigest,
+    pub is_txn_valid: bool,
+    pub signature: Signature,
+}
+
+impl SignedVote {
+    /// Returns the payload farmers sign when voting on a transaction. It
+    /// commits to the verdict so that voting both ways can be proven.
+    pub fn payload(txn_id: &TransactionDigest, is_txn_valid: bool) -> Vec<u8> {
+        hash_data!(txn_id, is_txn_valid).to_vec()
+    }
+
+    fn verify(&self, public_key: &PublicKey) -> bool {
+        verify_signature(
+            public_key,
+            &self.signature,
+            &Self::payload(&self.txn_id, self.is_txn_valid),
+        )
+    }
+}
+
+/// A convergence block header along with a harvester's partial signature
+/// over the hash of the block
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SignedBlockHeader {
+    pub header: BlockHeader,
+    pub signature: Signature,
+}
+
+impl SignedBlockHeader {
+    fn verify(&self, public_key: &PublicKey) -> bool {
+        verify_signature(public_key, &self.signature, self.header.hash().as_bytes())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Offense {
+    /// A farmer voted both for and against the validity of a transaction
+    DoubleVote {
+        first: SignedVote,
+        second: SignedVote,
+    },
+
+    /// A harvester signed two different convergence blocks in the same round
+    ConflictingBlockSignatures {
+        first: SignedBlockHeader,
+        second: SignedBlockHeader,
+    },
+}
+
+impl Offense {
+    pub fn slash_percentage(&self) -> u8 {
+        match self {
+            Offense::DoubleVote { .. } => DOUBLE_VOTE_SLASH_PERCENTAGE,
+            Offense::ConflictingBlockSignatures { .. } => {
+                CONFLICTING_BLOCK_SIGNATURES_SLASH_PERCENTAGE
+            },
+        }
+    }
+
+    /// Orders the conflicting messages so that every node reporting the same
+    /// offense produces the same evidence
+    fn canonical(self) -> Self {
+        match self {
+            Offense::DoubleVote { first, second } if first.is_txn_valid => Offense::DoubleVote {
+                first: second,
+                second: first,
+            },
+            Offense::ConflictingBlockSignatures { first, second }
+                if first.header.hash() > second.header.hash() =>
+            {
+                Offense::ConflictingBlockSignatures {
+                    first: second,
+                    second: first,
+                }
+            },
+            offense => offense,
+        }
+    }
+
+    fn check_conflict(&self) -> Result<(), SlashingError> {
+        match self {
+            Offense::DoubleVote { first, second } => {
+                if first.txn_id != second.txn_id {
+                    return Err(SlashingError::NoConflict(
+                        "votes are on different transactions".to_string(),
+                    ));
+                }
+
+                if first.is_txn_valid == second.is_txn_valid {
+                    return Err(SlashingError::NoConflict(
+                        "votes agree on the validity of the transaction".to_string(),
+                    ));
+                }
+            },
+            Offense::ConflictingBlockSignatures { first, second } => {
+                if first.header.round != second.header.round {
+                    return Err(SlashingError::NoConflict(
+                        "blocks belong to different rounds".to_string(),
+                    ));
+                }
+
+                if first.header.hash() == second.header.hash() {
+                    return Err(SlashingError::NoConflict(
+                        "signatures are on the same block".to_string(),
+                    ));
+                }
+            },
+        }
+
+        Ok(())
+    }
+
+    fn check_signatures(&self, public_key: &PublicKey) -> bool {
+        match self {
+            Offense::DoubleVote { first, second } => {
+                first.verify(public_key) && second.verify(public_key)
+            },
+            Offense::ConflictingBlockSignatures { first, second } => {
+                first.verify(public_key) && second.verify(public_key)
+            },
+        }
+    }
+}
+
+/// Proof that a quorum member signed two conflicting messages, along with the
+/// penalty to apply to its claim.
+///
+/// The penalty is a `StakeUpdate::Slash` issued by the node that reported the
+/// offense. It travels with the evidence so that every node applies the exact
+/// same update to the offender's stake.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SlashingEvidence {
+    pub offender: NodeId,
+    pub offense: Offense,
+    pub penalty: Stake,
+}
+
+impl SlashingEvidence {
+    /// Packages an offense into evidence, issuing the penalty against
+    /// `offender_address` with the reporter's keys
+    pub fn new(
+        offender: NodeId,
+        offender_address: Address,
+        offense: Offense,
+        reporter_secret_key: SecretKey,
+        reporter_public_key: PublicKey,
+    ) -> Result<Self, SlashingError> {
+        let offense = offense.canonical();
+        let penalty = Stake::new(
+            StakeUpdate::Slash(offense.slash_percentage()),
+            reporter_secret_key,
+            reporter_public_key,
+            offender_address,
+            None,
+        )
+        .ok_or(SlashingError::InvalidPenalty(
+            "unable to sign penalty".to_string(),
+        ))?;
+
+        Ok(Self {
+            offender,
+            offense,
+            penalty,
+        })
+    }
+
+    /// Identifies the offense regardless of who reported it. A farmer is
+    /// slashed at most once per transaction and a harvester at most once per
+    /// round.
+    pub fn id(&self) -> SlashingEvidenceId {
+        let id = match &self.offense {
+            Offense::DoubleVote { first, .. } => {
+                hash_data!(self.offender, "double_vote".to_string(), first.txn_id)
+            },
+            Offense::ConflictingBlockSignatures { first, .. } => hash_data!(
+                self.offender,
+                "conflicting_block_signatures".to_string(),
+                first.header.round
+            ),
+        };
+
+        format!("{id:x}")
+    }
+
+    /// Checks that the offender signed both of the conflicting messages and
+    /// that the penalty slashes the offender's claim by the amount the offense
+    /// calls for
+    pub fn verify(
+        &self,
+        offender_public_key: &PublicKey,
+        offender_address: &Address,
+    ) -> Result<(), SlashingError> {
+        self.offense.check_conflict()?;
+
+        if !self.offense.check_signatures(offender_public_key) {
+            return Err(SlashingError::InvalidSignature(self.offender.clone()));
+        }
+
+        if self.penalty.get_amount() != StakeUpdate::Slash(self.offense.slash_percentage()) {
+            return Err(SlashingError::InvalidPenalty(
+                "penalty does not match the offense".to_string(),
+            ));
+        }
+
+        if &self.penalty.get_receiver() != offender_address {
+            return Err(SlashingError::InvalidPenalty(
+                "penalty is not issued against the offender".to_string(),
+            ));
+        }
+
+        self.penalty
+            .verify()
+            .map_err(|err| SlashingError::InvalidPenalty(err.to_string()))
+    }
+}
+
+/// Returns the hex encoded root committing to every piece of slashing
+/// evidence included in a convergence block, in order
+pub fn evidence_root(evidence: &[SlashingEvidence]) -> String {
+    hex::encode(hash_data!(evidence.to_vec()))
+}
+
+/// Checks a signature produced by `SignerEngine::sign`
+fn verify_signature(public_key: &PublicKey, signature: &Signature, data: &[u8]) -> bool {
+    let digest = Sha256::digest(data);
+
+    Message::from_slice(&digest)
+        .map(|message| signature.verify(&message, public_key).is_ok())
+        .unwrap_or(false)
+}
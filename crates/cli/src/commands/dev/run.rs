@@ -171,6 +171,12 @@ impl From<RunOpts> for NodeConfig {
             enable_block_indexing: default_node_config.enable_block_indexing,
             threshold_config: default_node_config.threshold_config,
             whitelisted_nodes: default_node_config.whitelisted_nodes,
+            mempool_config: default_node_config.mempool_config,
+            pruning_config: default_node_config.pruning_config,
+            unbonding_epochs: default_node_config.unbonding_epochs,
+            commission_rate: default_node_config.commission_rate,
+            fee_burn_rate: default_node_config.fee_burn_rate,
+            dkg_timeout: default_node_config.dkg_timeout,
         }
     }
 }
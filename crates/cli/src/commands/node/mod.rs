@@ -1,9 +1,13 @@
 mod info;
+mod prune;
 mod run;
+mod snapshot;
 
 use clap::{Parser, Subcommand};
 pub use info::*;
+pub use prune::*;
 pub use run::*;
+pub use snapshot::*;
 
 use crate::result::{CliError, Result};
 
@@ -17,6 +21,12 @@ pub enum NodeCmd {
 
     /// Stops any node currrently running in detached mode
     Stop,
+
+    /// Exports or imports state snapshots used to bootstrap new nodes
+    Snapshot(SnapshotOpts),
+
+    /// Deletes stale state of a stopped node outside of the retention window
+    Prune(PruneOpts),
 }
 
 #[derive(Parser, Debug)]
@@ -31,6 +41,8 @@ pub async fn exec(args: NodeOpts) -> Result<()> {
     match sub_cmd {
         NodeCmd::Run(opts) => run(*opts).await,
         NodeCmd::Info => Ok(()),
+        NodeCmd::Snapshot(opts) => snapshot::exec(opts),
+        NodeCmd::Prune(opts) => prune::exec(opts),
         _ => Err(CliError::InvalidCommand(format!("{sub_cmd:?}"))),
     }
 }
@@ -0,0 +1,47 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use primitives::DEFAULT_VRRB_DB_PATH;
+use storage::vrrbdb::{StatePruner, VrrbDb, VrrbDbConfig};
+use vrrb_config::{PruningConfig, DEFAULT_PRUNING_BATCH_SIZE, DEFAULT_PRUNING_RETAINED_VERSIONS};
+
+use crate::result::{CliError, Result};
+
+#[derive(Parser, Debug, Clone)]
+pub struct PruneOpts {
+    #[clap(long, value_parser, default_value = DEFAULT_VRRB_DB_PATH)]
+    pub db_path: PathBuf,
+
+    /// Number of trie versions kept readable, counted back from the latest
+    #[clap(long, value_parser, default_value_t = DEFAULT_PRUNING_RETAINED_VERSIONS)]
+    pub retained_versions: u64,
+
+    /// Maximum number of stale nodes deleted from each trie per pass
+    #[clap(long, value_parser, default_value_t = DEFAULT_PRUNING_BATCH_SIZE)]
+    pub batch_size: usize,
+}
+
+/// Prunes the database of a stopped node until only the retained versions
+/// are left
+pub fn exec(opts: PruneOpts) -> Result<()> {
+    let config = PruningConfig {
+        batch_size: opts.batch_size,
+        ..PruningConfig::prune(opts.retained_versions)
+    };
+    config
+        .validate()
+        .map_err(|err| CliError::OptsError(err.to_string()))?;
+
+    let db = VrrbDb::new(VrrbDbConfig::default().with_path(opts.db_path))?;
+    let pruner = StatePruner::new(&db, config.retained_versions, config.batch_size)?;
+
+    let pruned = pruner.prune_all()?;
+    let stats = pruner.stats();
+
+    println!(
+        "Pruned {pruned} stale nodes in {} passes, {} stale nodes within the retention window",
+        stats.runs, stats.pending_stale_nodes
+    );
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use std::{fs::File, io::BufReader, path::PathBuf};
+
+use block::header::BlockHeader;
+use clap::{Parser, Subcommand};
+use primitives::DEFAULT_VRRB_DB_PATH;
+use storage::vrrbdb::{VrrbDb, VrrbDbConfig};
+
+use crate::result::{CliError, Result};
+
+#[derive(Debug, Subcommand)]
+pub enum SnapshotCmd {
+    /// Writes a snapshot of a stopped node's state to a file
+    Export(SnapshotExportOpts),
+
+    /// Bootstraps an empty node database from a snapshot file
+    Import(SnapshotImportOpts),
+}
+
+#[derive(Parser, Debug)]
+pub struct SnapshotOpts {
+    #[clap(subcommand)]
+    pub subcommand: SnapshotCmd,
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct SnapshotExportOpts {
+    #[clap(long, value_parser, default_value = DEFAULT_VRRB_DB_PATH)]
+    pub db_path: PathBuf,
+
+    /// Hash of the last convergence block applied to the database. The
+    /// export fails unless the database holds that block and its state.
+    #[clap(long, value_parser)]
+    pub block_hash: String,
+
+    /// Height of the last convergence block applied to the database
+    #[clap(long, value_parser)]
+    pub block_height: u128,
+
+    /// File the snapshot is written to
+    #[clap(short, long, value_parser)]
+    pub output: PathBuf,
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct SnapshotImportOpts {
+    #[clap(long, value_parser, default_value = DEFAULT_VRRB_DB_PATH)]
+    pub db_path: PathBuf,
+
+    /// Snapshot file to bootstrap the database from
+    #[clap(short, long, value_parser)]
+    pub input: PathBuf,
+
+    /// JSON file holding the header of the block following the snapshot's
+    /// block, taken from a node you trust. The snapshot must rebuild the
+    /// state roots it commits to.
+    #[clap(long, value_parser)]
+    pub trusted_header: PathBuf,
+}
+
+pub fn exec(args: SnapshotOpts) -> Result<()> {
+    match args.subcommand {
+        SnapshotCmd::Export(opts) => export(opts),
+        SnapshotCmd::Import(opts) => import(opts),
+    }
+}
+
+fn open_db(db_path: PathBuf) -> Result<VrrbDb> {
+    Ok(VrrbDb::new(VrrbDbConfig::default().with_path(db_path))?)
+}
+
+fn export(opts: SnapshotExportOpts) -> Result<()> {
+    let db = open_db(opts.db_path)?;
+    let manifest = db.export_state(opts.block_hash, opts.block_height, &opts.output)?;
+
+    println!(
+        "Exported {} chunks at height {} with state root {} to {}",
+        manifest.chunks.len(),
+        manifest.block_height,
+        manifest.state_root_hash,
+        opts.output.display()
+    );
+
+    Ok(())
+}
+
+fn import(opts: SnapshotImportOpts) -> Result<()> {
+    let trusted_header: BlockHeader =
+        serde_json::from_reader(BufReader::new(File::open(&opts.trusted_header)?))
+            .map_err(|err| CliError::OptsError(format!("invalid trusted header: {err}")))?;
+
+    let mut db = open_db(opts.db_path)?;
+    let snapshot = db.import_state(&opts.input, &trusted_header)?;
+
+    println!(
+        "Imported {} accounts, {} claims and {} transactions of block {} at height {}, state root \
+         {} verified",
+        snapshot.accounts.len(),
+        snapshot.claims.len(),
+        snapshot.transactions.len(),
+        snapshot.block_hash,
+        snapshot.block_height,
+        snapshot.state_root_hash
+    );
+
+    Ok(())
+}
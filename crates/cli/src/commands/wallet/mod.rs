@@ -9,6 +9,7 @@ use std::{collections::HashMap, net::SocketAddr, path::PathBuf, str::FromStr};
 use clap::{Parser, Subcommand};
 use primitives::Address;
 use serde_json;
+use vrrb_config::DEFAULT_REPLACEMENT_FEE_BUMP_PERCENT;
 use vrrb_core::{account::Account, helpers::read_or_generate_keypair_file};
 use vrrb_core::transactions::Token;
 use wallet::v2::{AddressAlias, Wallet, WalletConfig};
@@ -24,6 +25,11 @@ pub struct WalletOpts {
     #[clap(long, default_value = "default")]
     pub identity: String,
 
+    /// Smallest fee bump, in percent, the node accepts to replace a pending
+    /// transfer
+    #[clap(long, default_value_t = DEFAULT_REPLACEMENT_FEE_BUMP_PERCENT)]
+    pub min_fee_bump_percent: u64,
+
     #[clap(subcommand)]
     pub subcommand: WalletCmd,
 }
@@ -96,6 +102,7 @@ pub async fn exec(args: WalletOpts) -> Result<()> {
         rpc_server_address,
         secret_key,
         public_key,
+        min_fee_bump_percent: args.min_fee_bump_percent,
         // accounts,
         // addresses,
     };
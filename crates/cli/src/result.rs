@@ -20,6 +20,9 @@ pub enum CliError {
     #[error("storage error: {0}")]
     Storage(#[from] vrrb_core::storage_utils::StorageError),
 
+    #[error("database error: {0}")]
+    Database(#[from] storage::storage_utils::StorageError),
+
     #[error("primitive error: {0}")]
     Primitive(#[from] primitives::Error),
 
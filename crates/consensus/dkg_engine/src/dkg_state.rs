@@ -1,6 +1,4 @@
-use std::{
-    collections::{BTreeMap, HashMap},
-};
+use std::collections::{BTreeMap, HashMap};
 
 use hbbft::{
     crypto::{PublicKey, PublicKeySet, SecretKeyShare},
@@ -9,9 +7,7 @@ use hbbft::{
 use primitives::NodeId;
 use rand::rngs::OsRng;
 
-use crate::{
-    prelude::{ReceiverId, SenderId},
-};
+use crate::prelude::{DkgRound, KeyReshare, ReceiverId, SenderId};
 
 #[derive(Debug, Default)]
 pub struct DkgState {
@@ -22,6 +18,8 @@ pub struct DkgState {
     secret_key_share: Option<SecretKeyShare>,
     sync_key_gen: Option<SyncKeyGen<NodeId>>,
     random_number_gen: Option<OsRng>,
+    round: Option<DkgRound>,
+    reshare: Option<KeyReshare>,
 }
 
 impl DkgState {
@@ -37,6 +35,17 @@ impl DkgState {
         self.public_key_set = None;
         self.peer_public_keys.clear();
         self.secret_key_share = None;
+        self.round = None;
+        self.reshare = None;
+    }
+
+    /// Drops the `Part` and `Ack` messages and the `SyncKeyGen` instance of
+    /// the current round, keeping the public keys announced by peers
+    pub fn clear_commitments(&mut self) {
+        self.part_message_store.clear();
+        self.ack_message_store.clear();
+        self.sync_key_gen = None;
+        self.random_number_gen = None;
     }
 
     pub fn part_message_store_owned(&self) -> HashMap<NodeId, Part> {
@@ -153,4 +162,28 @@ impl DkgState {
     pub fn add_peer_public_key(&mut self, node_id: NodeId, public_key: PublicKey) {
         self.peer_public_keys.insert(node_id, public_key);
     }
+
+    pub fn round(&self) -> &Option<DkgRound> {
+        &self.round
+    }
+
+    pub fn round_mut(&mut self) -> &mut Option<DkgRound> {
+        &mut self.round
+    }
+
+    pub fn set_round(&mut self, round: Option<DkgRound>) {
+        self.round = round;
+    }
+
+    pub fn reshare(&self) -> &Option<KeyReshare> {
+        &self.reshare
+    }
+
+    pub fn reshare_mut(&mut self) -> &mut Option<KeyReshare> {
+        &mut self.reshare
+    }
+
+    pub fn set_reshare(&mut self, reshare: Option<KeyReshare>) {
+        self.reshare = reshare;
+    }
 }
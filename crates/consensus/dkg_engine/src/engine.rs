@@ -1,15 +1,22 @@
-use std::sync::Arc;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
 
 use hbbft::{
     crypto::{PublicKey, PublicKeySet, SecretKey},
-    sync_key_gen::{Ack, Part, PartOutcome, SyncKeyGen},
+    sync_key_gen::{Ack, AckOutcome, Part, PartOutcome, SyncKeyGen},
 };
 use primitives::{NodeId, NodeType, ValidatorPublicKey};
 use rand::rngs::OsRng;
 use vrrb_config::ThresholdConfig;
 
 use crate::{
-    prelude::{DkgGenerator, DkgState, ReceiverId, SenderId},
+    prelude::{
+        DkgGenerator, DkgOutput, DkgPhase, DkgPublicKeyAnnouncement, DkgRound, DkgRoundId,
+        DkgState, DkgTimeoutVote, KeyReshare, QuorumKeyShare, ReceiverId, ResharePart, SenderId,
+    },
+    reshare::{self, DealtKeyShare},
     DkgError, Result,
 };
 
@@ -43,45 +50,13 @@ pub struct DkgEngine {
 
     /// Harvester Distributed  Group public key
     pub harvester_public_key: Option<PublicKey>,
-}
-
-impl Clone for DkgEngine {
-    fn clone(&self) -> Self {
-        let peer_public_keys = Arc::new(self.dkg_state.peer_public_keys().clone());
 
-        // TODO: fix unwraps
-        let mut rng = OsRng::new()
-            .map_err(|err| DkgError::Unknown(err.to_string()))
-            .unwrap();
+    /// Key the node signs its DKG messages with
+    pub(crate) validator_secret_key: primitives::SecretKey,
 
-        let (sync_key_gen, _) = SyncKeyGen::new(
-            self.node_id(),
-            self.secret_key.clone(),
-            peer_public_keys,
-            self.threshold_config().threshold as usize,
-            &mut rng,
-        )
-        .unwrap();
-
-        let mut dkg_state = DkgState::new();
-
-        dkg_state.set_part_message_store(self.dkg_state.part_message_store_owned());
-        dkg_state.set_ack_message_store(self.dkg_state.ack_message_store_owned());
-        dkg_state.set_peer_public_keys(self.dkg_state.peer_public_keys_owned());
-        dkg_state.set_public_key_set(self.dkg_state.public_key_set_owned());
-        dkg_state.set_secret_key_share(self.dkg_state.secret_key_share_owned());
-        dkg_state.set_sync_key_gen(Some(sync_key_gen));
-        dkg_state.set_random_number_gen(self.dkg_state.random_number_gen_owned());
-
-        Self {
-            node_id: self.node_id.clone(),
-            node_type: self.node_type,
-            threshold_config: self.threshold_config(),
-            secret_key: self.secret_key.clone(),
-            dkg_state,
-            harvester_public_key: self.harvester_public_key,
-        }
-    }
+    /// Validator keys of the known quorum members, used to verify the DKG
+    /// messages they sign
+    validator_public_keys: BTreeMap<NodeId, primitives::PublicKey>,
 }
 
 #[derive(Debug, Clone)]
@@ -89,6 +64,7 @@ pub struct DkgEngineConfig {
     pub node_id: NodeId,
     pub node_type: NodeType,
     pub secret_key: SecretKey,
+    pub validator_secret_key: primitives::SecretKey,
     pub threshold_config: vrrb_config::ThresholdConfig,
 }
 
@@ -101,9 +77,28 @@ impl DkgEngine {
             threshold_config: config.threshold_config,
             dkg_state: DkgState::default(),
             harvester_public_key: None,
+            validator_secret_key: config.validator_secret_key,
+            validator_public_keys: BTreeMap::new(),
         }
     }
 
+    pub fn add_validator_public_key(
+        &mut self,
+        node_id: NodeId,
+        validator_public_key: primitives::PublicKey,
+    ) {
+        self.validator_public_keys
+            .insert(node_id, validator_public_key);
+    }
+
+    /// Replaces the validator keys of the known quorum members
+    pub fn set_validator_public_keys(
+        &mut self,
+        validator_public_keys: BTreeMap<NodeId, primitives::PublicKey>,
+    ) {
+        self.validator_public_keys = validator_public_keys;
+    }
+
     pub fn add_peer_public_key(&mut self, node_id: NodeId, public_key: PublicKey) {
         self.dkg_state
             .peer_public_keys_mut()
@@ -118,6 +113,15 @@ impl DkgEngine {
         self.secret_key.public_key()
     }
 
+    /// DKG public key of the node, signed with its validator key
+    pub fn public_key_announcement(&self) -> DkgPublicKeyAnnouncement {
+        DkgPublicKeyAnnouncement::new(
+            self.node_id(),
+            self.get_public_key(),
+            &self.validator_secret_key,
+        )
+    }
+
     pub fn node_id(&self) -> NodeId {
         self.node_id.clone()
     }
@@ -126,6 +130,642 @@ impl DkgEngine {
     pub fn clear_state(&mut self) {
         self.dkg_state.clear();
     }
+
+    pub fn round(&self) -> Option<&DkgRound> {
+        self.dkg_state.round().as_ref()
+    }
+
+    /// Starts a DKG round for a newly elected quorum. Messages received from
+    /// the members before the round started are kept and handled right away.
+    pub fn start_round(&mut self, members: BTreeSet<NodeId>) -> Result<DkgOutput> {
+        if !members.contains(&self.node_id) {
+            return Err(DkgError::ObserverNotAllowed);
+        }
+
+        let round_id = self.round().map(|round| round.id() + 1).unwrap_or_default();
+
+        if let Some(round) = self.round().cloned() {
+            self.drop_handled_commitments(&round);
+        }
+
+        self.dkg_state.set_public_key_set(None);
+        self.dkg_state.set_secret_key_share(None);
+
+        self.begin_round(round_id, members, BTreeSet::new())
+    }
+
+    /// Handles the DKG public key announced by a member of the quorum. The
+    /// announcement has to be signed with the validator key the member is
+    /// known by, so no one can announce a key on its behalf.
+    pub fn handle_public_key(
+        &mut self,
+        announcement: DkgPublicKeyAnnouncement,
+    ) -> Result<DkgOutput> {
+        let validator_public_key = self.validator_public_key(&announcement.node_id)?;
+        announcement.verify(&validator_public_key)?;
+
+        self.add_peer_public_key(announcement.node_id, announcement.public_key);
+
+        let mut output = DkgOutput::default();
+        self.advance_round(&mut output)?;
+        self.advance_reshare(&mut output)?;
+
+        Ok(output)
+    }
+
+    /// Handles the `Part` multicasted by a member of the quorum, returning
+    /// the `Ack` to multicast back once the part can be verified
+    pub fn handle_part_commitment(&mut self, sender_id: SenderId, part: Part) -> Result<DkgOutput> {
+        if sender_id != self.node_id {
+            self.dkg_state
+                .part_message_store_mut()
+                .insert(sender_id, part);
+        }
+
+        let mut output = DkgOutput::default();
+        self.advance_round(&mut output)?;
+
+        Ok(output)
+    }
+
+    /// Handles the `Ack` sent by `receiver_id` for the `Part` of `sender_id`
+    pub fn handle_part_acknowledgement(
+        &mut self,
+        receiver_id: ReceiverId,
+        sender_id: SenderId,
+        ack: Ack,
+    ) -> Result<DkgOutput> {
+        if receiver_id != self.node_id {
+            self.dkg_state
+                .ack_message_store_mut()
+                .insert((receiver_id, sender_id), ack);
+        }
+
+        let mut output = DkgOutput::default();
+        self.advance_round(&mut output)?;
+
+        Ok(output)
+    }
+
+    /// Handles the timeout of a DKG round. Rather than dropping members on
+    /// its own, the node votes to exclude the ones it found non-responsive
+    /// and the round only moves on once the responsive members agree, see
+    /// `handle_timeout_vote`. The timeout is scheduled again in case they
+    /// don't. While resharing, the node votes on the dealers it is missing
+    /// parts from instead, and falls back to a new DKG round if the key set
+    /// still wasn't reshared by the next timeout.
+    pub fn handle_round_timeout(&mut self, round_id: DkgRoundId) -> Result<DkgOutput> {
+        let round = match self.round() {
+            Some(round) if round.id() == round_id && !round.is_completed() => round.clone(),
+            _ => return Ok(DkgOutput::default()),
+        };
+
+        if round.phase() == &DkgPhase::Resharing {
+            let missing_dealers = match self.dkg_state.reshare() {
+                Some(reshare)
+                    if !reshare.parts().is_empty() && !reshare.has_voted(&self.node_id) =>
+                {
+                    reshare.missing_dealers()
+                },
+                _ => {
+                    // NOTE: the key set couldn't be reshared in time, so the
+                    // quorum falls back to generating a new one
+                    self.dkg_state.set_reshare(None);
+
+                    return self.begin_round(
+                        round.id() + 1,
+                        round.members().clone(),
+                        BTreeSet::new(),
+                    );
+                },
+            };
+
+            // NOTE: the members vote on the dealers they are missing parts
+            // from, so they can all reshare the key set with the others
+            return self.cast_timeout_vote(round_id, missing_dealers);
+        }
+
+        let non_responsive = round.non_responsive_members(self.dkg_state.peer_public_keys());
+
+        self.cast_timeout_vote(round_id, non_responsive)
+    }
+
+    /// Handles the vote of a member of the quorum on the members to exclude
+    /// from a timed out round, or on the dealers to skip if it was resharing.
+    /// Votes on other rounds or from non-members are ignored.
+    pub fn handle_timeout_vote(&mut self, vote: DkgTimeoutVote) -> Result<DkgOutput> {
+        let validator_public_key = self.validator_public_key(&vote.voter)?;
+        vote.verify(&validator_public_key)?;
+
+        let mut output = DkgOutput::default();
+        self.apply_timeout_vote(vote, &mut output)?;
+
+        Ok(output)
+    }
+
+    /// Starts resharing the key set of the quorum to `members`, the quorum
+    /// elected for the next epoch, so it keeps the same public key. Members
+    /// of the current quorum deal their key share to the new members once
+    /// their DKG public keys are known, and drop it afterwards if they don't
+    /// belong to the new quorum. New members combine the parts of every
+    /// dealer into their own key share.
+    pub fn start_reshare(&mut self, members: BTreeSet<NodeId>) -> Result<DkgOutput> {
+        if self.is_resharing_to(&members) {
+            return Ok(DkgOutput::default());
+        }
+
+        let key_share = self.completed_key_share();
+
+        self.begin_reshare(members, key_share)
+    }
+
+    /// Handles the `ResharePart` multicasted by a member of the previous
+    /// quorum. New members that didn't start resharing yet join it right
+    /// away.
+    pub fn handle_reshare_part(&mut self, dealer: NodeId, part: ResharePart) -> Result<DkgOutput> {
+        let mut output = DkgOutput::default();
+        let members = part.members();
+
+        if dealer == self.node_id || !members.contains(&self.node_id) {
+            return Ok(output);
+        }
+
+        if !self.is_resharing_to(&members) {
+            let key_share = self
+                .completed_key_share()
+                .filter(|key_share| key_share.members == part.dealers);
+
+            output = self.begin_reshare(members, key_share)?;
+        }
+
+        if let Some(reshare) = self.dkg_state.reshare_mut() {
+            // NOTE: invalid parts are dropped, so the quorum falls back to a
+            // new DKG round once resharing times out
+            if reshare::validate_part(&dealer, &part, reshare).is_ok() {
+                reshare.insert_part(dealer, part);
+            }
+        }
+
+        self.advance_reshare(&mut output)?;
+
+        Ok(output)
+    }
+
+    /// Restores the key material of a round completed before a restart
+    pub fn restore_key_share(&mut self, key_share: QuorumKeyShare) {
+        let threshold = self.threshold_for(key_share.members.len());
+        let mut round = DkgRound::new(0, key_share.members, BTreeSet::new(), threshold);
+        round.set_phase(DkgPhase::Completed);
+
+        self.dkg_state
+            .set_public_key_set(Some(key_share.public_key_set));
+        self.dkg_state
+            .set_secret_key_share(Some(key_share.secret_key_share));
+        self.dkg_state.set_round(Some(round));
+    }
+
+    /// Threshold of a round, capped so the key set can still be generated if
+    /// every member responds
+    fn threshold_for(&self, members: usize) -> usize {
+        (self.threshold_config.threshold as usize).min(members.saturating_sub(1) / 2)
+    }
+
+    fn cast_timeout_vote(
+        &mut self,
+        round_id: DkgRoundId,
+        non_responsive: BTreeSet<NodeId>,
+    ) -> Result<DkgOutput> {
+        let vote = DkgTimeoutVote::new(
+            round_id,
+            self.node_id(),
+            non_responsive,
+            &self.validator_secret_key,
+        );
+
+        let mut output = DkgOutput {
+            started_round: Some(round_id),
+            timeout_vote: Some(vote.clone()),
+            ..Default::default()
+        };
+
+        self.apply_timeout_vote(vote, &mut output)?;
+
+        Ok(output)
+    }
+
+    fn validator_public_key(&self, node_id: &NodeId) -> Result<primitives::PublicKey> {
+        self.validator_public_keys
+            .get(node_id)
+            .copied()
+            .ok_or_else(|| DkgError::UnknownMember(node_id.clone()))
+    }
+
+    /// Records a timeout vote on the current round and, once the responsive
+    /// members agree on the members to exclude, either completes the round or
+    /// restarts it without them
+    fn apply_timeout_vote(&mut self, vote: DkgTimeoutVote, output: &mut DkgOutput) -> Result<()> {
+        let is_resharing = self
+            .round()
+            .map(|round| {
+                round.id() == vote.round_id
+                    && round.is_member(&vote.voter)
+                    && round.phase() == &DkgPhase::Resharing
+            })
+            .unwrap_or(false);
+
+        if is_resharing {
+            if let Some(reshare) = self.dkg_state.reshare_mut() {
+                reshare.missing_dealers_voted(vote.voter, vote.non_responsive);
+            }
+
+            return self.advance_reshare(output);
+        }
+
+        let round = match self.dkg_state.round_mut() {
+            Some(round)
+                if round.id() == vote.round_id
+                    && round.is_member(&vote.voter)
+                    && round.phase() != &DkgPhase::Resharing
+                    && !round.is_completed() =>
+            {
+                round.timeout_voted(vote.voter, vote.non_responsive);
+                round.clone()
+            },
+            _ => return Ok(()),
+        };
+
+        let non_responsive = match round.agreed_exclusion() {
+            Some(non_responsive) => non_responsive,
+            None => return Ok(()),
+        };
+
+        let mut excluded = round.excluded().clone();
+        excluded.extend(non_responsive.iter().cloned());
+
+        let is_ready = self
+            .dkg_state
+            .sync_key_gen()
+            .as_ref()
+            .map(|keygen| keygen.is_ready())
+            .unwrap_or(false);
+
+        if non_responsive.is_empty() && is_ready {
+            let mut round = round;
+
+            self.complete_round(&mut round, excluded, output)?;
+            self.dkg_state.set_round(Some(round));
+
+            return Ok(());
+        }
+
+        self.drop_handled_commitments(&round);
+
+        if non_responsive.contains(&self.node_id) {
+            self.dkg_state.set_round(None);
+            return Ok(());
+        }
+
+        let members = round
+            .members()
+            .difference(&non_responsive)
+            .cloned()
+            .collect::<BTreeSet<NodeId>>();
+
+        let restarted = self.begin_round(round.id() + 1, members, excluded)?;
+        output.merge(restarted);
+
+        Ok(())
+    }
+
+    /// Drops the commitments handled by `round` along with its `SyncKeyGen`
+    /// instance. Commitments that weren't handled yet may come from members
+    /// that already started the next round, so they are kept.
+    fn drop_handled_commitments(&mut self, round: &DkgRound) {
+        self.dkg_state
+            .part_message_store_mut()
+            .retain(|sender_id, _| !round.has_handled_part(sender_id));
+        self.dkg_state
+            .ack_message_store_mut()
+            .retain(|(receiver_id, sender_id), _| !round.has_handled_ack(receiver_id, sender_id));
+        self.dkg_state.set_sync_key_gen(None);
+        self.dkg_state.set_random_number_gen(None);
+    }
+
+    fn begin_round(
+        &mut self,
+        round_id: DkgRoundId,
+        members: BTreeSet<NodeId>,
+        excluded: BTreeSet<NodeId>,
+    ) -> Result<DkgOutput> {
+        let threshold = self.threshold_for(members.len());
+
+        self.add_peer_public_key(self.node_id(), self.get_public_key());
+        self.dkg_state
+            .set_round(Some(DkgRound::new(round_id, members, excluded, threshold)));
+
+        let mut output = DkgOutput {
+            public_key: Some(self.public_key_announcement()),
+            started_round: Some(round_id),
+            ..Default::default()
+        };
+
+        self.advance_round(&mut output)?;
+
+        Ok(output)
+    }
+
+    /// Returns true if the key set is being or was already reshared to
+    /// `members`
+    fn is_resharing_to(&self, members: &BTreeSet<NodeId>) -> bool {
+        let is_resharing = self
+            .dkg_state
+            .reshare()
+            .as_ref()
+            .map(|reshare| reshare.members() == members)
+            .unwrap_or(false);
+
+        let is_completed = self
+            .round()
+            .map(|round| round.is_completed() && round.members() == members)
+            .unwrap_or(false);
+
+        is_resharing || is_completed
+    }
+
+    /// Key material of the last completed round, which can be reshared
+    fn completed_key_share(&self) -> Option<DealtKeyShare> {
+        let round = self.round().filter(|round| round.is_completed())?;
+
+        Some(DealtKeyShare {
+            members: round.members().clone(),
+            public_key_set: self.dkg_state.public_key_set_owned()?,
+            secret_key_share: self.dkg_state.secret_key_share_owned()?,
+        })
+    }
+
+    fn begin_reshare(
+        &mut self,
+        members: BTreeSet<NodeId>,
+        key_share: Option<DealtKeyShare>,
+    ) -> Result<DkgOutput> {
+        let is_member = members.contains(&self.node_id);
+
+        if key_share.is_none() && !is_member {
+            return Err(DkgError::ObserverNotAllowed);
+        }
+
+        let round_id = self.round().map(|round| round.id() + 1).unwrap_or_default();
+        let threshold = self.threshold_for(members.len());
+
+        self.add_peer_public_key(self.node_id(), self.get_public_key());
+
+        let mut output = DkgOutput {
+            public_key: Some(self.public_key_announcement()),
+            ..Default::default()
+        };
+
+        if is_member {
+            if let Some(round) = self.round().cloned() {
+                self.drop_handled_commitments(&round);
+            }
+
+            let mut round = DkgRound::new(round_id, members.clone(), BTreeSet::new(), threshold);
+            round.set_phase(DkgPhase::Resharing);
+
+            self.dkg_state.set_public_key_set(None);
+            self.dkg_state.set_secret_key_share(None);
+            self.dkg_state.set_round(Some(round));
+
+            output.started_round = Some(round_id);
+        }
+
+        self.dkg_state.set_reshare(Some(KeyReshare::new(
+            round_id, members, threshold, key_share,
+        )));
+
+        self.advance_reshare(&mut output)?;
+
+        Ok(output)
+    }
+
+    fn advance_reshare(&mut self, output: &mut DkgOutput) -> Result<()> {
+        let mut reshare = match self.dkg_state.reshare_mut().take() {
+            Some(reshare) => reshare,
+            None => return Ok(()),
+        };
+
+        let is_member = reshare.members().contains(&self.node_id);
+
+        let member_keys = self
+            .dkg_state
+            .peer_public_keys()
+            .iter()
+            .filter(|(node_id, _)| reshare.members().contains(*node_id))
+            .map(|(node_id, public_key)| (node_id.clone(), *public_key))
+            .collect::<BTreeMap<NodeId, PublicKey>>();
+
+        if member_keys.len() == reshare.members().len() {
+            if let Some(key_share) = reshare.take_key_share() {
+                let mut rng = OsRng::new().map_err(|err| DkgError::Unknown(err.to_string()))?;
+                let part = reshare::deal(
+                    &self.node_id,
+                    &key_share,
+                    &member_keys,
+                    reshare.threshold(),
+                    &mut rng,
+                )?;
+
+                if is_member {
+                    reshare.insert_part(self.node_id(), part.clone());
+                }
+
+                output.reshare_part = Some(part);
+            }
+        }
+
+        if !is_member {
+            if reshare.is_dealing() {
+                self.dkg_state.set_reshare(Some(reshare));
+            } else {
+                // NOTE: members leaving the quorum drop their key share once
+                // it was dealt to the new members
+                self.dkg_state.set_public_key_set(None);
+                self.dkg_state.set_secret_key_share(None);
+                self.dkg_state.set_round(None);
+            }
+
+            return Ok(());
+        }
+
+        let dealers = match reshare.dealer_subset() {
+            Some(dealers) => dealers,
+            None => {
+                self.dkg_state.set_reshare(Some(reshare));
+                return Ok(());
+            },
+        };
+
+        let (public_key_set, secret_key_share) =
+            match reshare::combine(&self.node_id, &self.secret_key, &reshare, &dealers) {
+                Ok(keys) => keys,
+                Err(err) => {
+                    self.dkg_state.set_reshare(Some(reshare));
+                    return Err(err);
+                },
+            };
+
+        if let Some(round) = self.dkg_state.round_mut() {
+            round.set_phase(DkgPhase::Completed);
+        }
+
+        self.dkg_state
+            .set_public_key_set(Some(public_key_set.clone()));
+        self.dkg_state.set_secret_key_share(Some(secret_key_share));
+
+        output.public_key_set = Some(public_key_set);
+        output.reshared = true;
+
+        Ok(())
+    }
+
+    fn advance_round(&mut self, output: &mut DkgOutput) -> Result<()> {
+        let mut round = match self.dkg_state.round_mut().take() {
+            Some(round) => round,
+            None => return Ok(()),
+        };
+
+        let result = self.advance(&mut round, output);
+        self.dkg_state.set_round(Some(round));
+
+        result
+    }
+
+    fn advance(&mut self, round: &mut DkgRound, output: &mut DkgOutput) -> Result<()> {
+        if round.phase() == &DkgPhase::CollectingPublicKeys {
+            let peer_public_keys = self
+                .dkg_state
+                .peer_public_keys()
+                .iter()
+                .filter(|(node_id, _)| round.is_member(node_id))
+                .map(|(node_id, public_key)| (node_id.clone(), *public_key))
+                .collect::<BTreeMap<NodeId, PublicKey>>();
+
+            if peer_public_keys.len() < round.members().len() {
+                return Ok(());
+            }
+
+            let node_id = self.node_id();
+            let mut rng = OsRng::new().map_err(|err| DkgError::Unknown(err.to_string()))?;
+
+            let (sync_key_gen, part) = SyncKeyGen::new(
+                node_id.clone(),
+                self.secret_key.clone(),
+                Arc::new(peer_public_keys),
+                round.threshold(),
+                &mut rng,
+            )
+            .map_err(|err| {
+                DkgError::SyncKeyGenError(format!(
+                    "Failed to create instance for node {node_id:?}: {err}"
+                ))
+            })?;
+
+            let part = part.ok_or(DkgError::PartCommitmentNotGenerated)?;
+
+            self.dkg_state.set_random_number_gen(Some(rng));
+            self.dkg_state.set_sync_key_gen(Some(sync_key_gen));
+            self.dkg_state
+                .part_message_store_mut()
+                .insert(node_id, part.clone());
+
+            output.part = Some(part);
+            round.set_phase(DkgPhase::CollectingCommitments);
+        }
+
+        if round.phase() != &DkgPhase::CollectingCommitments {
+            return Ok(());
+        }
+
+        let parts = self
+            .dkg_state
+            .part_message_store()
+            .keys()
+            .filter(|sender_id| round.is_member(sender_id) && !round.has_handled_part(sender_id))
+            .cloned()
+            .collect::<Vec<SenderId>>();
+
+        for sender_id in parts {
+            match self.ack_partial_commitment(sender_id.clone()) {
+                Ok((_, _, ack)) => {
+                    round.part_handled(sender_id.clone());
+                    output.acks.push((sender_id, ack));
+                },
+                Err(DkgError::InvalidPartMessage(_)) => {
+                    // NOTE: the sender gets excluded if it doesn't send a valid part
+                    // before the round times out
+                    self.dkg_state.part_message_store_mut().remove(&sender_id);
+                },
+                Err(err) => return Err(err),
+            }
+        }
+
+        let acks = self
+            .dkg_state
+            .ack_message_store()
+            .iter()
+            .filter(|((receiver_id, sender_id), _)| {
+                round.is_member(receiver_id)
+                    && round.has_handled_part(sender_id)
+                    && !round.has_handled_ack(receiver_id, sender_id)
+            })
+            .map(|(key, ack)| (key.clone(), ack.clone()))
+            .collect::<Vec<((ReceiverId, SenderId), Ack)>>();
+
+        let keygen = self
+            .dkg_state
+            .sync_key_gen_mut()
+            .as_mut()
+            .ok_or(DkgError::SyncKeyGenInstanceNotCreated)?;
+
+        let mut invalid_acks = vec![];
+
+        for ((receiver_id, sender_id), ack) in acks {
+            match keygen.handle_ack(&receiver_id, ack) {
+                Ok(AckOutcome::Valid) => round.ack_handled(receiver_id, sender_id),
+                _ => invalid_acks.push((receiver_id, sender_id)),
+            }
+        }
+
+        for key in invalid_acks {
+            self.dkg_state.ack_message_store_mut().remove(&key);
+        }
+
+        if round.has_all_commitments() {
+            let excluded = round.excluded().clone();
+            self.complete_round(round, excluded, output)?;
+        }
+
+        Ok(())
+    }
+
+    fn complete_round(
+        &mut self,
+        round: &mut DkgRound,
+        excluded: BTreeSet<NodeId>,
+        output: &mut DkgOutput,
+    ) -> Result<()> {
+        let public_key_set = self
+            .generate_key_sets()?
+            .ok_or(DkgError::NotEnoughPartsCompleted)?;
+
+        self.dkg_state.clear_commitments();
+        round.set_phase(DkgPhase::Completed);
+
+        output.public_key_set = Some(public_key_set);
+        output.excluded = excluded;
+
+        Ok(())
+    }
 }
 
 impl DkgGenerator for DkgEngine {
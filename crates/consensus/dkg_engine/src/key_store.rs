@@ -0,0 +1,170 @@
+use std::{
+    collections::BTreeSet,
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+use hbbft::crypto::{serde_impl::SerdeSecret, PublicKeySet, SecretKey, SecretKeyShare};
+use primitives::NodeId;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaChaRng;
+use ring::{
+    aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN},
+    hkdf,
+    rand::{SecureRandom, SystemRandom},
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{DkgError, Result};
+
+const KEY_SHARE_FILE_NAME: &str = "key_share";
+const KEY_SHARE_SALT: &[u8] = b"vrrb-dkg-key-share";
+const DKG_SECRET_KEY_DOMAIN: &[u8] = b"vrrb-dkg-secret-key";
+
+/// Derives the DKG secret key of a node from its validator secret key, so
+/// the key announced to the quorum stays the same across restarts. The key is
+/// drawn from ChaCha20, whose output is fixed for a given seed, unlike
+/// `StdRng` which may change algorithm between releases of `rand`.
+pub fn derive_dkg_secret_key(validator_secret: &[u8]) -> SecretKey {
+    let mut hasher = Sha256::new();
+    hasher.update(DKG_SECRET_KEY_DOMAIN);
+    hasher.update(validator_secret);
+
+    let mut rng = ChaChaRng::from_seed(hasher.finalize().into());
+
+    rng.gen()
+}
+
+/// Key material a node holds for its quorum once a DKG round completed
+#[derive(Debug, Clone)]
+pub struct QuorumKeyShare {
+    pub node_id: NodeId,
+    pub members: BTreeSet<NodeId>,
+    pub public_key_set: PublicKeySet,
+    pub secret_key_share: SecretKeyShare,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KeyShareRecord {
+    node_id: NodeId,
+    members: BTreeSet<NodeId>,
+    public_key_set: PublicKeySet,
+    secret_key_share: SerdeSecret<SecretKeyShare>,
+}
+
+/// Stores the quorum key share of a node on disk, encrypted with
+/// ChaCha20-Poly1305 under a key derived from the node's validator secret key.
+#[derive(Clone)]
+pub struct KeyShareStore {
+    path: PathBuf,
+    encryption_key: [u8; 32],
+}
+
+impl fmt::Debug for KeyShareStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyShareStore")
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+impl KeyShareStore {
+    pub fn new(dir: impl Into<PathBuf>, validator_secret: &[u8]) -> Result<Self> {
+        let mut encryption_key = [0u8; 32];
+
+        hkdf::Salt::new(hkdf::HKDF_SHA256, KEY_SHARE_SALT)
+            .extract(validator_secret)
+            .expand(&[KEY_SHARE_FILE_NAME.as_bytes()], &CHACHA20_POLY1305)
+            .and_then(|okm| okm.fill(&mut encryption_key))
+            .map_err(|_| DkgError::KeyStore("failed to derive encryption key".into()))?;
+
+        Ok(Self {
+            path: dir.into().join(KEY_SHARE_FILE_NAME),
+            encryption_key,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Encrypts and writes the key share to disk, replacing the previous one
+    pub fn save(&self, key_share: &QuorumKeyShare) -> Result<()> {
+        let record = KeyShareRecord {
+            node_id: key_share.node_id.clone(),
+            members: key_share.members.clone(),
+            public_key_set: key_share.public_key_set.clone(),
+            secret_key_share: SerdeSecret(key_share.secret_key_share.clone()),
+        };
+
+        let mut in_out =
+            bincode::serialize(&record).map_err(|err| DkgError::KeyStore(err.to_string()))?;
+
+        let mut nonce = [0u8; NONCE_LEN];
+        SystemRandom::new()
+            .fill(&mut nonce)
+            .map_err(|_| DkgError::KeyStore("failed to generate nonce".into()))?;
+
+        self.key()?
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(key_share.node_id.as_bytes()),
+                &mut in_out,
+            )
+            .map_err(|_| DkgError::KeyStore("failed to encrypt key share".into()))?;
+
+        let mut contents = nonce.to_vec();
+        contents.extend(in_out);
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(|err| DkgError::KeyStore(err.to_string()))?;
+        }
+
+        let tmp_path = self.path.with_extension("tmp");
+        fs::write(&tmp_path, contents).map_err(|err| DkgError::KeyStore(err.to_string()))?;
+        fs::rename(&tmp_path, &self.path).map_err(|err| DkgError::KeyStore(err.to_string()))?;
+
+        Ok(())
+    }
+
+    /// Reads and decrypts the key share of `node_id`, if one was saved
+    pub fn load(&self, node_id: &NodeId) -> Result<Option<QuorumKeyShare>> {
+        if !self.path.exists() {
+            return Ok(None);
+        }
+
+        let contents = fs::read(&self.path).map_err(|err| DkgError::KeyStore(err.to_string()))?;
+
+        if contents.len() < NONCE_LEN {
+            return Err(DkgError::KeyStore("key share file is truncated".into()));
+        }
+
+        let (nonce, ciphertext) = contents.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce)
+            .map_err(|_| DkgError::KeyStore("invalid nonce".into()))?;
+
+        let mut in_out = ciphertext.to_vec();
+        let plaintext = self
+            .key()?
+            .open_in_place(nonce, Aad::from(node_id.as_bytes()), &mut in_out)
+            .map_err(|_| DkgError::KeyStore("failed to decrypt key share".into()))?;
+
+        let record: KeyShareRecord =
+            bincode::deserialize(plaintext).map_err(|err| DkgError::KeyStore(err.to_string()))?;
+
+        Ok(Some(QuorumKeyShare {
+            node_id: record.node_id,
+            members: record.members,
+            public_key_set: record.public_key_set,
+            secret_key_share: record.secret_key_share.0,
+        }))
+    }
+
+    fn key(&self) -> Result<LessSafeKey> {
+        let key = UnboundKey::new(&CHACHA20_POLY1305, &self.encryption_key)
+            .map_err(|_| DkgError::KeyStore("invalid encryption key".into()))?;
+
+        Ok(LessSafeKey::new(key))
+    }
+}
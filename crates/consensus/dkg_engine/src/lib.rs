@@ -1,301 +1,760 @@
-// pub mod dkg;
-// pub mod dkg_state;
-// pub mod engine;
-// pub mod result;
-// pub mod test_utils;
-
-// pub use crate::result::*;
-
-// pub mod prelude {
-//     pub use crate::dkg::*;
-//     pub use crate::dkg_state::*;
-//     pub use crate::engine::*;
-// }
-
-// #[cfg(test)]
-// mod tests {
-//     use std::{borrow::BorrowMut, collections::HashMap};
-
-//     use hbbft::sync_key_gen::Ack;
-//     use primitives::{NodeId, NodeType};
-//     use vrrb_core::is_enum_variant;
-
-//     use crate::dkg::DkgGenerator;
-//     use crate::{prelude::*, result::DkgError, test_utils::generate_dkg_engines};
-
-//     #[tokio::test]
-//     #[ignore]
-//     async fn failed_to_generate_part_commitment_message_since_only_master_node_allowed() {
-//         let mut dkg_engines = generate_dkg_engines(4, NodeType::Miner).await;
-//         let dkg_engine = dkg_engines.get_mut(0).unwrap();
-//         let result = dkg_engine.generate_partial_commitment(1);
-
-//         assert!(result.is_err());
-//         assert!(is_enum_variant!(result, Err(DkgError::InvalidNode { .. })));
-//     }
-
-//     #[tokio::test]
-//     async fn generate_part_commitment_message() {
-//         let mut dkg_engines = generate_dkg_engines(4, NodeType::MasterNode).await;
-//         let dkg_engine = dkg_engines.get_mut(0).unwrap();
-//         let (part, _) = dkg_engine.generate_partial_commitment(1).unwrap();
-//     }
-
-//     #[tokio::test]
-//     async fn successfull_acknowledge_part_commitment_message() {
-//         let mut dkg_engines = generate_dkg_engines(4, NodeType::MasterNode).await;
-//         let dkg_engine = dkg_engines.get_mut(0).unwrap();
-//         let _ = dkg_engine.generate_partial_commitment(1);
-//         dkg_engine
-//             .ack_partial_commitment(String::from("node-0"))
-//             .unwrap();
-//     }
-
-//     #[tokio::test]
-//     async fn failed_to_acknowledge_part_commitment_missing_commitment() {
-//         let mut dkg_engines = generate_dkg_engines(4, NodeType::MasterNode).await;
-//         let dkg_engine = dkg_engines.get_mut(0).unwrap();
-//         let _ = dkg_engine.generate_partial_commitment(1).unwrap();
-//         let result = dkg_engine.ack_partial_commitment(String::from("node-1"));
-//         assert!(result.is_err());
-//         assert!(is_enum_variant!(
-//             result,
-//             Err(DkgError::PartMsgMissingForNode { .. })
-//         ));
-//     }
-
-//     #[tokio::test]
-//     async fn failed_to_acknowledge_part_commitment_missing_syncgen_instance() {
-//         let mut dkg_engines = generate_dkg_engines(4, NodeType::MasterNode).await;
-//         let dkg_engine = dkg_engines.get_mut(0).unwrap();
-//         let result = dkg_engine.ack_partial_commitment(String::from("node-0"));
-
-//         assert!(result.is_err());
-//         assert!(is_enum_variant!(result, Err(DkgError::Unknown { .. })));
-//     }
-
-//     #[tokio::test]
-//     async fn successfull_acknowledge_all_acks() {
-//         let mut dkg_engines = generate_dkg_engines(4, NodeType::MasterNode).await;
-//         let mut dkg_engine_node4 = dkg_engines.pop().unwrap();
-//         let mut dkg_engine_node3 = dkg_engines.pop().unwrap();
-//         let mut dkg_engine_node2 = dkg_engines.pop().unwrap();
-//         let mut dkg_engine_node1 = dkg_engines.pop().unwrap();
-
-//         let (_, id_1) = dkg_engine_node1.generate_partial_commitment(1).unwrap();
-//         let (_, id_2) = dkg_engine_node2.generate_partial_commitment(1).unwrap();
-//         let (_, id_3) = dkg_engine_node3.generate_partial_commitment(1).unwrap();
-//         let (_, id_4) = dkg_engine_node4.generate_partial_commitment(1).unwrap();
-
-//         add_part_commitment_to_node_dkg_state(
-//             dkg_engine_node1.borrow_mut(),
-//             dkg_engine_node2.borrow_mut(),
-//             id_1,
-//         );
-
-//         add_part_commitment_to_node_dkg_state(
-//             dkg_engine_node1.borrow_mut(),
-//             dkg_engine_node3.borrow_mut(),
-//             id_2,
-//         );
-
-//         add_part_commitment_to_node_dkg_state(
-//             dkg_engine_node1.borrow_mut(),
-//             dkg_engine_node4.borrow_mut(),
-//             id_3,
-//         );
-
-//         let _ = dkg_engine_node1.ack_partial_commitment(String::from("node-0"));
-//         let _ = dkg_engine_node1.ack_partial_commitment(String::from("node-1"));
-//         let _ = dkg_engine_node1.ack_partial_commitment(String::from("node-2"));
-//         let _ = dkg_engine_node1.ack_partial_commitment(String::from("node-3"));
-
-//         let result = dkg_engine_node1.handle_ack_messages();
-
-//         assert!(result.is_ok());
-//         assert!(is_enum_variant!(result, Ok(())));
-//     }
-
-//     #[tokio::test]
-//     async fn successful_generations_of_key_sets() {
-//         let mut dkg_engines = generate_dkg_engines(5, NodeType::MasterNode).await;
-//         let mut dkg_engine_node4 = dkg_engines.pop().unwrap();
-//         let mut dkg_engine_node3 = dkg_engines.pop().unwrap();
-//         let mut dkg_engine_node2 = dkg_engines.pop().unwrap();
-//         let mut dkg_engine_node1 = dkg_engines.pop().unwrap();
-
-//         let (part_commitment_node1, id_1) =
-//             dkg_engine_node1.generate_partial_commitment(1).unwrap();
-
-//         let (part_commitment_node2, id_2) =
-//             dkg_engine_node2.generate_partial_commitment(1).unwrap();
-
-//         let (part_commitment_node3, id_3) =
-//             dkg_engine_node3.generate_partial_commitment(1).unwrap();
-
-//         let (part_commitment_node4, id_4) =
-//             dkg_engine_node4.generate_partial_commitment(1).unwrap();
-
-//         let part_commitment_tuples = vec![
-//             (part_commitment_node1, id_1),
-//             (part_commitment_node2, id_2),
-//             (part_commitment_node3, id_3),
-//             (part_commitment_node4, id_4),
-//         ];
-
-//         for (part, node_id) in part_commitment_tuples.iter() {
-//             if node_id.to_string() != dkg_engine_node1.node_id() {
-//                 dkg_engine_node1
-//                     .dkg_state
-//                     .part_message_store_mut()
-//                     .insert(node_id.to_owned(), part.clone());
-//             }
-
-//             if node_id.to_string() != dkg_engine_node2.node_id() {
-//                 dkg_engine_node2
-//                     .dkg_state
-//                     .part_message_store_mut()
-//                     .insert(node_id.to_owned(), part.clone());
-//             }
-
-//             if node_id.to_string() != dkg_engine_node3.node_id() {
-//                 dkg_engine_node3
-//                     .dkg_state
-//                     .part_message_store_mut()
-//                     .insert(node_id.to_owned(), part.clone());
-//             }
-
-//             if node_id.to_string() != dkg_engine_node4.node_id() {
-//                 dkg_engine_node4
-//                     .dkg_state
-//                     .part_message_store_mut()
-//                     .insert(node_id.to_owned(), part.clone());
-//             }
-//         }
-
-//         // println!(
-//         //     "Node 1{:?}",
-//         //     dkg_engine_node1.dkg_state.part_message_store()
-//         // );
-//         //
-//         // println!(
-//         //     "Node 2{:?}",
-//         //     dkg_engine_node2.dkg_state.part_message_store()
-//         // );
-//         //
-//         // println!(
-//         //     "Node 3{:?}",
-//         //     dkg_engine_node3.dkg_state.part_message_store()
-//         // );
-//         //
-//         // println!(
-//         //     "Node 4{:?}",
-//         //     dkg_engine_node4.dkg_state.part_message_store()
-//         // );
-//         //
-//         // let dkg_engine_node1_acks=vec![];
-//         for i in 1..=4 {
-//             let _ = dkg_engine_node1
-//                 .ack_partial_commitment(format!("node-{}", i))
-//                 .unwrap();
-//             //println!("Node 1 Ack Part Committment status {:?}", s);
-
-//             let _ = dkg_engine_node2
-//                 .ack_partial_commitment(format!("node-{}", i))
-//                 .unwrap();
-//             //  println!("Node 2 Ack Part Committment status {:?}", s);
-
-//             let _ = dkg_engine_node3
-//                 .ack_partial_commitment(format!("node-{}", i))
-//                 .unwrap();
-//             //println!("Node 3 Ack Part Committment status{:?}", s);
-
-//             let _ = dkg_engine_node4
-//                 .ack_partial_commitment(format!("node-{}", i))
-//                 .unwrap();
-//             // println!("Node 4 Ack Part Committment status {:?}", s);
-//         }
-
-//         // println!(
-//         //     "Node 1 {:?}",
-//         //     dkg_engine_node1.dkg_state.ack_message_store()
-//         // );
-//         //
-//         // println!(
-//         //     "Node 2 {:?}",
-//         //     dkg_engine_node2.dkg_state.ack_message_store()
-//         // );
-//         //
-//         // println!(
-//         //     "Node 3 {:?}",
-//         //     dkg_engine_node3.dkg_state.ack_message_store()
-//         // );
-//         //
-//         // println!(
-//         //     "Node 4 {:?}",
-//         //     dkg_engine_node4.dkg_state.ack_message_store()
-//         // );
-
-//         let mut new_store: HashMap<(NodeId, SenderId), Ack>;
-
-//         new_store = dkg_engine_node1
-//             .dkg_state
-//             .ack_message_store_mut()
-//             .clone()
-//             .into_iter()
-//             .chain(dkg_engine_node2.dkg_state.ack_message_store().clone())
-//             .collect();
-
-//         new_store = new_store
-//             .into_iter()
-//             .chain(dkg_engine_node3.dkg_state.ack_message_store().clone())
-//             .collect();
-
-//         new_store = new_store
-//             .into_iter()
-//             .chain(dkg_engine_node4.dkg_state.ack_message_store().clone())
-//             .collect();
-
-//         dkg_engine_node1
-//             .dkg_state
-//             .set_ack_message_store(new_store.clone());
-
-//         dkg_engine_node2
-//             .dkg_state
-//             .set_ack_message_store(new_store.clone());
-
-//         dkg_engine_node3
-//             .dkg_state
-//             .set_ack_message_store(new_store.clone());
-
-//         dkg_engine_node4
-//             .dkg_state
-//             .set_ack_message_store(new_store.clone());
-
-//         for _ in 0..4 {
-//             dkg_engine_node1.handle_ack_messages().unwrap();
-//             dkg_engine_node2.handle_ack_messages().unwrap();
-//             dkg_engine_node3.handle_ack_messages().unwrap();
-//             dkg_engine_node4.handle_ack_messages().unwrap();
-//         }
-
-//         let result = dkg_engine_node1.generate_key_sets();
-
-//         assert!(result.is_ok());
-//         assert!(dkg_engine_node1.dkg_state.public_key_set().is_some());
-//         assert!(dkg_engine_node1.dkg_state.secret_key_share().is_some());
-//     }
-
-//     fn add_part_commitment_to_node_dkg_state(
-//         dkg_engine_node1: &mut DkgEngine,
-//         dkg_engine_node2: &mut DkgEngine,
-//         node_id: NodeId,
-//     ) {
-//         let (part, _) = dkg_engine_node2.generate_partial_commitment(1).unwrap();
-
-//         dkg_engine_node1
-//             .dkg_state
-//             .part_message_store_mut()
-//             .insert(node_id, part);
-//     }
-// }
+pub mod dkg;
+pub mod dkg_state;
+pub mod engine;
+pub mod key_store;
+pub mod messages;
+pub mod reshare;
+pub mod result;
+pub mod round;
+pub mod test_utils;
+
+pub use crate::result::*;
+
+pub mod prelude {
+    pub use crate::dkg::*;
+    pub use crate::dkg_state::*;
+    pub use crate::engine::*;
+    pub use crate::key_store::*;
+    pub use crate::messages::*;
+    pub use crate::reshare::*;
+    pub use crate::round::*;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        borrow::BorrowMut,
+        collections::{BTreeMap, BTreeSet, HashMap, VecDeque},
+        env,
+    };
+
+    use hbbft::sync_key_gen::Ack;
+    use primitives::{NodeId, NodeType};
+    use vrrb_core::is_enum_variant;
+
+    use crate::{
+        prelude::*,
+        reshare::{self, DealtKeyShare},
+        result::DkgError,
+        test_utils::{generate_dkg_engines, generate_quorum_dkg_engines},
+    };
+
+    #[tokio::test]
+    #[ignore]
+    async fn failed_to_generate_part_commitment_message_since_only_master_node_allowed() {
+        let mut dkg_engines = generate_dkg_engines(4, NodeType::Miner).await;
+        let dkg_engine = dkg_engines.get_mut(0).unwrap();
+        let result = dkg_engine.generate_partial_commitment(1);
+
+        assert!(result.is_err());
+        assert!(is_enum_variant!(result, Err(DkgError::InvalidNode { .. })));
+    }
+
+    #[tokio::test]
+    async fn generate_part_commitment_message() {
+        let mut dkg_engines = generate_dkg_engines(4, NodeType::MasterNode).await;
+        let dkg_engine = dkg_engines.get_mut(0).unwrap();
+        let (_part, _) = dkg_engine.generate_partial_commitment(1).unwrap();
+    }
+
+    #[tokio::test]
+    async fn successfull_acknowledge_part_commitment_message() {
+        let mut dkg_engines = generate_dkg_engines(4, NodeType::MasterNode).await;
+        let dkg_engine = dkg_engines.get_mut(0).unwrap();
+        let _ = dkg_engine.generate_partial_commitment(1);
+        dkg_engine
+            .ack_partial_commitment(String::from("node-0"))
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn failed_to_acknowledge_part_commitment_missing_commitment() {
+        let mut dkg_engines = generate_dkg_engines(4, NodeType::MasterNode).await;
+        let dkg_engine = dkg_engines.get_mut(0).unwrap();
+        let _ = dkg_engine.generate_partial_commitment(1).unwrap();
+        let result = dkg_engine.ack_partial_commitment(String::from("node-1"));
+        assert!(result.is_err());
+        assert!(is_enum_variant!(
+            result,
+            Err(DkgError::PartMsgMissingForNode { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn failed_to_acknowledge_part_commitment_missing_syncgen_instance() {
+        let mut dkg_engines = generate_dkg_engines(4, NodeType::MasterNode).await;
+        let dkg_engine = dkg_engines.get_mut(0).unwrap();
+        let result = dkg_engine.ack_partial_commitment(String::from("node-0"));
+
+        assert!(result.is_err());
+        assert!(is_enum_variant!(result, Err(DkgError::Unknown { .. })));
+    }
+
+    #[tokio::test]
+    async fn successfull_acknowledge_all_acks() {
+        let mut dkg_engines = generate_dkg_engines(4, NodeType::MasterNode).await;
+        let mut dkg_engine_node4 = dkg_engines.pop().unwrap();
+        let mut dkg_engine_node3 = dkg_engines.pop().unwrap();
+        let mut dkg_engine_node2 = dkg_engines.pop().unwrap();
+        let mut dkg_engine_node1 = dkg_engines.pop().unwrap();
+
+        let (_, id_1) = dkg_engine_node1.generate_partial_commitment(1).unwrap();
+        let (_, id_2) = dkg_engine_node2.generate_partial_commitment(1).unwrap();
+        let (_, id_3) = dkg_engine_node3.generate_partial_commitment(1).unwrap();
+        let (_, _id_4) = dkg_engine_node4.generate_partial_commitment(1).unwrap();
+
+        add_part_commitment_to_node_dkg_state(
+            dkg_engine_node1.borrow_mut(),
+            dkg_engine_node2.borrow_mut(),
+            id_1,
+        );
+
+        add_part_commitment_to_node_dkg_state(
+            dkg_engine_node1.borrow_mut(),
+            dkg_engine_node3.borrow_mut(),
+            id_2,
+        );
+
+        add_part_commitment_to_node_dkg_state(
+            dkg_engine_node1.borrow_mut(),
+            dkg_engine_node4.borrow_mut(),
+            id_3,
+        );
+
+        let _ = dkg_engine_node1.ack_partial_commitment(String::from("node-0"));
+        let _ = dkg_engine_node1.ack_partial_commitment(String::from("node-1"));
+        let _ = dkg_engine_node1.ack_partial_commitment(String::from("node-2"));
+        let _ = dkg_engine_node1.ack_partial_commitment(String::from("node-3"));
+
+        let result = dkg_engine_node1.handle_ack_messages();
+
+        assert!(result.is_ok());
+        assert!(is_enum_variant!(result, Ok(())));
+    }
+
+    #[tokio::test]
+    async fn successful_generations_of_key_sets() {
+        let mut dkg_engines = generate_dkg_engines(5, NodeType::MasterNode).await;
+        let mut dkg_engine_node4 = dkg_engines.pop().unwrap();
+        let mut dkg_engine_node3 = dkg_engines.pop().unwrap();
+        let mut dkg_engine_node2 = dkg_engines.pop().unwrap();
+        let mut dkg_engine_node1 = dkg_engines.pop().unwrap();
+
+        let (part_commitment_node1, id_1) =
+            dkg_engine_node1.generate_partial_commitment(1).unwrap();
+
+        let (part_commitment_node2, id_2) =
+            dkg_engine_node2.generate_partial_commitment(1).unwrap();
+
+        let (part_commitment_node3, id_3) =
+            dkg_engine_node3.generate_partial_commitment(1).unwrap();
+
+        let (part_commitment_node4, id_4) =
+            dkg_engine_node4.generate_partial_commitment(1).unwrap();
+
+        let part_commitment_tuples = vec![
+            (part_commitment_node1, id_1),
+            (part_commitment_node2, id_2),
+            (part_commitment_node3, id_3),
+            (part_commitment_node4, id_4),
+        ];
+
+        for (part, node_id) in part_commitment_tuples.iter() {
+            if node_id.to_string() != dkg_engine_node1.node_id() {
+                dkg_engine_node1
+                    .dkg_state
+                    .part_message_store_mut()
+                    .insert(node_id.to_owned(), part.clone());
+            }
+
+            if node_id.to_string() != dkg_engine_node2.node_id() {
+                dkg_engine_node2
+                    .dkg_state
+                    .part_message_store_mut()
+                    .insert(node_id.to_owned(), part.clone());
+            }
+
+            if node_id.to_string() != dkg_engine_node3.node_id() {
+                dkg_engine_node3
+                    .dkg_state
+                    .part_message_store_mut()
+                    .insert(node_id.to_owned(), part.clone());
+            }
+
+            if node_id.to_string() != dkg_engine_node4.node_id() {
+                dkg_engine_node4
+                    .dkg_state
+                    .part_message_store_mut()
+                    .insert(node_id.to_owned(), part.clone());
+            }
+        }
+
+        for i in 1..=4 {
+            let _ = dkg_engine_node1
+                .ack_partial_commitment(format!("node-{}", i))
+                .unwrap();
+
+            let _ = dkg_engine_node2
+                .ack_partial_commitment(format!("node-{}", i))
+                .unwrap();
+
+            let _ = dkg_engine_node3
+                .ack_partial_commitment(format!("node-{}", i))
+                .unwrap();
+
+            let _ = dkg_engine_node4
+                .ack_partial_commitment(format!("node-{}", i))
+                .unwrap();
+        }
+
+        let mut new_store: HashMap<(NodeId, SenderId), Ack>;
+
+        new_store = dkg_engine_node1
+            .dkg_state
+            .ack_message_store_mut()
+            .clone()
+            .into_iter()
+            .chain(dkg_engine_node2.dkg_state.ack_message_store().clone())
+            .collect();
+
+        new_store = new_store
+            .into_iter()
+            .chain(dkg_engine_node3.dkg_state.ack_message_store().clone())
+            .collect();
+
+        new_store = new_store
+            .into_iter()
+            .chain(dkg_engine_node4.dkg_state.ack_message_store().clone())
+            .collect();
+
+        dkg_engine_node1
+            .dkg_state
+            .set_ack_message_store(new_store.clone());
+
+        dkg_engine_node2
+            .dkg_state
+            .set_ack_message_store(new_store.clone());
+
+        dkg_engine_node3
+            .dkg_state
+            .set_ack_message_store(new_store.clone());
+
+        dkg_engine_node4
+            .dkg_state
+            .set_ack_message_store(new_store.clone());
+
+        for _ in 0..4 {
+            dkg_engine_node1.handle_ack_messages().unwrap();
+            dkg_engine_node2.handle_ack_messages().unwrap();
+            dkg_engine_node3.handle_ack_messages().unwrap();
+            dkg_engine_node4.handle_ack_messages().unwrap();
+        }
+
+        let result = dkg_engine_node1.generate_key_sets();
+
+        assert!(result.is_ok());
+        assert!(dkg_engine_node1.dkg_state.public_key_set().is_some());
+        assert!(dkg_engine_node1.dkg_state.secret_key_share().is_some());
+    }
+
+    #[test]
+    fn dkg_round_generates_the_same_key_set_for_every_member() {
+        let mut dkg_engines = generate_quorum_dkg_engines(4, NodeType::Validator);
+        let members = quorum_members(&dkg_engines);
+
+        for i in 0..dkg_engines.len() {
+            let output = dkg_engines[i].start_round(members.clone()).unwrap();
+            let node_id = dkg_engines[i].node_id();
+            multicast(&mut dkg_engines, node_id, output);
+        }
+
+        let public_key_set = dkg_engines[0].dkg_state.public_key_set_owned().unwrap();
+
+        for dkg_engine in dkg_engines.iter() {
+            assert!(dkg_engine.round().unwrap().is_completed());
+            assert_eq!(
+                dkg_engine.dkg_state.public_key_set_owned(),
+                Some(public_key_set.clone())
+            );
+            assert!(dkg_engine.dkg_state.secret_key_share().is_some());
+        }
+    }
+
+    #[test]
+    fn dkg_round_excludes_non_responsive_members_after_a_timeout() {
+        let mut dkg_engines = generate_quorum_dkg_engines(4, NodeType::Validator);
+        let members = quorum_members(&dkg_engines);
+
+        // node-3 never starts the round
+        let _offline = dkg_engines.pop().unwrap();
+
+        for i in 0..dkg_engines.len() {
+            let output = dkg_engines[i].start_round(members.clone()).unwrap();
+            let node_id = dkg_engines[i].node_id();
+            multicast(&mut dkg_engines, node_id, output);
+        }
+
+        for dkg_engine in dkg_engines.iter() {
+            assert_eq!(
+                dkg_engine.round().unwrap().phase(),
+                &DkgPhase::CollectingPublicKeys
+            );
+        }
+
+        for i in 0..dkg_engines.len() {
+            let round_id = dkg_engines[i].round().unwrap().id();
+            let output = dkg_engines[i].handle_round_timeout(round_id).unwrap();
+            let vote = output.timeout_vote.clone().unwrap();
+            assert_eq!(
+                vote.non_responsive,
+                BTreeSet::from([String::from("node-3")])
+            );
+
+            // the round only moves on once the responsive members agree
+            if i + 1 < dkg_engines.len() {
+                assert_eq!(dkg_engines[i].round().unwrap().id(), round_id);
+            }
+
+            let node_id = dkg_engines[i].node_id();
+            multicast(&mut dkg_engines, node_id, output);
+        }
+
+        for dkg_engine in dkg_engines.iter() {
+            let round = dkg_engine.round().unwrap();
+
+            assert!(round.is_completed());
+            assert_eq!(round.members().len(), 3);
+            assert_eq!(round.excluded(), &BTreeSet::from([String::from("node-3")]));
+            assert!(dkg_engine.dkg_state.public_key_set().is_some());
+        }
+    }
+
+    #[test]
+    fn a_single_member_cannot_exclude_others_from_a_round() {
+        let mut dkg_engines = generate_quorum_dkg_engines(4, NodeType::Validator);
+        let members = quorum_members(&dkg_engines);
+
+        for dkg_engine in dkg_engines.iter_mut() {
+            dkg_engine.start_round(members.clone()).unwrap();
+        }
+
+        let output = dkg_engines[0].handle_round_timeout(0).unwrap();
+        let node_id = dkg_engines[0].node_id();
+        multicast(&mut dkg_engines, node_id, output);
+
+        for dkg_engine in dkg_engines.iter() {
+            let round = dkg_engine.round().unwrap();
+
+            assert_eq!(round.id(), 0);
+            assert_eq!(round.members(), &members);
+        }
+    }
+
+    #[test]
+    fn dkg_public_keys_must_be_signed_by_the_announcing_member() {
+        let mut dkg_engines = generate_quorum_dkg_engines(2, NodeType::Validator);
+        let members = quorum_members(&dkg_engines);
+
+        dkg_engines[0].start_round(members).unwrap();
+
+        let forged = DkgPublicKeyAnnouncement::new(
+            dkg_engines[1].node_id(),
+            derive_dkg_secret_key(b"attacker").public_key(),
+            &dkg_engines[0].validator_secret_key,
+        );
+        let result = dkg_engines[0].handle_public_key(forged);
+        assert!(is_enum_variant!(
+            result,
+            Err(DkgError::InvalidSignature { .. })
+        ));
+
+        let unknown = DkgPublicKeyAnnouncement::new(
+            String::from("node-9"),
+            derive_dkg_secret_key(b"attacker").public_key(),
+            &dkg_engines[0].validator_secret_key,
+        );
+        let result = dkg_engines[0].handle_public_key(unknown);
+        assert!(is_enum_variant!(
+            result,
+            Err(DkgError::UnknownMember { .. })
+        ));
+
+        assert!(!dkg_engines[0]
+            .dkg_state
+            .peer_public_keys()
+            .contains_key("node-1"));
+
+        let announcement = dkg_engines[1].public_key_announcement();
+        dkg_engines[0].handle_public_key(announcement).unwrap();
+
+        assert_eq!(
+            dkg_engines[0].round().unwrap().phase(),
+            &DkgPhase::CollectingCommitments
+        );
+    }
+
+    #[test]
+    fn stale_round_timeouts_are_ignored() {
+        let mut dkg_engines = generate_quorum_dkg_engines(4, NodeType::Validator);
+        let members = quorum_members(&dkg_engines);
+
+        for i in 0..dkg_engines.len() {
+            let output = dkg_engines[i].start_round(members.clone()).unwrap();
+            let node_id = dkg_engines[i].node_id();
+            multicast(&mut dkg_engines, node_id, output);
+        }
+
+        let output = dkg_engines[0].handle_round_timeout(0).unwrap();
+
+        assert!(output.started_round.is_none());
+        assert!(output.public_key_set.is_none());
+        assert!(dkg_engines[0].round().unwrap().is_completed());
+    }
+
+    #[test]
+    fn key_shares_survive_a_restart_encrypted() {
+        let mut dkg_engines = generate_quorum_dkg_engines(4, NodeType::Validator);
+        let members = quorum_members(&dkg_engines);
+
+        for i in 0..dkg_engines.len() {
+            let output = dkg_engines[i].start_round(members.clone()).unwrap();
+            let node_id = dkg_engines[i].node_id();
+            multicast(&mut dkg_engines, node_id, output);
+        }
+
+        let dkg_engine = &dkg_engines[0];
+        let key_share = QuorumKeyShare {
+            node_id: dkg_engine.node_id(),
+            members: members.clone(),
+            public_key_set: dkg_engine.dkg_state.public_key_set_owned().unwrap(),
+            secret_key_share: dkg_engine.dkg_state.secret_key_share_owned().unwrap(),
+        };
+
+        let dir = env::temp_dir().join(format!("dkg-{}", rand::random::<u64>()));
+        let store = KeyShareStore::new(&dir, b"validator secret").unwrap();
+        store.save(&key_share).unwrap();
+
+        let contents = std::fs::read(store.path()).unwrap();
+        let plaintext = bincode::serialize(&key_share.public_key_set).unwrap();
+        assert!(!contents
+            .windows(plaintext.len())
+            .any(|window| window == plaintext.as_slice()));
+
+        let wrong_store = KeyShareStore::new(&dir, b"another secret").unwrap();
+        assert!(wrong_store.load(&dkg_engine.node_id()).is_err());
+
+        let loaded = store.load(&dkg_engine.node_id()).unwrap().unwrap();
+        assert_eq!(loaded.members, members);
+        assert_eq!(loaded.public_key_set, key_share.public_key_set);
+        assert_eq!(loaded.secret_key_share, key_share.secret_key_share);
+
+        let mut restarted = generate_quorum_dkg_engines(1, NodeType::Validator).remove(0);
+        restarted.restore_key_share(loaded);
+        assert!(restarted.round().unwrap().is_completed());
+        assert_eq!(
+            restarted.dkg_state.public_key_set_owned(),
+            Some(key_share.public_key_set)
+        );
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn dkg_secret_keys_are_derived_deterministically() {
+        let secret_key = derive_dkg_secret_key(b"validator secret");
+
+        assert_eq!(
+            secret_key.public_key(),
+            derive_dkg_secret_key(b"validator secret").public_key()
+        );
+        assert_ne!(
+            secret_key.public_key(),
+            derive_dkg_secret_key(b"another secret").public_key()
+        );
+    }
+
+    #[test]
+    fn resharing_keeps_the_quorum_public_key() {
+        let mut dkg_engines = generate_quorum_dkg_engines(6, NodeType::Validator);
+        let members = quorum_members(&dkg_engines[..4]);
+
+        for i in 0..4 {
+            let output = dkg_engines[i].start_round(members.clone()).unwrap();
+            let node_id = dkg_engines[i].node_id();
+            multicast(&mut dkg_engines, node_id, output);
+        }
+
+        let public_key = dkg_engines[0]
+            .dkg_state
+            .public_key_set_owned()
+            .unwrap()
+            .public_key();
+
+        // node-0 and node-1 leave the quorum, node-4 and node-5 join it
+        let next_members = quorum_members(&dkg_engines[2..]);
+
+        for i in 0..dkg_engines.len() {
+            let output = dkg_engines[i].start_reshare(next_members.clone()).unwrap();
+            let node_id = dkg_engines[i].node_id();
+            multicast(&mut dkg_engines, node_id, output);
+        }
+
+        for dkg_engine in dkg_engines[..2].iter() {
+            assert!(dkg_engine.round().is_none());
+            assert!(dkg_engine.dkg_state.secret_key_share().is_none());
+        }
+
+        let public_key_set = dkg_engines[2].dkg_state.public_key_set_owned().unwrap();
+        assert_eq!(public_key_set.public_key(), public_key);
+
+        let message = b"convergence block";
+        let mut signature_shares = BTreeMap::new();
+
+        for dkg_engine in dkg_engines[2..].iter() {
+            let round = dkg_engine.round().unwrap();
+            assert!(round.is_completed());
+            assert_eq!(round.members(), &next_members);
+            assert_eq!(
+                dkg_engine.dkg_state.public_key_set_owned(),
+                Some(public_key_set.clone())
+            );
+
+            let index = member_index(&next_members, &dkg_engine.node_id()).unwrap();
+            let signature_share = dkg_engine
+                .dkg_state
+                .secret_key_share_owned()
+                .unwrap()
+                .sign(message);
+
+            assert!(public_key_set
+                .public_key_share(index)
+                .verify(&signature_share, message));
+
+            signature_shares.insert(index, signature_share);
+        }
+
+        let signature = public_key_set
+            .combine_signatures(
+                signature_shares
+                    .iter()
+                    .take(2)
+                    .map(|(index, signature_share)| (*index, signature_share)),
+            )
+            .unwrap();
+
+        assert!(public_key.verify(&signature, message));
+    }
+
+    #[test]
+    fn resharing_skips_dealers_the_members_agree_are_missing() {
+        let mut dkg_engines = generate_quorum_dkg_engines(5, NodeType::Validator);
+        let members = quorum_members(&dkg_engines[..4]);
+
+        for i in 0..4 {
+            let output = dkg_engines[i].start_round(members.clone()).unwrap();
+            let node_id = dkg_engines[i].node_id();
+            multicast(&mut dkg_engines, node_id, output);
+        }
+
+        let public_key = dkg_engines[0]
+            .dkg_state
+            .public_key_set_owned()
+            .unwrap()
+            .public_key();
+
+        // node-0 goes offline before dealing its key share
+        let next_members = quorum_members(&dkg_engines[1..]);
+        let _offline = dkg_engines.remove(0);
+
+        for i in 0..dkg_engines.len() {
+            let output = dkg_engines[i].start_reshare(next_members.clone()).unwrap();
+            let node_id = dkg_engines[i].node_id();
+            multicast(&mut dkg_engines, node_id, output);
+        }
+
+        for i in 0..dkg_engines.len() {
+            let round = dkg_engines[i].round().unwrap();
+            assert_eq!(round.phase(), &DkgPhase::Resharing);
+
+            let round_id = round.id();
+            let output = dkg_engines[i].handle_round_timeout(round_id).unwrap();
+            assert_eq!(
+                output.timeout_vote.clone().unwrap().non_responsive,
+                BTreeSet::from([String::from("node-0")])
+            );
+
+            let node_id = dkg_engines[i].node_id();
+            multicast(&mut dkg_engines, node_id, output);
+        }
+
+        let public_key_set = dkg_engines[0].dkg_state.public_key_set_owned().unwrap();
+        assert_eq!(public_key_set.public_key(), public_key);
+
+        for dkg_engine in dkg_engines.iter() {
+            let round = dkg_engine.round().unwrap();
+
+            assert!(round.is_completed());
+            assert_eq!(round.members(), &next_members);
+            assert_eq!(
+                dkg_engine.dkg_state.public_key_set_owned(),
+                Some(public_key_set.clone())
+            );
+        }
+    }
+
+    #[test]
+    fn resharing_falls_back_to_a_new_dkg_round_without_enough_dealers() {
+        let mut dkg_engines = generate_quorum_dkg_engines(5, NodeType::Validator);
+        let members = quorum_members(&dkg_engines[..4]);
+
+        for i in 0..4 {
+            let output = dkg_engines[i].start_round(members.clone()).unwrap();
+            let node_id = dkg_engines[i].node_id();
+            multicast(&mut dkg_engines, node_id, output);
+        }
+
+        let public_key = dkg_engines[0]
+            .dkg_state
+            .public_key_set_owned()
+            .unwrap()
+            .public_key();
+
+        // only node-3 is left to deal its key share, which isn't enough to
+        // reshare the key set
+        let next_members = quorum_members(&dkg_engines[3..]);
+        dkg_engines.drain(..3);
+
+        for i in 0..dkg_engines.len() {
+            let output = dkg_engines[i].start_reshare(next_members.clone()).unwrap();
+            let node_id = dkg_engines[i].node_id();
+            multicast(&mut dkg_engines, node_id, output);
+        }
+
+        for _ in 0..2 {
+            for i in 0..dkg_engines.len() {
+                let round = dkg_engines[i].round().unwrap();
+                assert_eq!(round.phase(), &DkgPhase::Resharing);
+
+                let round_id = round.id();
+                let output = dkg_engines[i].handle_round_timeout(round_id).unwrap();
+                let node_id = dkg_engines[i].node_id();
+                multicast(&mut dkg_engines, node_id, output);
+            }
+        }
+
+        for dkg_engine in dkg_engines.iter() {
+            let round = dkg_engine.round().unwrap();
+            let public_key_set = dkg_engine.dkg_state.public_key_set_owned().unwrap();
+
+            assert!(round.is_completed());
+            assert_eq!(round.members(), &next_members);
+            assert_ne!(public_key_set.public_key(), public_key);
+        }
+    }
+
+    #[test]
+    fn reshare_parts_must_deal_the_key_share_of_the_dealer() {
+        let mut dkg_engines = generate_quorum_dkg_engines(4, NodeType::Validator);
+        let members = quorum_members(&dkg_engines);
+
+        for i in 0..dkg_engines.len() {
+            let output = dkg_engines[i].start_round(members.clone()).unwrap();
+            let node_id = dkg_engines[i].node_id();
+            multicast(&mut dkg_engines, node_id, output);
+        }
+
+        let member_keys = dkg_engines
+            .iter()
+            .map(|dkg_engine| (dkg_engine.node_id(), dkg_engine.get_public_key()))
+            .collect::<BTreeMap<NodeId, _>>();
+        let reshare = KeyReshare::new(1, members.clone(), 1, None);
+
+        // node-0 deals the key share of node-1
+        let key_share = DealtKeyShare {
+            members: members.clone(),
+            public_key_set: dkg_engines[0].dkg_state.public_key_set_owned().unwrap(),
+            secret_key_share: dkg_engines[1].dkg_state.secret_key_share_owned().unwrap(),
+        };
+
+        let dealer = dkg_engines[0].node_id();
+        let mut rng = rand::thread_rng();
+        let part = reshare::deal(&dealer, &key_share, &member_keys, 1, &mut rng).unwrap();
+
+        assert!(reshare::validate_part(&dealer, &part, &reshare).is_err());
+        assert!(reshare::validate_part(&dkg_engines[1].node_id(), &part, &reshare).is_ok());
+    }
+
+    fn quorum_members(dkg_engines: &[DkgEngine]) -> BTreeSet<NodeId> {
+        dkg_engines.iter().map(|engine| engine.node_id()).collect()
+    }
+
+    /// Delivers the messages of `output` to every other engine, the same way
+    /// the node runtime multicasts them to the rest of the quorum
+    fn multicast(dkg_engines: &mut [DkgEngine], sender_id: NodeId, output: DkgOutput) {
+        let mut queue = VecDeque::from([(sender_id, output)]);
+
+        while let Some((sender_id, output)) = queue.pop_front() {
+            for dkg_engine in dkg_engines
+                .iter_mut()
+                .filter(|engine| engine.node_id() != sender_id)
+            {
+                let mut outputs = vec![];
+
+                if let Some(announcement) = output.public_key.clone() {
+                    outputs.push(dkg_engine.handle_public_key(announcement).unwrap());
+                }
+
+                if let Some(vote) = output.timeout_vote.clone() {
+                    outputs.push(dkg_engine.handle_timeout_vote(vote).unwrap());
+                }
+
+                if let Some(part) = output.part.clone() {
+                    outputs.push(
+                        dkg_engine
+                            .handle_part_commitment(sender_id.clone(), part)
+                            .unwrap(),
+                    );
+                }
+
+                if let Some(part) = output.reshare_part.clone() {
+                    outputs.push(
+                        dkg_engine
+                            .handle_reshare_part(sender_id.clone(), part)
+                            .unwrap(),
+                    );
+                }
+
+                for (part_sender_id, ack) in output.acks.iter() {
+                    outputs.push(
+                        dkg_engine
+                            .handle_part_acknowledgement(
+                                sender_id.clone(),
+                                part_sender_id.clone(),
+                                ack.clone(),
+                            )
+                            .unwrap(),
+                    );
+                }
+
+                queue.extend(
+                    outputs
+                        .into_iter()
+                        .map(|output| (dkg_engine.node_id(), output)),
+                );
+            }
+        }
+    }
+
+    fn add_part_commitment_to_node_dkg_state(
+        dkg_engine_node1: &mut DkgEngine,
+        dkg_engine_node2: &mut DkgEngine,
+        node_id: NodeId,
+    ) {
+        let (part, _) = dkg_engine_node2.generate_partial_commitment(1).unwrap();
+
+        dkg_engine_node1
+            .dkg_state
+            .part_message_store_mut()
+            .insert(node_id, part);
+    }
+}
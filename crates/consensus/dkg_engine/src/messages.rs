@@ -0,0 +1,127 @@
+use std::collections::BTreeSet;
+
+use hbbft::crypto::PublicKey;
+use primitives::{NodeId, PublicKey as Secp256k1PublicKey, SecretKey, Signature};
+use secp256k1::Message;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{prelude::DkgRoundId, DkgError, Result};
+
+const DKG_PUBLIC_KEY_DOMAIN: &[u8] = b"vrrb-dkg-public-key";
+const DKG_TIMEOUT_VOTE_DOMAIN: &[u8] = b"vrrb-dkg-timeout-vote";
+
+/// DKG public key of a node, signed with its validator key so the members of
+/// its quorum can tell it apart from a key announced on its behalf
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DkgPublicKeyAnnouncement {
+    pub node_id: NodeId,
+    pub public_key: PublicKey,
+    pub signature: Signature,
+}
+
+impl DkgPublicKeyAnnouncement {
+    pub fn new(node_id: NodeId, public_key: PublicKey, validator_secret_key: &SecretKey) -> Self {
+        let signature = sign(validator_secret_key, &Self::payload(&node_id, &public_key));
+
+        Self {
+            node_id,
+            public_key,
+            signature,
+        }
+    }
+
+    pub fn payload(node_id: &NodeId, public_key: &PublicKey) -> Vec<u8> {
+        let mut payload = DKG_PUBLIC_KEY_DOMAIN.to_vec();
+        payload.extend_from_slice(node_id.as_bytes());
+        payload.extend_from_slice(&public_key.to_bytes());
+        payload
+    }
+
+    /// Checks the announcement was signed by the owner of
+    /// `validator_public_key`
+    pub fn verify(&self, validator_public_key: &Secp256k1PublicKey) -> Result<()> {
+        let payload = Self::payload(&self.node_id, &self.public_key);
+
+        if !verify(validator_public_key, &self.signature, &payload) {
+            return Err(DkgError::InvalidSignature(self.node_id.clone()));
+        }
+
+        Ok(())
+    }
+}
+
+/// Members of a DKG round a node found non-responsive once the round timed
+/// out, or the dealers it is missing parts from if the round was resharing
+/// the key set. Nodes are only skipped once the members agree on the same
+/// ones.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct DkgTimeoutVote {
+    pub round_id: DkgRoundId,
+    pub voter: NodeId,
+    pub non_responsive: BTreeSet<NodeId>,
+    pub signature: Signature,
+}
+
+impl DkgTimeoutVote {
+    pub fn new(
+        round_id: DkgRoundId,
+        voter: NodeId,
+        non_responsive: BTreeSet<NodeId>,
+        validator_secret_key: &SecretKey,
+    ) -> Self {
+        let signature = sign(
+            validator_secret_key,
+            &Self::payload(round_id, &voter, &non_responsive),
+        );
+
+        Self {
+            round_id,
+            voter,
+            non_responsive,
+            signature,
+        }
+    }
+
+    pub fn payload(
+        round_id: DkgRoundId,
+        voter: &NodeId,
+        non_responsive: &BTreeSet<NodeId>,
+    ) -> Vec<u8> {
+        let mut payload = DKG_TIMEOUT_VOTE_DOMAIN.to_vec();
+        payload.extend_from_slice(&round_id.to_be_bytes());
+        // NOTE: node ids are length prefixed so different votes can't produce
+        // the same payload
+        for node_id in std::iter::once(voter).chain(non_responsive) {
+            payload.extend_from_slice(&(node_id.len() as u64).to_be_bytes());
+            payload.extend_from_slice(node_id.as_bytes());
+        }
+        payload
+    }
+
+    /// Checks the vote was signed by the owner of `validator_public_key`
+    pub fn verify(&self, validator_public_key: &Secp256k1PublicKey) -> Result<()> {
+        let payload = Self::payload(self.round_id, &self.voter, &self.non_responsive);
+
+        if !verify(validator_public_key, &self.signature, &payload) {
+            return Err(DkgError::InvalidSignature(self.voter.clone()));
+        }
+
+        Ok(())
+    }
+}
+
+fn sign(secret_key: &SecretKey, payload: &[u8]) -> Signature {
+    let digest = Sha256::digest(payload);
+    let message = Message::from_slice(&digest).expect("sha256 digests are 32 bytes long");
+
+    secret_key.sign_ecdsa(message)
+}
+
+fn verify(public_key: &Secp256k1PublicKey, signature: &Signature, payload: &[u8]) -> bool {
+    let digest = Sha256::digest(payload);
+
+    Message::from_slice(&digest)
+        .map(|message| signature.verify(&message, public_key).is_ok())
+        .unwrap_or(false)
+}
@@ -0,0 +1,452 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use hbbft::{
+    crypto::{
+        poly::{Commitment, Poly},
+        serde_impl::{FieldWrap, SerdeSecret},
+        Ciphertext, Fr, IntoFr, PublicKey, PublicKeySet, SecretKey, SecretKeyShare,
+    },
+    pairing::{
+        bls12_381::{G1Compressed, G1},
+        CurveAffine, CurveProjective, EncodedPoint, Field, PrimeField,
+    },
+};
+use primitives::NodeId;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{prelude::DkgRoundId, DkgError, Result};
+
+/// Size of the length prefix bincode writes before the coefficients of a
+/// commitment
+const COMMITMENT_LEN_SIZE: usize = std::mem::size_of::<u64>();
+
+/// Sub-shares of the key share of a member of the previous quorum, dealt to
+/// the members of the next one.
+///
+/// The dealer evaluates a random polynomial whose constant term is its key
+/// share. Incoming members weight the parts of any `threshold + 1` dealers by
+/// their Lagrange coefficients over those dealers, so the constant terms add
+/// up to the quorum secret key and the key set they combine keeps the same
+/// public key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ResharePart {
+    /// Members of the previous quorum, any `threshold + 1` of which are
+    /// enough to reshare the key set
+    pub dealers: BTreeSet<NodeId>,
+
+    /// Key set being reshared
+    pub public_key_set: PublicKeySet,
+
+    /// Commitment to the polynomial the sub-shares were evaluated from
+    pub commitment: Commitment,
+
+    /// Sub-share of every incoming member, encrypted to its DKG public key
+    pub values: BTreeMap<NodeId, Ciphertext>,
+}
+
+impl ResharePart {
+    /// Members of the quorum the key set is reshared to
+    pub fn members(&self) -> BTreeSet<NodeId> {
+        self.values.keys().cloned().collect()
+    }
+}
+
+/// Key material a member of the previous quorum still has to deal
+#[derive(Debug, Clone)]
+pub(crate) struct DealtKeyShare {
+    pub(crate) members: BTreeSet<NodeId>,
+    pub(crate) public_key_set: PublicKeySet,
+    pub(crate) secret_key_share: SecretKeyShare,
+}
+
+/// Book keeping of a resharing of the quorum key set
+#[derive(Debug, Clone)]
+pub struct KeyReshare {
+    id: DkgRoundId,
+    members: BTreeSet<NodeId>,
+    threshold: usize,
+    key_share: Option<DealtKeyShare>,
+    parts: BTreeMap<NodeId, ResharePart>,
+    missing_dealer_votes: BTreeMap<NodeId, BTreeSet<NodeId>>,
+}
+
+impl KeyReshare {
+    pub(crate) fn new(
+        id: DkgRoundId,
+        members: BTreeSet<NodeId>,
+        threshold: usize,
+        key_share: Option<DealtKeyShare>,
+    ) -> Self {
+        Self {
+            id,
+            members,
+            threshold,
+            key_share,
+            parts: BTreeMap::new(),
+            missing_dealer_votes: BTreeMap::new(),
+        }
+    }
+
+    pub fn id(&self) -> DkgRoundId {
+        self.id
+    }
+
+    /// Members of the quorum the key set is reshared to
+    pub fn members(&self) -> &BTreeSet<NodeId> {
+        &self.members
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Returns true until the node dealt its own key share, if it had one
+    pub fn is_dealing(&self) -> bool {
+        self.key_share.is_some()
+    }
+
+    pub fn parts(&self) -> &BTreeMap<NodeId, ResharePart> {
+        &self.parts
+    }
+
+    pub(crate) fn take_key_share(&mut self) -> Option<DealtKeyShare> {
+        self.key_share.take()
+    }
+
+    pub(crate) fn insert_part(&mut self, dealer: NodeId, part: ResharePart) {
+        self.parts.insert(dealer, part);
+    }
+
+    /// Dealers listed by the parts received so far that didn't send their own
+    pub fn missing_dealers(&self) -> BTreeSet<NodeId> {
+        match self.parts.values().next() {
+            Some(part) => part
+                .dealers
+                .iter()
+                .filter(|dealer| !self.parts.contains_key(*dealer))
+                .cloned()
+                .collect(),
+            None => BTreeSet::new(),
+        }
+    }
+
+    pub fn has_voted(&self, node_id: &NodeId) -> bool {
+        self.missing_dealer_votes.contains_key(node_id)
+    }
+
+    /// Records the dealers `voter` didn't receive a part from once resharing
+    /// timed out, replacing any earlier vote of it
+    pub(crate) fn missing_dealers_voted(&mut self, voter: NodeId, missing: BTreeSet<NodeId>) {
+        self.missing_dealer_votes.insert(voter, missing);
+    }
+
+    /// Dealers every member of the quorum voted as missing. All members have
+    /// to agree, since they all have to combine the parts of the same dealers
+    /// to end up with shares of the same key set.
+    pub fn agreed_missing_dealers(&self) -> Option<BTreeSet<NodeId>> {
+        let missing = self.missing_dealer_votes.values().next()?;
+
+        if self.missing_dealer_votes.keys().ne(self.members.iter())
+            || self
+                .missing_dealer_votes
+                .values()
+                .any(|vote| vote != missing)
+        {
+            return None;
+        }
+
+        Some(missing.clone())
+    }
+
+    /// The first `threshold + 1` dealers, in id order, whose parts are
+    /// combined. Dealers are only skipped once every member agreed they are
+    /// missing, so every member picks the same ones.
+    pub fn dealer_subset(&self) -> Option<BTreeSet<NodeId>> {
+        let reference = self.parts.values().next()?;
+        let required = reference.public_key_set.threshold() + 1;
+
+        let missing = self.missing_dealers();
+        let skipped = if missing.is_empty() {
+            missing
+        } else {
+            self.agreed_missing_dealers()?
+        };
+
+        let dealers = reference
+            .dealers
+            .iter()
+            .filter(|dealer| !skipped.contains(*dealer))
+            .take(required)
+            .cloned()
+            .collect::<BTreeSet<NodeId>>();
+
+        if dealers.len() < required
+            || dealers
+                .iter()
+                .any(|dealer| !self.parts.contains_key(dealer))
+        {
+            return None;
+        }
+
+        Some(dealers)
+    }
+}
+
+/// Deals the sub-shares of `key_share`, held by `dealer`, to `members`.
+/// `member_keys` has to hold the DKG public key of every member.
+pub(crate) fn deal<R: Rng>(
+    dealer: &NodeId,
+    key_share: &DealtKeyShare,
+    member_keys: &BTreeMap<NodeId, PublicKey>,
+    threshold: usize,
+    rng: &mut R,
+) -> Result<ResharePart> {
+    if !key_share.members.contains(dealer) {
+        return Err(DkgError::InvalidResharePart(format!(
+            "{dealer} is not a dealer"
+        )));
+    }
+
+    let mut constant = secret_share_value(&key_share.secret_key_share)?;
+
+    let mut poly = Poly::random(threshold, rng);
+    constant.sub_assign(&poly.evaluate(0));
+    poly += Poly::constant(constant);
+
+    let mut values = BTreeMap::new();
+
+    for (index, (node_id, public_key)) in member_keys.iter().enumerate() {
+        let value = bincode::serialize(&FieldWrap(poly.evaluate(index as u64 + 1)))
+            .map_err(|err| DkgError::Unknown(err.to_string()))?;
+
+        values.insert(node_id.clone(), public_key.encrypt_with_rng(rng, value));
+    }
+
+    Ok(ResharePart {
+        dealers: key_share.members.clone(),
+        public_key_set: key_share.public_key_set.clone(),
+        commitment: poly.commitment(),
+        values,
+    })
+}
+
+/// Decrypts the sub-shares `dealers` dealt to `node_id` and combines them,
+/// weighted by the Lagrange coefficients of the dealers, into its share of a
+/// key set with the same public key as the one being reshared. Every
+/// sub-share is checked against the commitment of its dealer.
+pub(crate) fn combine(
+    node_id: &NodeId,
+    secret_key: &SecretKey,
+    reshare: &KeyReshare,
+    dealers: &BTreeSet<NodeId>,
+) -> Result<(PublicKeySet, SecretKeyShare)> {
+    let parts = dealers
+        .iter()
+        .map(|dealer| {
+            reshare
+                .parts()
+                .get(dealer)
+                .map(|part| (dealer, part))
+                .ok_or_else(|| DkgError::InvalidResharePart(format!("missing part of {dealer}")))
+        })
+        .collect::<Result<Vec<(&NodeId, &ResharePart)>>>()?;
+
+    let reference = match parts.first() {
+        Some((_, part)) => *part,
+        None => return Err(DkgError::InvalidResharePart("missing parts".into())),
+    };
+
+    let index = member_index(reshare.members(), node_id)
+        .ok_or_else(|| DkgError::InvalidResharePart(format!("{node_id} is not a member")))?;
+
+    let dealer_indices = dealers
+        .iter()
+        .map(|dealer| member_index(&reference.dealers, dealer))
+        .collect::<Option<Vec<usize>>>()
+        .ok_or_else(|| DkgError::InvalidResharePart("unknown dealer".into()))?;
+
+    let mut coefficients = vec![G1::zero(); reshare.threshold() + 1];
+    let mut value = Fr::zero();
+
+    for ((dealer, part), dealer_index) in parts.iter().zip(dealer_indices.iter()) {
+        if part.dealers != reference.dealers || part.public_key_set != reference.public_key_set {
+            return Err(DkgError::InvalidResharePart(format!(
+                "{dealer} reshares another key set"
+            )));
+        }
+
+        let lagrange = lagrange_coefficient(*dealer_index, &dealer_indices)?;
+
+        let mut sub_share = open_sub_share(part, node_id, index, secret_key)?;
+        sub_share.mul_assign(&lagrange);
+        value.add_assign(&sub_share);
+
+        let dealer_coefficients = commitment_coefficients(&part.commitment)?;
+        if dealer_coefficients.len() != coefficients.len() {
+            return Err(DkgError::InvalidResharePart(format!(
+                "{dealer} dealt sub-shares of the wrong degree"
+            )));
+        }
+
+        for (coefficient, mut dealer_coefficient) in
+            coefficients.iter_mut().zip(dealer_coefficients)
+        {
+            dealer_coefficient.mul_assign(lagrange.into_repr());
+            coefficient.add_assign(&dealer_coefficient);
+        }
+    }
+
+    let reshared_key_set = PublicKeySet::from(commitment_from_coefficients(&coefficients)?);
+
+    if reshared_key_set.public_key() != reference.public_key_set.public_key() {
+        return Err(DkgError::InvalidResharePart(
+            "sub-shares don't preserve the quorum public key".into(),
+        ));
+    }
+
+    Ok((reshared_key_set, SecretKeyShare::from_mut(&mut value)))
+}
+
+/// Checks a part dealt to the quorum is consistent with the resharing
+pub(crate) fn validate_part(
+    dealer: &NodeId,
+    part: &ResharePart,
+    reshare: &KeyReshare,
+) -> Result<()> {
+    let dealer_index = member_index(&part.dealers, dealer)
+        .ok_or_else(|| DkgError::InvalidResharePart(format!("{dealer} is not a dealer")))?;
+
+    if &part.members() != reshare.members() {
+        return Err(DkgError::InvalidResharePart(format!(
+            "{dealer} dealt its share to another quorum"
+        )));
+    }
+
+    if part.commitment.degree() != reshare.threshold() {
+        return Err(DkgError::InvalidResharePart(format!(
+            "{dealer} dealt sub-shares of the wrong degree"
+        )));
+    }
+
+    // NOTE: the constant term of the dealt polynomial has to be the key share
+    // of the dealer, otherwise the reshared key set would not keep the quorum
+    // public key
+    let dealt_key = PublicKeySet::from(part.commitment.clone()).public_key();
+    let key_share = part.public_key_set.public_key_share(dealer_index);
+
+    if dealt_key.to_bytes() != key_share.to_bytes() {
+        return Err(DkgError::InvalidResharePart(format!(
+            "{dealer} didn't deal its own key share"
+        )));
+    }
+
+    Ok(())
+}
+
+/// Index of the key share of `node_id`, which `SyncKeyGen` assigns following
+/// the order of the member ids
+pub fn member_index(members: &BTreeSet<NodeId>, node_id: &NodeId) -> Option<usize> {
+    members.iter().position(|member| member == node_id)
+}
+
+fn open_sub_share(
+    part: &ResharePart,
+    node_id: &NodeId,
+    index: usize,
+    secret_key: &SecretKey,
+) -> Result<Fr> {
+    let ciphertext = part
+        .values
+        .get(node_id)
+        .ok_or_else(|| DkgError::InvalidResharePart(format!("no sub-share for {node_id}")))?;
+
+    let bytes = secret_key
+        .decrypt(ciphertext)
+        .ok_or_else(|| DkgError::InvalidResharePart("failed to decrypt sub-share".into()))?;
+
+    let value = bincode::deserialize::<FieldWrap<Fr>>(&bytes)
+        .map_err(|err| DkgError::InvalidResharePart(err.to_string()))?
+        .into_inner();
+
+    let mut share = value;
+    let expected = PublicKeySet::from(part.commitment.clone()).public_key_share(index);
+
+    if SecretKeyShare::from_mut(&mut share).public_key_share() != expected {
+        return Err(DkgError::InvalidResharePart(
+            "sub-share doesn't match the commitment of its dealer".into(),
+        ));
+    }
+
+    Ok(value)
+}
+
+/// Lagrange coefficient of the share at `index` when interpolating the
+/// shares at `indices` at zero
+fn lagrange_coefficient(index: usize, indices: &[usize]) -> Result<Fr> {
+    let x = (index as u64 + 1).into_fr();
+    let mut numerator = Fr::one();
+    let mut denominator = Fr::one();
+
+    for other in indices.iter().filter(|other| **other != index) {
+        let other_x = (*other as u64 + 1).into_fr();
+        numerator.mul_assign(&other_x);
+
+        let mut difference = other_x;
+        difference.sub_assign(&x);
+        denominator.mul_assign(&difference);
+    }
+
+    let inverse = denominator
+        .inverse()
+        .ok_or_else(|| DkgError::Unknown("duplicate key share index".into()))?;
+    numerator.mul_assign(&inverse);
+
+    Ok(numerator)
+}
+
+/// Coefficients of a commitment, which `threshold_crypto` only exposes
+/// through its serialized form: the number of coefficients followed by each
+/// of them compressed
+fn commitment_coefficients(commitment: &Commitment) -> Result<Vec<G1>> {
+    let bytes = bincode::serialize(commitment).map_err(|err| DkgError::Unknown(err.to_string()))?;
+    let compressed_size = G1Compressed::size();
+
+    bytes
+        .get(COMMITMENT_LEN_SIZE..)
+        .unwrap_or_default()
+        .chunks(compressed_size)
+        .map(|chunk| {
+            let mut compressed = G1Compressed::empty();
+            if chunk.len() != compressed_size {
+                return Err(DkgError::InvalidResharePart("invalid commitment".into()));
+            }
+            compressed.as_mut().copy_from_slice(chunk);
+
+            compressed
+                .into_affine()
+                .map(|point| point.into_projective())
+                .map_err(|err| DkgError::InvalidResharePart(err.to_string()))
+        })
+        .collect()
+}
+
+fn commitment_from_coefficients(coefficients: &[G1]) -> Result<Commitment> {
+    let mut bytes = (coefficients.len() as u64).to_le_bytes().to_vec();
+    for coefficient in coefficients {
+        bytes.extend_from_slice(coefficient.into_affine().into_compressed().as_ref());
+    }
+
+    bincode::deserialize(&bytes).map_err(|err| DkgError::Unknown(err.to_string()))
+}
+
+/// Field element of a key share, which `threshold_crypto` only exposes
+/// through the serialized form of the share
+fn secret_share_value(secret_key_share: &SecretKeyShare) -> Result<Fr> {
+    let bytes = bincode::serialize(&SerdeSecret(secret_key_share.clone()))
+        .map_err(|err| DkgError::Unknown(err.to_string()))?;
+
+    bincode::deserialize::<FieldWrap<Fr>>(&bytes)
+        .map(FieldWrap::into_inner)
+        .map_err(|err| DkgError::Unknown(err.to_string()))
+}
@@ -34,6 +34,14 @@ pub enum DkgError {
     InvalidNode,
     #[error("All participants of Quorum need to actively participate in DKG")]
     ObserverNotAllowed,
+    #[error("Invalid reshare part: {0}")]
+    InvalidResharePart(String),
+    #[error("Invalid signature on DKG message from {0}")]
+    InvalidSignature(NodeId),
+    #[error("No validator public key known for {0}")]
+    UnknownMember(NodeId),
+    #[error("Failed to persist key share: {0}")]
+    KeyStore(String),
     #[error("Unknown Error: {0}")]
     Unknown(String),
 }
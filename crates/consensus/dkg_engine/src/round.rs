@@ -0,0 +1,228 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use hbbft::{
+    crypto::{PublicKey, PublicKeySet},
+    sync_key_gen::{Ack, Part},
+};
+use primitives::NodeId;
+
+use crate::prelude::{DkgPublicKeyAnnouncement, DkgTimeoutVote, ReceiverId, ResharePart, SenderId};
+
+pub type DkgRoundId = u64;
+
+/// Phases a DKG round goes through before the quorum key set is generated
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DkgPhase {
+    /// Waiting for the DKG public key of every member of the round
+    CollectingPublicKeys,
+    /// Exchanging `Part` and `Ack` messages between the members
+    CollectingCommitments,
+    /// Waiting for the members of the previous quorum to deal their key
+    /// shares to the members of the round
+    Resharing,
+    /// The key set of the quorum was generated
+    Completed,
+}
+
+/// Book keeping of a single DKG round run by a quorum
+#[derive(Debug, Clone)]
+pub struct DkgRound {
+    id: DkgRoundId,
+    members: BTreeSet<NodeId>,
+    excluded: BTreeSet<NodeId>,
+    threshold: usize,
+    phase: DkgPhase,
+    handled_parts: BTreeSet<SenderId>,
+    handled_acks: BTreeSet<(ReceiverId, SenderId)>,
+    timeout_votes: BTreeMap<NodeId, BTreeSet<NodeId>>,
+}
+
+impl DkgRound {
+    pub fn new(
+        id: DkgRoundId,
+        members: BTreeSet<NodeId>,
+        excluded: BTreeSet<NodeId>,
+        threshold: usize,
+    ) -> Self {
+        Self {
+            id,
+            members,
+            excluded,
+            threshold,
+            phase: DkgPhase::CollectingPublicKeys,
+            handled_parts: BTreeSet::new(),
+            handled_acks: BTreeSet::new(),
+            timeout_votes: BTreeMap::new(),
+        }
+    }
+
+    pub fn id(&self) -> DkgRoundId {
+        self.id
+    }
+
+    pub fn members(&self) -> &BTreeSet<NodeId> {
+        &self.members
+    }
+
+    /// Members dropped from the quorum by earlier attempts of this round
+    pub fn excluded(&self) -> &BTreeSet<NodeId> {
+        &self.excluded
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    pub fn phase(&self) -> &DkgPhase {
+        &self.phase
+    }
+
+    pub fn is_member(&self, node_id: &NodeId) -> bool {
+        self.members.contains(node_id)
+    }
+
+    pub fn is_completed(&self) -> bool {
+        self.phase == DkgPhase::Completed
+    }
+
+    pub(crate) fn set_phase(&mut self, phase: DkgPhase) {
+        self.phase = phase;
+    }
+
+    pub(crate) fn has_handled_part(&self, sender_id: &SenderId) -> bool {
+        self.handled_parts.contains(sender_id)
+    }
+
+    pub(crate) fn part_handled(&mut self, sender_id: SenderId) {
+        self.handled_parts.insert(sender_id);
+    }
+
+    pub(crate) fn has_handled_ack(&self, receiver_id: &ReceiverId, sender_id: &SenderId) -> bool {
+        self.handled_acks
+            .contains(&(receiver_id.to_owned(), sender_id.to_owned()))
+    }
+
+    pub(crate) fn ack_handled(&mut self, receiver_id: ReceiverId, sender_id: SenderId) {
+        self.handled_acks.insert((receiver_id, sender_id));
+    }
+
+    /// Records the members `voter` found non-responsive once the round timed
+    /// out, replacing any earlier vote of it
+    pub(crate) fn timeout_voted(&mut self, voter: NodeId, non_responsive: BTreeSet<NodeId>) {
+        self.timeout_votes.insert(voter, non_responsive);
+    }
+
+    /// Members to exclude from the round once every member that isn't being
+    /// excluded voted to exclude exactly those, so that every node restarts
+    /// the round with the same members. The voters have to make up more than
+    /// two thirds of the round, so a node cut off from the quorum can't
+    /// exclude everyone else on its own.
+    pub fn agreed_exclusion(&self) -> Option<BTreeSet<NodeId>> {
+        let non_responsive = self.timeout_votes.values().next()?;
+
+        if self.timeout_votes.len() * 3 <= self.members.len() * 2
+            || !non_responsive.is_subset(&self.members)
+            || self
+                .timeout_votes
+                .values()
+                .any(|vote| vote != non_responsive)
+        {
+            return None;
+        }
+
+        let responsive = self
+            .members
+            .difference(non_responsive)
+            .collect::<BTreeSet<&NodeId>>();
+
+        if self.timeout_votes.keys().collect::<BTreeSet<&NodeId>>() != responsive {
+            return None;
+        }
+
+        Some(non_responsive.clone())
+    }
+
+    /// Returns true once every member's `Part` was handled and acknowledged by
+    /// every other member
+    pub fn has_all_commitments(&self) -> bool {
+        let members = self.members.len();
+
+        self.handled_parts.len() == members && self.handled_acks.len() == members * members
+    }
+
+    /// Members that did not announce their DKG public key or, once the
+    /// commitments are being exchanged, did not send their `Part` or did not
+    /// acknowledge every handled `Part` yet
+    pub fn non_responsive_members(
+        &self,
+        peer_public_keys: &BTreeMap<NodeId, PublicKey>,
+    ) -> BTreeSet<NodeId> {
+        self.members
+            .iter()
+            .filter(|member| match self.phase {
+                DkgPhase::CollectingPublicKeys => !peer_public_keys.contains_key(*member),
+                DkgPhase::CollectingCommitments => {
+                    !self.handled_parts.contains(*member)
+                        || self
+                            .handled_parts
+                            .iter()
+                            .any(|sender_id| !self.has_handled_ack(member, sender_id))
+                },
+                DkgPhase::Resharing | DkgPhase::Completed => false,
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+/// Messages and results produced while driving a DKG round
+#[derive(Debug, Clone, Default)]
+pub struct DkgOutput {
+    /// Signed DKG public key of the node, to be announced to the quorum
+    pub public_key: Option<DkgPublicKeyAnnouncement>,
+
+    /// `Part` of the node, to be multicasted to the quorum
+    pub part: Option<Part>,
+
+    /// Acks for the `Part` sent by each listed node, to be multicasted to the
+    /// quorum
+    pub acks: Vec<(SenderId, Ack)>,
+
+    /// Set whenever a round was started, or timed out without its members
+    /// agreeing on how to move on, and its timeout has to be scheduled
+    pub started_round: Option<DkgRoundId>,
+
+    /// Vote of the node on the members to exclude from a timed out round, to
+    /// be multicasted to the quorum
+    pub timeout_vote: Option<DkgTimeoutVote>,
+
+    /// Sub-shares of the node's key share, to be multicasted to the quorum
+    /// the key set is reshared to
+    pub reshare_part: Option<ResharePart>,
+
+    /// Key set of the quorum, set once the round completed
+    pub public_key_set: Option<PublicKeySet>,
+
+    /// Set if the key set was reshared rather than generated, in which case
+    /// it keeps the public key of the previous one
+    pub reshared: bool,
+
+    /// Members the key set was generated without
+    pub excluded: BTreeSet<NodeId>,
+}
+
+impl DkgOutput {
+    /// Adds the messages and results of `other`, which was produced after
+    /// `self`
+    pub(crate) fn merge(&mut self, other: DkgOutput) {
+        self.public_key = other.public_key.or(self.public_key.take());
+        self.part = other.part.or(self.part.take());
+        self.acks.extend(other.acks);
+        self.started_round = other.started_round.or(self.started_round);
+        self.timeout_vote = other.timeout_vote.or(self.timeout_vote.take());
+        self.reshare_part = other.reshare_part.or(self.reshare_part.take());
+        self.public_key_set = other.public_key_set.or(self.public_key_set.take());
+        self.reshared |= other.reshared;
+        self.excluded.extend(other.excluded);
+    }
+}
@@ -6,11 +6,12 @@ use hbbft::{
 };
 use primitives::{NodeId, NodeType};
 use vrrb_config::valid_threshold_config;
+use vrrb_core::keypair::Keypair;
 
 use crate::{
     dkg::DkgGenerator,
     dkg_state::DkgState,
-    engine::DkgEngine,
+    engine::{DkgEngine, DkgEngineConfig},
     prelude::{ReceiverId, SenderId},
 };
 
@@ -57,19 +58,50 @@ pub async fn generate_dkg_engines(total_nodes: u16, node_type: NodeType) -> Vec<
         let mut dkg_state = DkgState::default();
         dkg_state.set_peer_public_keys(pub_keys.clone());
 
-        dkg_instances.push(DkgEngine {
+        let mut dkg_engine = DkgEngine::new(DkgEngineConfig {
             node_id: format!("node-{}", i),
             node_type,
-            threshold_config: valid_threshold_config(),
             secret_key: sec_keys.get(i as usize).unwrap().clone(),
-            dkg_state,
-            harvester_public_key: None,
+            validator_secret_key: Keypair::random().get_validator_secret_key_owned(),
+            threshold_config: valid_threshold_config(),
         });
+        dkg_engine.dkg_state = dkg_state;
+
+        dkg_instances.push(dkg_engine);
     }
 
     dkg_instances
 }
 
+/// Generates DKG engines that only know their own public key, so the members
+/// of a round have to announce their keys to each other first. Every engine
+/// knows the validator key of the others.
+pub fn generate_quorum_dkg_engines(total_nodes: u16, node_type: NodeType) -> Vec<DkgEngine> {
+    let keypairs = (0..total_nodes)
+        .map(|i| (format!("node-{i}"), Keypair::random()))
+        .collect::<BTreeMap<NodeId, Keypair>>();
+
+    let validator_public_keys = keypairs
+        .iter()
+        .map(|(node_id, keypair)| (node_id.clone(), keypair.get_validator_public_key_owned()))
+        .collect::<BTreeMap<NodeId, primitives::PublicKey>>();
+
+    keypairs
+        .into_iter()
+        .map(|(node_id, keypair)| {
+            let mut dkg_engine = DkgEngine::new(DkgEngineConfig {
+                node_id,
+                node_type,
+                secret_key: rand::random(),
+                validator_secret_key: keypair.get_validator_secret_key_owned(),
+                threshold_config: valid_threshold_config(),
+            });
+            dkg_engine.set_validator_public_keys(validator_public_keys.clone());
+            dkg_engine
+        })
+        .collect()
+}
+
 pub async fn generate_dkg_engine_with_states() -> Vec<DkgEngine> {
     let mut dkg_engines = generate_dkg_engines(4, NodeType::Full).await;
     let mut dkg_engine_node4 = dkg_engines.pop().unwrap();
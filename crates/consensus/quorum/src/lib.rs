@@ -4,16 +4,64 @@ pub mod quorum;
 #[cfg(test)]
 mod tests {
     use std::{
-        collections::hash_map::DefaultHasher,
+        collections::{hash_map::DefaultHasher, HashSet},
         hash::{Hash, Hasher},
         net::SocketAddr,
     };
 
-    use primitives::{Address, NodeId};
+    use primitives::{Address, NodeId, QuorumKind};
+    use proptest::prelude::*;
     use sha256::digest;
-    use vrrb_core::{claim::Claim, keypair::KeyPair};
+    use vrrb_core::{
+        claim::{Claim, Eligibility},
+        keypair::KeyPair,
+        staking::{Stake, StakeUpdate},
+    };
+
+    use crate::{
+        election::Election,
+        quorum::{Quorum, QuorumError},
+    };
+
+    fn claim_with_stake(amount: u128) -> Claim {
+        let keypair = KeyPair::random();
+        let public_key = keypair.get_miner_public_key().clone();
+        let address = Address::new(public_key);
+        let ip_address = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+        let signature = Claim::signature_for_valid_claim(
+            public_key,
+            ip_address,
+            keypair.get_miner_secret_key().secret_bytes().to_vec(),
+        )
+        .unwrap();
+
+        let mut claim = Claim::new(
+            public_key,
+            address.clone(),
+            ip_address,
+            signature,
+            public_key.to_string(),
+        )
+        .unwrap();
+        claim.eligibility = Eligibility::Validator;
+
+        let mut stake = Stake::new(
+            StakeUpdate::Add(amount),
+            keypair.miner_kp.0,
+            keypair.miner_kp.1,
+            address,
+            None,
+        )
+        .unwrap();
+        stake.certify((vec![0; 96], vec![0; 96])).unwrap();
+        claim.update_stake(stake).unwrap();
+
+        claim
+    }
 
-    use crate::{election::Election, quorum::Quorum};
+    fn election_quorum(seed: u64) -> Quorum {
+        Quorum::new(seed, Quorum::BLOCKS_PER_ELECTION, None).unwrap()
+    }
 
     #[test]
     fn it_works() {
@@ -337,4 +385,108 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn election_favours_claims_with_more_stake() {
+        let mut claims = vec![claim_with_stake(Stake::MAX)];
+        claims.extend((0..19).map(|_| claim_with_stake(Stake::MIN)));
+
+        let heavy = claims[0].node_id.clone();
+        let light = claims[1].node_id.clone();
+
+        let mut heavy_wins = 0;
+        let mut light_wins = 0;
+        for seed in 1..=500 {
+            let winner = election_quorum(seed).elect_by_stake(&claims, 1);
+            if winner[0].node_id == heavy {
+                heavy_wins += 1;
+            } else if winner[0].node_id == light {
+                light_wins += 1;
+            }
+        }
+
+        // The heavy claim holds ~34% of the total stake, each light one ~3%
+        assert!(
+            heavy_wins > 100,
+            "heavy claim won {heavy_wins} of 500 draws"
+        );
+        assert!(light_wins < 50, "light claim won {light_wins} of 500 draws");
+    }
+
+    #[test]
+    fn elections_over_mostly_duplicate_claims_are_rejected() {
+        let claim = claim_with_stake(Stake::MIN);
+        let mut claims = vec![claim; 15];
+        claims.extend((0..5).map(|_| claim_with_stake(Stake::MIN)));
+
+        assert!(matches!(
+            election_quorum(7).run_election(claims),
+            Err(QuorumError::InvalidPointerSumError(_))
+        ));
+    }
+
+    #[test]
+    fn trusted_peers_are_elected_from_eligible_claims() {
+        let mut claims: Vec<Claim> = (0..5).map(|_| claim_with_stake(Stake::MIN)).collect();
+        claims[0].eligibility = Eligibility::Miner;
+
+        let quorum = election_quorum(42)
+            .get_trusted_peers(claims.clone())
+            .unwrap();
+
+        assert_eq!(quorum.members.len(), 4);
+        assert!(!quorum
+            .members
+            .iter()
+            .any(|(node_id, _)| node_id == &claims[0].node_id));
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(16))]
+
+        #[test]
+        fn election_is_reproducible_regardless_of_claim_order(
+            seed in 1u64..,
+            stakes in prop::collection::vec(Stake::MIN..=Stake::MAX, 20..30),
+            rotation in any::<usize>(),
+        ) {
+            let claims: Vec<Claim> = stakes.into_iter().map(claim_with_stake).collect();
+            let mut reordered = claims.clone();
+            reordered.rotate_left(rotation % claims.len());
+            reordered.reverse();
+
+            let quorums = election_quorum(seed).run_election(claims).unwrap();
+            let reordered_quorums = election_quorum(seed).run_election(reordered).unwrap();
+
+            prop_assert_eq!(quorums, reordered_quorums);
+        }
+
+        #[test]
+        fn elected_members_are_distinct_eligible_claims(
+            seed in 1u64..,
+            stakes in prop::collection::vec(Stake::MIN..=Stake::MAX, 20..30),
+        ) {
+            let claims: Vec<Claim> = stakes.into_iter().map(claim_with_stake).collect();
+            let expected_members = ((claims.len() as f32) * 0.51).ceil() as usize;
+
+            let quorums = election_quorum(seed).run_election(claims.clone()).unwrap();
+
+            prop_assert_eq!(quorums[0].quorum_kind.clone(), Some(QuorumKind::Harvester));
+            prop_assert!(quorums[1..]
+                .iter()
+                .all(|quorum| quorum.quorum_kind == Some(QuorumKind::Farmer)));
+
+            let members: Vec<NodeId> = quorums
+                .iter()
+                .flat_map(|quorum| quorum.members.iter().map(|(node_id, _)| node_id.clone()))
+                .collect();
+            let unique: HashSet<NodeId> = members.iter().cloned().collect();
+
+            prop_assert_eq!(members.len(), expected_members);
+            prop_assert_eq!(unique.len(), members.len());
+            prop_assert!(members
+                .iter()
+                .all(|node_id| claims.iter().any(|claim| &claim.node_id == node_id)));
+        }
+    }
 }
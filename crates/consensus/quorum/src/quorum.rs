@@ -1,7 +1,9 @@
-use std::collections::BTreeMap;
+use std::collections::HashSet;
 
 use ethereum_types::U256;
 use primitives::{NodeId, PublicKey, QuorumKind};
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use vrrb_core::{
@@ -153,8 +155,9 @@ impl Quorum {
         Ok(eligible_claims)
     }
 
-    /// Gets the final quorum by getting 51% of master nodes with lowest pointer
-    /// sums
+    /// Gets the final quorum by electing 51% of master nodes, weighted by
+    /// stake. The nodes drawn first make up the harvester quorum, the rest
+    /// are split into farmer quorums.
     pub fn get_final_quorum(&mut self, claims: Vec<Claim>) -> Result<Vec<Quorum>, QuorumError> {
         if self.quorum_seed == 0 {
             return Err(QuorumError::NoSeedError);
@@ -162,25 +165,87 @@ impl Quorum {
 
         let num_claims = ((claims.len() as f32) * 0.51).ceil() as usize;
 
-        let election_results: BTreeMap<U256, Claim> = claims
+        let election_results: HashSet<U256> = claims
             .iter()
-            .map(|claim| (claim.get_election_result(self.quorum_seed), claim.clone()))
+            .map(|claim| claim.get_election_result(self.quorum_seed))
             .collect();
 
         if election_results.len() < (((claims.len() as f32) * 0.65).ceil() as usize) {
             return Err(QuorumError::InvalidPointerSumError(claims));
         }
 
-        let members: Vec<(NodeId, PublicKey)> = election_results
-            .values()
+        let members: Vec<(NodeId, PublicKey)> = self
+            .elect_by_stake(&claims, num_claims)
+            .iter()
             .map(|claim| (claim.node_id().clone(), claim.public_key))
             .collect();
 
-        let final_pubkeys = Vec::from_iter(members[0..num_claims].iter().cloned());
-        let quorums = self.split_into_quorums(final_pubkeys)?;
+        let quorums = self.split_into_quorums(members)?;
         Ok(quorums)
     }
 
+    /// Draws up to `count` distinct claims, each draw picking one of the
+    /// remaining claims with probability proportional to its stake.
+    ///
+    /// The draw is seeded by the quorum seed, so every node running the
+    /// election over the same claims elects the same members in the same
+    /// order, regardless of the order the claims were received in.
+    pub fn elect_by_stake(&self, claims: &[Claim], count: usize) -> Vec<Claim> {
+        let mut candidates: Vec<(U256, &Claim)> = claims
+            .iter()
+            .map(|claim| (Self::election_weight(claim), claim))
+            .collect();
+
+        candidates
+            .sort_by(|(_, a), (_, b)| a.hash.cmp(&b.hash).then_with(|| a.node_id.cmp(&b.node_id)));
+
+        // Weights are at most u128::MAX each, so their sum can't overflow a
+        // U256 for any number of claims that fits in memory
+        let mut total_weight = candidates
+            .iter()
+            .fold(U256::zero(), |acc, (weight, _)| acc + weight);
+
+        let mut rng = ChaCha20Rng::seed_from_u64(self.quorum_seed);
+        let mut elected = Vec::with_capacity(count.min(candidates.len()));
+
+        while elected.len() < count && !candidates.is_empty() {
+            let mut target = Self::draw_below(&mut rng, total_weight);
+
+            let index = candidates
+                .iter()
+                .position(|(weight, _)| {
+                    if target < *weight {
+                        return true;
+                    }
+                    target -= *weight;
+                    false
+                })
+                .unwrap_or(candidates.len() - 1);
+
+            let (weight, claim) = candidates.remove(index);
+            total_weight -= weight;
+            elected.push(claim.clone());
+        }
+
+        elected
+    }
+
+    /// Draws a number in `0..bound` out of 256 random bits. `bound` is far
+    /// below 2^256, which keeps the modulo bias negligible.
+    fn draw_below(rng: &mut ChaCha20Rng, bound: U256) -> U256 {
+        let mut bytes = [0u8; 32];
+        rng.fill_bytes(&mut bytes);
+
+        U256::from_big_endian(&bytes) % bound
+    }
+
+    /// Claims are weighted by their own and delegated stake. Claims without
+    /// any stake are given the smallest possible weight, so they can still be
+    /// elected while no node has staked yet
+    fn election_weight(claim: &Claim) -> U256 {
+        U256::from(claim.get_total_stake().max(1))
+    }
+
     fn split_into_quorums(
         &self,
         nodes: Vec<(NodeId, PublicKey)>,
@@ -223,10 +288,28 @@ impl Quorum {
         Ok(quorums)
     }
 
-    pub fn get_trusted_peers(&mut self, _claims: Vec<Claim>) -> Self {
-        //get the weighted value hashmap
-        //calcualte all the t values
+    /// Elects up to `MAX_QUORUM_SIZE` trusted peers out of the eligible
+    /// claims, weighted by stake, and makes them the members of this quorum
+    pub fn get_trusted_peers(&mut self, claims: Vec<Claim>) -> Result<Self, QuorumError> {
+        if self.quorum_seed == 0 {
+            return Err(QuorumError::NoSeedError);
+        }
+
+        let eligible_claims: Vec<Claim> = claims
+            .into_iter()
+            .filter(|claim| claim.eligibility == Eligibility::Validator)
+            .collect();
+
+        if eligible_claims.len() < Self::MIN_QUORUM_SIZE {
+            return Err(QuorumError::InsufficientNodesError);
+        }
+
+        self.members = self
+            .elect_by_stake(&eligible_claims, Self::MAX_QUORUM_SIZE)
+            .iter()
+            .map(|claim| (claim.node_id().clone(), claim.public_key))
+            .collect();
 
-        todo!();
+        Ok(self.clone())
     }
 }
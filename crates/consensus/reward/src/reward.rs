@@ -1,5 +1,6 @@
 //FEATURE TAG(S): Rewards, Block Structure
 
+use primitives::{Round, DEFAULT_EPOCH_LENGTH};
 use serde::{Deserialize, Serialize};
 use vrrb_core::accountable::Accountable;
 
@@ -18,7 +19,6 @@ pub const MAX_REWARD_ADJUSTMENT: f32 = 0.25;
 pub const BASELINE_REWARD: u128 = 20;
 pub const MIN_BASELINE_REWARD: u128 = 15;
 pub const MAX_BASELINE_REWARD: u128 = 25;
-pub const NUMBER_OF_BLOCKS_PER_EPOCH: u128 = 30000000;
 pub const GENESIS_REWARD: u128 = 400_000_000;
 
 /// `Reward` is a struct that contains the epoch, next epoch block, current
@@ -54,38 +54,35 @@ impl Reward {
         Reward {
             current_block: 0,
             epoch: 1,
-            next_epoch_block: NUMBER_OF_BLOCKS_PER_EPOCH,
+            next_epoch_block: DEFAULT_EPOCH_LENGTH,
             miner,
             amount: BASELINE_REWARD,
         }
     }
 
-    pub fn generate_next_reward(&self, adjustment_to_next_epoch: i128) -> Reward {
-        let rem = (self.current_block + 1) % NUMBER_OF_BLOCKS_PER_EPOCH;
-        if rem == 0 {
-            let nr_epoch = self.epoch + 1;
-            let nr_next_epoch_block = self.next_epoch_block + NUMBER_OF_BLOCKS_PER_EPOCH;
-            let mut nr_amount = {
-                (self.amount as i128
-                    + (adjustment_to_next_epoch / NUMBER_OF_BLOCKS_PER_EPOCH as i128))
-                    as u128
-            };
-
-            if nr_amount < MIN_BASELINE_REWARD {
-                nr_amount = MIN_BASELINE_REWARD;
-            } else if nr_amount > MAX_BASELINE_REWARD {
-                nr_amount = MAX_BASELINE_REWARD;
-            }
-
-            Reward {
-                current_block: self.current_block,
-                epoch: nr_epoch,
-                next_epoch_block: nr_next_epoch_block,
-                miner: None,
-                amount: nr_amount,
-            }
-        } else {
-            self.clone()
+    /// Returns the reward of the next block. Rewards only change when a
+    /// block opens a new epoch, see `advance_epoch`.
+    pub fn generate_next_reward(&self) -> Reward {
+        self.clone()
+    }
+
+    /// Returns the reward of the next epoch, with the amount adjusted by
+    /// `adjustment_to_next_epoch` spread over the `epoch_length` rounds of an
+    /// epoch and kept within the baseline range
+    pub fn advance_epoch(&self, adjustment_to_next_epoch: i128, epoch_length: Round) -> Reward {
+        let epoch_length = epoch_length.max(1);
+        let nr_epoch = self.epoch + 1;
+        let nr_next_epoch_block = self.next_epoch_block + epoch_length;
+        let nr_amount = (self.amount as i128 + (adjustment_to_next_epoch / epoch_length as i128))
+            .clamp(MIN_BASELINE_REWARD as i128, MAX_BASELINE_REWARD as i128)
+            as u128;
+
+        Reward {
+            current_block: self.current_block,
+            epoch: nr_epoch,
+            next_epoch_block: nr_next_epoch_block,
+            miner: None,
+            amount: nr_amount,
         }
     }
 
@@ -131,7 +128,7 @@ impl Default for Reward {
         Reward {
             current_block: 0,
             epoch: 0,
-            next_epoch_block: NUMBER_OF_BLOCKS_PER_EPOCH,
+            next_epoch_block: DEFAULT_EPOCH_LENGTH,
             miner: None,
             amount: BASELINE_REWARD,
         }
@@ -160,31 +157,47 @@ impl Accountable for Reward {
 
 #[cfg(test)]
 mod tests {
+    use primitives::DEFAULT_EPOCH_LENGTH;
+
     use super::{Reward, BASELINE_REWARD};
-    use crate::reward::{MAX_BASELINE_REWARD, MIN_BASELINE_REWARD, NUMBER_OF_BLOCKS_PER_EPOCH};
+    use crate::reward::{MAX_BASELINE_REWARD, MIN_BASELINE_REWARD};
 
     #[test]
     fn test_reward_state_starting_point() {
         let reward = Reward::genesis(Some("MINER_1".to_string()));
         assert!(reward.amount == BASELINE_REWARD);
         assert!(reward.epoch == 1);
-        assert!(reward.next_epoch_block == NUMBER_OF_BLOCKS_PER_EPOCH);
+        assert!(reward.next_epoch_block == DEFAULT_EPOCH_LENGTH);
     }
 
     #[test]
     fn test_reward_state_after_next_epoch() {
         let reward = Reward::genesis(Some("MINER_1".to_string()));
-        reward.generate_next_reward(15);
+        reward.generate_next_reward();
         assert!(reward.amount >= MIN_BASELINE_REWARD && reward.amount <= MAX_BASELINE_REWARD);
     }
 
     #[test]
     fn test_restored_reward_state() {
         let mut reward = Reward::genesis(Some("MINER".to_string()));
-        reward.generate_next_reward(15);
+        reward.generate_next_reward();
         assert!(reward.amount >= MIN_BASELINE_REWARD && reward.amount <= MAX_BASELINE_REWARD);
         assert!(reward.valid_reward());
         reward.reset();
         assert!(reward.amount == BASELINE_REWARD);
     }
+
+    #[test]
+    fn test_reward_adjusted_when_advancing_epoch() {
+        let reward = Reward::genesis(Some("MINER".to_string()));
+
+        let next = reward.advance_epoch(1_000, 1_000);
+        assert_eq!(next.epoch, reward.epoch + 1);
+        assert_eq!(next.next_epoch_block, reward.next_epoch_block + 1_000);
+        assert_eq!(next.amount, BASELINE_REWARD + 1);
+        assert!(next.miner.is_none());
+
+        let capped = reward.advance_epoch(-100_000, 1_000);
+        assert_eq!(capped.amount, MIN_BASELINE_REWARD);
+    }
 }
@@ -1,6 +1,10 @@
 //! This crate provides functionality for generating/verification of  partial
 //! and threshold signatures
-use std::{collections::BTreeMap, str::FromStr};
+use std::{
+    collections::BTreeMap,
+    str::FromStr,
+    sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
 
 // use dkg_engine::prelude::*;
 use hbbft::{
@@ -54,10 +58,19 @@ pub trait NodeIdFrBuilder: AsRef<str> {
 }
 impl NodeIdFrBuilder for NodeId {}
 
-#[derive(Clone, Debug)]
-pub struct SignatureProvider {
+/// Key set of the quorum and key share of the node, kept behind a single
+/// lock so they are always swapped together
+#[derive(Debug, Default)]
+struct QuorumKeys {
     public_key_set: Option<PublicKeySet>,
     secret_key_share: Option<SecretKeyShare>,
+}
+
+/// Produces and checks the threshold signatures of a quorum. Clones share the
+/// same keys, so rotating them applies to every clone at once.
+#[derive(Clone, Debug)]
+pub struct SignatureProvider {
+    keys: Arc<RwLock<QuorumKeys>>,
     pub quorum_config: ThresholdConfig,
 }
 
@@ -90,18 +103,51 @@ pub struct SignatureProvider {
 impl SignatureProvider {
     pub fn new(quorum_config: ThresholdConfig) -> Self {
         Self {
-            public_key_set: None,
-            secret_key_share: None,
+            keys: Arc::new(RwLock::new(QuorumKeys::default())),
             quorum_config,
         }
     }
 
     pub fn set_public_key_set(&mut self, public_key_set: PublicKeySet) {
-        self.public_key_set = Some(public_key_set.clone());
+        self.write_keys().public_key_set = Some(public_key_set);
     }
 
     pub fn set_secret_key_share(&mut self, secret_key_share: SecretKeyShare) {
-        self.secret_key_share = Some(secret_key_share.clone());
+        self.write_keys().secret_key_share = Some(secret_key_share);
+    }
+
+    /// Switches to the key set and key share a quorum was reshared to. Both
+    /// are replaced at once, so no signature is produced or checked with a
+    /// share of one key set and the other key set. The reshared key set must
+    /// keep the group public key of the current one.
+    pub fn rotate_keys(
+        &self,
+        public_key_set: PublicKeySet,
+        secret_key_share: SecretKeyShare,
+    ) -> SignerResult<()> {
+        let mut keys = self.write_keys();
+
+        if let Some(current) = &keys.public_key_set {
+            if current.public_key() != public_key_set.public_key() {
+                return Err(SignerError::GroupPublicKeyMismatch);
+            }
+        }
+
+        *keys = QuorumKeys {
+            public_key_set: Some(public_key_set),
+            secret_key_share: Some(secret_key_share),
+        };
+
+        Ok(())
+    }
+
+    /// Switches to the key set and key share generated by a new DKG round,
+    /// which comes with a new group public key. Both are replaced at once.
+    pub fn replace_keys(&self, public_key_set: PublicKeySet, secret_key_share: SecretKeyShare) {
+        *self.write_keys() = QuorumKeys {
+            public_key_set: Some(public_key_set),
+            secret_key_share: Some(secret_key_share),
+        };
     }
 
     pub fn set_threshold_config(&mut self, threshold_config: ThresholdConfig) {
@@ -109,16 +155,26 @@ impl SignatureProvider {
     }
 
     pub fn secret_key_share(&self) -> Option<SecretKeyShare> {
-        self.secret_key_share.clone()
+        self.read_keys().secret_key_share.clone()
     }
 
     pub fn public_key_set(&self) -> Option<PublicKeySet> {
-        self.public_key_set.clone()
+        self.read_keys().public_key_set.clone()
     }
 
     pub fn quorum_config(&self) -> ThresholdConfig {
         self.quorum_config.clone()
     }
+
+    // NOTE: the keys are only ever replaced as a whole, so they are still
+    // consistent if a writer panicked while holding the lock
+    fn read_keys(&self) -> RwLockReadGuard<QuorumKeys> {
+        self.keys.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn write_keys(&self) -> RwLockWriteGuard<QuorumKeys> {
+        self.keys.write().unwrap_or_else(PoisonError::into_inner)
+    }
 }
 
 impl Signer for SignatureProvider {
@@ -7,6 +7,8 @@ pub enum SignerError {
     DkgStateCannotBeRead,
     #[error("SignerError: Group public key missing for the quorum")]
     GroupPublicKeyMissing,
+    #[error("SignerError: Key set doesn't match the group public key of the quorum")]
+    GroupPublicKeyMismatch,
     #[error("SignerError: Secret key share for current master node is missing ")]
     SecretKeyShareMissing,
     #[error("SignerError: Error generating threshold signature: {0}")]
@@ -126,4 +126,20 @@ mod tests {
         let rn = vvrf1.generate_u8_in_range(10, 100);
         assert!(10 <= rn && rn <= 100);
     }
+
+    #[test]
+    fn proof_reproduces_rng_without_secret_key() {
+        let kp = KeyPair::random();
+        let message = b"test";
+        let sk = kp.miner_kp.0.secret_bytes().to_vec();
+        let mut vvrf1: VVRF = VVRF::new(message, &sk);
+        let mut vvrf2 =
+            VVRF::from_proof(&kp.miner_kp.1.serialize(), message, &vvrf1.get_proof()).unwrap();
+        assert_eq!(vvrf1.generate_u64(), vvrf2.generate_u64());
+
+        let other = KeyPair::random();
+        assert!(
+            VVRF::from_proof(&other.miner_kp.1.serialize(), message, &vvrf1.get_proof()).is_err()
+        );
+    }
 }
@@ -169,6 +169,33 @@ impl VVRF {
         }
     }
 
+    ///rebuild a VVRF type from a proof generated by the holder of the sk
+    /// behind `pubkey`, so anyone can check the proof and draw the same
+    /// random numbers without the sk
+    pub fn from_proof(pubkey: &[u8], message: &[u8], proof: &[u8]) -> Result<VVRF, InvalidVVRF> {
+        let mut vrf = VVRF::generate_vrf(CipherSuite::SECP256K1_SHA256_TAI);
+        let beta = vrf
+            .verify(pubkey, proof, message)
+            .map_err(|_| InvalidVVRF::InvalidProofError)?;
+
+        let proof: [u8; 81] = proof
+            .try_into()
+            .map_err(|_| InvalidVVRF::InvalidProofError)?;
+        let hash: [u8; 32] = beta
+            .as_slice()
+            .try_into()
+            .map_err(|_| InvalidVVRF::InvalidProofError)?;
+
+        Ok(VVRF {
+            vrf,
+            pubkey: pubkey.to_vec(),
+            message: message.to_vec(),
+            proof,
+            hash,
+            rng: ChaCha20Rng::from_seed(hash),
+        })
+    }
+
     ///get vrf from openssl struct ECVRF (eliptic curve vrf)
     fn generate_vrf(suite: CipherSuite) -> ECVRF {
         ECVRF::from_suite(suite).unwrap()
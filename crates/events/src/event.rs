@@ -1,7 +1,9 @@
 use block::GenesisReceiver;
 use block::{
-    header::BlockHeader, Block, BlockHash, Certificate, ConvergenceBlock, ProposalBlock, RefHash,
+    header::BlockHeader, slashing::SlashingEvidence, Block, BlockHash, Certificate,
+    ConvergenceBlock, ProposalBlock, RefHash,
 };
+use dkg_engine::prelude::{DkgPublicKeyAnnouncement, DkgTimeoutVote, ResharePart};
 use ethereum_types::U256;
 use hbbft::sync_key_gen::Ack;
 use hbbft::{crypto::PublicKeySet, sync_key_gen::Part};
@@ -20,6 +22,7 @@ use vrrb_core::transactions::{TransactionDigest, TransactionKind};
 use crate::event_data::*;
 
 pub type AccountBytes = Vec<u8>;
+pub type ProgramBytes = Vec<u8>;
 pub type BlockBytes = Vec<u8>;
 pub type HeaderBytes = Vec<u8>;
 pub type ConflictBytes = Vec<u8>;
@@ -105,6 +108,10 @@ pub enum Event {
     /// request for Account updation on the chain has been requested.
     AccountUpdateRequested((Address, AccountBytes)),
 
+    /// `ProgramDeployRequested(ProgramBytes)` is triggered when a program is
+    /// submitted to be registered under its content ID.
+    ProgramDeployRequested(ProgramBytes),
+
     /// `PeerSyncFailed(Vec<SocketAddr>)` is an event that is triggered when a
     /// peer address synchronization attempt fails. The `Vec<SocketAddr>`
     /// parameter contains a list of socket addresses of the peers
@@ -126,6 +133,11 @@ pub enum Event {
     /// Signals thaa a node acknowledges belonging to a quorum
     QuorumMembershipSet(NodeId),
 
+    /// Carries the DKG public key a quorum member announces, signed with its
+    /// validator key, before the `Part` and `Ack` messages of a DKG round can
+    /// be exchanged
+    DkgPublicKeyAnnounced(DkgPublicKeyAnnouncement),
+
     PartCommitmentCreated(NodeId, Part),
 
     PartCommitmentAcknowledged {
@@ -137,12 +149,32 @@ pub enum Event {
         ack: Ack,
     },
 
+    /// Carries the sub-shares a member of the previous quorum deals to the
+    /// members of the next one when the quorum key set is reshared
+    ResharePartCreated(NodeId, ResharePart),
+
     /// `HarvesterPublicKeyReceived(Vec<u8>)` is an event that carries a vector of bytes
     /// representing the public key of a harvester node. This event is used
     /// to communicate the public key of a harvester node to other nodes in
     /// the network.
     HarvesterPublicKeyReceived(PublicKeySet),
 
+    /// Emitted by a node to itself once a DKG round it runs reached its
+    /// timeout
+    DkgRoundTimedOut(u64),
+
+    /// Carries the vote of a quorum member on the members to exclude from a
+    /// DKG round that timed out
+    DkgTimeoutVoteCast(DkgTimeoutVote),
+
+    /// Emitted once the DKG round of a quorum completed. Members that did
+    /// not respond in time are left out of the quorum
+    QuorumKeySetGenerated {
+        public_key_set: PublicKeySet,
+        members: Vec<NodeId>,
+        excluded: Vec<NodeId>,
+    },
+
     /// This events triggers the generation of a certificate for a given transaction
     TransactionCertificateRequested {
         votes: Vec<Vote>,
@@ -302,6 +334,15 @@ pub enum Event {
     BlockAppended(String),
     BuildProposalBlock(ConvergenceBlock),
     BroadcastProposalBlock(ProposalBlock),
+
+    /// `SlashingEvidenceCreated` is emitted when the local node catches a
+    /// quorum member signing conflicting messages. The evidence is gossiped to
+    /// the rest of the network.
+    SlashingEvidenceCreated(SlashingEvidence),
+
+    /// `SlashingEvidenceReceived` carries slashing evidence gossiped by
+    /// another node so it can be included in an upcoming convergence block.
+    SlashingEvidenceReceived(SlashingEvidence),
 }
 
 impl From<&theater::Message> for Event {
@@ -333,6 +374,7 @@ impl From<Event> for messr::Message<Event> {
         match &evt {
             Event::Stop => messr::Message::stop_signal(None),
             Event::CreateAccountRequested(_)
+            | Event::ProgramDeployRequested(_)
             | Event::NewTxnCreated(_)
             | Event::TxnAddedToMempool(_) => {
                 messr::Message::new(Some(RUNTIME_TOPIC_STR.into()), evt)
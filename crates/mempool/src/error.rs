@@ -1,4 +1,5 @@
-use vrrb_core::transactions::TransactionDigest;
+use primitives::Address;
+use vrrb_core::transactions::{TransactionDigest, TxAmount, TxFee};
 
 #[derive(thiserror::Error, PartialEq, Eq, Debug)]
 pub enum MempoolError {
@@ -14,4 +15,22 @@ pub enum MempoolError {
 
     #[error("transaction {0} already exists")]
     TransactionExists(TransactionDigest),
+
+    #[error("sender {0} reached the maximum number of pending transactions")]
+    SenderLimitReached(Address),
+
+    #[error("mempool is full and transaction {0} does not pay enough fees to evict another")]
+    FeeTooLow(TransactionDigest),
+
+    #[error("transaction {0} pays less than the minimum fee of {1}")]
+    FeeBelowMinimum(TransactionDigest, TxFee),
+
+    #[error("sender of transaction {0} cannot cover the {1} its pending transactions cost")]
+    InsufficientBalance(TransactionDigest, TxAmount),
+
+    #[error("transaction {0} must pay a fee of at least {1} to replace a pending transaction")]
+    ReplacementUnderpriced(TransactionDigest, TxFee),
+
+    #[error("mempool journal error: {0}")]
+    Journal(String),
 }
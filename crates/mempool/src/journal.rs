@@ -0,0 +1,143 @@
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    error::MempoolError,
+    mempool::{MempoolOp, Result},
+};
+
+pub const MEMPOOL_JOURNAL_FILE_NAME: &str = "mempool.journal";
+
+/// Append-only log of the operations applied to a `LeftRightMempool`, one
+/// JSON encoded `MempoolOp` per line. Replaying it rebuilds the pool as it
+/// was before the node stopped.
+///
+/// Compacting the journal rewrites it with the minimal set of operations
+/// that reproduce the current state of the pool, so it doesn't grow with
+/// every transaction that ever went through the node.
+#[derive(Debug)]
+pub struct MempoolJournal {
+    path: PathBuf,
+    writer: BufWriter<File>,
+    len: usize,
+    appended: usize,
+    appended_bytes: u64,
+}
+
+impl MempoolJournal {
+    /// Opens the journal stored within `dir`, creating both if needed
+    pub fn open(dir: &Path) -> Result<Self> {
+        fs::create_dir_all(dir).map_err(journal_error)?;
+
+        let path = dir.join(MEMPOOL_JOURNAL_FILE_NAME);
+        let file = open_append(&path)?;
+        let len = BufReader::new(File::open(&path).map_err(journal_error)?)
+            .lines()
+            .count();
+
+        Ok(Self {
+            path,
+            writer: BufWriter::new(file),
+            len,
+            appended: 0,
+            appended_bytes: 0,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the number of operations in the journal
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of operations appended since the journal was
+    /// opened or last compacted
+    pub fn appended(&self) -> usize {
+        self.appended
+    }
+
+    /// Returns the number of bytes appended since the journal was opened or
+    /// last compacted
+    pub fn appended_bytes(&self) -> u64 {
+        self.appended_bytes
+    }
+
+    /// Records an operation. The entry is synced to disk before returning.
+    pub fn append(&mut self, op: &MempoolOp) -> Result<()> {
+        let mut entry = serde_json::to_vec(op).map_err(journal_error)?;
+        entry.push(b'\n');
+
+        self.writer.write_all(&entry).map_err(journal_error)?;
+        self.writer.flush().map_err(journal_error)?;
+        self.writer.get_ref().sync_data().map_err(journal_error)?;
+        self.len += 1;
+        self.appended += 1;
+        self.appended_bytes += entry.len() as u64;
+
+        Ok(())
+    }
+
+    /// Reads back every recorded operation in the order it was appended.
+    /// Entries that can't be decoded, usually a line left incomplete by a
+    /// crash, are skipped.
+    pub fn replay(&self) -> Result<Vec<MempoolOp>> {
+        let file = File::open(&self.path).map_err(journal_error)?;
+        let mut ops = Vec::with_capacity(self.len);
+
+        for line in BufReader::new(file).lines() {
+            let line = line.map_err(journal_error)?;
+            match serde_json::from_str::<MempoolOp>(&line) {
+                Ok(op) => ops.push(op),
+                Err(err) => telemetry::warn!("skipping corrupt mempool journal entry: {err}"),
+            }
+        }
+
+        Ok(ops)
+    }
+
+    /// Replaces the contents of the journal with `ops`. The new journal is
+    /// written to a temporary file first and then moved over the old one, so
+    /// a crash halfway through leaves the previous journal intact.
+    pub fn compact(&mut self, ops: &[MempoolOp]) -> Result<()> {
+        let tmp_path = self.path.with_extension("journal.tmp");
+
+        let mut writer = BufWriter::new(File::create(&tmp_path).map_err(journal_error)?);
+        for op in ops {
+            serde_json::to_writer(&mut writer, op).map_err(journal_error)?;
+            writer.write_all(b"\n").map_err(journal_error)?;
+        }
+
+        let file = writer.into_inner().map_err(journal_error)?;
+        file.sync_all().map_err(journal_error)?;
+        fs::rename(&tmp_path, &self.path).map_err(journal_error)?;
+
+        self.writer = BufWriter::new(open_append(&self.path)?);
+        self.len = ops.len();
+        self.appended = 0;
+        self.appended_bytes = 0;
+
+        Ok(())
+    }
+}
+
+fn open_append(path: &Path) -> Result<File> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(journal_error)
+}
+
+fn journal_error(err: impl std::fmt::Display) -> MempoolError {
+    MempoolError::Journal(err.to_string())
+}
@@ -1,10 +1,14 @@
 pub mod error;
+pub mod journal;
 pub mod mempool;
+pub mod sender_queue;
 
 use anyhow::Context;
 use reqwest::StatusCode;
 
+pub use crate::journal::*;
 pub use crate::mempool::*;
+pub use crate::sender_queue::*;
 
 pub async fn create_tx_indexer(txn_record: &TxnRecord) -> anyhow::Result<StatusCode> {
     let url = "http://localhost:3444/transactions"; // TODO: Move to config
@@ -35,14 +39,19 @@ mod tests {
 
     use std::collections::{HashMap, HashSet};
 
+    use left_right::Absorb;
     use primitives::{Address, Signature};
     use rand::{thread_rng, Rng};
     use secp256k1::ecdsa;
     use tokio;
+    use vrrb_config::MempoolConfig;
     use vrrb_core::keypair::KeyPair;
-    use vrrb_core::transactions::{Transaction, TransactionKind};
+    use vrrb_core::transactions::{Transaction, TransactionKind, BASE_FEE};
 
-    use crate::mempool::{LeftRightMempool, TxnRecord, TxnStatus};
+    use crate::error::MempoolError;
+    use crate::mempool::{
+        FetchFiltered, LeftRightMempool, Mempool, MempoolOp, TxnRecord, TxnStatus,
+    };
 
     fn mock_txn_signature() -> Signature {
         ecdsa::Signature::from_compact(&[
@@ -55,6 +64,32 @@ mod tests {
         .unwrap()
     }
 
+    fn mock_txn_with_fee(keypair: &KeyPair, amount: u128, fee: u128) -> TransactionKind {
+        mock_txn_with_nonce(keypair, amount, fee, 0)
+    }
+
+    fn mock_txn_with_nonce(
+        keypair: &KeyPair,
+        amount: u128,
+        fee: u128,
+        nonce: u128,
+    ) -> TransactionKind {
+        let recv_keypair = KeyPair::random();
+
+        TransactionKind::transfer_builder()
+            .timestamp(0)
+            .sender_address(Address::new(keypair.get_miner_public_key().clone()))
+            .sender_public_key(keypair.get_miner_public_key().clone())
+            .receiver_address(Address::new(recv_keypair.get_miner_public_key().clone()))
+            .amount(amount)
+            .fee(fee)
+            .validators(HashMap::<String, bool>::new())
+            .nonce(nonce)
+            .signature(mock_txn_signature())
+            .build_kind()
+            .expect("Failed to build transaction")
+    }
+
     #[test]
     fn creates_new_lrmempooldb() {
         let lrmpooldb = LeftRightMempool::new();
@@ -157,6 +192,7 @@ mod tests {
         let txn2 = transfer_builder
             .clone()
             .receiver_address(Address::new(recv2_keypair.get_miner_public_key().clone()))
+            .nonce(1)
             .build_kind()
             .expect("Failed to build transaction");
 
@@ -259,13 +295,13 @@ mod tests {
             .sender_public_key(keypair.get_miner_public_key().clone())
             .receiver_address(receiver_address.clone())
             .validators(HashMap::<String, bool>::new())
-            .nonce(0)
             .signature(mock_txn_signature());
 
         for n in 1..101 {
             let txn = transfer_builder
                 .clone()
                 .amount(txn_amount + n)
+                .nonce(n)
                 .build_kind()
                 .expect("Failed to build transaction");
 
@@ -333,7 +369,7 @@ mod tests {
             .receiver_address(recv2_address.clone())
             .amount(0)
             .validators(HashMap::<String, bool>::new())
-            .nonce(0)
+            .nonce(1)
             .signature(mock_txn_signature())
             .build_kind()
             .expect("Failed to build transaction");
@@ -399,7 +435,7 @@ mod tests {
             .receiver_address(recv2_address.clone())
             .amount(0)
             .validators(HashMap::<String, bool>::new())
-            .nonce(0)
+            .nonce(1)
             .signature(mock_txn_signature())
             .build_kind()
             .expect("Failed to build transaction");
@@ -452,13 +488,13 @@ mod tests {
             .sender_public_key(keypair.get_miner_public_key().clone())
             .receiver_address(recv_address.clone())
             .validators(HashMap::<String, bool>::new())
-            .nonce(0)
             .signature(mock_txn_signature());
 
         for n in 1..101 {
             let txn = transfer_builder
                 .clone()
                 .amount(txn_amount + n)
+                .nonce(n)
                 .build_kind()
                 .expect("Failed to build transaction");
 
@@ -498,7 +534,6 @@ mod tests {
             .sender_address(Address::new(keypair.get_miner_public_key().clone()))
             .sender_public_key(keypair.get_miner_public_key().clone())
             .validators(HashMap::<String, bool>::new())
-            .nonce(0)
             .signature(mock_txn_signature());
 
         for n in 1..u128::try_from(txn_id_max).unwrap_or(0) {
@@ -509,6 +544,7 @@ mod tests {
                 .clone()
                 .receiver_address(recv_address.clone())
                 .amount(txn_amount + n)
+                .nonce(n)
                 .build_kind()
                 .expect("Failed to build transaction");
 
@@ -538,4 +574,430 @@ mod tests {
                 handle.join().unwrap();
             });
     }
+
+    #[test]
+    fn fetch_filtered_returns_less_than_requested_amount() {
+        let mut mpooldb = LeftRightMempool::new();
+
+        for n in 1..4 {
+            mpooldb
+                .insert(mock_txn_with_fee(&KeyPair::random(), n, BASE_FEE))
+                .unwrap();
+        }
+
+        let records = mpooldb.read.fetch_filtered(10, |_, _| true);
+        assert_eq!(records.len(), 3);
+
+        let records = mpooldb.read.fetch_filtered(2, |_, _| true);
+        assert_eq!(records.len(), 2);
+    }
+
+    #[test]
+    fn fetch_best_returns_highest_fees_first() {
+        let mut mpooldb = LeftRightMempool::new();
+
+        let low = mock_txn_with_fee(&KeyPair::random(), 1, BASE_FEE);
+        let mid = mock_txn_with_fee(&KeyPair::random(), 1, BASE_FEE * 2);
+        let high = mock_txn_with_fee(&KeyPair::random(), 1, BASE_FEE * 3);
+
+        mpooldb.insert(mid.clone()).unwrap();
+        mpooldb.insert(low).unwrap();
+        mpooldb.insert(high.clone()).unwrap();
+
+        let best = mpooldb
+            .fetch_best(2)
+            .into_iter()
+            .map(|record| record.txn_id)
+            .collect::<Vec<_>>();
+
+        assert_eq!(best, vec![high.id(), mid.id()]);
+        assert_eq!(mpooldb.factory().fetch_best(5).len(), 3);
+    }
+
+    #[test]
+    fn full_mempool_evicts_lowest_fee_txn() {
+        let mut mpooldb = LeftRightMempool::with_config(MempoolConfig {
+            max_size: 2,
+            max_txns_per_sender: 2,
+            ..Default::default()
+        });
+
+        let low = mock_txn_with_fee(&KeyPair::random(), 1, BASE_FEE);
+        let mid = mock_txn_with_fee(&KeyPair::random(), 1, BASE_FEE * 2);
+        let high = mock_txn_with_fee(&KeyPair::random(), 1, BASE_FEE * 3);
+
+        mpooldb.insert(low.clone()).unwrap();
+        mpooldb.insert(mid.clone()).unwrap();
+        mpooldb.insert(high.clone()).unwrap();
+
+        assert_eq!(mpooldb.size(), 2);
+        assert!(mpooldb.get(&low.id()).is_none());
+        assert!(mpooldb.get(&mid.id()).is_some());
+        assert!(mpooldb.get(&high.id()).is_some());
+
+        let cheap = mock_txn_with_fee(&KeyPair::random(), 2, BASE_FEE);
+        assert_eq!(
+            mpooldb.insert(cheap.clone()),
+            Err(MempoolError::FeeTooLow(cheap.id()))
+        );
+        assert_eq!(mpooldb.size(), 2);
+    }
+
+    #[test]
+    fn rejects_txns_over_the_sender_limit() {
+        let keypair = KeyPair::random();
+        let mut mpooldb = LeftRightMempool::with_config(MempoolConfig {
+            max_size: 10,
+            max_txns_per_sender: 2,
+            ..Default::default()
+        });
+
+        mpooldb
+            .insert(mock_txn_with_nonce(&keypair, 1, BASE_FEE, 0))
+            .unwrap();
+        mpooldb
+            .insert(mock_txn_with_nonce(&keypair, 1, BASE_FEE, 1))
+            .unwrap();
+
+        let txn = mock_txn_with_nonce(&keypair, 1, BASE_FEE, 2);
+        assert_eq!(
+            mpooldb.insert(txn.clone()),
+            Err(MempoolError::SenderLimitReached(txn.sender_address()))
+        );
+
+        mpooldb
+            .insert(mock_txn_with_fee(&KeyPair::random(), 1, BASE_FEE))
+            .unwrap();
+
+        assert_eq!(mpooldb.size(), 3);
+    }
+
+    #[test]
+    fn fetch_best_skips_txns_behind_a_nonce_gap() {
+        let keypair = KeyPair::random();
+        let sender = Address::new(keypair.get_miner_public_key().clone());
+        let mut mpooldb = LeftRightMempool::new();
+
+        let first = mock_txn_with_nonce(&keypair, 1, BASE_FEE, 1);
+        let second = mock_txn_with_nonce(&keypair, 1, BASE_FEE * 2, 2);
+        let fourth = mock_txn_with_nonce(&keypair, 1, BASE_FEE * 3, 4);
+
+        mpooldb.insert(fourth.clone()).unwrap();
+        mpooldb.insert(second.clone()).unwrap();
+        mpooldb.insert(first.clone()).unwrap();
+        mpooldb.update_sender_nonce(&sender, 1).unwrap();
+
+        let best = mpooldb
+            .fetch_best(10)
+            .into_iter()
+            .map(|record| record.txn_id)
+            .collect::<Vec<_>>();
+
+        assert_eq!(best, vec![first.id(), second.id()]);
+
+        let queue = mpooldb.sender_queue(&sender).unwrap();
+        assert_eq!(queue.future().keys().copied().collect::<Vec<_>>(), vec![4]);
+
+        let third = mock_txn_with_nonce(&keypair, 1, BASE_FEE, 3);
+        mpooldb.insert(third.clone()).unwrap();
+
+        let best = mpooldb
+            .fetch_best(10)
+            .into_iter()
+            .map(|record| record.txn_id)
+            .collect::<Vec<_>>();
+
+        assert_eq!(best, vec![first.id(), second.id(), third.id(), fourth.id()]);
+    }
+
+    #[test]
+    fn updating_sender_nonce_drops_stale_txns() {
+        let keypair = KeyPair::random();
+        let sender = Address::new(keypair.get_miner_public_key().clone());
+        let mut mpooldb = LeftRightMempool::new();
+
+        let first = mock_txn_with_nonce(&keypair, 1, BASE_FEE, 0);
+        let second = mock_txn_with_nonce(&keypair, 1, BASE_FEE, 1);
+
+        mpooldb.insert(first.clone()).unwrap();
+        mpooldb.insert(second.clone()).unwrap();
+        mpooldb.update_sender_nonce(&sender, 1).unwrap();
+
+        assert_eq!(mpooldb.size(), 1);
+        assert!(mpooldb.get(&first.id()).is_none());
+        assert_eq!(mpooldb.fetch_best(10)[0].txn_id, second.id());
+    }
+
+    #[test]
+    fn adding_a_txn_with_a_pending_nonce_drops_the_pending_one() {
+        let keypair = KeyPair::random();
+        let sender = Address::new(keypair.get_miner_public_key().clone());
+        let mut mempool = Mempool::default();
+
+        let first = mock_txn_with_nonce(&keypair, 1, BASE_FEE, 0);
+        let second = mock_txn_with_nonce(&keypair, 2, BASE_FEE, 0);
+
+        for txn in [first.clone(), second.clone()] {
+            let mut op = MempoolOp::Add(Box::new(TxnRecord::new(txn)));
+            mempool.absorb_first(&mut op, &Mempool::default());
+        }
+
+        assert_eq!(mempool.len(), 1);
+        assert!(mempool.get(&first.id()).is_none());
+        assert_eq!(mempool.lowest_priority().unwrap().txn_id, second.id());
+        assert_eq!(
+            mempool.sender_queue(&sender).unwrap().get(0),
+            Some(&second.id())
+        );
+    }
+
+    #[test]
+    fn replaces_pending_txn_paying_a_higher_fee() {
+        let keypair = KeyPair::random();
+        let mut mpooldb = LeftRightMempool::new();
+
+        let original = mock_txn_with_nonce(&keypair, 1, BASE_FEE, 0);
+        let replacement =
+            mock_txn_with_nonce(&keypair, 1, mpooldb.min_replacement_fee(BASE_FEE), 0);
+
+        mpooldb.insert(original.clone()).unwrap();
+        mpooldb.insert(replacement.clone()).unwrap();
+
+        assert_eq!(mpooldb.size(), 1);
+        assert!(mpooldb.get_txn(&original.id()).is_none());
+        assert_eq!(mpooldb.fetch_best(10)[0].txn_id, replacement.id());
+
+        let replaced = mpooldb.get(&original.id()).unwrap();
+        assert_eq!(replaced.status, TxnStatus::Replaced);
+        assert_eq!(replaced.replaced_by, Some(replacement.id()));
+
+        mpooldb.remove(&replacement.id()).unwrap();
+        assert!(mpooldb.get(&original.id()).is_none());
+    }
+
+    #[test]
+    fn rejects_underpriced_replacements() {
+        let keypair = KeyPair::random();
+        let mut mpooldb = LeftRightMempool::new();
+
+        let original = mock_txn_with_nonce(&keypair, 1, BASE_FEE, 0);
+        let min_fee = mpooldb.min_replacement_fee(BASE_FEE);
+        let replacement = mock_txn_with_nonce(&keypair, 1, min_fee - 1, 0);
+
+        mpooldb.insert(original.clone()).unwrap();
+
+        assert_eq!(
+            mpooldb.insert(replacement.clone()),
+            Err(MempoolError::ReplacementUnderpriced(
+                replacement.id(),
+                min_fee
+            ))
+        );
+        assert_eq!(
+            mpooldb.get(&original.id()).unwrap().status,
+            TxnStatus::Pending
+        );
+    }
+
+    #[test]
+    fn rejects_txns_paying_less_than_the_base_fee() {
+        let mut mpooldb = LeftRightMempool::new();
+
+        let txn = mock_txn_with_fee(&KeyPair::random(), 1, BASE_FEE - 1);
+
+        assert_eq!(
+            mpooldb.insert(txn.clone()),
+            Err(MempoolError::FeeBelowMinimum(txn.id(), BASE_FEE))
+        );
+        assert!(mpooldb.is_empty());
+    }
+
+    #[test]
+    fn rejects_txns_the_sender_cannot_pay_for() {
+        let keypair = KeyPair::random();
+        let mut mpooldb = LeftRightMempool::new();
+        let balance = 10 + 2 * BASE_FEE;
+
+        let first = mock_txn_with_nonce(&keypair, 5, BASE_FEE, 0);
+        let second = mock_txn_with_nonce(&keypair, 6, BASE_FEE, 1);
+
+        mpooldb.insert_funded(first.clone(), balance).unwrap();
+        assert_eq!(
+            mpooldb.insert_funded(second.clone(), balance),
+            Err(MempoolError::InsufficientBalance(
+                second.id(),
+                11 + 2 * BASE_FEE
+            ))
+        );
+
+        let replacement =
+            mock_txn_with_nonce(&keypair, 5, mpooldb.min_replacement_fee(BASE_FEE), 0);
+        assert!(matches!(
+            mpooldb.insert_funded(replacement, BASE_FEE),
+            Err(MempoolError::InsufficientBalance(..))
+        ));
+
+        let cheaper = mock_txn_with_nonce(&keypair, 5, BASE_FEE, 1);
+        mpooldb.insert_funded(cheaper, balance).unwrap();
+        assert_eq!(mpooldb.size(), 2);
+    }
+
+    fn journal_dir() -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("mempool-journal-{}", thread_rng().gen::<u64>()))
+    }
+
+    #[test]
+    fn journal_restores_pending_txns_after_restart() {
+        let dir = journal_dir();
+        let keypair = KeyPair::random();
+
+        let first = mock_txn_with_nonce(&keypair, 1, BASE_FEE, 0);
+        let second = mock_txn_with_nonce(&keypair, 1, BASE_FEE, 1);
+        let removed = mock_txn_with_fee(&KeyPair::random(), 1, BASE_FEE);
+
+        {
+            let mut mpooldb =
+                LeftRightMempool::with_journal(MempoolConfig::default(), &dir, |_| false).unwrap();
+
+            mpooldb.insert(first.clone()).unwrap();
+            mpooldb.insert(second.clone()).unwrap();
+            mpooldb.insert(removed.clone()).unwrap();
+            mpooldb.remove(&removed.id()).unwrap();
+        }
+
+        let mut mpooldb =
+            LeftRightMempool::with_journal(MempoolConfig::default(), &dir, |_| false).unwrap();
+
+        assert_eq!(mpooldb.size(), 2);
+        assert!(mpooldb.get(&removed.id()).is_none());
+        assert_eq!(
+            mpooldb
+                .fetch_best(10)
+                .into_iter()
+                .map(|record| record.txn_id)
+                .collect::<Vec<_>>(),
+            vec![first.id(), second.id()]
+        );
+        assert_eq!(mpooldb.journal().unwrap().len(), 2);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn journal_replay_drops_confirmed_txns() {
+        let dir = journal_dir();
+        let confirmed = mock_txn_with_fee(&KeyPair::random(), 1, BASE_FEE);
+        let pending = mock_txn_with_fee(&KeyPair::random(), 1, BASE_FEE);
+
+        {
+            let mut mpooldb =
+                LeftRightMempool::with_journal(MempoolConfig::default(), &dir, |_| false).unwrap();
+
+            mpooldb.insert(confirmed.clone()).unwrap();
+            mpooldb.insert(pending.clone()).unwrap();
+        }
+
+        let confirmed_id = confirmed.id();
+        let mut mpooldb =
+            LeftRightMempool::with_journal(MempoolConfig::default(), &dir, |digest| {
+                digest == &confirmed_id
+            })
+            .unwrap();
+
+        assert_eq!(mpooldb.size(), 1);
+        assert!(mpooldb.get(&confirmed.id()).is_none());
+        assert!(mpooldb.get(&pending.id()).is_some());
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn journal_replay_enforces_the_mempool_limits() {
+        let dir = journal_dir();
+        let keypair = KeyPair::random();
+
+        let txns = (0..3)
+            .map(|n| mock_txn_with_nonce(&keypair, 1, BASE_FEE * (n + 2), n))
+            .chain([mock_txn_with_fee(&KeyPair::random(), 1, BASE_FEE)])
+            .collect::<Vec<_>>();
+
+        {
+            let mut mpooldb =
+                LeftRightMempool::with_journal(MempoolConfig::default(), &dir, |_| false).unwrap();
+
+            for txn in txns.iter() {
+                mpooldb.insert(txn.clone()).unwrap();
+            }
+        }
+
+        let config = MempoolConfig {
+            max_size: 2,
+            max_txns_per_sender: 2,
+            ..Default::default()
+        };
+        let mpooldb = LeftRightMempool::with_journal(config, &dir, |_| false).unwrap();
+
+        assert_eq!(mpooldb.size(), 2);
+        assert_eq!(
+            mpooldb
+                .sender_queue(&txns[0].sender_address())
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(mpooldb.journal().unwrap().len(), 2);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn journal_is_compacted_past_the_threshold() {
+        let dir = journal_dir();
+        let config = MempoolConfig {
+            journal_compaction_threshold: 4,
+            ..Default::default()
+        };
+
+        let mut mpooldb = LeftRightMempool::with_journal(config, &dir, |_| false).unwrap();
+
+        let txns = (0..3)
+            .map(|n| mock_txn_with_fee(&KeyPair::random(), n, BASE_FEE))
+            .collect::<Vec<_>>();
+
+        for txn in txns.iter() {
+            mpooldb.insert(txn.clone()).unwrap();
+        }
+        mpooldb.remove(&txns[0].id()).unwrap();
+
+        let journal = mpooldb.journal().unwrap();
+        assert_eq!(journal.len(), 2);
+        assert_eq!(journal.appended(), 0);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn journal_is_compacted_past_the_size_threshold() {
+        let dir = journal_dir();
+        let config = MempoolConfig {
+            journal_compaction_bytes: 1,
+            ..Default::default()
+        };
+
+        let mut mpooldb = LeftRightMempool::with_journal(config, &dir, |_| false).unwrap();
+
+        let txns = (0..3)
+            .map(|n| mock_txn_with_fee(&KeyPair::random(), n, BASE_FEE))
+            .collect::<Vec<_>>();
+
+        for txn in txns.iter() {
+            mpooldb.insert(txn.clone()).unwrap();
+        }
+
+        let journal = mpooldb.journal().unwrap();
+        assert_eq!(journal.len(), 3);
+        assert_eq!(journal.appended_bytes(), 0);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
 }
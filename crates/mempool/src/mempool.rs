@@ -1,16 +1,23 @@
 use std::{
-    collections::{HashMap, HashSet},
+    cmp::Reverse,
+    collections::{BTreeSet, BinaryHeap, HashMap, HashSet},
     hash::Hash,
+    path::Path,
     result::Result as StdResult,
 };
 
 use fxhash::FxBuildHasher;
 use indexmap::IndexMap;
 use left_right::{Absorb, ReadHandle, ReadHandleFactory, WriteHandle};
+use primitives::Address;
 use serde::{Deserialize, Serialize};
-use vrrb_core::transactions::{Transaction, TransactionDigest, TransactionKind, TxTimestamp};
+use vrrb_config::MempoolConfig;
+use vrrb_core::transactions::{
+    Transaction, TransactionDigest, TransactionKind, TxAmount, TxFee, TxNonce, TxTimestamp,
+    BASE_FEE,
+};
 
-use super::error::MempoolError;
+use super::{error::MempoolError, journal::MempoolJournal, sender_queue::SenderQueue};
 
 pub type Result<T> = StdResult<T, MempoolError>;
 
@@ -24,6 +31,9 @@ pub struct TxnRecord {
     pub validated_timestamp: TxTimestamp,
     pub rejected_timestamp: TxTimestamp,
     pub deleted_timestamp: TxTimestamp,
+    /// Digest of the transaction that replaced this one, if any
+    #[serde(default)]
+    pub replaced_by: Option<TransactionDigest>,
 }
 
 impl TxnRecord {
@@ -50,6 +60,26 @@ impl TxnRecord {
 
 pub type PoolType = IndexMap<TransactionDigest, TxnRecord, FxBuildHasher>;
 
+/// Ordering key of a transaction within the mempool. Transactions paying a
+/// higher fee rank higher, ties are broken in favour of the ones that have
+/// been waiting in the pool for longer.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TxnPriority {
+    pub fee: TxFee,
+    pub age: Reverse<TxTimestamp>,
+    pub txn_id: TransactionDigest,
+}
+
+impl From<&TxnRecord> for TxnPriority {
+    fn from(record: &TxnRecord) -> Self {
+        TxnPriority {
+            fee: record.txn.fee(),
+            age: Reverse(record.added_timestamp),
+            txn_id: record.txn_id.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TxnStatus {
     #[default]
@@ -57,12 +87,16 @@ pub enum TxnStatus {
     Validating,
     Validated,
     Rejected,
+    Replaced,
 }
 
 /// Mempool stores unprocessed transactions
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Mempool {
     pool: PoolType,
+    priority: BTreeSet<TxnPriority>,
+    senders: HashMap<Address, SenderQueue>,
+    replaced: HashMap<TransactionDigest, TxnRecord>,
 }
 
 pub const DEFAULT_INITIAL_MEMPOOL_CAPACITY: usize = 10000;
@@ -74,6 +108,9 @@ impl Default for Mempool {
                 DEFAULT_INITIAL_MEMPOOL_CAPACITY,
                 <_>::default(),
             ),
+            priority: BTreeSet::new(),
+            senders: HashMap::new(),
+            replaced: HashMap::new(),
         }
     }
 }
@@ -86,22 +123,207 @@ impl Mempool {
     pub fn is_empty(&self) -> bool {
         self.pool.is_empty()
     }
+
+    /// Returns the record of a pending transaction, or of a transaction that
+    /// was replaced by one that is still pending
+    pub fn get(&self, id: &TransactionDigest) -> Option<&TxnRecord> {
+        self.pool.get(id).or_else(|| self.replaced.get(id))
+    }
+
+    /// Returns up to `amount` ready records, highest priority first. The
+    /// transactions of a sender are always returned in nonce order, so a
+    /// nonce is never picked without all the ones preceding it.
+    pub fn best(&self, amount: usize) -> Vec<TxnRecord> {
+        let mut ready_queues = self
+            .senders
+            .values()
+            .map(|queue| queue.ready().values())
+            .collect::<Vec<_>>();
+
+        let mut heads = BinaryHeap::new();
+        for (idx, ready) in ready_queues.iter_mut().enumerate() {
+            if let Some(head) = ready.next().and_then(|digest| self.priority_of(digest)) {
+                heads.push((head, idx));
+            }
+        }
+
+        let mut best = Vec::with_capacity(amount);
+        while best.len() < amount {
+            let (priority, idx) = match heads.pop() {
+                Some(head) => head,
+                None => break,
+            };
+
+            if let Some(record) = self.pool.get(&priority.txn_id) {
+                best.push(record.clone());
+            }
+
+            let next = ready_queues[idx]
+                .next()
+                .and_then(|digest| self.priority_of(digest));
+
+            if let Some(next) = next {
+                heads.push((next, idx));
+            }
+        }
+
+        best
+    }
+
+    fn priority_of(&self, digest: &TransactionDigest) -> Option<TxnPriority> {
+        self.pool.get(digest).map(TxnPriority::from)
+    }
+
+    /// Returns the priority of the transaction that would be evicted first
+    pub fn lowest_priority(&self) -> Option<&TxnPriority> {
+        self.priority.first()
+    }
+
+    /// Returns the number of pending transactions sent by `address`
+    pub fn sender_txn_count(&self, address: &Address) -> usize {
+        self.senders
+            .get(address)
+            .map(|queue| queue.len())
+            .unwrap_or_default()
+    }
+
+    /// Returns the ready and future transactions sent by `address`
+    pub fn sender_queue(&self, address: &Address) -> Option<&SenderQueue> {
+        self.senders.get(address)
+    }
+
+    /// Returns what the pending transactions of `address` take out of its
+    /// balance together, fees included
+    pub fn sender_cost(&self, address: &Address) -> TxAmount {
+        self.senders
+            .get(address)
+            .map(|queue| {
+                queue
+                    .ready()
+                    .values()
+                    .chain(queue.future().values())
+                    .filter_map(|digest| self.pool.get(digest))
+                    .map(|record| record.txn.cost())
+                    .fold(0, TxAmount::saturating_add)
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the operations that rebuild the pending transactions of this
+    /// mempool, and the nonces expected from their senders, on an empty one.
+    /// Records of replaced transactions are not carried over.
+    pub fn snapshot_ops(&self) -> Vec<MempoolOp> {
+        let records = self
+            .pool
+            .values()
+            .map(|record| MempoolOp::Add(Box::new(record.clone())));
+
+        let nonces = self.senders.iter().filter_map(|(address, queue)| {
+            queue
+                .next_nonce()
+                .map(|nonce| MempoolOp::UpdateNonce(address.clone(), nonce))
+        });
+
+        records.chain(nonces).collect()
+    }
+
+    fn update_sender_nonce(&mut self, address: &Address, nonce: TxNonce) {
+        if let Some(queue) = self.senders.get_mut(address) {
+            queue.set_next_nonce(nonce);
+        }
+    }
+
+    fn insert_record(&mut self, record: TxnRecord) {
+        if let Some(previous) = self.pool.insert(record.txn_id.clone(), record.clone()) {
+            self.unindex_record(&previous);
+        }
+        self.index_record(&record);
+    }
+
+    fn remove_record(&mut self, id: &TransactionDigest) {
+        if let Some(record) = self.pool.remove(id) {
+            self.unindex_record(&record);
+            self.replaced
+                .retain(|_, replaced| replaced.replaced_by.as_ref() != Some(id));
+        }
+    }
+
+    /// Swaps a pending transaction for one with the same sender and nonce.
+    /// The old record is kept around, marked as replaced, for as long as its
+    /// replacement stays in the pool.
+    fn replace_record(&mut self, old: &TransactionDigest, record: TxnRecord) {
+        if let Some(mut replaced) = self.pool.remove(old) {
+            self.unindex_record(&replaced);
+
+            self.replaced
+                .values_mut()
+                .filter(|previous| previous.replaced_by.as_ref() == Some(old))
+                .for_each(|previous| previous.replaced_by = Some(record.txn_id.clone()));
+
+            replaced.status = TxnStatus::Replaced;
+            replaced.replaced_by = Some(record.txn_id.clone());
+            self.replaced.insert(old.clone(), replaced);
+        }
+
+        self.insert_record(record);
+    }
+
+    /// Indexes a record by priority and by sender. A record that takes the
+    /// nonce of another pending transaction of its sender pushes that one out
+    /// of the pool, so the pool never holds transactions its sender queues
+    /// don't know about. `LeftRightMempool` records such additions as
+    /// replacements, so the journal never restores the displaced one.
+    fn index_record(&mut self, record: &TxnRecord) {
+        self.priority.insert(TxnPriority::from(record));
+
+        let displaced = self
+            .senders
+            .entry(record.txn.sender_address())
+            .or_default()
+            .insert(record.txn.nonce(), record.txn_id.clone());
+
+        if let Some(displaced) = displaced.filter(|displaced| displaced != &record.txn_id) {
+            if let Some(displaced) = self.pool.remove(&displaced) {
+                self.priority.remove(&TxnPriority::from(&displaced));
+            }
+        }
+    }
+
+    fn unindex_record(&mut self, record: &TxnRecord) {
+        self.priority.remove(&TxnPriority::from(record));
+
+        let sender = record.txn.sender_address();
+        if let Some(queue) = self.senders.get_mut(&sender) {
+            queue.remove(record.txn.nonce(), &record.txn_id);
+            if queue.is_empty() {
+                self.senders.remove(&sender);
+            }
+        }
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MempoolOp {
     Add(Box<TxnRecord>),
     Remove(TransactionDigest),
+    Replace(TransactionDigest, Box<TxnRecord>),
+    UpdateNonce(Address, TxNonce),
 }
 
 impl Absorb<MempoolOp> for Mempool {
     fn absorb_first(&mut self, op: &mut MempoolOp, _: &Self) {
         match op {
             MempoolOp::Add(record) => {
-                self.pool.insert(record.txn_id.clone(), *record.clone());
+                self.insert_record(*record.clone());
             },
             MempoolOp::Remove(id) => {
-                self.pool.remove(id);
+                self.remove_record(id);
+            },
+            MempoolOp::Replace(old, record) => {
+                self.replace_record(old, *record.clone());
+            },
+            MempoolOp::UpdateNonce(address, nonce) => {
+                self.update_sender_nonce(address, *nonce);
             },
         }
     }
@@ -125,28 +347,29 @@ impl FetchFiltered for ReadHandle<Mempool> {
         if let Some(map) = self.enter().map(|guard| guard.clone()) {
             let mut result = map.pool;
             result.retain(f);
-            let mut returned = Vec::<TxnRecord>::new();
-            for (_, v) in &result {
-                returned.push(v.clone());
-            }
-            // TODO:  Error - length
-            return returned[0..amount as usize].to_vec();
+            return result.into_values().take(amount as usize).collect();
         };
         Vec::<TxnRecord>::new()
     }
 }
 
+/// Outcome of checking a new record against the mempool limits
+enum Admission {
+    Insert { evict: Option<TransactionDigest> },
+    Replace { replaced: TransactionDigest },
+}
+
 #[derive(Debug)]
 pub struct LeftRightMempool {
     pub read: ReadHandle<Mempool>,
     pub write: WriteHandle<Mempool, MempoolOp>,
+    config: MempoolConfig,
+    journal: Option<MempoolJournal>,
 }
 
 impl Default for LeftRightMempool {
     fn default() -> Self {
-        let (write, read) = left_right::new::<Mempool, MempoolOp>();
-
-        LeftRightMempool { read, write }
+        Self::with_config(MempoolConfig::default())
     }
 }
 
@@ -156,6 +379,90 @@ impl LeftRightMempool {
         Self::default()
     }
 
+    /// Creates new Mempool DB bounded by the limits within `config`
+    pub fn with_config(config: MempoolConfig) -> Self {
+        let (write, read) = left_right::new::<Mempool, MempoolOp>();
+
+        LeftRightMempool {
+            read,
+            write,
+            config,
+            journal: None,
+        }
+    }
+
+    /// Creates new Mempool DB that records every change in the journal stored
+    /// within `journal_dir`, so pending transactions survive restarts.
+    ///
+    /// The operations already in the journal are replayed first, skipping the
+    /// transactions for which `is_confirmed` returns true, then the journal
+    /// is compacted. Replayed transactions go through the same admission
+    /// checks as new ones, so the ones `config` no longer admits are dropped.
+    pub fn with_journal<F>(
+        config: MempoolConfig,
+        journal_dir: &Path,
+        is_confirmed: F,
+    ) -> Result<Self>
+    where
+        F: Fn(&TransactionDigest) -> bool,
+    {
+        let mut journal = MempoolJournal::open(journal_dir)?;
+        let mut mempool_db = Self::with_config(config);
+
+        for op in journal.replay()? {
+            let replayed = match op {
+                MempoolOp::Add(record) if is_confirmed(&record.txn_id) => Ok(()),
+                MempoolOp::Replace(old, record) if is_confirmed(&record.txn_id) => {
+                    mempool_db.remove(&old)
+                },
+                MempoolOp::Add(record) | MempoolOp::Replace(_, record) => {
+                    mempool_db.admit(*record, None)
+                },
+                MempoolOp::Remove(id) => mempool_db.remove(&id),
+                MempoolOp::UpdateNonce(address, nonce) => {
+                    mempool_db.update_sender_nonce(&address, nonce)
+                },
+            };
+
+            if let Err(err) = replayed {
+                telemetry::warn!("dropping mempool journal entry on replay: {err}");
+            }
+        }
+
+        journal.compact(&mempool_db.snapshot_ops())?;
+        mempool_db.journal = Some(journal);
+
+        Ok(mempool_db)
+    }
+
+    pub fn config(&self) -> &MempoolConfig {
+        &self.config
+    }
+
+    /// Returns the journal changes are recorded in, if any
+    pub fn journal(&self) -> Option<&MempoolJournal> {
+        self.journal.as_ref()
+    }
+
+    /// Rewrites the journal, if any, with the operations that rebuild the
+    /// current pool
+    pub fn compact_journal(&mut self) -> Result<()> {
+        let ops = self.snapshot_ops();
+
+        if let Some(journal) = self.journal.as_mut() {
+            journal.compact(&ops)?;
+        }
+
+        Ok(())
+    }
+
+    fn snapshot_ops(&self) -> Vec<MempoolOp> {
+        self.read
+            .enter()
+            .map(|guard| guard.snapshot_ops())
+            .unwrap_or_default()
+    }
+
     /// Getter for Mempool DB
     pub fn pool(&self) -> PoolType {
         self.read
@@ -181,11 +488,14 @@ impl LeftRightMempool {
     /// Return the number of key-value pairs in the map.
     ///
     pub fn len(&self) -> usize {
-        self.pool().len()
+        self.read
+            .enter()
+            .map(|guard| guard.len())
+            .unwrap_or_default()
     }
 
     pub fn is_empty(&self) -> bool {
-        self.pool().is_empty()
+        self.len() == 0
     }
 
     /// Adds a new transaction, makes sure it is unique in db.
@@ -198,29 +508,141 @@ impl LeftRightMempool {
 
     pub fn insert(&mut self, txn: TransactionKind) -> Result<usize> {
         let txn_record = TxnRecord::new(txn);
-        self.write
-            .append(MempoolOp::Add(Box::new(txn_record)))
-            .publish();
+        self.admit(txn_record, None)?;
+
+        Ok(self.size_in_kilobytes())
+    }
+
+    /// Same as `insert`, but only admits the transaction if `balance`, the
+    /// spendable balance of its sender, covers its amount and fee on top of
+    /// those of the sender's other pending transactions
+    pub fn insert_funded(&mut self, txn: TransactionKind, balance: TxAmount) -> Result<usize> {
+        let txn_record = TxnRecord::new(txn);
+        self.admit(txn_record, Some(balance))?;
 
         Ok(self.size_in_kilobytes())
     }
 
+    /// Checks a record against the mempool limits and publishes it. Records
+    /// paying less than `BASE_FEE`, or costing more than the sender's
+    /// `balance` when given, are rejected before anything is evicted. A
+    /// record sharing sender and nonce with a pending one replaces it if it
+    /// pays a high enough fee, otherwise the lowest priority transaction is
+    /// evicted when the pool is full. Records that are already in the pool
+    /// are overwritten without further checks.
+    fn admit(&mut self, record: TxnRecord, balance: Option<TxAmount>) -> Result<()> {
+        match self.admission(&record, balance)? {
+            Admission::Insert { evict } => {
+                if let Some(evicted) = evict {
+                    self.append_op(MempoolOp::Remove(evicted))?;
+                }
+                self.append_op(MempoolOp::Add(Box::new(record)))?;
+            },
+            Admission::Replace { replaced } => {
+                self.append_op(MempoolOp::Replace(replaced, Box::new(record)))?;
+            },
+        }
+
+        self.publish()
+    }
+
+    fn admission(&self, record: &TxnRecord, balance: Option<TxAmount>) -> Result<Admission> {
+        if record.txn.fee() < BASE_FEE {
+            return Err(MempoolError::FeeBelowMinimum(
+                record.txn_id.clone(),
+                BASE_FEE,
+            ));
+        }
+
+        let mempool = match self.read.enter() {
+            Some(mempool) => mempool,
+            None => return Ok(Admission::Insert { evict: None }),
+        };
+
+        if mempool.pool.contains_key(&record.txn_id) {
+            return Ok(Admission::Insert { evict: None });
+        }
+
+        let sender = record.txn.sender_address();
+        let pending = mempool
+            .sender_queue(&sender)
+            .and_then(|queue| queue.get(record.txn.nonce()))
+            .and_then(|digest| mempool.pool.get(digest));
+
+        if let Some(balance) = balance {
+            let replaced_cost = pending
+                .map(|pending| pending.txn.cost())
+                .unwrap_or_default();
+            let cost = mempool
+                .sender_cost(&sender)
+                .saturating_sub(replaced_cost)
+                .saturating_add(record.txn.cost());
+
+            if cost > balance {
+                return Err(MempoolError::InsufficientBalance(
+                    record.txn_id.clone(),
+                    cost,
+                ));
+            }
+        }
+
+        if let Some(pending) = pending {
+            let min_fee = self.min_replacement_fee(pending.txn.fee());
+            if record.txn.fee() < min_fee {
+                return Err(MempoolError::ReplacementUnderpriced(
+                    record.txn_id.clone(),
+                    min_fee,
+                ));
+            }
+
+            return Ok(Admission::Replace {
+                replaced: pending.txn_id.clone(),
+            });
+        }
+
+        if mempool.sender_txn_count(&sender) >= self.config.max_txns_per_sender {
+            return Err(MempoolError::SenderLimitReached(sender));
+        }
+
+        if mempool.len() < self.config.max_size {
+            return Ok(Admission::Insert { evict: None });
+        }
+
+        match mempool.lowest_priority() {
+            Some(lowest) if TxnPriority::from(record) > *lowest => Ok(Admission::Insert {
+                evict: Some(lowest.txn_id.clone()),
+            }),
+            _ => Err(MempoolError::FeeTooLow(record.txn_id.clone())),
+        }
+    }
+
+    /// Returns the lowest fee a transaction has to pay in order to replace a
+    /// pending one paying `fee`
+    pub fn min_replacement_fee(&self, fee: TxFee) -> TxFee {
+        let bump = fee.saturating_mul(self.config.replacement_fee_bump_percent as TxFee) / 100;
+
+        fee.saturating_add(bump.max(1))
+    }
+
     /// Retrieves a single transaction identified by id, makes sure it exists in
     /// db
     pub fn get_txn(&mut self, txn_hash: &TransactionDigest) -> Option<TransactionKind> {
-        if let Some(record) = self.get(txn_hash) {
-            return Some(record.txn);
+        match self.get(txn_hash) {
+            Some(record) if record.status != TxnStatus::Replaced => Some(record.txn),
+            _ => None,
         }
-        None
     }
 
-    /// Getter for an entire pending Txn record
+    /// Getter for an entire pending Txn record. Records of replaced
+    /// transactions are returned for as long as their replacement is pending.
     pub fn get(&mut self, txn_id: &TransactionDigest) -> Option<TxnRecord> {
         if txn_id.to_string().is_empty() {
             return None;
         }
 
-        self.pool().get(txn_id).cloned()
+        self.read
+            .enter()
+            .and_then(|guard| guard.get(txn_id).cloned())
     }
 
     /// It fetches the transactions from the pool and returns them.
@@ -245,6 +667,53 @@ impl LeftRightMempool {
         txns_records
     }
 
+    /// Returns up to `amount` ready records ordered by fee, highest first.
+    /// Transactions paying the same fee are returned oldest first, and the
+    /// transactions of a single sender are always returned in nonce order.
+    pub fn fetch_best(&self, amount: usize) -> Vec<TxnRecord> {
+        self.read
+            .enter()
+            .map(|guard| guard.best(amount))
+            .unwrap_or_default()
+    }
+
+    /// Returns the next nonce the mempool expects from `address`, if it has
+    /// been told about it. Senders without pending transactions are unknown.
+    pub fn sender_next_nonce(&self, address: &Address) -> Option<TxNonce> {
+        self.read.enter().and_then(|guard| {
+            guard
+                .sender_queue(address)
+                .and_then(SenderQueue::next_nonce)
+        })
+    }
+
+    /// Returns a snapshot of the ready and future transactions of a sender
+    pub fn sender_queue(&self, address: &Address) -> Option<SenderQueue> {
+        self.read
+            .enter()
+            .and_then(|guard| guard.sender_queue(address).cloned())
+    }
+
+    /// Sets the next nonce expected from `address`, usually one past its
+    /// account nonce, promoting the transactions that no longer have a gap in
+    /// front of them. Transactions with a lower nonce can no longer be applied
+    /// and are removed from the pool.
+    pub fn update_sender_nonce(&mut self, address: &Address, nonce: TxNonce) -> Result<()> {
+        self.append_op(MempoolOp::UpdateNonce(address.to_owned(), nonce))?;
+        self.publish()?;
+
+        let stale = self
+            .sender_queue(address)
+            .map(|queue| queue.stale())
+            .unwrap_or_default();
+
+        if !stale.is_empty() {
+            self.remove_txns(&stale.into_iter().collect())?;
+        }
+
+        Ok(())
+    }
+
     /// Adds a batch of new transaction, makes sure that each is unique in db.
     /// Pushes to ReadHandle after processing of the entire batch.
     #[deprecated(note = "use extend instead")]
@@ -256,23 +725,53 @@ impl LeftRightMempool {
         self.extend(txn_batch.clone())
     }
 
+    /// Adds a batch of transactions. Transactions rejected by the mempool
+    /// limits are skipped and the first rejection is returned once the rest
+    /// of the batch has been added.
     pub fn extend(&mut self, txn_batch: HashSet<TransactionKind>) -> Result<()> {
-        txn_batch.into_iter().for_each(|t| {
-            self.write
-                .append(MempoolOp::Add(Box::new(TxnRecord::new(t))));
-        });
+        self.extend_with_records(txn_batch.into_iter().map(TxnRecord::new).collect())
+    }
 
-        self.publish();
-        Ok(())
+    /// Same as `extend`, but only admits the transactions whose senders can
+    /// afford them given their spendable `balances`. Senders missing from
+    /// `balances` are taken to have none.
+    pub fn extend_funded(
+        &mut self,
+        txn_batch: HashSet<TransactionKind>,
+        balances: &HashMap<Address, TxAmount>,
+    ) -> Result<()> {
+        let mut rejected = None;
+
+        for txn in txn_batch {
+            let balance = balances
+                .get(&txn.sender_address())
+                .copied()
+                .unwrap_or_default();
+
+            if let Err(err) = self.admit(TxnRecord::new(txn), Some(balance)) {
+                rejected.get_or_insert(err);
+            }
+        }
+
+        match rejected {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
     }
 
     pub fn extend_with_records(&mut self, record_batch: HashSet<TxnRecord>) -> Result<()> {
-        record_batch.into_iter().for_each(|t| {
-            self.write.append(MempoolOp::Add(Box::new(t)));
-        });
+        let mut rejected = None;
 
-        self.publish();
-        Ok(())
+        for record in record_batch {
+            if let Err(err) = self.admit(record, None) {
+                rejected.get_or_insert(err);
+            }
+        }
+
+        match rejected {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
     }
 
     /// Removes a single transaction identified by id, makes sure it exists in
@@ -290,10 +789,8 @@ impl LeftRightMempool {
     }
 
     pub fn remove(&mut self, id: &TransactionDigest) -> Result<()> {
-        self.write
-            .append(MempoolOp::Remove(id.to_owned()))
-            .publish();
-        Ok(())
+        self.append_op(MempoolOp::Remove(id.to_owned()))?;
+        self.publish()
     }
 
     /// Removes a batch of transactions, makes sure that each is unique in db.
@@ -304,23 +801,19 @@ impl LeftRightMempool {
         txn_batch: &HashSet<TransactionKind>,
         _txns_status: TxnStatus,
     ) -> Result<()> {
-        txn_batch.iter().for_each(|t| {
-            self.write.append(MempoolOp::Remove(t.id()));
-        });
-
-        self.publish();
+        for txn in txn_batch {
+            self.append_op(MempoolOp::Remove(txn.id()))?;
+        }
 
-        Ok(())
+        self.publish()
     }
 
     pub fn remove_txns(&mut self, txn_batch: &HashSet<TransactionDigest>) -> Result<()> {
-        txn_batch.iter().for_each(|t| {
-            self.write.append(MempoolOp::Remove(t.to_owned()));
-        });
-
-        self.publish();
+        for id in txn_batch {
+            self.append_op(MempoolOp::Remove(id.to_owned()))?;
+        }
 
-        Ok(())
+        self.publish()
     }
 
     /// Was the Txn validated ? And when ?
@@ -336,7 +829,7 @@ impl LeftRightMempool {
 
     /// Retrieves actual size of the mempooldb.
     pub fn size(&self) -> usize {
-        self.pool().len()
+        self.len()
     }
 
     /// Retrieves actual size of the mempooldb in Kilobytes.
@@ -347,16 +840,63 @@ impl LeftRightMempool {
         (mempool_items * txn_size_factor) / 1024
     }
 
-    /// Pushes changes to Reader.
-    fn publish(&mut self) {
+    /// Records an operation in the journal, if any, and queues it to be
+    /// published. Additions that displace a pending transaction of the same
+    /// sender and nonce are recorded as replacements of it.
+    fn append_op(&mut self, op: MempoolOp) -> Result<()> {
+        let op = self.displacing_op(op);
+
+        if let Some(journal) = self.journal.as_mut() {
+            journal.append(&op)?;
+        }
+        self.write.append(op);
+
+        Ok(())
+    }
+
+    /// Turns the addition of a record that takes the nonce of another
+    /// pending transaction of its sender into the replacement of that
+    /// transaction
+    fn displacing_op(&self, op: MempoolOp) -> MempoolOp {
+        let record = match op {
+            MempoolOp::Add(record) => record,
+            op => return op,
+        };
+
+        let displaced = self.read.enter().and_then(|mempool| {
+            mempool
+                .sender_queue(&record.txn.sender_address())
+                .and_then(|queue| queue.get(record.txn.nonce()))
+                .filter(|displaced| *displaced != &record.txn_id)
+                .cloned()
+        });
+
+        match displaced {
+            Some(displaced) => MempoolOp::Replace(displaced, record),
+            None => MempoolOp::Add(record),
+        }
+    }
+
+    /// Pushes changes to Reader. The journal is compacted once enough
+    /// operations, or bytes, have been recorded since it was last compacted.
+    fn publish(&mut self) -> Result<()> {
         self.write.publish();
+
+        let threshold = self.config.journal_compaction_threshold;
+        let threshold_bytes = self.config.journal_compaction_bytes;
+        if self.journal.as_ref().is_some_and(|journal| {
+            journal.appended() >= threshold || journal.appended_bytes() >= threshold_bytes
+        }) {
+            self.compact_journal()?;
+        }
+
+        Ok(())
     }
 }
 
 impl From<PoolType> for LeftRightMempool {
     fn from(pool: PoolType) -> Self {
-        let (write, read) = left_right::new::<Mempool, MempoolOp>();
-        let mut mempool_db = Self { read, write };
+        let mut mempool_db = Self::new();
 
         let records = pool.values().cloned().collect::<HashSet<TxnRecord>>();
 
@@ -368,7 +908,17 @@ impl From<PoolType> for LeftRightMempool {
 
 impl Clone for LeftRightMempool {
     fn clone(&self) -> Self {
-        Self::from(self.pool())
+        let mut mempool_db = Self::with_config(self.config.clone());
+
+        let records = self
+            .pool()
+            .values()
+            .cloned()
+            .collect::<HashSet<TxnRecord>>();
+
+        mempool_db.extend_with_records(records).unwrap_or_default();
+
+        mempool_db
     }
 }
 
@@ -406,9 +956,20 @@ impl MempoolReadHandleFactory {
     }
 
     pub fn get(&self, digest: &TransactionDigest) -> Option<TxnRecord> {
-        if let Some(record) = self.handle().get(digest) {
-            return Some(record.clone());
-        }
-        None
+        self.factory
+            .handle()
+            .enter()
+            .and_then(|guard| guard.get(digest).cloned())
+    }
+
+    /// Returns up to `amount` ready records ordered by fee, highest first.
+    /// Future transactions, whose nonce doesn't follow the sender's previous
+    /// one, are never returned.
+    pub fn fetch_best(&self, amount: usize) -> Vec<TxnRecord> {
+        self.factory
+            .handle()
+            .enter()
+            .map(|guard| guard.best(amount))
+            .unwrap_or_default()
     }
 }
@@ -0,0 +1,194 @@
+use std::collections::BTreeMap;
+
+use vrrb_core::transactions::{TransactionDigest, TxNonce};
+
+/// Pending transactions of a single sender ordered by nonce. A sender can
+/// only have one pending transaction per nonce, a second one can only take
+/// its place by replacing it.
+///
+/// Transactions whose nonces follow the sender's next expected nonce without
+/// gaps are `ready` to be included in a block. The rest wait in `future` and
+/// get promoted once the missing nonces arrive. When the next expected nonce
+/// is unknown, the lowest pending nonce is assumed to be it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SenderQueue {
+    next_nonce: Option<TxNonce>,
+    ready: BTreeMap<TxNonce, TransactionDigest>,
+    future: BTreeMap<TxNonce, TransactionDigest>,
+}
+
+impl SenderQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the next nonce expected from the sender, if known
+    pub fn next_nonce(&self) -> Option<TxNonce> {
+        self.next_nonce
+    }
+
+    /// Sets the next nonce expected from the sender, usually one past its
+    /// `Account::nonce`, and moves transactions between queues accordingly.
+    pub fn set_next_nonce(&mut self, nonce: TxNonce) {
+        self.next_nonce = Some(nonce);
+        self.rebalance();
+    }
+
+    /// Inserts a transaction into the queue, returning the digest of the
+    /// transaction it took the place of, if any.
+    pub fn insert(
+        &mut self,
+        nonce: TxNonce,
+        digest: TransactionDigest,
+    ) -> Option<TransactionDigest> {
+        let previous = self
+            .ready
+            .remove(&nonce)
+            .or_else(|| self.future.remove(&nonce));
+
+        self.future.insert(nonce, digest);
+        self.rebalance();
+
+        previous
+    }
+
+    /// Removes a transaction from the queue. Removing a ready transaction
+    /// opens a gap that demotes every ready transaction after it.
+    pub fn remove(&mut self, nonce: TxNonce, digest: &TransactionDigest) {
+        for queue in [&mut self.ready, &mut self.future] {
+            if queue.get(&nonce) == Some(digest) {
+                queue.remove(&nonce);
+            }
+        }
+        self.rebalance();
+    }
+
+    /// Returns the digest of the pending transaction with the given nonce
+    pub fn get(&self, nonce: TxNonce) -> Option<&TransactionDigest> {
+        self.ready.get(&nonce).or_else(|| self.future.get(&nonce))
+    }
+
+    /// Ready transactions keyed by nonce, in ascending nonce order
+    pub fn ready(&self) -> &BTreeMap<TxNonce, TransactionDigest> {
+        &self.ready
+    }
+
+    /// Transactions waiting for a nonce gap to close, in ascending nonce order
+    pub fn future(&self) -> &BTreeMap<TxNonce, TransactionDigest> {
+        &self.future
+    }
+
+    /// Returns the digests of all transactions with a nonce lower than the
+    /// next expected one. These can never be applied to the sender's account.
+    pub fn stale(&self) -> Vec<TransactionDigest> {
+        match self.next_nonce {
+            Some(next_nonce) => self
+                .future
+                .range(..next_nonce)
+                .map(|(_, digest)| digest.clone())
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.ready.len() + self.future.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ready.is_empty() && self.future.is_empty()
+    }
+
+    fn rebalance(&mut self) {
+        let mut pending = std::mem::take(&mut self.ready);
+        pending.append(&mut self.future);
+
+        let mut expected = match self.next_nonce.or(pending.keys().next().copied()) {
+            Some(nonce) => nonce,
+            None => return,
+        };
+
+        while let Some(digest) = pending.remove(&expected) {
+            self.ready.insert(expected, digest);
+            expected += 1;
+        }
+
+        self.future = pending;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digest(n: u8) -> TransactionDigest {
+        TransactionDigest::from(vec![n; 32])
+    }
+
+    #[test]
+    fn gaps_are_kept_in_future_until_closed() {
+        let mut queue = SenderQueue::new();
+        queue.set_next_nonce(1);
+
+        queue.insert(1, digest(1));
+        queue.insert(3, digest(3));
+        queue.insert(4, digest(4));
+
+        assert_eq!(queue.ready().keys().copied().collect::<Vec<_>>(), vec![1]);
+        assert_eq!(
+            queue.future().keys().copied().collect::<Vec<_>>(),
+            vec![3, 4]
+        );
+
+        queue.insert(2, digest(2));
+
+        assert_eq!(
+            queue.ready().keys().copied().collect::<Vec<_>>(),
+            vec![1, 2, 3, 4]
+        );
+        assert!(queue.future().is_empty());
+    }
+
+    #[test]
+    fn removing_a_ready_txn_demotes_the_ones_after_it() {
+        let mut queue = SenderQueue::new();
+        queue.set_next_nonce(0);
+
+        for n in 0..4 {
+            queue.insert(n as u128, digest(n));
+        }
+
+        queue.remove(1, &digest(1));
+
+        assert_eq!(queue.ready().keys().copied().collect::<Vec<_>>(), vec![0]);
+        assert_eq!(
+            queue.future().keys().copied().collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        assert_eq!(queue.len(), 3);
+    }
+
+    #[test]
+    fn txns_below_the_next_nonce_are_stale() {
+        let mut queue = SenderQueue::new();
+
+        queue.insert(2, digest(2));
+        queue.insert(3, digest(3));
+        assert_eq!(queue.ready().len(), 2);
+
+        queue.set_next_nonce(3);
+
+        assert_eq!(queue.stale(), vec![digest(2)]);
+        assert_eq!(queue.ready().keys().copied().collect::<Vec<_>>(), vec![3]);
+    }
+
+    #[test]
+    fn inserting_an_existing_nonce_returns_the_previous_txn() {
+        let mut queue = SenderQueue::new();
+
+        assert_eq!(queue.insert(0, digest(1)), None);
+        assert_eq!(queue.insert(0, digest(2)), Some(digest(1)));
+        assert_eq!(queue.get(0), Some(&digest(2)));
+        assert_eq!(queue.len(), 1);
+    }
+}
@@ -0,0 +1,395 @@
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt,
+    sync::Arc,
+};
+
+use block::{Conflict, ConflictList, ProposalBlock, RefHash};
+use ethereum_types::U256;
+use primitives::{Address, ConflictResolutionKind};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use storage::vrrbdb::ClaimStoreReadHandleFactory;
+use vrrb_core::transactions::Transaction;
+
+/// Picks which of the proposal blocks that included the same transaction
+/// gets to keep it. Every other proposal block has the transaction removed
+/// before it is referenced by the convergence block.
+///
+/// Strategies have to be deterministic: every miner resolving the same
+/// conflict with the same seed must pick the same winner.
+pub trait ConflictResolutionStrategy: fmt::Debug + Send + Sync {
+    fn kind(&self) -> ConflictResolutionKind;
+
+    /// Returns the hash of the proposal block that keeps the transaction of
+    /// `conflict`. `proposals` are the proposal blocks of the round, which
+    /// include the blocks the conflict is between.
+    fn select_winner(
+        &self,
+        conflict: &Conflict,
+        proposals: &[ProposalBlock],
+        seed: u64,
+    ) -> Option<RefHash>;
+}
+
+/// The proposer with the lowest pointer sum for the round seed wins
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PointerSumStrategy;
+
+impl ConflictResolutionStrategy for PointerSumStrategy {
+    fn kind(&self) -> ConflictResolutionKind {
+        ConflictResolutionKind::PointerSum
+    }
+
+    fn select_winner(
+        &self,
+        conflict: &Conflict,
+        proposals: &[ProposalBlock],
+        seed: u64,
+    ) -> Option<RefHash> {
+        contenders(conflict, proposals)
+            .into_iter()
+            .min_by(|a, b| by_pointer_sum(a, b, seed))
+            .map(|block| block.hash.clone())
+    }
+}
+
+/// The proposal block paying the lowest fee per byte of transactions loses,
+/// so proposers are rewarded for packing high value transactions. Ties are
+/// broken by pointer sum.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FeePerByteStrategy;
+
+impl ConflictResolutionStrategy for FeePerByteStrategy {
+    fn kind(&self) -> ConflictResolutionKind {
+        ConflictResolutionKind::FeePerByte
+    }
+
+    fn select_winner(
+        &self,
+        conflict: &Conflict,
+        proposals: &[ProposalBlock],
+        seed: u64,
+    ) -> Option<RefHash> {
+        contenders(conflict, proposals)
+            .into_iter()
+            .map(|block| (block, fee_per_byte(block)))
+            .min_by(|(a, (a_fees, a_size)), (b, (b_fees, b_size))| {
+                // a / a_size > b / b_size, compared without dividing
+                let a_rate = U256::from(*a_fees) * U256::from(*b_size);
+                let b_rate = U256::from(*b_fees) * U256::from(*a_size);
+
+                b_rate.cmp(&a_rate).then_with(|| by_pointer_sum(a, b, seed))
+            })
+            .map(|(block, _)| block.hash.clone())
+    }
+}
+
+/// Proposers win with a probability proportional to the stake their claim
+/// holds in the claim store. The claim a proposal block embeds is not
+/// trusted, as the proposer is free to report any stake in it. The draw is
+/// derived from the round seed and the conflicting transaction, so every
+/// conflict of a round gets its own draw. Falls back to the pointer sum
+/// when none of the proposers has stake.
+#[derive(Debug, Clone)]
+pub struct StakeWeightedStrategy {
+    claims: ClaimStoreReadHandleFactory,
+}
+
+impl StakeWeightedStrategy {
+    pub fn new(claims: ClaimStoreReadHandleFactory) -> Self {
+        Self { claims }
+    }
+
+    /// Stake of the claim that proposed `block`, zero if the claim store
+    /// doesn't know the claim
+    fn stake_of(&self, block: &ProposalBlock) -> u128 {
+        self.claims
+            .get_by_hash(&block.from.hash)
+            .ok()
+            .flatten()
+            .map(|claim| claim.get_stake())
+            .unwrap_or_default()
+    }
+}
+
+impl ConflictResolutionStrategy for StakeWeightedStrategy {
+    fn kind(&self) -> ConflictResolutionKind {
+        ConflictResolutionKind::StakeWeighted
+    }
+
+    fn select_winner(
+        &self,
+        conflict: &Conflict,
+        proposals: &[ProposalBlock],
+        seed: u64,
+    ) -> Option<RefHash> {
+        let mut contenders = contenders(conflict, proposals);
+        contenders.sort_by(|a, b| a.hash.cmp(&b.hash));
+
+        let stakes = contenders
+            .iter()
+            .map(|block| U256::from(self.stake_of(block)))
+            .collect::<Vec<_>>();
+        let total_stake = stakes
+            .iter()
+            .fold(U256::zero(), |total, stake| total + *stake);
+
+        if total_stake.is_zero() {
+            return PointerSumStrategy.select_winner(conflict, proposals, seed);
+        }
+
+        let mut hasher = Sha256::new();
+        hasher.update(seed.to_be_bytes());
+        hasher.update(conflict.txn_id.digest_string().as_bytes());
+        let draw = U256::from_big_endian(&hasher.finalize()) % total_stake;
+
+        let mut cumulative_stake = U256::zero();
+
+        contenders
+            .into_iter()
+            .zip(stakes)
+            .find(|(_, stake)| {
+                cumulative_stake = cumulative_stake + *stake;
+                draw < cumulative_stake
+            })
+            .map(|(block, _)| block.hash.clone())
+    }
+}
+
+/// Strategies available to the miner, one per `ConflictResolutionKind`
+#[derive(Debug, Clone, Default)]
+pub struct ConflictResolutionRegistry {
+    strategies: BTreeMap<ConflictResolutionKind, Arc<dyn ConflictResolutionStrategy>>,
+}
+
+impl ConflictResolutionRegistry {
+    /// Creates a registry without any strategy
+    pub fn new() -> Self {
+        Self {
+            strategies: BTreeMap::new(),
+        }
+    }
+
+    /// Registers `strategy` under its kind, returning the strategy it
+    /// replaced, if any
+    pub fn register(
+        &mut self,
+        strategy: Arc<dyn ConflictResolutionStrategy>,
+    ) -> Option<Arc<dyn ConflictResolutionStrategy>> {
+        self.strategies.insert(strategy.kind(), strategy)
+    }
+
+    pub fn get(&self, kind: ConflictResolutionKind) -> Option<Arc<dyn ConflictResolutionStrategy>> {
+        self.strategies.get(&kind).cloned()
+    }
+
+    pub fn strategies(&self) -> impl Iterator<Item = &Arc<dyn ConflictResolutionStrategy>> {
+        self.strategies.values()
+    }
+
+    /// Creates a registry holding every built-in strategy, weighing stake
+    /// by the claims of `claims`
+    pub fn with_builtin_strategies(claims: ClaimStoreReadHandleFactory) -> Self {
+        let mut registry = Self::new();
+        registry.register(Arc::new(PointerSumStrategy));
+        registry.register(Arc::new(FeePerByteStrategy));
+        registry.register(Arc::new(StakeWeightedStrategy::new(claims)));
+        registry
+    }
+}
+
+/// Collects the transactions included by more than one of `proposals`,
+/// along with the proposers that included them
+pub fn identify_conflicts(proposals: &[ProposalBlock]) -> ConflictList {
+    let mut conflicts: ConflictList = HashMap::new();
+
+    for block in proposals {
+        for (id, _) in block.txns.iter() {
+            conflicts
+                .entry(id.clone())
+                .or_insert_with(|| Conflict {
+                    txn_id: id.clone(),
+                    proposers: HashSet::new(),
+                    winner: None,
+                })
+                .proposers
+                .insert((block.from.clone(), block.hash.clone()));
+        }
+    }
+
+    conflicts.retain(|_, conflict| conflict.proposers.len() > 1);
+    conflicts
+}
+
+/// Proposal blocks of a past round, recorded so their conflicts can be
+/// replayed through different strategies
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedRound {
+    pub round: u128,
+    pub seed: u64,
+    pub proposals: Vec<ProposalBlock>,
+}
+
+/// How a single proposer fared over the conflicts of a replay
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProposerOutcome {
+    /// Stake of the proposer's claim as of the last conflict it was part of
+    pub stake: u128,
+
+    /// Conflicts the proposer was part of
+    pub contested: usize,
+
+    /// Conflicts the proposer won
+    pub won: usize,
+}
+
+impl ProposerOutcome {
+    pub fn win_rate(&self) -> f64 {
+        if self.contested == 0 {
+            return 0.0;
+        }
+
+        self.won as f64 / self.contested as f64
+    }
+}
+
+/// Outcome of replaying recorded rounds through a single strategy
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayReport {
+    pub kind: ConflictResolutionKind,
+
+    /// Conflicts found across the recorded rounds
+    pub conflicts: usize,
+
+    /// Conflicts the strategy could not pick a winner for
+    pub unresolved: usize,
+
+    /// Outcomes keyed by the address of the proposers' claims
+    pub proposers: BTreeMap<Address, ProposerOutcome>,
+}
+
+impl ReplayReport {
+    /// Share of the resolved conflicts won by each proposer, to be compared
+    /// against its share of the stake
+    pub fn win_shares(&self) -> BTreeMap<Address, f64> {
+        let resolved = self.conflicts - self.unresolved;
+
+        self.proposers
+            .iter()
+            .map(|(address, outcome)| {
+                let share = if resolved == 0 {
+                    0.0
+                } else {
+                    outcome.won as f64 / resolved as f64
+                };
+
+                (address.clone(), share)
+            })
+            .collect()
+    }
+}
+
+/// Replays the conflicts of recorded rounds through conflict resolution
+/// strategies, so their fairness can be compared on the same proposals
+#[derive(Debug, Clone, Default)]
+pub struct ConflictReplay {
+    rounds: Vec<RecordedRound>,
+}
+
+impl ConflictReplay {
+    pub fn new(rounds: Vec<RecordedRound>) -> Self {
+        Self { rounds }
+    }
+
+    pub fn record(&mut self, round: RecordedRound) {
+        self.rounds.push(round);
+    }
+
+    pub fn rounds(&self) -> &[RecordedRound] {
+        &self.rounds
+    }
+
+    /// Resolves every recorded conflict with `strategy`
+    pub fn run(&self, strategy: &dyn ConflictResolutionStrategy) -> ReplayReport {
+        let mut report = ReplayReport {
+            kind: strategy.kind(),
+            conflicts: 0,
+            unresolved: 0,
+            proposers: BTreeMap::new(),
+        };
+
+        for recorded in self.rounds.iter() {
+            let mut conflicts = identify_conflicts(&recorded.proposals)
+                .into_values()
+                .collect::<Vec<_>>();
+            conflicts.sort_by(|a, b| a.txn_id.digest_string().cmp(&b.txn_id.digest_string()));
+
+            for conflict in conflicts {
+                report.conflicts += 1;
+
+                let winner = strategy.select_winner(&conflict, &recorded.proposals, recorded.seed);
+
+                if winner.is_none() {
+                    report.unresolved += 1;
+                }
+
+                for (claim, ref_hash) in conflict.proposers.iter() {
+                    let outcome = report.proposers.entry(claim.address.clone()).or_default();
+
+                    outcome.stake = claim.get_stake();
+                    outcome.contested += 1;
+
+                    if winner.as_ref() == Some(ref_hash) {
+                        outcome.won += 1;
+                    }
+                }
+            }
+        }
+
+        report
+    }
+
+    /// Resolves every recorded conflict with each strategy of `registry`
+    pub fn run_all(&self, registry: &ConflictResolutionRegistry) -> Vec<ReplayReport> {
+        registry
+            .strategies()
+            .map(|strategy| self.run(strategy.as_ref()))
+            .collect()
+    }
+}
+
+/// Proposal blocks that included the transaction of `conflict`
+fn contenders<'a>(conflict: &Conflict, proposals: &'a [ProposalBlock]) -> Vec<&'a ProposalBlock> {
+    let mut seen = HashSet::new();
+
+    proposals
+        .iter()
+        .filter(|block| {
+            conflict
+                .proposers
+                .contains(&(block.from.clone(), block.hash.clone()))
+        })
+        .filter(|block| seen.insert(block.hash.clone()))
+        .collect()
+}
+
+fn by_pointer_sum(a: &ProposalBlock, b: &ProposalBlock, seed: u64) -> Ordering {
+    a.from
+        .get_election_result(seed)
+        .cmp(&b.from.get_election_result(seed))
+        .then_with(|| a.hash.cmp(&b.hash))
+}
+
+/// Total fees and encoded size of the transactions of a proposal block
+fn fee_per_byte(block: &ProposalBlock) -> (u128, u64) {
+    let fees = block
+        .txns
+        .iter()
+        .fold(0u128, |fees, (_, txn)| fees.saturating_add(txn.fee()));
+
+    let size = bincode::serialized_size(&block.txns).unwrap_or(u64::MAX);
+
+    (fees, size.max(1))
+}
@@ -3,6 +3,7 @@ pub mod result;
 pub use crate::miner::*;
 pub mod block_builder;
 pub mod conflict_resolver;
+pub mod conflict_strategy;
 pub mod miner_impl;
 pub mod test_helpers;
 
@@ -16,16 +17,25 @@ mod tests {
 
     use block::{Block, ProposalBlock};
     use bulldag::vertex::Vertex;
-    use primitives::Address;
+    use primitives::{Address, ConflictResolutionKind};
     use ritelinked::LinkedHashMap;
-    use vrrb_core::transactions::{TransactionDigest, TransactionKind};
+    use vrrb_core::transactions::{TransactionDigest, TransactionKind, BASE_FEE};
     use vrrb_core::{claim::Claim, keypair::Keypair};
 
-    use crate::test_helpers::{
-        build_single_proposal_block, build_single_proposal_block_from_txns,
-        create_and_sign_message, create_miner, create_miner_from_keypair,
-        create_miner_from_keypair_and_dag, create_miner_from_keypair_return_dag,
-        create_miner_return_dag, create_txns, mine_genesis,
+    use crate::{
+        conflict_resolver::Resolver,
+        conflict_strategy::{
+            identify_conflicts, ConflictReplay, ConflictResolutionRegistry,
+            ConflictResolutionStrategy, FeePerByteStrategy, RecordedRound, StakeWeightedStrategy,
+        },
+        test_helpers::{
+            build_conflicting_proposal_blocks, build_proposal_block_from_claim,
+            build_single_proposal_block, build_single_proposal_block_from_txns,
+            create_and_sign_message, create_claim_store, create_miner, create_miner_from_keypair,
+            create_miner_from_keypair_and_dag, create_miner_from_keypair_return_dag,
+            create_miner_return_dag, create_staked_claim, create_txns, create_txns_with_fee,
+            mine_genesis,
+        },
     };
 
     #[test]
@@ -423,10 +433,11 @@ mod tests {
             }
 
             miner.set_next_epoch_adjustment(30_000_000_i128);
+            miner.set_epoch_length(30_000_000);
 
             let convergence = miner.try_mine();
             if let Ok(Block::Convergence { mut block }) = convergence {
-                block.header.round = 29_999_998;
+                block.header.round = 29_999_999;
                 block.header.block_height = 29_999_998;
                 block.header.block_reward.current_block = 29_999_998;
                 miner.last_block = Some(Arc::new(block.to_owned()));
@@ -448,4 +459,241 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_miner_derives_block_epoch_from_round() {
+        let m1kp = Keypair::random();
+        let (mut miner, dag) = create_miner_from_keypair_return_dag(&m1kp);
+
+        let genesis = mine_genesis();
+        if let Some(genesis) = genesis {
+            miner.last_block = Some(Arc::new(genesis.clone()));
+            let gblock = Block::Genesis {
+                block: genesis.clone(),
+            };
+            let gvtx: Vertex<Block, String> = gblock.into();
+            let txns: LinkedHashMap<TransactionDigest, TransactionKind> = create_txns(5).collect();
+            let prop1 =
+                build_single_proposal_block_from_txns(genesis.hash.clone(), txns.clone(), 0, 0);
+            let pblock1 = Block::Proposal {
+                block: prop1.clone(),
+            };
+            let pvtx1: Vertex<Block, String> = pblock1.into();
+            if let Ok(mut guard) = dag.write() {
+                let edge1 = (&gvtx, &pvtx1);
+                guard.add_edge(edge1);
+            }
+
+            miner.set_next_epoch_adjustment(1);
+            miner.set_epoch_length(1);
+
+            let convergence = miner.try_mine();
+            if let Ok(Block::Convergence { ref block }) = convergence {
+                assert_eq!(1, block.header.epoch);
+                assert_eq!(
+                    genesis.header.next_block_reward.amount + 1,
+                    block.header.block_reward.amount
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_miner_records_fees_of_included_txns() {
+        let m1kp = Keypair::random();
+        let (mut miner, dag) = create_miner_from_keypair_return_dag(&m1kp);
+        miner.fee_burn_rate = 10;
+
+        let genesis = mine_genesis();
+        if let Some(genesis) = genesis {
+            miner.last_block = Some(Arc::new(genesis.clone()));
+            let gblock = Block::Genesis {
+                block: genesis.clone(),
+            };
+            let gvtx: Vertex<Block, String> = gblock.into();
+            let txns: LinkedHashMap<TransactionDigest, TransactionKind> =
+                create_txns_with_fee(4, BASE_FEE * 2).collect();
+            let prop1 =
+                build_single_proposal_block_from_txns(genesis.hash.clone(), txns.clone(), 0, 0);
+            let pblock1 = Block::Proposal {
+                block: prop1.clone(),
+            };
+            let pvtx1: Vertex<Block, String> = pblock1.into();
+            if let Ok(mut guard) = dag.write() {
+                let edge1 = (&gvtx, &pvtx1);
+                guard.add_edge(edge1);
+            }
+
+            let convergence = miner.try_mine();
+            if let Ok(Block::Convergence { ref block }) = convergence {
+                assert_eq!(10, block.fees.burn_rate);
+                assert_eq!(BASE_FEE * 8, block.fees.collected);
+                assert_eq!(BASE_FEE * 8 / 10, block.fees.burned);
+                assert_eq!(
+                    block.fees.collected,
+                    block.fees.validators + block.fees.proposers + block.fees.burned
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_every_registered_strategy_picks_a_single_winner() {
+        let (prop1, prop2) = build_conflicting_proposal_blocks("genesis".into(), 0, 0);
+        let proposals = vec![prop1.clone(), prop2.clone()];
+        let conflicts = identify_conflicts(&proposals);
+        let claims = create_claim_store(&[prop1.from.clone(), prop2.from.clone()]);
+        let registry = ConflictResolutionRegistry::with_builtin_strategies(claims);
+
+        assert_eq!(conflicts.len(), 5);
+        assert_eq!(registry.strategies().count(), 3);
+
+        for strategy in registry.strategies() {
+            for conflict in conflicts.values() {
+                let winner = strategy.select_winner(conflict, &proposals, 42);
+
+                assert!(winner == Some(prop1.hash.clone()) || winner == Some(prop2.hash.clone()));
+                assert_eq!(winner, strategy.select_winner(conflict, &proposals, 42));
+            }
+        }
+    }
+
+    #[test]
+    fn test_lowest_fee_per_byte_proposal_loses_conflicts() {
+        let genesis = mine_genesis().unwrap();
+        let mut miner = create_miner();
+        miner.last_block = Some(Arc::new(genesis.clone()));
+        miner.set_conflict_resolution_strategy(Arc::new(FeePerByteStrategy));
+
+        let shared: LinkedHashMap<TransactionDigest, TransactionKind> =
+            create_txns_with_fee(3, BASE_FEE).collect();
+        let cheap = build_single_proposal_block_from_txns(
+            genesis.hash.clone(),
+            shared
+                .clone()
+                .into_iter()
+                .chain(create_txns_with_fee(3, BASE_FEE)),
+            0,
+            0,
+        );
+        let expensive = build_single_proposal_block_from_txns(
+            genesis.hash.clone(),
+            shared
+                .clone()
+                .into_iter()
+                .chain(create_txns_with_fee(3, BASE_FEE * 10)),
+            0,
+            0,
+        );
+
+        assert_eq!(
+            miner.conflict_resolution(),
+            ConflictResolutionKind::FeePerByte
+        );
+
+        let resolved = miner.resolve(&[cheap.clone(), expensive.clone()], 0, 42);
+
+        for block in resolved {
+            let keeps_shared = shared.keys().all(|id| block.txns.contains_key(id));
+            assert_eq!(keeps_shared, block.hash == expensive.hash);
+        }
+    }
+
+    #[test]
+    fn test_stake_weighted_strategy_ignores_the_stake_reported_by_proposers() {
+        let honest_kp = Keypair::random();
+        let boasting_kp = Keypair::random();
+        let honest = create_staked_claim(&honest_kp, 1_000);
+        let boasting = create_staked_claim(&boasting_kp, 1_000_000_000);
+
+        // The claim store only knows the boasting proposer without stake
+        let unstaked = create_miner_from_keypair(&boasting_kp).claim;
+        let claims = create_claim_store(&[honest.clone(), unstaked]);
+        let strategy = StakeWeightedStrategy::new(claims);
+
+        let shared: LinkedHashMap<TransactionDigest, TransactionKind> = create_txns(20).collect();
+        let proposals = vec![
+            build_proposal_block_from_claim(
+                "genesis".into(),
+                &honest_kp,
+                honest,
+                shared.clone(),
+                0,
+                0,
+            ),
+            build_proposal_block_from_claim(
+                "genesis".into(),
+                &boasting_kp,
+                boasting,
+                shared.clone(),
+                0,
+                0,
+            ),
+        ];
+
+        for conflict in identify_conflicts(&proposals).values() {
+            assert_eq!(
+                strategy.select_winner(conflict, &proposals, 42),
+                Some(proposals[0].hash.clone())
+            );
+        }
+    }
+
+    #[test]
+    fn test_conflict_replay_reports_outcomes_of_every_strategy() {
+        let whale_kp = Keypair::random();
+        let minnow_kp = Keypair::random();
+        let whale = create_staked_claim(&whale_kp, 1_000_000_000);
+        let minnow = create_staked_claim(&minnow_kp, 1);
+
+        let shared: LinkedHashMap<TransactionDigest, TransactionKind> = create_txns(20).collect();
+        let proposals = vec![
+            build_proposal_block_from_claim(
+                "genesis".into(),
+                &whale_kp,
+                whale.clone(),
+                shared.clone(),
+                0,
+                0,
+            ),
+            build_proposal_block_from_claim(
+                "genesis".into(),
+                &minnow_kp,
+                minnow.clone(),
+                shared.clone(),
+                0,
+                0,
+            ),
+        ];
+
+        let claims = create_claim_store(&[whale.clone(), minnow.clone()]);
+        let replay = ConflictReplay::new(vec![RecordedRound {
+            round: 0,
+            seed: 42,
+            proposals,
+        }]);
+        let registry = ConflictResolutionRegistry::with_builtin_strategies(claims.clone());
+        let reports = replay.run_all(&registry);
+
+        assert_eq!(reports.len(), 3);
+
+        for report in reports.iter() {
+            let won: usize = report.proposers.values().map(|outcome| outcome.won).sum();
+
+            assert_eq!(report.conflicts, 20);
+            assert_eq!(report.unresolved, 0);
+            assert_eq!(won, 20);
+            assert!(report
+                .proposers
+                .values()
+                .all(|outcome| outcome.contested == 20));
+        }
+
+        let stake_weighted = replay.run(&StakeWeightedStrategy::new(claims));
+        let whale_outcome = &stake_weighted.proposers[&whale.address];
+
+        assert_eq!(whale_outcome.stake, 1_000_000_000);
+        assert_eq!(whale_outcome.won, 20);
+        assert_eq!(stake_weighted.win_shares()[&minnow.address], 0.0);
+    }
 }
@@ -8,12 +8,19 @@ use std::sync::{Arc, RwLock};
 
 use block::GenesisRewards;
 use block::{
-    block::Block, header::BlockHeader, ClaimHash, ClaimList, ConsolidatedClaims, ConsolidatedTxns,
-    ConvergenceBlock, GenesisBlock, InnerBlock, ProposalBlock, RefHash,
+    block::Block,
+    election::ClaimInclusionProof,
+    header::{BlockHeader, StateRoots},
+    slashing::{evidence_root, SlashingEvidence},
+    ClaimHash, ClaimList, Conflict, ConsolidatedClaims, ConsolidatedTxns, ConvergenceBlock,
+    GenesisBlock, InnerBlock, ProposalBlock, RefHash,
 };
 use bulldag::graph::BullDag;
 use ethereum_types::U256;
-use primitives::{Address, NodeId, PublicKey, Signature};
+use primitives::{
+    Address, ChainParameters, ConflictResolutionKind, NodeId, PublicKey, Round, Signature,
+    DEFAULT_EPOCH_LENGTH,
+};
 use reward::reward::Reward;
 use ritelinked::{LinkedHashMap, LinkedHashSet};
 use secp256k1::Message;
@@ -23,7 +30,9 @@ use utils::hash_data;
 use vrrb_core::claim::{Claim, ClaimError};
 use vrrb_core::keypair::{MinerPublicKey, MinerSecretKey};
 
-use crate::{block_builder::BlockBuilder, result::MinerError};
+use crate::{
+    block_builder::BlockBuilder, conflict_strategy::ConflictResolutionStrategy, result::MinerError,
+};
 
 pub const VALIDATOR_THRESHOLD: f64 = 0.60;
 pub const NANO: u128 = 1;
@@ -65,7 +74,8 @@ pub enum MinerStatus {
 ///     pub secret_key: MinerSk,
 ///     pub public_key: MinerPk,
 ///     pub ip_address:SocketAddr,
-///     pub dag: Arc<RwLock<BullDag<Block, String>>>
+///     pub dag: Arc<RwLock<BullDag<Block, String>>>,
+///     pub fee_burn_rate: u8,
 /// }
 #[derive(Debug)]
 pub struct MinerConfig {
@@ -74,6 +84,11 @@ pub struct MinerConfig {
     pub ip_address: SocketAddr,
     pub dag: Arc<RwLock<BullDag<Block, String>>>,
     pub claim: Claim,
+    /// Strategy conflicts between proposal blocks are resolved with, set by
+    /// the chain parameters of the genesis block
+    pub conflict_resolution: Arc<dyn ConflictResolutionStrategy>,
+    /// Percentage of every transaction fee burned by the mined blocks
+    pub fee_burn_rate: u8,
 }
 
 /// Miner struct which exposes methods to mine convergence blocks
@@ -102,6 +117,10 @@ pub struct MinerConfig {
 ///     pub last_block: Option<Arc<dyn InnerBlock<Header = BlockHeader, RewardType = Reward>>>,
 ///     pub status: MinerStatus,
 ///     pub next_epoch_adjustment: i128,
+///     pub epoch_length: Round,
+///     pub state_roots: StateRoots,
+///     pub slashing_evidence: Vec<SlashingEvidence>,
+///     pub fee_burn_rate: u8,
 /// }
 #[derive(Debug, Clone)]
 pub struct Miner {
@@ -114,6 +133,14 @@ pub struct Miner {
     pub last_block: Option<Arc<dyn InnerBlock<Header = BlockHeader, RewardType = Reward>>>,
     pub status: MinerStatus,
     pub next_epoch_adjustment: i128,
+    /// Number of rounds an epoch lasts for, as set by the genesis block
+    pub epoch_length: Round,
+    pub state_roots: StateRoots,
+    pub slashing_evidence: Vec<SlashingEvidence>,
+    pub claim_inclusion: ClaimInclusionProof,
+    /// Percentage of every transaction fee burned by the mined blocks
+    pub fee_burn_rate: u8,
+    conflict_resolution: Arc<dyn ConflictResolutionStrategy>,
 }
 
 pub type Result<T> = std::result::Result<T, MinerError>;
@@ -140,9 +167,12 @@ impl Miner {
     /// };
     ///
     /// use bulldag::graph::BullDag;
-    /// use miner::miner::{Miner, MinerConfig};
+    /// use miner::{
+    ///     conflict_strategy::PointerSumStrategy,
+    ///     miner::{Miner, MinerConfig},
+    /// };
     /// use primitives::{Address, NodeId};
-    /// use vrrb_core::{keypair::Keypair, claim::Claim};
+    /// use vrrb_core::{keypair::Keypair, claim::Claim, transactions::DEFAULT_FEE_BURN_RATE};
     ///
     /// let keypair = Keypair::random();
     /// let (secret_key, public_key) = keypair.miner_kp;
@@ -168,6 +198,8 @@ impl Miner {
     ///     ip_address,
     ///     dag,
     ///     claim,
+    ///     conflict_resolution: Arc::new(PointerSumStrategy),
+    ///     fee_burn_rate: DEFAULT_FEE_BURN_RATE,
     /// };
     ///
     /// let miner = Miner::new(config, NodeId::default());
@@ -189,6 +221,12 @@ impl Miner {
             last_block: None,
             status: MinerStatus::Waiting,
             next_epoch_adjustment: 0,
+            epoch_length: DEFAULT_EPOCH_LENGTH,
+            state_roots: StateRoots::default(),
+            slashing_evidence: vec![],
+            claim_inclusion: ClaimInclusionProof::default(),
+            fee_burn_rate: config.fee_burn_rate,
+            conflict_resolution: config.conflict_resolution,
         })
     }
 
@@ -202,6 +240,50 @@ impl Miner {
         self.ip_address
     }
 
+    /// Sets the trie roots committed to by the headers of the blocks mined
+    /// from now on
+    pub fn set_state_roots(&mut self, state_roots: StateRoots) {
+        self.state_roots = state_roots;
+    }
+
+    /// Kind of the strategy conflicts between proposal blocks are resolved
+    /// with
+    pub fn conflict_resolution(&self) -> ConflictResolutionKind {
+        self.conflict_resolution.kind()
+    }
+
+    /// Replaces the strategy conflicts between proposal blocks are resolved
+    /// with, e.g. once the chain parameters of the genesis block are known
+    pub fn set_conflict_resolution_strategy(
+        &mut self,
+        strategy: Arc<dyn ConflictResolutionStrategy>,
+    ) {
+        self.conflict_resolution = strategy;
+    }
+
+    /// Returns the hash of the proposal block that keeps the transaction of
+    /// `conflict`
+    pub fn select_conflict_winner(
+        &self,
+        conflict: &Conflict,
+        proposals: &[ProposalBlock],
+        seed: u64,
+    ) -> Option<RefHash> {
+        self.conflict_resolution
+            .select_winner(conflict, proposals, seed)
+    }
+
+    /// Sets the proof that the miner's claim is part of the claims trie,
+    /// included in the election proof of the blocks mined from now on
+    pub fn set_claim_inclusion_proof(&mut self, claim_inclusion: ClaimInclusionProof) {
+        self.claim_inclusion = claim_inclusion;
+    }
+
+    /// Sets the slashing evidence included in the blocks mined from now on
+    pub fn set_slashing_evidence(&mut self, slashing_evidence: Vec<SlashingEvidence>) {
+        self.slashing_evidence = slashing_evidence;
+    }
+
     /// Retrieves the `PublicKey` of the current `Miner` instance
     pub fn public_key(&self) -> PublicKey {
         self.public_key
@@ -246,6 +328,18 @@ impl Miner {
         self.next_epoch_adjustment += adjustment;
     }
 
+    /// Get the number of rounds an epoch lasts for
+    pub fn epoch_length(&self) -> Round {
+        self.epoch_length
+    }
+
+    /// Sets the number of rounds an epoch lasts for, e.g. once the chain
+    /// parameters of the genesis block are known. The first block mined in a
+    /// new epoch has its reward adjusted by the `next_epoch_adjustment`
+    pub fn set_epoch_length(&mut self, epoch_length: Round) {
+        self.epoch_length = epoch_length;
+    }
+
     /// Attempts to mine a `ConvergenceBlock` using the
     /// `miner.mine_convergence_block()` method, which in turn uses the
     /// `<Miner as BlockBuilder>::build()` method
@@ -302,6 +396,11 @@ impl Miner {
             format!("{claim_list_hash:x}"),
         );
 
+        let chain_parameters = ChainParameters {
+            conflict_resolution: self.conflict_resolution(),
+            epoch_length: self.epoch_length,
+        };
+
         let block_hash = hash_data!(
             header.ref_hashes,
             header.round,
@@ -314,7 +413,8 @@ impl Miner {
             header.claim_list_hash,
             header.block_reward,
             header.next_block_reward,
-            header.miner_signature
+            header.miner_signature,
+            chain_parameters
         );
 
         let mut claims = LinkedHashMap::new();
@@ -328,6 +428,7 @@ impl Miner {
             header,
             genesis_rewards,
             claims,
+            chain_parameters,
             hash: format!("{block_hash:x}"),
             certificate: None,
         };
@@ -407,31 +508,36 @@ impl Miner {
         txns_hash: String,
         claims_hash: String,
     ) -> Option<BlockHeader> {
-        if let (Some(block), None) = self.convert_last_block_to_static() {
-            return BlockHeader::new(
+        let header = match self.convert_last_block_to_static() {
+            (Some(block), None) => BlockHeader::new(
                 block.into(),
                 ref_hashes,
                 self.claim.clone(),
                 self.secret_key,
                 txns_hash,
                 claims_hash,
+                self.epoch_length,
                 self.next_epoch_adjustment,
-            );
-        }
-
-        if let (None, Some(block)) = self.convert_last_block_to_static() {
-            return BlockHeader::new(
+            ),
+            (None, Some(block)) => BlockHeader::new(
                 block.into(),
                 ref_hashes,
                 self.claim.clone(),
                 self.secret_key,
                 txns_hash,
                 claims_hash,
+                self.epoch_length,
                 self.next_epoch_adjustment,
-            );
-        }
+            ),
+            _ => None,
+        };
 
-        None
+        header.map(|mut header| {
+            header.state_roots = self.state_roots.clone();
+            header.election_proof.claim_inclusion = self.claim_inclusion.clone();
+            header.slashing_evidence_root = evidence_root(&self.slashing_evidence);
+            header
+        })
     }
 
     pub(crate) fn convert_last_block_to_static(
@@ -451,22 +557,7 @@ impl Miner {
     /// Hashes the current `ConvergenceBlock` being mined using
     /// the fields from the `BlockHeader`
     pub(crate) fn hash_block(&self, header: &BlockHeader) -> String {
-        let block_hash = hash_data!(
-            header.ref_hashes,
-            header.round,
-            header.block_seed,
-            header.next_block_seed,
-            header.block_height,
-            header.timestamp,
-            header.txn_hash,
-            header.miner_claim,
-            header.claim_list_hash,
-            header.block_reward,
-            header.next_block_reward,
-            header.miner_signature
-        );
-
-        format!("{block_hash:x}")
+        header.hash()
     }
 
     /// Gets the current election `seed` from the
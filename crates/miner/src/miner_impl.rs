@@ -4,7 +4,7 @@ use std::{
 };
 
 use block::{
-    header::BlockHeader, Block, Conflict, ConflictList, ConvergenceBlock, InnerBlock,
+    fees::BlockFees, header::BlockHeader, Block, Conflict, ConvergenceBlock, InnerBlock,
     ProposalBlock, RefHash,
 };
 use bulldag::vertex::{Direction, Vertex};
@@ -14,7 +14,10 @@ use ritelinked::LinkedHashSet;
 use vrrb_core::claim::Claim;
 use vrrb_core::transactions::TransactionDigest;
 
-use crate::{block_builder::BlockBuilder, conflict_resolver::Resolver, Miner};
+use crate::{
+    block_builder::BlockBuilder, conflict_resolver::Resolver,
+    conflict_strategy::identify_conflicts, Miner,
+};
 
 impl BlockBuilder for Miner {
     type BlockType = ConvergenceBlock;
@@ -46,6 +49,7 @@ impl BlockBuilder for Miner {
             let claims_hash = self.get_claim_hash(&claims);
             let header = self.build_header(ref_hashes, txns_hash, claims_hash)?;
             let hash = self.hash_block(&header);
+            let fees = BlockFees::new(&txns, &resolved, self.fee_burn_rate);
 
             Some(ConvergenceBlock {
                 header,
@@ -53,6 +57,8 @@ impl BlockBuilder for Miner {
                 claims,
                 hash,
                 certificate: None,
+                slashing_evidence: self.slashing_evidence.clone(),
+                fees,
             })
         } else {
             None
@@ -122,30 +128,7 @@ impl Resolver for Miner {
     /// a given transaction digest. It then filters the HashMap to
     /// only keep Conflicts with more than 1 proposer.
     fn identify(&self, proposals: &[Self::Proposal]) -> Self::Identified {
-        let mut conflicts: ConflictList = HashMap::new();
-        proposals.iter().for_each(|block| {
-            let mut proposer = HashSet::new();
-
-            proposer.insert((block.from.clone(), block.hash.clone()));
-
-            for (id, _) in block.txns.iter() {
-                let conflict = Conflict {
-                    txn_id: id.clone(),
-                    proposers: proposer.clone(),
-                    winner: None,
-                };
-
-                conflicts
-                    .entry(id.clone())
-                    .and_modify(|e| {
-                        e.proposers.insert((block.from.clone(), block.hash.clone()));
-                    })
-                    .or_insert(conflict);
-            }
-        });
-
-        conflicts.retain(|_, conflict| conflict.proposers.len() > 1);
-        conflicts
+        identify_conflicts(proposals)
     }
 
     /// Splits proposal blocks by current round and previous rounds
@@ -156,8 +139,8 @@ impl Resolver for Miner {
     /// included in the convergence block from the round which they were
     /// originally proposed in.
     ///
-    /// After this, the method identifies conflicts, creates an election
-    /// results map (`BTreeMap`), elects and appends winners to the conflict.
+    /// After this, the method identifies conflicts and lets the miner's
+    /// conflict resolution strategy pick the winner of each of them.
     /// It then resolves all conflicts in the current round blocks, by removing
     /// the txns associated with the block proposed by the losing party in the
     /// conflict resolution protocol.
@@ -166,13 +149,12 @@ impl Resolver for Miner {
         let prev_resolved = self.resolve_earlier(&prev, round);
         curr.extend(prev_resolved);
         let mut conflicts = self.identify(&curr);
-        let proposers = self.get_proposers(&curr);
-        // Construct a BTreeMap of all election results
-        let mut election_results = self.get_election_results(&proposers, seed);
         let mut curr_resolved = curr.clone();
 
-        // Iterate, mutably through all the conflicts identified
-        self.append_winner(&mut conflicts, &mut election_results);
+        for conflict in conflicts.values_mut() {
+            conflict.winner = self.select_conflict_winner(conflict, &curr, seed);
+        }
+
         self.resolve_current(&mut curr_resolved, &conflicts);
         curr_resolved.clone()
     }
@@ -1,4 +1,5 @@
 use std::{
+    env,
     net::SocketAddr,
     sync::{Arc, RwLock},
 };
@@ -10,12 +11,18 @@ use primitives::{Address, NodeId, PublicKey, SecretKey, Signature};
 use ritelinked::LinkedHashMap;
 use secp256k1::Message;
 use sha2::Digest;
+use storage::vrrbdb::{ClaimStore, ClaimStoreReadHandleFactory};
 use vrrb_core::transactions::{
     generate_transfer_digest_vec, Transaction, TransactionDigest, TransactionKind,
+    DEFAULT_FEE_BURN_RATE,
+};
+use vrrb_core::{
+    claim::Claim,
+    keypair::Keypair,
+    staking::{Stake, StakeUpdate},
 };
-use vrrb_core::{claim::Claim, keypair::Keypair};
 
-use crate::{result::MinerError, Miner, MinerConfig};
+use crate::{conflict_strategy::PointerSumStrategy, result::MinerError, Miner, MinerConfig};
 
 /// Move this into primitives and call it simply `BlockDag`
 pub type MinerDag = Arc<RwLock<BullDag<Block, String>>>;
@@ -45,6 +52,8 @@ pub fn create_miner() -> Miner {
         ip_address,
         dag,
         claim,
+        conflict_resolution: Arc::new(PointerSumStrategy),
+        fee_burn_rate: DEFAULT_FEE_BURN_RATE,
     };
     Miner::new(config, NodeId::default()).unwrap()
 }
@@ -73,6 +82,8 @@ pub fn create_miner_from_keypair(kp: &Keypair) -> Miner {
         public_key,
         claim,
         dag,
+        conflict_resolution: Arc::new(PointerSumStrategy),
+        fee_burn_rate: DEFAULT_FEE_BURN_RATE,
     };
     Miner::new(config, NodeId::default()).unwrap()
 }
@@ -185,6 +196,63 @@ pub(crate) fn create_txns(n: usize) -> impl Iterator<Item = (TransactionDigest,
     })
 }
 
+/// Helper function to create `n` number of `Txn`s paying `fee` each and
+/// return an `Iterator` of `(TransactionDigest, Txn)`
+pub fn create_txns_with_fee(
+    n: usize,
+    fee: u128,
+) -> impl Iterator<Item = (TransactionDigest, TransactionKind)> {
+    (0..n).map(move |n| {
+        let (sk, pk) = create_keypair();
+        let (_, rpk) = create_keypair();
+
+        let mut txn =
+            TransactionKind::transfer_builder()
+                .timestamp(0)
+                .sender_address(create_address(&pk))
+                .sender_public_key(pk)
+                .receiver_address(create_address(&rpk))
+                .amount(n as u128)
+                .fee(fee)
+                .signature(sk.sign_ecdsa(Message::from_hashed_data::<
+                    secp256k1::hashes::sha256::Hash,
+                >(b"vrrb")))
+                .nonce(n as u128)
+                .build_kind()
+                .expect("Failed to build transaction");
+
+        txn.sign(&sk);
+
+        (txn.id(), txn)
+    })
+}
+
+/// Helper function to create the `Claim` of the miner of `kp`, holding
+/// `amount` of stake
+pub fn create_staked_claim(kp: &Keypair, amount: u128) -> Claim {
+    let (sk, pk) = kp.miner_kp;
+    let mut claim = create_miner_from_keypair(kp).claim;
+    let mut stake = Stake::new(StakeUpdate::Add(amount), sk, pk, Address::new(pk), None).unwrap();
+    stake.certify((vec![0; 96], vec![0; 96])).unwrap();
+    claim.update_stake(stake).unwrap();
+
+    claim
+}
+
+/// Helper function to create a claim store in a temporary directory holding
+/// `claims`, returning a factory of read handles to it
+pub fn create_claim_store(claims: &[Claim]) -> ClaimStoreReadHandleFactory {
+    let (_, public_key) = create_keypair();
+    let path = env::temp_dir().join(format!("claims-{public_key}"));
+    let mut store = ClaimStore::new(&path).unwrap();
+
+    for claim in claims {
+        store.insert(claim.clone()).unwrap();
+    }
+
+    store.factory()
+}
+
 /// Helper function to create `n` number of `Claim`s and
 /// return an `Iterator` of `(String, Claim)` to be collected
 /// by the caller
@@ -533,6 +601,30 @@ pub fn build_single_proposal_block_from_txns(
     prop
 }
 
+/// Builds a `ProposalBlock` proposed by the miner of `kp` with `from` as
+/// its claim, that holds only the transactions provided in the function call
+pub fn build_proposal_block_from_claim(
+    last_block_hash: String,
+    kp: &Keypair,
+    from: Claim,
+    txns: impl IntoIterator<Item = (TransactionDigest, TransactionKind)>,
+    round: u128,
+    epoch: u128,
+) -> ProposalBlock {
+    let engine =
+        signer::engine::SignerEngine::new(*kp.get_miner_public_key(), *kp.get_miner_secret_key());
+
+    ProposalBlock::build(
+        last_block_hash,
+        round,
+        epoch,
+        txns.into_iter().collect(),
+        LinkedHashMap::new(),
+        from,
+        engine,
+    )
+}
+
 pub fn get_genesis_block_from_dag(dag: MinerDag) -> Option<GenesisBlock> {
     let last_block = {
         if let Ok(guard) = dag.read() {
@@ -3,10 +3,16 @@ use std::net::SocketAddr;
 use events::{Event, EventPublisher, EventSubscriber};
 use mempool::MempoolReadHandleFactory;
 use storage::vrrbdb::VrrbDbReadHandle;
-use telemetry::info;
-use tokio::task::JoinHandle;
+use telemetry::{info, warn};
+use tokio::{
+    sync::broadcast::{self, error::RecvError},
+    task::JoinHandle,
+};
 use vrrb_config::NodeConfig;
-use vrrb_rpc::rpc::{JsonRpcServer, JsonRpcServerConfig};
+use vrrb_rpc::{
+    http::{HttpApiServer, HttpApiServerConfig},
+    rpc::{JsonRpcServer, JsonRpcServerConfig, RateLimitConfig, RpcAccessConfig, RpcSubscriptions},
+};
 
 use crate::result::{NodeError, Result};
 
@@ -17,12 +23,17 @@ pub async fn setup_rpc_api_server(
     mempool_read_handle_factory: MempoolReadHandleFactory,
     mut jsonrpc_events_rx: EventSubscriber,
 ) -> Result<(JoinHandle<Result<()>>, SocketAddr)> {
+    let subscriptions = RpcSubscriptions::default();
+
     let jsonrpc_server_config = JsonRpcServerConfig {
         address: config.jsonrpc_server_address,
         node_type: config.node_type,
         events_tx,
         vrrbdb_read_handle,
         mempool_read_handle_factory,
+        subscriptions: subscriptions.clone(),
+        access: RpcAccessConfig::default(),
+        rate_limit: Some(RateLimitConfig::default()),
     };
 
     let (jsonrpc_server_handle, resolved_jsonrpc_server_addr) =
@@ -31,16 +42,23 @@ pub async fn setup_rpc_api_server(
             .map_err(|err| NodeError::Other(format!("unable to start JSON-RPC server: {err}")))?;
 
     let jsonrpc_server_handle = tokio::spawn(async move {
-        if let Ok(evt) = jsonrpc_events_rx.recv().await {
-            if let Event::Stop = evt.into() {
-                jsonrpc_server_handle.stop().map_err(|err| {
-                    NodeError::Other(format!("JSON-RPC event has stopped: {err}"))
-                })?;
-                return Ok(());
+        loop {
+            match jsonrpc_events_rx.recv().await {
+                Ok(evt) => match evt.into() {
+                    Event::Stop => {
+                        jsonrpc_server_handle.stop().map_err(|err| {
+                            NodeError::Other(format!("JSON-RPC event has stopped: {err}"))
+                        })?;
+                        return Ok(());
+                    }
+                    event => subscriptions.publish(&event),
+                },
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!("JSON-RPC server missed {skipped} events");
+                }
+                Err(RecvError::Closed) => return Ok(()),
             }
         }
-
-        Ok(())
     });
 
     info!(
@@ -52,3 +70,62 @@ pub async fn setup_rpc_api_server(
 
     Ok((jsonrpc_server_handle, resolved_jsonrpc_server_addr))
 }
+
+/// Starts the REST API of the node, which stops along with the JSON-RPC server
+pub fn setup_http_api_server(
+    config: &NodeConfig,
+    events_tx: EventPublisher,
+    vrrbdb_read_handle: VrrbDbReadHandle,
+    mempool_read_handle_factory: MempoolReadHandleFactory,
+    mut http_api_events_rx: EventSubscriber,
+) -> Result<(JoinHandle<Result<()>>, SocketAddr)> {
+    let http_api_server_config = HttpApiServerConfig {
+        address: config.http_api_address.to_string(),
+        api_title: config.http_api_title.clone(),
+        api_version: config.http_api_version.clone(),
+        server_timeout: config.http_api_shutdown_timeout,
+        tls_config: None,
+        vrrbdb_read_handle,
+        mempool_read_handle_factory,
+        events_tx,
+        access: RpcAccessConfig::default(),
+    };
+
+    let http_api_server = HttpApiServer::new(http_api_server_config)
+        .map_err(|err| NodeError::Other(format!("unable to start HTTP API server: {err}")))?;
+
+    let resolved_http_api_addr = http_api_server
+        .address()
+        .map_err(|err| NodeError::Other(format!("unable to start HTTP API server: {err}")))?;
+
+    let (ctrl_tx, mut ctrl_rx) = broadcast::channel(1);
+
+    let http_api_server_handle = tokio::spawn(async move {
+        let server = tokio::spawn(async move { http_api_server.start(&mut ctrl_rx).await });
+
+        loop {
+            match http_api_events_rx.recv().await {
+                Ok(evt) => {
+                    if let Event::Stop = evt.into() {
+                        break;
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!("HTTP API server missed {skipped} events");
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+
+        let _ = ctrl_tx.send(Event::Stop);
+
+        server
+            .await
+            .map_err(|err| NodeError::Other(format!("HTTP API server panicked: {err}")))?
+            .map_err(|err| NodeError::Other(format!("HTTP API server failed: {err}")))
+    });
+
+    info!("HTTP API server started at {}", resolved_http_api_addr);
+
+    Ok((http_api_server_handle, resolved_http_api_addr))
+}
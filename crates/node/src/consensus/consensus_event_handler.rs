@@ -1,6 +1,9 @@
 use super::ConsensusModule;
 use crate::{NodeError, Result};
-use block::{header::BlockHeader, Block, ConvergenceBlock, InnerBlock, ProposalBlock};
+use block::{
+    election::verify_miner_election, header::BlockHeader, Block, ConvergenceBlock, InnerBlock,
+    ProposalBlock,
+};
 use bulldag::graph::BullDag;
 use ethereum_types::U256;
 use events::{AssignedQuorumMembership, PeerData};
@@ -360,35 +363,51 @@ impl ConsensusModule {
     pub fn precheck_convergence_block<R: Resolver<Proposal = ProposalBlock>>(
         &mut self,
         block: ConvergenceBlock,
-        // TODO: use last_confirmed_block_header for seed & round
-        // for conflict resolution
-        _last_confirmed_block_header: BlockHeader,
+        last_confirmed_block_header: BlockHeader,
         resolver: R,
         dag: Arc<RwLock<BullDag<Block, String>>>,
     ) -> Result<(bool, bool)> {
         self.is_harvester()?;
-        self.precheck_convergence_block_miner_is_winner(block.clone())?;
+        self.precheck_convergence_block_miner_is_winner(&block, &last_confirmed_block_header)?;
+        self.precheck_convergence_block_slashing_evidence(&block)?;
         let proposal_block_hashes = block.header.ref_hashes.clone();
         self.precheck_convergence_block_transactions(block, proposal_block_hashes, resolver, dag)
     }
 
+    /// Checks that the miner of `block` won the election of the round
+    /// following `last_block_header`. The claims root the header commits to
+    /// was checked against the claim store when the block was appended, so
+    /// the winner is recomputed from the claims stored in it.
     pub fn precheck_convergence_block_miner_is_winner(
         &self,
-        block: ConvergenceBlock,
+        block: &ConvergenceBlock,
+        last_block_header: &BlockHeader,
     ) -> Result<()> {
-        let miner = block.header.miner_claim.clone();
-
-        if let Some(results) = &self.miner_election_results {
-            for (idx, claim) in results.clone().values().enumerate() {
-                if idx < 5 {
-                    if claim == &miner {
-                        return Ok(());
-                    }
-                } else {
-                    break;
-                }
-            }
+        let claims = self.claim_reader.handle().entries()?;
+
+        verify_miner_election(
+            &block.header,
+            last_block_header,
+            &block.header.state_roots.claims_root_hash,
+            claims.values(),
+        )
+        .map_err(|err| NodeError::Other(format!("miner was not elected: {err}")))
+    }
+
+    /// Checks every piece of slashing evidence the miner included in the block
+    /// and that the header commits to it
+    pub fn precheck_convergence_block_slashing_evidence(
+        &self,
+        block: &ConvergenceBlock,
+    ) -> Result<()> {
+        block
+            .verify_slashing_evidence_root()
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
+        for evidence in block.slashing_evidence.iter() {
+            self.verify_slashing_evidence(evidence)?;
         }
-        Err(NodeError::Other("miner was not elected".to_string()))
+
+        Ok(())
     }
 }
@@ -1,20 +1,27 @@
 use super::{QuorumModule, QuorumModuleConfig};
 use crate::{NodeError, Result};
 use block::{
-    header::BlockHeader, Block, Certificate, ConvergenceBlock, GenesisBlock, ProposalBlock,
+    header::BlockHeader,
+    slashing::{Offense, SignedBlockHeader, SignedVote, SlashingEvidence, SlashingEvidenceId},
+    Block, Certificate, ConvergenceBlock, GenesisBlock, ProposalBlock,
 };
 use bulldag::graph::BullDag;
+use dkg_engine::prelude::{derive_dkg_secret_key, DkgEngine, DkgEngineConfig, KeyShareStore};
 use ethereum_types::U256;
 use events::{SyncPeerData, Vote};
+use indexmap::IndexMap;
 use mempool::MempoolReadHandleFactory;
 use miner::conflict_resolver::Resolver;
 use primitives::{
-    NodeId, NodeType, NodeTypeBytes, PKShareBytes, PayloadBytes, PublicKey, QuorumId, QuorumKind,
-    QuorumPublicKey, RawSignature, Signature,
+    Address, NodeId, NodeType, NodeTypeBytes, PKShareBytes, PayloadBytes, PublicKey, QuorumId,
+    QuorumKind, QuorumPublicKey, RawSignature, Round, Signature,
 };
 use secp256k1::Message;
 use serde::{Deserialize, Serialize};
-use signer::engine::{QuorumData, SignerEngine, VALIDATION_THRESHOLD};
+use signer::{
+    engine::{QuorumData, SignerEngine, VALIDATION_THRESHOLD},
+    signer::SignatureProvider,
+};
 use std::collections::{hash_map::Entry, BTreeMap, HashMap, HashSet};
 use std::sync::{Arc, RwLock};
 use storage::vrrbdb::{ClaimStoreReadHandleFactory, StateStoreReadHandleFactory};
@@ -31,7 +38,6 @@ use vrrb_core::transactions::{Transaction, TransactionDigest, TransactionKind};
 pub struct ConsensusModuleConfig {
     pub keypair: Keypair,
     pub node_config: NodeConfig,
-    // pub dkg_generator: DkgEngine,
     pub validator_public_key: PublicKey,
 }
 
@@ -86,6 +92,18 @@ pub struct ConsensusModule {
     pub votes_pool: HashMap<QuorumId, HashMap<TransactionDigest, HashSet<Vote>>>,
     pub(crate) validator_core_manager: ValidatorCoreManager,
     pub miner_election_results: Option<BTreeMap<U256, Claim>>,
+    /// Verified slashing evidence waiting to be included in a convergence block
+    pub(crate) slashing_evidence: IndexMap<SlashingEvidenceId, SlashingEvidence>,
+    /// The convergence block each harvester signed in a given round
+    pub(crate) convergence_block_signatures: HashMap<(NodeId, Round), SignedBlockHeader>,
+    /// Shared by every clone of the module so they all drive the same round
+    pub(crate) dkg_engine: Arc<RwLock<DkgEngine>>,
+    /// Keeps the quorum key share generated by the DKG engine across restarts
+    pub(crate) key_share_store: KeyShareStore,
+    /// Signs with the quorum key share, switching to a reshared one at once
+    pub(crate) signature_provider: SignatureProvider,
+    /// Claims the miner elections of convergence blocks are checked against
+    pub(crate) claim_reader: ClaimStoreReadHandleFactory,
 }
 
 impl ConsensusModule {
@@ -102,20 +120,52 @@ impl ConsensusModule {
         };
 
         let validator_core_manager =
-            ValidatorCoreManager::new(cores, mempool_reader, state_reader, claim_reader).map_err(
-                |err| NodeError::Other(format!("failed to generate validator core manager: {err}")),
-            )?;
+            ValidatorCoreManager::new(cores, mempool_reader, state_reader, claim_reader.clone())
+                .map_err(|err| {
+                    NodeError::Other(format!("failed to generate validator core manager: {err}"))
+                })?;
 
         let sig_engine = SignerEngine::new(
             *cfg.keypair.get_miner_public_key(),
             *cfg.keypair.get_miner_secret_key(),
         );
 
+        let validator_secret = cfg.keypair.get_validator_secret_key().secret_bytes();
+
+        let mut dkg_engine = DkgEngine::new(DkgEngineConfig {
+            node_id: cfg.node_config.id.clone(),
+            node_type: cfg.node_config.node_type,
+            secret_key: derive_dkg_secret_key(&validator_secret),
+            validator_secret_key: *cfg.keypair.get_validator_secret_key(),
+            threshold_config: cfg.node_config.threshold_config.clone(),
+        });
+
+        let key_share_store =
+            KeyShareStore::new(cfg.node_config.data_dir().join("dkg"), &validator_secret)?;
+
+        let mut quorum_driver = QuorumModule::new(quorum_module_config);
+        let signature_provider = SignatureProvider::new(cfg.node_config.threshold_config.clone());
+
+        match key_share_store.load(&cfg.node_config.id) {
+            Ok(Some(key_share)) => {
+                quorum_driver.quorum_public_key_set = Some(key_share.public_key_set.clone());
+                signature_provider.replace_keys(
+                    key_share.public_key_set.clone(),
+                    key_share.secret_key_share.clone(),
+                );
+                dkg_engine.restore_key_share(key_share);
+            }
+            Ok(None) => {}
+            Err(err) => {
+                telemetry::warn!("failed to restore quorum key share: {err}");
+            }
+        }
+
         Ok(Self {
             quorum_certified_txns: HashMap::new(),
             quorum_certified_claims: HashMap::new(),
             keypair: cfg.keypair,
-            quorum_driver: QuorumModule::new(quorum_module_config),
+            quorum_driver,
             sig_engine,
             node_config: cfg.node_config.clone(),
             quorum_membership: None,
@@ -123,6 +173,12 @@ impl ConsensusModule {
             validator_core_manager,
             votes_pool: Default::default(),
             miner_election_results: None,
+            slashing_evidence: IndexMap::new(),
+            convergence_block_signatures: HashMap::new(),
+            dkg_engine: Arc::new(RwLock::new(dkg_engine)),
+            key_share_store,
+            signature_provider,
+            claim_reader,
         })
     }
 
@@ -269,8 +325,8 @@ impl ConsensusModule {
         let receiver_farmer_id = self.node_config.id.clone();
         let farmer_node_id = self.node_config.id.clone();
 
-        let txn_bytes = bincode::serialize(&transaction.clone()).ok()?;
-        let signature = self.sig_engine.sign(txn_bytes).ok()?;
+        let payload = SignedVote::payload(&transaction.id(), valid);
+        let signature = self.sig_engine.sign(payload).ok()?;
 
         Some(Vote {
             farmer_id: receiver_farmer_id.clone(),
@@ -292,6 +348,14 @@ impl ConsensusModule {
             )))?
             .0;
         self.check_vote_is_valid(&quorum_id, &vote).await?;
+        if self.conflicting_vote(&quorum_id, &vote).is_some() {
+            return Err(NodeError::Other(format!(
+                "node {} already cast a conflicting vote on transaction {}",
+                vote.farmer_node_id,
+                vote.txn.id()
+            )));
+        }
+
         match self.votes_pool.entry(quorum_id.clone()) {
             Entry::Occupied(mut entry) => {
                 let map = entry.get_mut();
@@ -360,27 +424,20 @@ impl ConsensusModule {
         let set = self.get_quorum_pending_votes_for_transaction(quorum_id, vote)?;
         let quorum_members = self.get_quorum_members(quorum_id)?;
         if self.double_check_vote_threshold_reached(&set, quorum_members) {
-            let batch_sigs = set
-                .iter()
-                .map(|vote| (vote.farmer_node_id.clone(), vote.signature))
-                .collect();
-
-            let data = bincode::serialize(&vote.txn.clone()).map_err(|err| {
-                NodeError::Other(format!(
-                    "unable to serialize txn: {} to verify vote signature. err: {}",
-                    &vote.txn.id(),
-                    err
-                ))
-            })?;
-            self.sig_engine
-                .verify_batch(&batch_sigs, &data)
-                .map_err(|err| {
-                    NodeError::Other(format!(
-                        "unable to batch verify vote signatures for txn: {}, err: {}",
-                        &vote.txn.id().clone(),
-                        err
-                    ))
-                })?;
+            for (is_txn_valid, vote_shares) in Self::group_votes_by_validity(&set) {
+                let batch_sigs = vote_shares.into_iter().collect();
+                let data = SignedVote::payload(&vote.txn.id(), is_txn_valid);
+
+                self.sig_engine
+                    .verify_batch(&batch_sigs, &data)
+                    .map_err(|err| {
+                        NodeError::Other(format!(
+                            "unable to batch verify vote signatures for txn: {}, err: {}",
+                            &vote.txn.id().clone(),
+                            err
+                        ))
+                    })?;
+            }
 
             return Ok(());
         }
@@ -453,13 +510,7 @@ impl ConsensusModule {
                 ))
             })?;
 
-        let data = bincode::serialize(&vote.txn.clone()).map_err(|err| {
-            NodeError::Other(format!(
-                "unable to serialize txn: {} to verify vote signature. err: {}",
-                &vote.txn.id(),
-                err
-            ))
-        })?;
+        let data = SignedVote::payload(&vote.txn.id(), vote.is_txn_valid);
         self.sig_engine
             .verify(&voter, &vote.signature, &data)
             .map_err(|err| {
@@ -554,8 +605,9 @@ impl ConsensusModule {
         Ok(())
     }
 
-    //TODO: this funciton is never used.
-    fn _group_votes_by_validity(votes: &[Vote]) -> HashMap<bool, BTreeMap<NodeId, Signature>> {
+    fn group_votes_by_validity(
+        votes: &HashSet<Vote>,
+    ) -> HashMap<bool, BTreeMap<NodeId, Signature>> {
         let mut vote_shares: HashMap<bool, BTreeMap<NodeId, Signature>> = HashMap::new();
 
         for v in votes.iter() {
@@ -586,4 +638,143 @@ impl ConsensusModule {
 
         Ok(())
     }
+
+    /// Returns a previously received vote from the same farmer on the same
+    /// transaction that disagrees with `vote`, if there is one
+    fn conflicting_vote(&self, quorum_id: &QuorumId, vote: &Vote) -> Option<Vote> {
+        self.votes_pool
+            .get(quorum_id)?
+            .get(&vote.txn.id())?
+            .iter()
+            .find(|pending| {
+                pending.farmer_node_id == vote.farmer_node_id
+                    && pending.is_txn_valid != vote.is_txn_valid
+            })
+            .cloned()
+    }
+
+    /// Checks whether `vote` contradicts a vote its farmer already cast, and if
+    /// so, produces slashing evidence against the farmer
+    pub fn report_double_vote(&mut self, vote: &Vote) -> Result<Option<SlashingEvidence>> {
+        self.is_harvester()?;
+        let quorum_id = match self.get_node_quorum_id(&vote.farmer_node_id) {
+            Some((quorum_id, _)) => quorum_id,
+            None => return Ok(None),
+        };
+
+        if let Some(previous) = self.conflicting_vote(&quorum_id, vote) {
+            let offense = Offense::DoubleVote {
+                first: SignedVote {
+                    txn_id: previous.txn.id(),
+                    is_txn_valid: previous.is_txn_valid,
+                    signature: previous.signature,
+                },
+                second: SignedVote {
+                    txn_id: vote.txn.id(),
+                    is_txn_valid: vote.is_txn_valid,
+                    signature: vote.signature,
+                },
+            };
+
+            return self
+                .report_offense(vote.farmer_node_id.clone(), offense)
+                .map(Some);
+        }
+
+        Ok(None)
+    }
+
+    /// Records a harvester's signature on a convergence block, producing
+    /// slashing evidence if the harvester already signed a different block in
+    /// the same round
+    pub fn record_convergence_block_signature(
+        &mut self,
+        node_id: NodeId,
+        header: &BlockHeader,
+        signature: Signature,
+    ) -> Result<Option<SlashingEvidence>> {
+        let signed_header = SignedBlockHeader {
+            header: header.clone(),
+            signature,
+        };
+
+        let key = (node_id.clone(), header.round);
+        if let Some(previous) = self.convergence_block_signatures.get(&key).cloned() {
+            if previous.header.hash() == header.hash() {
+                return Ok(None);
+            }
+
+            let offense = Offense::ConflictingBlockSignatures {
+                first: previous,
+                second: signed_header,
+            };
+
+            return self.report_offense(node_id, offense).map(Some);
+        }
+
+        self.convergence_block_signatures.insert(key, signed_header);
+
+        Ok(None)
+    }
+
+    fn report_offense(&mut self, offender: NodeId, offense: Offense) -> Result<SlashingEvidence> {
+        let offender_public_key = self.get_member_public_key(&offender)?;
+        let evidence = SlashingEvidence::new(
+            offender,
+            Address::new(offender_public_key),
+            offense,
+            *self.keypair.get_miner_secret_key(),
+            *self.keypair.get_miner_public_key(),
+        )
+        .map_err(|err| NodeError::Other(format!("failed to create slashing evidence: {err}")))?;
+
+        self.handle_slashing_evidence_received(evidence.clone())?;
+
+        Ok(evidence)
+    }
+
+    fn get_member_public_key(&self, node_id: &NodeId) -> Result<PublicKey> {
+        self.sig_engine
+            .quorum_members()
+            .get_public_key_from_members(node_id)
+            .ok_or(NodeError::Other(format!(
+                "node {node_id} is not a quorum member"
+            )))
+    }
+
+    /// Checks that the offender signed both conflicting messages and that the
+    /// penalty is issued against its claim
+    pub fn verify_slashing_evidence(&self, evidence: &SlashingEvidence) -> Result<()> {
+        let offender_public_key = self.get_member_public_key(&evidence.offender)?;
+
+        evidence
+            .verify(&offender_public_key, &Address::new(offender_public_key))
+            .map_err(|err| NodeError::Other(format!("invalid slashing evidence: {err}")))
+    }
+
+    /// Adds slashing evidence gossiped by another node to the pool of
+    /// evidence waiting to be included in a block
+    pub fn handle_slashing_evidence_received(&mut self, evidence: SlashingEvidence) -> Result<()> {
+        self.verify_slashing_evidence(&evidence)?;
+        self.slashing_evidence
+            .entry(evidence.id())
+            .or_insert(evidence);
+
+        Ok(())
+    }
+
+    pub fn pending_slashing_evidence(&self) -> Vec<SlashingEvidence> {
+        self.slashing_evidence.values().cloned().collect()
+    }
+
+    /// Drops the evidence included in a confirmed block, along with the
+    /// convergence block signatures of earlier rounds
+    pub fn prune_slashing_evidence(&mut self, block: &ConvergenceBlock) {
+        for evidence in block.slashing_evidence.iter() {
+            self.slashing_evidence.shift_remove(&evidence.id());
+        }
+
+        self.convergence_block_signatures
+            .retain(|(_, round), _| *round >= block.header.round);
+    }
 }
@@ -0,0 +1,169 @@
+use std::{collections::BTreeSet, sync::RwLockWriteGuard};
+
+use dkg_engine::prelude::{
+    DkgEngine, DkgOutput, DkgPublicKeyAnnouncement, DkgRoundId, DkgTimeoutVote, QuorumKeyShare,
+    ReceiverId, ResharePart, SenderId,
+};
+use hbbft::sync_key_gen::{Ack, Part};
+use primitives::{NodeId, QuorumKind};
+
+use super::ConsensusModule;
+use crate::{NodeError, Result};
+
+impl ConsensusModule {
+    /// Starts the DKG round of the quorum the node was assigned to. Nodes
+    /// without a quorum and miner quorums, which don't share a threshold key,
+    /// don't run one. Harvesters already holding a key share reshare it to
+    /// the new quorum instead, so the harvester public key stays the same
+    /// across epochs.
+    pub fn start_dkg_round(&mut self) -> Result<DkgOutput> {
+        let membership_config = match &self.quorum_driver.membership_config {
+            Some(membership_config) if membership_config.quorum_kind != QuorumKind::Miner => {
+                membership_config
+            }
+            _ => return Ok(DkgOutput::default()),
+        };
+
+        let mut members = membership_config
+            .quorum_members
+            .keys()
+            .cloned()
+            .collect::<BTreeSet<NodeId>>();
+        members.insert(self.node_config.id.clone());
+
+        let mut dkg_engine = self.lock_dkg_engine()?;
+
+        // NOTE: keys of earlier quorums are kept, so members of the previous
+        // quorum can still be verified while they reshare their key shares
+        dkg_engine.add_validator_public_key(
+            self.node_config.id.clone(),
+            self.keypair.get_validator_public_key_owned(),
+        );
+        for (node_id, member) in membership_config.quorum_members.iter() {
+            dkg_engine.add_validator_public_key(node_id.clone(), member.validator_public_key);
+        }
+
+        let has_key_share = dkg_engine
+            .round()
+            .map(|round| round.is_completed())
+            .unwrap_or(false);
+
+        if membership_config.quorum_kind == QuorumKind::Harvester && has_key_share {
+            return Ok(dkg_engine.start_reshare(members)?);
+        }
+
+        Ok(dkg_engine.start_round(members)?)
+    }
+
+    pub fn handle_dkg_public_key_announced(
+        &mut self,
+        announcement: DkgPublicKeyAnnouncement,
+    ) -> Result<DkgOutput> {
+        let output = self.lock_dkg_engine()?.handle_public_key(announcement)?;
+
+        self.persist_quorum_key_share(output)
+    }
+
+    pub fn handle_part_commitment_created(
+        &mut self,
+        sender_id: SenderId,
+        part: Part,
+    ) -> Result<DkgOutput> {
+        let output = self
+            .lock_dkg_engine()?
+            .handle_part_commitment(sender_id, part)?;
+
+        self.persist_quorum_key_share(output)
+    }
+
+    pub fn handle_part_commitment_acknowledged(
+        &mut self,
+        receiver_id: ReceiverId,
+        sender_id: SenderId,
+        ack: Ack,
+    ) -> Result<DkgOutput> {
+        let output =
+            self.lock_dkg_engine()?
+                .handle_part_acknowledgement(receiver_id, sender_id, ack)?;
+
+        self.persist_quorum_key_share(output)
+    }
+
+    pub fn handle_reshare_part_created(
+        &mut self,
+        node_id: NodeId,
+        part: ResharePart,
+    ) -> Result<DkgOutput> {
+        let output = self.lock_dkg_engine()?.handle_reshare_part(node_id, part)?;
+
+        self.persist_quorum_key_share(output)
+    }
+
+    pub fn handle_dkg_round_timed_out(&mut self, round_id: DkgRoundId) -> Result<DkgOutput> {
+        let output = self.lock_dkg_engine()?.handle_round_timeout(round_id)?;
+
+        self.persist_quorum_key_share(output)
+    }
+
+    pub fn handle_dkg_timeout_vote_cast(&mut self, vote: DkgTimeoutVote) -> Result<DkgOutput> {
+        let output = self.lock_dkg_engine()?.handle_timeout_vote(vote)?;
+
+        self.persist_quorum_key_share(output)
+    }
+
+    /// Members of the quorum's latest DKG round
+    pub fn dkg_members(&self) -> Vec<NodeId> {
+        self.dkg_engine
+            .read()
+            .ok()
+            .and_then(|dkg_engine| {
+                dkg_engine
+                    .round()
+                    .map(|round| round.members().iter().cloned().collect())
+            })
+            .unwrap_or_default()
+    }
+
+    fn lock_dkg_engine(&self) -> Result<RwLockWriteGuard<'_, DkgEngine>> {
+        self.dkg_engine
+            .write()
+            .map_err(|err| NodeError::Other(format!("failed to lock the DKG engine: {err}")))
+    }
+
+    /// Writes the key share of a completed round to disk so the quorum key
+    /// material survives a restart, and switches the signature provider over
+    /// to it. A reshared key set has to keep the quorum public key.
+    fn persist_quorum_key_share(&mut self, output: DkgOutput) -> Result<DkgOutput> {
+        let public_key_set = match &output.public_key_set {
+            Some(public_key_set) => public_key_set.clone(),
+            None => return Ok(output),
+        };
+
+        let dkg_engine = self.lock_dkg_engine()?;
+        let (members, secret_key_share) = match (
+            dkg_engine.round(),
+            dkg_engine.dkg_state.secret_key_share_owned(),
+        ) {
+            (Some(round), Some(secret_key_share)) => (round.members().clone(), secret_key_share),
+            _ => return Ok(output),
+        };
+        drop(dkg_engine);
+
+        self.key_share_store.save(&QuorumKeyShare {
+            node_id: self.node_config.id.clone(),
+            members,
+            public_key_set: public_key_set.clone(),
+            secret_key_share: secret_key_share.clone(),
+        })?;
+
+        if output.reshared {
+            self.signature_provider
+                .rotate_keys(public_key_set, secret_key_share)?;
+        } else {
+            self.signature_provider
+                .replace_keys(public_key_set, secret_key_share);
+        }
+
+        Ok(output)
+    }
+}
@@ -1,5 +1,6 @@
 mod consensus_event_handler;
 mod consensus_module;
+mod dkg_event_handler;
 
 mod quorum_module;
 
@@ -3,6 +3,7 @@ use std::collections::{BTreeMap, HashMap};
 use block::header::BlockHeader;
 use ethereum_types::U256;
 use events::{AssignedQuorumMembership, PeerData};
+use hbbft::crypto::PublicKeySet;
 use primitives::{NodeId, NodeType, QuorumKind};
 use quorum::{
     election::Election,
@@ -22,6 +23,9 @@ pub struct QuorumModule {
 
     /// A map of all nodes known to are available in the bootstrap quorum
     pub(crate) bootstrap_quorum_available_nodes: HashMap<NodeId, (PeerData, bool)>,
+
+    /// Key set generated by the quorum's latest DKG round
+    pub(crate) quorum_public_key_set: Option<PublicKeySet>,
 }
 
 #[derive(Debug, Clone)]
@@ -63,6 +67,7 @@ impl QuorumModule {
             node_config: cfg.node_config.clone(),
             bootstrap_config: cfg.node_config.bootstrap_config.clone(),
             bootstrap_quorum_available_nodes,
+            quorum_public_key_set: None,
         }
     }
 
@@ -72,6 +77,26 @@ impl QuorumModule {
         self.membership_config = Some(membership_config);
     }
 
+    pub fn quorum_public_key_set(&self) -> Option<&PublicKeySet> {
+        self.quorum_public_key_set.as_ref()
+    }
+
+    /// Stores the key set generated by the quorum's DKG round and drops the
+    /// members that were excluded from it for not responding in time
+    pub fn handle_quorum_key_set_generated(
+        &mut self,
+        public_key_set: PublicKeySet,
+        excluded: Vec<NodeId>,
+    ) {
+        if let Some(membership_config) = self.membership_config.as_mut() {
+            for node_id in excluded.iter() {
+                membership_config.quorum_members.remove(node_id);
+            }
+        }
+
+        self.quorum_public_key_set = Some(public_key_set);
+    }
+
     async fn assign_membership_to_quorum(
         &self,
         quorum_kind: QuorumKind,
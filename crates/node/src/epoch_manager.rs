@@ -0,0 +1,153 @@
+use block::{ConvergenceBlock, ProposalBlock};
+use primitives::{epoch_of, Epoch, Round, GENESIS_EPOCH};
+use storage::vrrbdb::EpochSummary;
+
+/// Splits rounds into epochs of the number of rounds set by the genesis block
+/// and keeps track of what happens during the epoch in progress.
+///
+/// Blocks are grouped by the epoch in their header, so every node summarizes
+/// the same blocks into the same epoch regardless of when it applies them.
+#[derive(Debug, Clone)]
+pub struct EpochManager {
+    epoch_length: Round,
+    current: Option<EpochSummary>,
+}
+
+impl EpochManager {
+    /// Creates an epoch manager for epochs of `epoch_length` rounds. Epochs
+    /// last for at least one round.
+    pub fn new(epoch_length: Round) -> Self {
+        Self {
+            epoch_length: epoch_length.max(1),
+            current: None,
+        }
+    }
+
+    /// Picks up the epoch in progress where the node left it before it
+    /// restarted
+    pub fn resume(mut self, current: Option<EpochSummary>) -> Self {
+        self.current = current;
+        self
+    }
+
+    /// Switches to epochs of `epoch_length` rounds, once the genesis block
+    /// setting it is applied
+    pub fn set_epoch_length(&mut self, epoch_length: Round) {
+        self.epoch_length = epoch_length.max(1);
+    }
+
+    /// Returns the epoch `round` belongs to
+    pub fn epoch_of(&self, round: Round) -> Epoch {
+        epoch_of(round, self.epoch_length)
+    }
+
+    /// Returns the summary of the epoch in progress so far
+    pub fn current(&self) -> Option<&EpochSummary> {
+        self.current.as_ref()
+    }
+
+    /// Returns the epoch of the blocks applied so far
+    pub fn current_epoch(&self) -> Epoch {
+        self.current
+            .as_ref()
+            .map(|summary| summary.epoch)
+            .unwrap_or(GENESIS_EPOCH)
+    }
+
+    /// Accounts for a convergence block applied by the node along with the
+    /// proposal blocks it references. Returns the summary of the previous
+    /// epoch when the block is the first one of a new epoch.
+    ///
+    /// Blocks of an epoch that is already over are ignored.
+    pub fn record_block(
+        &mut self,
+        convergence: &ConvergenceBlock,
+        proposals: &[ProposalBlock],
+    ) -> Option<EpochSummary> {
+        let epoch = convergence.header.epoch;
+        let round = convergence.header.round;
+
+        let finished = match self.current.take() {
+            Some(current) if current.epoch == epoch => {
+                self.current = Some(current);
+                None
+            }
+            Some(current) if current.epoch > epoch => {
+                telemetry::warn!(
+                    "ignoring block {} of epoch {epoch}, epoch {} is in progress",
+                    convergence.hash,
+                    current.epoch
+                );
+                self.current = Some(current);
+                return None;
+            }
+            finished => finished,
+        };
+
+        self.current
+            .get_or_insert_with(|| EpochSummary::new(epoch, round))
+            .record_block(convergence, proposals);
+
+        finished
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_utils::dummy_convergence_block;
+
+    use super::*;
+
+    fn block_at(epoch: Epoch, round: Round) -> ConvergenceBlock {
+        let mut block = dummy_convergence_block();
+        block.header.epoch = epoch;
+        block.header.round = round;
+
+        block
+    }
+
+    #[test]
+    fn rounds_are_split_into_epochs() {
+        let manager = EpochManager::new(10);
+
+        assert_eq!(manager.epoch_of(0), 0);
+        assert_eq!(manager.epoch_of(9), 0);
+        assert_eq!(manager.epoch_of(10), 1);
+
+        assert_eq!(EpochManager::new(0).epoch_of(3), 3);
+    }
+
+    #[test]
+    fn epochs_are_summarized_once_the_next_one_starts() {
+        let mut manager = EpochManager::new(10);
+
+        assert!(manager.record_block(&block_at(0, 1), &[]).is_none());
+        assert!(manager.record_block(&block_at(0, 9), &[]).is_none());
+        assert_eq!(manager.current_epoch(), 0);
+
+        let summary = manager.record_block(&block_at(1, 10), &[]).unwrap();
+        assert_eq!(summary.epoch, 0);
+        assert_eq!(summary.blocks, 2);
+        assert_eq!(summary.start_round, 1);
+        assert_eq!(summary.end_round, 9);
+        assert_eq!(manager.current_epoch(), 1);
+
+        // late blocks of an epoch that is over are not accounted for
+        assert!(manager.record_block(&block_at(0, 8), &[]).is_none());
+        let summary = manager.record_block(&block_at(2, 20), &[]).unwrap();
+        assert_eq!(summary.epoch, 1);
+        assert_eq!(summary.blocks, 1);
+    }
+
+    #[test]
+    fn epoch_in_progress_survives_a_restart() {
+        let mut manager = EpochManager::new(10);
+        manager.record_block(&block_at(0, 1), &[]);
+        manager.record_block(&block_at(0, 2), &[]);
+
+        let mut resumed = EpochManager::new(10).resume(manager.current().cloned());
+        let summary = resumed.record_block(&block_at(1, 10), &[]).unwrap();
+        assert_eq!(summary.epoch, 0);
+        assert_eq!(summary.blocks, 2);
+    }
+}
@@ -7,6 +7,7 @@ mod runtime_module;
 pub(crate) mod api;
 pub(crate) mod consensus;
 pub(crate) mod data_store;
+pub(crate) mod epoch_manager;
 pub(crate) mod indexer_module;
 pub(crate) mod mining_module;
 pub(crate) mod network;
@@ -53,6 +53,16 @@ impl Handler<EventMessage> for NetworkModule {
                 self.broadcast_claim(claim).await?;
             }
 
+            Event::DkgPublicKeyAnnounced(announcement) => {
+                info!("Broadcasting DKG public key to peers in quorum");
+                self.broadcast_dkg_public_key(announcement).await?;
+            }
+
+            Event::DkgTimeoutVoteCast(vote) => {
+                info!("Broadcasting DKG timeout vote to peers in quorum");
+                self.broadcast_dkg_timeout_vote(vote).await?;
+            }
+
             Event::PartCommitmentCreated(node_id, part) => {
                 info!("Broadcasting part commitment to peers in quorum");
                 self.broadcast_part_commitment(node_id, part).await?;
@@ -68,6 +78,11 @@ impl Handler<EventMessage> for NetworkModule {
                     .await?;
             }
 
+            Event::ResharePartCreated(node_id, part) => {
+                info!("Broadcasting reshare part to peers");
+                self.broadcast_reshare_part(node_id, part).await?;
+            }
+
             Event::ConvergenceBlockCertified(block) => {
                 info!("Broadcasting certified convergence block to network");
                 self.broadcast_certified_convergence_block(block).await?;
@@ -91,6 +106,10 @@ impl Handler<EventMessage> for NetworkModule {
                 info!("Broadcasting transaction vote to network");
                 self.broadcast_transaction_vote(vote).await?;
             }
+            Event::SlashingEvidenceCreated(evidence) => {
+                info!("Broadcasting slashing evidence to network");
+                self.broadcast_slashing_evidence(evidence).await?;
+            }
 
             Event::BlockCreated(block) => {
                 info!("Broadcasting block to network");
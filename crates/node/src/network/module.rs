@@ -1,6 +1,7 @@
 use std::net::SocketAddr;
 
-use block::{Block, Certificate, ConvergenceBlock};
+use block::{slashing::SlashingEvidence, Block, Certificate, ConvergenceBlock};
+use dkg_engine::prelude::{DkgPublicKeyAnnouncement, DkgTimeoutVote, ResharePart};
 use dyswarm::{
     client::{BroadcastArgs, BroadcastConfig},
     server::ServerConfig,
@@ -304,6 +305,95 @@ impl NetworkModule {
         Ok(())
     }
 
+    pub async fn broadcast_dkg_public_key(
+        &mut self,
+        announcement: DkgPublicKeyAnnouncement,
+    ) -> Result<()> {
+        let closest_nodes = self
+            .node_ref()
+            .get_routing_table()
+            .get_closest_nodes(&self.node_ref().node_data().id, 8);
+
+        let socket_addresses = closest_nodes
+            .iter()
+            .map(|node| node.udp_gossip_addr)
+            .collect();
+
+        self.dyswarm_client.add_peers(socket_addresses).await?;
+
+        let message =
+            dyswarm::types::Message::new(NetworkEvent::DkgPublicKeyAnnounced(announcement));
+
+        self.dyswarm_client
+            .broadcast(BroadcastArgs {
+                config: Default::default(),
+                message,
+                erasure_count: 0,
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn broadcast_dkg_timeout_vote(&mut self, vote: DkgTimeoutVote) -> Result<()> {
+        let closest_nodes = self
+            .node_ref()
+            .get_routing_table()
+            .get_closest_nodes(&self.node_ref().node_data().id, 8);
+
+        let socket_addresses = closest_nodes
+            .iter()
+            .map(|node| node.udp_gossip_addr)
+            .collect();
+
+        self.dyswarm_client.add_peers(socket_addresses).await?;
+
+        let message = dyswarm::types::Message::new(NetworkEvent::DkgTimeoutVoteCast(vote));
+
+        self.dyswarm_client
+            .broadcast(BroadcastArgs {
+                config: Default::default(),
+                message,
+                erasure_count: 0,
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    /// Broadcasts the sub-shares dealt to the next quorum. The members of
+    /// the next quorum may not belong to the current one, so the part is
+    /// gossiped to the closest peers rather than to the quorum
+    pub async fn broadcast_reshare_part(
+        &mut self,
+        node_id: NodeId,
+        part: ResharePart,
+    ) -> Result<()> {
+        let closest_nodes = self
+            .node_ref()
+            .get_routing_table()
+            .get_closest_nodes(&self.node_ref().node_data().id, 8);
+
+        let socket_addresses = closest_nodes
+            .iter()
+            .map(|node| node.udp_gossip_addr)
+            .collect();
+
+        self.dyswarm_client.add_peers(socket_addresses).await?;
+
+        let message = dyswarm::types::Message::new(NetworkEvent::ResharePartCreated(node_id, part));
+
+        self.dyswarm_client
+            .broadcast(BroadcastArgs {
+                config: Default::default(),
+                message,
+                erasure_count: 0,
+            })
+            .await?;
+
+        Ok(())
+    }
+
     pub async fn broadcast_part_commitment(&mut self, node_id: NodeId, part: Part) -> Result<()> {
         let closest_nodes = self
             .node_ref()
@@ -342,15 +432,15 @@ impl NetworkModule {
             .get_routing_table()
             .get_closest_nodes(&self.node_ref().node_data().id, 8);
 
-        let found_peer = closest_nodes
+        let socket_addresses = closest_nodes
             .iter()
-            .find(|node| node.node_id == node_id.clone())
-            .ok_or(NodeError::Other(
-                "Could not find peer in routing table".to_string(),
-            ))?;
+            .map(|node| node.udp_gossip_addr)
+            .collect();
 
-        let addr = found_peer.udp_gossip_addr;
+        self.dyswarm_client.add_peers(socket_addresses).await?;
 
+        // NOTE: every member of the quorum has to handle every ack, not only the
+        // node whose part was acknowledged
         let message = dyswarm::types::Message::new(NetworkEvent::PartCommitmentAcknowledged {
             node_id,
             sender_id,
@@ -358,7 +448,11 @@ impl NetworkModule {
         });
 
         self.dyswarm_client
-            .send_data_via_quic(message, addr)
+            .broadcast(BroadcastArgs {
+                config: Default::default(),
+                message,
+                erasure_count: 0,
+            })
             .await?;
 
         Ok(())
@@ -428,6 +522,21 @@ impl NetworkModule {
         Ok(())
     }
 
+    pub async fn broadcast_slashing_evidence(&mut self, evidence: SlashingEvidence) -> Result<()> {
+        telemetry::info!("Broadcasting slashing evidence to network");
+        let message =
+            dyswarm::types::Message::new(NetworkEvent::SlashingEvidenceCreated(Box::new(evidence)));
+        self.dyswarm_client
+            .broadcast(BroadcastArgs {
+                config: Default::default(),
+                message,
+                erasure_count: 0,
+            })
+            .await?;
+
+        Ok(())
+    }
+
     pub(crate) async fn broadcast_block(&mut self, block: Block) -> Result<()> {
         let closest_nodes = self
             .node_ref()
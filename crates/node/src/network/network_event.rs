@@ -1,6 +1,7 @@
 use std::net::SocketAddr;
 
-use block::{Block, Certificate, ConvergenceBlock};
+use block::{slashing::SlashingEvidence, Block, Certificate, ConvergenceBlock};
+use dkg_engine::prelude::{DkgPublicKeyAnnouncement, DkgTimeoutVote, ResharePart};
 use events::{AssignedQuorumMembership, Vote};
 use hbbft::sync_key_gen::{Ack, Part};
 use mempool::TxnRecord;
@@ -53,17 +54,21 @@ pub enum NetworkEvent {
 
     ForwardedTxn(Box<TxnRecord>),
 
+    DkgPublicKeyAnnounced(DkgPublicKeyAnnouncement),
+    DkgTimeoutVoteCast(DkgTimeoutVote),
     PartCommitmentCreated(NodeId, Part),
     PartCommitmentAcknowledged {
         node_id: NodeId,
         sender_id: NodeId,
         ack: Ack,
     },
+    ResharePartCreated(NodeId, ResharePart),
 
     ConvergenceBlockCertified(ConvergenceBlock),
     ConvergenceBlockPartialSignComplete(ConvergencePartialSig),
     BroadcastCertificate(Certificate),
     BroadcastTransactionVote(Box<Vote>),
+    SlashingEvidenceCreated(Box<SlashingEvidence>),
     Ping(NodeId),
 
     #[default]
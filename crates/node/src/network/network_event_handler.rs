@@ -95,6 +95,18 @@ impl dyswarm::server::Handler<NetworkEvent> for DyswarmHandler {
 
                 self.send_event_to_runtime(evt).await?;
             }
+            NetworkEvent::DkgPublicKeyAnnounced(announcement) => {
+                let evt = Event::DkgPublicKeyAnnounced(announcement);
+
+                self.send_event_to_runtime(evt).await?;
+            }
+
+            NetworkEvent::DkgTimeoutVoteCast(vote) => {
+                let evt = Event::DkgTimeoutVoteCast(vote);
+
+                self.send_event_to_runtime(evt).await?;
+            }
+
             NetworkEvent::PartCommitmentCreated(node_id, part) => {
                 let evt = Event::PartCommitmentCreated(node_id, part);
 
@@ -117,12 +129,24 @@ impl dyswarm::server::Handler<NetworkEvent> for DyswarmHandler {
                 self.send_event_to_runtime(evt).await?;
             }
 
+            NetworkEvent::ResharePartCreated(node_id, part) => {
+                let evt = Event::ResharePartCreated(node_id, part);
+
+                self.send_event_to_runtime(evt).await?;
+            }
+
             NetworkEvent::BlockCreated(block) => {
                 let evt = Event::BlockCreated(block);
 
                 self.send_event_to_runtime(evt).await?;
             }
 
+            NetworkEvent::SlashingEvidenceCreated(evidence) => {
+                let evt = Event::SlashingEvidenceReceived(*evidence);
+
+                self.send_event_to_runtime(evt).await?;
+            }
+
             _ => {}
         }
 
@@ -52,7 +52,7 @@ impl Node {
         let (events_tx, mut events_rx) = channel(events::DEFAULT_BUFFER);
 
         let mut router = EventRouter::new();
-        router.add_topic(Topic::from(JSON_RPC_API_TOPIC_STR), Some(1));
+        router.add_topic(Topic::from(JSON_RPC_API_TOPIC_STR), Some(1000));
         router.add_topic(Topic::from(NETWORK_TOPIC_STR), Some(1000));
         router.add_topic(Topic::from(RUNTIME_TOPIC_STR), Some(1000));
 
@@ -175,6 +175,10 @@ impl Node {
         self.config.jsonrpc_server_address
     }
 
+    pub fn http_api_address(&self) -> SocketAddr {
+        self.config.http_api_address
+    }
+
     /// Reports metrics about the node's health
     pub fn health_check(&self) -> Result<NodeHealthReport> {
         Ok(NodeHealthReport::default())
@@ -1,6 +1,6 @@
 use std::net::AddrParseError;
 
-// use dkg_engine::DkgError;
+use dkg_engine::DkgError;
 use dyswarm::types::DyswarmError;
 use events::EventMessage;
 use miner::result::MinerError;
@@ -56,8 +56,12 @@ pub enum NodeError {
     #[error("Error while creating claim for node: {0}")]
     Claim(#[from] ClaimError),
 
-    // #[error("DKG error: {0}")]
-    // Dkg(#[from] DkgError),
+    #[error("DKG error: {0}")]
+    Dkg(#[from] DkgError),
+
+    #[error("{0}")]
+    Signer(#[from] signer::types::SignerError),
+
     #[error("{0}")]
     Core(#[from] vrrb_core::Error),
 
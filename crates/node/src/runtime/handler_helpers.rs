@@ -1,12 +1,17 @@
 use block::{
     header::BlockHeader, Block, Certificate, ConvergenceBlock, GenesisBlock, ProposalBlock,
 };
-use events::{AccountBytes, AssignedQuorumMembership, Event, PeerData, Vote};
+use dkg_engine::prelude::{DkgOutput, DkgRoundId};
+use events::{
+    AccountBytes, AssignedQuorumMembership, Event, EventMessage, PeerData, ProgramBytes, Vote,
+};
 use miner::conflict_resolver::Resolver;
-use primitives::{Address, NodeId, PublicKey, QuorumId, QuorumKind, Signature};
+use primitives::{
+    Address, Epoch, NodeId, PublicKey, QuorumId, QuorumKind, Round, Signature, RUNTIME_TOPIC_STR,
+};
 use signer::engine::{QuorumData, QuorumMembers as InaugaratedMembers};
 use std::collections::HashMap;
-use storage::vrrbdb::ApplyBlockResult;
+use storage::vrrbdb::{ApplyBlockResult, EpochSummary};
 use vrrb_core::transactions::TransactionDigest;
 
 use crate::{
@@ -28,7 +33,9 @@ impl NodeRuntime {
     fn handle_genesis_block_received(&mut self, block: GenesisBlock) -> Result<ApplyBlockResult> {
         self.verify_genesis_block_origin(block.clone())?;
 
+        let chain_parameters = block.chain_parameters;
         let apply_result = self.state_driver.apply_block(Block::Genesis { block })?;
+        self.apply_chain_parameters(chain_parameters)?;
 
         Ok(apply_result)
     }
@@ -38,9 +45,10 @@ impl NodeRuntime {
     // whoever sent the proposal block must be a valid harvester
     // sig_engine.quorum_members().is_harvester()
     fn handle_proposal_block_received(&mut self, block: ProposalBlock) -> Result<ApplyBlockResult> {
+        self.verify_block_epoch(&block.hash, block.round, block.epoch)?;
+
         if let Err(e) = self
             .state_driver
-            .dag
             .append_proposal(&block, self.consensus_driver.sig_engine.clone())
         {
             let err_note = format!("Failed to append proposal block to DAG: {e:?}");
@@ -60,6 +68,8 @@ impl NodeRuntime {
         block: ConvergenceBlock,
     ) -> Result<ApplyBlockResult> {
         self.consensus_driver.is_harvester()?;
+        self.verify_block_epoch(&block.hash, block.header.round, block.header.epoch)?;
+
         let apply_result = self
             .state_driver
             .append_convergence(&block)
@@ -72,6 +82,59 @@ impl NodeRuntime {
         Ok(apply_result)
     }
 
+    /// Rejects blocks whose epoch does not follow from their round and the
+    /// epoch length set by the genesis block
+    fn verify_block_epoch(&self, block_hash: &str, round: Round, epoch: Epoch) -> Result<()> {
+        let expected = self.epoch_manager.epoch_of(round);
+        if epoch != expected {
+            return Err(NodeError::Other(format!(
+                "block {block_hash} of round {round} claims epoch {epoch} instead of {expected}"
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Accounts for a convergence block the node applied. The summary of the
+    /// epoch in progress is persisted, along with the summary of the previous
+    /// epoch when the block opens a new one, which is returned so the caller
+    /// can trigger the quorum re-election.
+    pub fn handle_convergence_block_applied(
+        &mut self,
+        convergence: &ConvergenceBlock,
+    ) -> Result<Option<EpochSummary>> {
+        let proposals: Vec<ProposalBlock> = self
+            .state_driver
+            .dag
+            .get_convergence_reference_blocks(convergence)
+            .iter()
+            .filter_map(|vertex| match vertex.get_data() {
+                Block::Proposal { block } => Some(block.clone()),
+                _ => None,
+            })
+            .collect();
+
+        let finished = self.epoch_manager.record_block(convergence, &proposals);
+
+        if let Some(current) = self.epoch_manager.current() {
+            self.state_driver
+                .database
+                .record_epoch(current, finished.as_ref())?;
+        }
+
+        if let Some(summary) = &finished {
+            telemetry::info!(
+                "epoch {} is over after {} blocks, {} rewards issued and {} fees burned",
+                summary.epoch,
+                summary.blocks,
+                summary.issued_rewards,
+                summary.fees_burned
+            );
+        }
+
+        Ok(finished)
+    }
+
     pub async fn handle_harvester_signature_received(
         &mut self,
         block_hash: String,
@@ -82,6 +145,8 @@ impl NodeRuntime {
             .sig_engine
             .verify(&node_id, &sig, &block_hash)
             .map_err(|err| NodeError::Other(err.to_string()))?;
+        self.report_conflicting_block_signature(&block_hash, node_id.clone(), sig)
+            .await?;
         let set = self
             .state_driver
             .dag
@@ -104,6 +169,40 @@ impl NodeRuntime {
         Ok(cert)
     }
 
+    /// Produces and gossips slashing evidence if the harvester already signed
+    /// a different convergence block in the same round
+    async fn report_conflicting_block_signature(
+        &mut self,
+        block_hash: &str,
+        node_id: NodeId,
+        sig: Signature,
+    ) -> Result<()> {
+        let header = match self
+            .state_driver
+            .dag
+            .get_pending_convergence_block_mut(&block_hash.to_string())
+        {
+            Some(block) if block.header.hash() == block_hash => block.header.clone(),
+            _ => return Ok(()),
+        };
+
+        if let Some(evidence) = self.consensus_driver.record_convergence_block_signature(
+            node_id.clone(),
+            &header,
+            sig,
+        )? {
+            self.send_event_to_network(Event::SlashingEvidenceCreated(evidence))
+                .await?;
+
+            return Err(NodeError::Other(format!(
+                "harvester {node_id} signed conflicting convergence blocks in round {}",
+                header.round
+            )));
+        }
+
+        Ok(())
+    }
+
     pub fn form_convergence_certificate(
         &mut self,
         block_hash: String,
@@ -259,6 +358,11 @@ impl NodeRuntime {
     }
 
     pub async fn handle_vote_received(&mut self, vote: Vote) -> Result<()> {
+        if let Some(evidence) = self.consensus_driver.report_double_vote(&vote)? {
+            self.send_event_to_network(Event::SlashingEvidenceCreated(evidence))
+                .await?;
+        }
+
         self.consensus_driver.handle_vote_received(vote).await
     }
 
@@ -302,6 +406,82 @@ impl NodeRuntime {
             )
     }
 
+    /// Starts the DKG round of the quorum the node was assigned to
+    pub async fn start_dkg_round(&mut self) -> Result<()> {
+        let output = self.consensus_driver.start_dkg_round()?;
+
+        self.dispatch_dkg_output(output).await
+    }
+
+    /// Multicasts the DKG messages produced by the consensus module to the
+    /// rest of the quorum, schedules the timeout of newly started rounds and
+    /// hands completed key sets over to the quorum module
+    pub async fn dispatch_dkg_output(&mut self, output: DkgOutput) -> Result<()> {
+        let node_id = self.config.id.clone();
+
+        if let Some(announcement) = output.public_key {
+            self.send_event_to_network(Event::DkgPublicKeyAnnounced(announcement))
+                .await?;
+        }
+
+        if let Some(part) = output.part {
+            self.send_event_to_network(Event::PartCommitmentCreated(node_id.clone(), part))
+                .await?;
+        }
+
+        for (sender_id, ack) in output.acks {
+            self.send_event_to_network(Event::PartCommitmentAcknowledged {
+                node_id: sender_id,
+                sender_id: node_id.clone(),
+                ack,
+            })
+            .await?;
+        }
+
+        if let Some(part) = output.reshare_part {
+            self.send_event_to_network(Event::ResharePartCreated(node_id.clone(), part))
+                .await?;
+        }
+
+        if let Some(vote) = output.timeout_vote {
+            self.send_event_to_network(Event::DkgTimeoutVoteCast(vote))
+                .await?;
+        }
+
+        if let Some(round_id) = output.started_round {
+            self.schedule_dkg_round_timeout(round_id);
+        }
+
+        if let Some(public_key_set) = output.public_key_set {
+            self.send_event_to_self(Event::QuorumKeySetGenerated {
+                public_key_set,
+                members: self.consensus_driver.dkg_members(),
+                excluded: output.excluded.into_iter().collect(),
+            })
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    fn schedule_dkg_round_timeout(&self, round_id: DkgRoundId) {
+        let events_tx = self.events_tx.clone();
+        let timeout = self.config.dkg_timeout;
+
+        tokio::spawn(async move {
+            tokio::time::sleep(timeout).await;
+
+            let message = EventMessage::new(
+                Some(RUNTIME_TOPIC_STR.into()),
+                Event::DkgRoundTimedOut(round_id),
+            );
+
+            if let Err(err) = events_tx.send(message).await {
+                telemetry::error!("failed to notify timeout of DKG round {round_id}: {err}");
+            }
+        });
+    }
+
     pub async fn handle_convergence_block_precheck_requested<
         R: Resolver<Proposal = ProposalBlock>,
     >(
@@ -438,4 +618,16 @@ impl NodeRuntime {
 
         self.state_driver.insert_account(address, account)
     }
+
+    pub fn handle_program_deploy_requested(&mut self, program_bytes: ProgramBytes) -> Result<()> {
+        let program = bincode::deserialize(&program_bytes).map_err(|err| {
+            NodeError::Other(format!("unable to deserialize program bytes: {err}"))
+        })?;
+
+        let content_id = self.state_driver.insert_program(program)?;
+
+        telemetry::info!("registered program {content_id}");
+
+        Ok(())
+    }
 }
@@ -23,7 +23,7 @@ mod tests {
     use primitives::{generate_account_keypair, Address, NodeId, NodeType, QuorumKind};
     use storage::storage_utils::remove_vrrb_data_dir;
     use vrrb_core::account::{Account, AccountField};
-    use vrrb_core::transactions::Transaction;
+    use vrrb_core::transactions::{Transaction, BASE_FEE};
 
     #[tokio::test]
     #[serial_test::serial]
@@ -201,19 +201,25 @@ mod tests {
         let (mut node_0, mut farmers, _harvesters, _miners) = setup_network(8).await;
 
         let (_, sender_public_key) = generate_account_keypair();
-        let sender_account = Account::new(sender_public_key.clone().into());
+        let mut sender_account = Account::new(sender_public_key.clone().into());
+        let _ = sender_account.update_field(AccountField::Credits(100000 + BASE_FEE));
         let sender_address = node_0.create_account(sender_public_key).unwrap();
+        let sender_account_bytes = bincode::serialize(&sender_account).unwrap();
 
         let (_, receiver_public_key) = generate_account_keypair();
         let receiver_address = node_0.create_account(receiver_public_key).unwrap();
 
         let txn = create_txn_from_accounts(
-            (sender_address, Some(sender_account)),
+            (sender_address.clone(), Some(sender_account)),
             receiver_address,
             vec![],
         );
 
         for (_node_id, farmer) in farmers.iter_mut() {
+            let _ = farmer.handle_create_account_requested(
+                sender_address.clone(),
+                sender_account_bytes.clone(),
+            );
             let _ = farmer.insert_txn_to_mempool(txn.clone());
             farmer
                 .validate_transaction_kind(
@@ -572,7 +578,7 @@ mod tests {
 
         let (_, sender_public_key) = generate_account_keypair();
         let mut sender_account = Account::new(sender_public_key.clone().into());
-        let update_field = AccountField::Credits(100000);
+        let update_field = AccountField::Credits(100000 + BASE_FEE);
         let _ = sender_account.update_field(update_field);
         let sender_address = node_0.create_account(sender_public_key).unwrap();
 
@@ -624,8 +630,7 @@ mod tests {
 
     #[tokio::test]
     #[serial_test::serial]
-    async fn farmer_node_runtime_can_form_invalid_vote_on_invalid_transaction_amount_greater_than_balance(
-    ) {
+    async fn farmer_node_runtime_rejects_transaction_amount_greater_than_balance() {
         let (events_tx, _rx) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
 
         let mut nodes = create_node_runtime_network(4, events_tx.clone()).await;
@@ -729,18 +734,14 @@ mod tests {
         );
 
         for farmer in farmer_nodes.iter_mut() {
-            let _ = farmer.insert_txn_to_mempool(txn.clone());
-            let (transaction_kind, validity) = farmer
+            assert!(farmer.insert_txn_to_mempool(txn.clone()).is_err());
+            assert!(farmer
                 .validate_transaction_kind(
                     txn.id(),
                     farmer.mempool_read_handle_factory().clone(),
                     farmer.state_store_read_handle_factory().clone(),
                 )
-                .unwrap();
-            assert!(!validity);
-            farmer
-                .cast_vote_on_transaction_kind(transaction_kind, validity)
-                .unwrap();
+                .is_err());
         }
     }
 
@@ -820,7 +821,7 @@ mod tests {
 
         let (_, sender_public_key) = generate_account_keypair();
         let mut sender_account = Account::new(sender_public_key.clone().into());
-        let update_field = AccountField::Credits(100000);
+        let update_field = AccountField::Credits(100000 + BASE_FEE);
         let _ = sender_account.update_field(update_field);
         let sender_address = node_0.create_account(sender_public_key).unwrap();
 
@@ -941,7 +942,7 @@ mod tests {
 
         let (_, sender_public_key) = generate_account_keypair();
         let mut sender_account = Account::new(sender_public_key.clone().into());
-        let update_field = AccountField::Credits(100000);
+        let update_field = AccountField::Credits(100000 + BASE_FEE);
         let _ = sender_account.update_field(update_field);
         let sender_address = node_0.create_account(sender_public_key).unwrap();
 
@@ -988,7 +989,7 @@ mod tests {
 
     #[tokio::test]
     #[serial_test::serial]
-    async fn farmer_node_runtime_can_form_invalid_vote_on_invalid_transaction_sender_missing() {
+    async fn farmer_node_runtime_rejects_transaction_sender_missing() {
         let (events_tx, _rx) = tokio::sync::mpsc::channel(DEFAULT_BUFFER);
 
         let mut nodes = create_node_runtime_network(4, events_tx.clone()).await;
@@ -1087,18 +1088,14 @@ mod tests {
         );
 
         for farmer in farmer_nodes.iter_mut() {
-            let _ = farmer.insert_txn_to_mempool(txn.clone());
-            let (transaction_kind, validity) = farmer
+            assert!(farmer.insert_txn_to_mempool(txn.clone()).is_err());
+            assert!(farmer
                 .validate_transaction_kind(
                     txn.id(),
                     farmer.mempool_read_handle_factory().clone(),
                     farmer.state_store_read_handle_factory().clone(),
                 )
-                .unwrap();
-            assert!(!validity);
-            farmer
-                .cast_vote_on_transaction_kind(transaction_kind, validity)
-                .unwrap();
+                .is_err());
         }
     }
 
@@ -1134,7 +1131,7 @@ mod tests {
         let ((mut sender_account, sender_address), receiver_address) =
             create_sender_receiver_addresses();
 
-        let update_field = AccountField::Credits(100000);
+        let update_field = AccountField::Credits(100000 + BASE_FEE);
         let _ = sender_account.update_field(update_field);
         let account_bytes = bincode::serialize(&sender_account.clone()).unwrap();
 
@@ -1,20 +1,24 @@
 use crate::{
     consensus::{ConsensusModule, ConsensusModuleConfig},
+    epoch_manager::EpochManager,
     result::{NodeError, Result},
     state_manager::{StateManager, StateManagerConfig},
 };
 
 use block::{
-    header::BlockHeader, Block, Certificate, ClaimHash, ConvergenceBlock, GenesisBlock,
-    GenesisReceiver, GenesisRewards, ProposalBlock, RefHash,
+    election::ClaimInclusionProof, header::BlockHeader, Block, Certificate, ClaimHash,
+    ConvergenceBlock, GenesisBlock, GenesisReceiver, GenesisRewards, ProposalBlock, RefHash,
 };
 use bulldag::graph::BullDag;
 use events::{Event, EventMessage, EventPublisher, Vote};
 use mempool::{LeftRightMempool, MempoolReadHandleFactory, TxnRecord};
-use miner::{Miner, MinerConfig};
+use miner::{
+    conflict_strategy::{ConflictResolutionRegistry, ConflictResolutionStrategy},
+    Miner, MinerConfig,
+};
 use primitives::{
-    Address, Epoch, NodeId, NodeType, PublicKey, QuorumKind, Round, Signature, NETWORK_TOPIC_STR,
-    RUNTIME_TOPIC_STR,
+    Address, ChainParameters, ConflictResolutionKind, Epoch, NodeId, NodeType, PublicKey,
+    QuorumKind, Round, Signature, JSON_RPC_API_TOPIC_STR, NETWORK_TOPIC_STR, RUNTIME_TOPIC_STR,
 };
 use ritelinked::LinkedHashMap;
 use secp256k1::{hashes::Hash, Message};
@@ -23,7 +27,10 @@ use std::{
     collections::HashMap,
     sync::{Arc, RwLock},
 };
-use storage::vrrbdb::{StateStoreReadHandleFactory, VrrbDbConfig, VrrbDbReadHandle};
+use storage::vrrbdb::{
+    PrunerHandle, PrunerStats, StatePruner, StateStoreReadHandleFactory, VrrbDb, VrrbDbConfig,
+    VrrbDbReadHandle,
+};
 use theater::{ActorId, ActorState};
 use tokio::task::JoinHandle;
 use utils::payload::digest_data_to_bytes;
@@ -49,6 +56,9 @@ pub struct NodeRuntime {
     pub mining_driver: Miner,
     pub claim: Claim,
     pub pending_quorum: Option<InaugaratedMembers>,
+    /// Background pruner of stale state, `None` on archive nodes
+    pruner: Option<Arc<PrunerHandle>>,
+    pub(crate) epoch_manager: EpochManager,
 }
 
 impl NodeRuntime {
@@ -70,7 +80,7 @@ impl NodeRuntime {
                 .to_vec(),
         )?;
 
-        let claim = Claim::new(
+        let mut claim = Claim::new(
             miner_public_key,
             Address::new(miner_public_key),
             config.public_ip_address,
@@ -79,34 +89,84 @@ impl NodeRuntime {
         )
         .map_err(NodeError::from)?;
 
+        claim
+            .set_commission_rate(config.commission_rate)
+            .map_err(|err| NodeError::Other(err.to_string()))?;
+
         let mut vrrbdb_config = VrrbDbConfig::default();
 
         if config.db_path() != &vrrbdb_config.path {
             vrrbdb_config.with_path(config.db_path().to_path_buf());
         }
 
-        let database = storage::vrrbdb::VrrbDb::new(vrrbdb_config);
-        let mempool = LeftRightMempool::new();
+        vrrbdb_config.with_unbonding_epochs(config.unbonding_epochs);
+
+        let database = storage::vrrbdb::VrrbDb::new(vrrbdb_config)?;
+
+        config
+            .pruning_config
+            .validate()
+            .map_err(|err| NodeError::ConfigError(err.to_string()))?;
+
+        let pruner = if config.pruning_config.is_archive() {
+            None
+        } else {
+            let pruner = StatePruner::new(
+                &database,
+                config.pruning_config.retained_versions,
+                config.pruning_config.batch_size,
+            )?;
+
+            Some(Arc::new(pruner.spawn(config.pruning_config.interval)))
+        };
+
+        config
+            .mempool_config
+            .validate()
+            .map_err(|err| NodeError::ConfigError(err.to_string()))?;
 
-        let state_driver = StateManager::new(StateManagerConfig {
+        let transactions = database.transaction_store_factory().handle();
+        let mempool = LeftRightMempool::with_journal(
+            config.mempool_config.clone(),
+            &config.data_dir().join("mempool"),
+            |digest| transactions.contains(digest),
+        )?;
+
+        let mut state_driver = StateManager::new(StateManagerConfig {
             database: database.clone(),
             mempool,
             dag: dag.clone(),
             claim: claim.clone(),
         });
 
+        let restored_blocks = state_driver.rebuild_dag()?;
+        if restored_blocks > 0 {
+            telemetry::info!("restored {restored_blocks} blocks into the DAG from the block store");
+        }
+
         let (_, miner_secret_key) = config.keypair.get_secret_keys();
         let (_, miner_public_key) = config.keypair.get_public_keys();
 
+        let chain_parameters = state_driver.chain_parameters().unwrap_or_default();
+        let conflict_resolution =
+            conflict_resolution_strategy(&database, chain_parameters.conflict_resolution)?;
+
         let miner_config = MinerConfig {
             secret_key: *miner_secret_key,
             public_key: *miner_public_key,
             ip_address: config.public_ip_address,
             dag: dag.clone(),
             claim: claim.clone(),
+            conflict_resolution,
+            fee_burn_rate: config.fee_burn_rate,
         };
 
-        let miner = miner::Miner::new(miner_config, config.id.clone()).map_err(NodeError::from)?;
+        let mut miner =
+            miner::Miner::new(miner_config, config.id.clone()).map_err(NodeError::from)?;
+        miner.set_epoch_length(chain_parameters.epoch_length);
+
+        let epoch_manager =
+            EpochManager::new(chain_parameters.epoch_length).resume(database.epoch_in_progress()?);
         let consensus_driver = ConsensusModule::new(
             ConsensusModuleConfig {
                 keypair: config.keypair.clone(),
@@ -130,9 +190,39 @@ impl NodeRuntime {
             mining_driver: miner,
             claim,
             pending_quorum: None,
+            pruner,
+            epoch_manager,
         })
     }
 
+    /// Progress of the background state pruner, if pruning is enabled
+    pub fn pruner_stats(&self) -> Option<PrunerStats> {
+        self.pruner.as_ref().map(|pruner| pruner.stats())
+    }
+
+    /// Switches the miner to the conflict resolution strategy and the epoch
+    /// length set by `chain_parameters`, once the genesis block carrying them
+    /// is applied
+    pub fn apply_chain_parameters(&mut self, chain_parameters: ChainParameters) -> Result<()> {
+        let strategy = conflict_resolution_strategy(
+            &self.state_driver.database,
+            chain_parameters.conflict_resolution,
+        )?;
+        self.mining_driver
+            .set_conflict_resolution_strategy(strategy);
+        self.mining_driver
+            .set_epoch_length(chain_parameters.epoch_length);
+        self.epoch_manager
+            .set_epoch_length(chain_parameters.epoch_length);
+
+        Ok(())
+    }
+
+    /// Epoch of the convergence blocks applied so far
+    pub fn current_epoch(&self) -> Epoch {
+        self.epoch_manager.current_epoch()
+    }
+
     pub fn certified_convergence_block_exists_within_dag(&self, block_hash: String) -> bool {
         if let Ok(guard) = self.state_driver.dag.read() {
             if let Some(vertex) = guard.get_vertex(block_hash) {
@@ -219,6 +309,11 @@ impl NodeRuntime {
         self.send_event(RUNTIME_TOPIC_STR, event).await
     }
 
+    /// Sends an EventMessage to the JSON-RPC API's event channel so it can notify its subscribers
+    pub async fn send_event_to_rpc(&mut self, event: Event) -> Result<()> {
+        self.send_event(JSON_RPC_API_TOPIC_STR, event).await
+    }
+
     async fn send_event(&mut self, topic: &str, event: Event) -> Result<()> {
         let message = EventMessage::new(Some(topic.into()), event);
 
@@ -282,6 +377,9 @@ impl NodeRuntime {
             hex::encode(claim_list_hash),
         );
 
+        // The genesis block carries the parameters the chain was started with
+        let chain_parameters = self.state_driver.chain_parameters().unwrap_or_default();
+
         let block_header = header.clone();
         let block_hash = digest_data_to_bytes(&(
             header.ref_hashes,
@@ -296,6 +394,7 @@ impl NodeRuntime {
             header.block_reward,
             header.next_block_reward,
             header.miner_signature,
+            chain_parameters,
         ));
 
         let mut claims = LinkedHashMap::new();
@@ -305,6 +404,7 @@ impl NodeRuntime {
             header: block_header,
             genesis_rewards,
             claims,
+            chain_parameters,
             hash: hex::encode(block_hash),
             certificate: None,
         };
@@ -418,6 +518,12 @@ impl NodeRuntime {
 
     pub fn mine_convergence_block(&mut self) -> Result<ConvergenceBlock> {
         self.has_required_node_type(NodeType::Miner, "mine convergence block")?;
+        self.mining_driver
+            .set_state_roots(self.state_driver.state_roots()?);
+        self.mining_driver
+            .set_claim_inclusion_proof(self.claim_inclusion_proof()?);
+        self.mining_driver
+            .set_slashing_evidence(self.consensus_driver.pending_slashing_evidence());
         self.mining_driver
             .mine_convergence_block()
             .ok_or(NodeError::Other(
@@ -425,6 +531,21 @@ impl NodeRuntime {
             ))
     }
 
+    /// Proves the miner's claim is part of the claims trie the miner election
+    /// of the next block runs against
+    fn claim_inclusion_proof(&self) -> Result<ClaimInclusionProof> {
+        let proof = self
+            .state_driver
+            .read_handle()
+            .get_claim_proof(&self.mining_driver.claim.hash)?;
+
+        Ok(ClaimInclusionProof {
+            proof: bincode::serialize(&proof.proof)
+                .map_err(|err| NodeError::Other(err.to_string()))?,
+            value: proof.value.unwrap_or_default(),
+        })
+    }
+
     pub fn certify_convergence_block(&mut self, block: ConvergenceBlock) -> Result<()> {
         self.consensus_driver.is_harvester()?;
         let last_block_header =
@@ -571,3 +692,14 @@ impl NodeRuntime {
             .cast_vote_on_transaction_kind(transaction, validity)
     }
 }
+
+/// Looks the strategy of `kind` up among the built-in conflict resolution
+/// strategies, weighing stake by the claims stored in `database`
+fn conflict_resolution_strategy(
+    database: &VrrbDb,
+    kind: ConflictResolutionKind,
+) -> Result<Arc<dyn ConflictResolutionStrategy>> {
+    ConflictResolutionRegistry::with_builtin_strategies(database.claim_store_factory())
+        .get(kind)
+        .ok_or_else(|| NodeError::Other(format!("no {kind} conflict resolution strategy")))
+}
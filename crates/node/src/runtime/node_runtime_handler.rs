@@ -60,6 +60,7 @@ impl Handler<EventMessage> for NodeRuntime {
             }
             Event::QuorumMembershipAssigmentsCreated(assignments) => {
                 self.handle_quorum_membership_assigments_created(assignments)?;
+                self.start_dkg_round().await?;
 
                 if let Some(quorum_kind) = &self.consensus_driver.quorum_kind {
                     if *quorum_kind == QuorumKind::Miner && self.config.node_type == NodeType::Miner
@@ -99,6 +100,65 @@ impl Handler<EventMessage> for NodeRuntime {
             }
             Event::QuorumMembershipAssigmentCreated(assigned_membership) => {
                 self.handle_quorum_membership_assigment_created(assigned_membership.clone())?;
+                self.start_dkg_round().await?;
+            }
+            Event::DkgPublicKeyAnnounced(announcement) => {
+                let output = self
+                    .consensus_driver
+                    .handle_dkg_public_key_announced(announcement)?;
+
+                self.dispatch_dkg_output(output).await?;
+            }
+            Event::PartCommitmentCreated(node_id, part) => {
+                let output = self
+                    .consensus_driver
+                    .handle_part_commitment_created(node_id, part)?;
+
+                self.dispatch_dkg_output(output).await?;
+            }
+            Event::PartCommitmentAcknowledged {
+                node_id,
+                sender_id,
+                ack,
+            } => {
+                // NOTE: `sender_id` acknowledged the part of `node_id`
+                let output = self
+                    .consensus_driver
+                    .handle_part_commitment_acknowledged(sender_id, node_id, ack)?;
+
+                self.dispatch_dkg_output(output).await?;
+            }
+            Event::ResharePartCreated(node_id, part) => {
+                let output = self
+                    .consensus_driver
+                    .handle_reshare_part_created(node_id, part)?;
+
+                self.dispatch_dkg_output(output).await?;
+            }
+            Event::DkgRoundTimedOut(round_id) => {
+                let output = self.consensus_driver.handle_dkg_round_timed_out(round_id)?;
+
+                self.dispatch_dkg_output(output).await?;
+            }
+            Event::DkgTimeoutVoteCast(vote) => {
+                let output = self.consensus_driver.handle_dkg_timeout_vote_cast(vote)?;
+
+                self.dispatch_dkg_output(output).await?;
+            }
+            Event::QuorumKeySetGenerated {
+                public_key_set,
+                members,
+                excluded,
+            } => {
+                telemetry::info!(
+                    "quorum key set generated for {} members, {} excluded",
+                    members.len(),
+                    excluded.len()
+                );
+
+                self.consensus_driver
+                    .quorum_driver
+                    .handle_quorum_key_set_generated(public_key_set, excluded);
             }
             Event::QuorumElectionStarted(header) => {
                 self.handle_quorum_election_started(header)
@@ -170,10 +230,20 @@ impl Handler<EventMessage> for NodeRuntime {
                     .send(Event::TxnAddedToMempool(txn_hash.clone()).into())
                     .await
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
+
+                self.send_event_to_rpc(Event::TxnAddedToMempool(txn_hash))
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
             }
 
             Event::TxnValidated(txn) => {
-                self.state_driver.handle_transaction_validated(txn).await?;
+                self.state_driver
+                    .handle_transaction_validated(txn.clone())
+                    .await?;
+
+                self.send_event_to_rpc(Event::TxnValidated(txn))
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
             }
             Event::CreateAccountRequested((address, account_bytes)) => {
                 // I think we can get rid of this, as we now add accounts
@@ -184,10 +254,14 @@ impl Handler<EventMessage> for NodeRuntime {
                 todo!()
                 // This can occur as a result of block application
             }
+            Event::ProgramDeployRequested(program_bytes) => {
+                self.handle_program_deploy_requested(program_bytes)?;
+            }
             Event::UpdateState(block) => {
                 if let Err(err) = self.state_driver.update_state(block.hash.clone()) {
                     telemetry::error!("error updating state: {}", err);
                 } else {
+                    self.consensus_driver.prune_slashing_evidence(&block);
                     self.events_tx
                         .send(Event::BuildProposalBlock(block).into())
                         .await
@@ -242,6 +316,11 @@ impl Handler<EventMessage> for NodeRuntime {
                     .handle_block_received(&mut block, self.consensus_driver.sig_engine.clone())
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
 
+                let convergence = match &block {
+                    Block::Convergence { block } => Some(block.clone()),
+                    _ => None,
+                };
+
                 let apply_result = self.handle_block_received(block)?;
 
                 telemetry::info!(
@@ -249,6 +328,22 @@ impl Handler<EventMessage> for NodeRuntime {
                     apply_result.state_root_hash_str()
                 );
 
+                if let Some(convergence) = convergence {
+                    let finished = self
+                        .handle_convergence_block_applied(&convergence)
+                        .map_err(|err| TheaterError::Other(err.to_string()))?;
+
+                    if finished.is_some() {
+                        self.send_event_to_self(Event::QuorumElectionStarted(convergence.header))
+                            .await
+                            .map_err(|err| TheaterError::Other(err.to_string()))?;
+                    }
+                }
+
+                self.send_event_to_rpc(next_event.clone())
+                    .await
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+
                 let em = EventMessage::new(Some(NETWORK_TOPIC_STR.into()), next_event);
 
                 self.events_tx
@@ -320,6 +415,11 @@ impl Handler<EventMessage> for NodeRuntime {
                     .await
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
             }
+            Event::SlashingEvidenceReceived(evidence) => {
+                self.consensus_driver
+                    .handle_slashing_evidence_received(evidence)
+                    .map_err(|err| TheaterError::Other(err.to_string()))?;
+            }
             Event::NoOp => {}
             _ => {}
         }
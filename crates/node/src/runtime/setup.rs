@@ -6,7 +6,7 @@ use telemetry::info;
 use vrrb_config::NodeConfig;
 
 use crate::{
-    api::setup_rpc_api_server,
+    api::{setup_http_api_server, setup_rpc_api_server},
     component::NodeRuntimeComponentConfig,
     indexer_module::setup_indexer_module,
     network::{NetworkModule, NetworkModuleComponentConfig},
@@ -31,6 +31,7 @@ pub async fn setup_runtime_components(
     let runtime_events_rx = router.subscribe(Some(RUNTIME_TOPIC_STR.into()))?;
     let network_events_rx = router.subscribe(Some(NETWORK_TOPIC_STR.into()))?;
     let jsonrpc_events_rx = router.subscribe(Some(JSON_RPC_API_TOPIC_STR.into()))?;
+    let http_api_events_rx = router.subscribe(Some(JSON_RPC_API_TOPIC_STR.into()))?;
     let indexer_events_rx = router.subscribe(None)?;
 
     let mut runtime_manager = RuntimeComponentManager::new();
@@ -95,6 +96,20 @@ pub async fn setup_runtime_components(
 
     runtime_manager.register_component("API".to_string(), jsonrpc_server_handle);
 
+    let (http_api_server_handle, resolved_http_api_addr) = setup_http_api_server(
+        &config,
+        events_tx.clone(),
+        state_read_handle.clone(),
+        mempool_read_handle_factory.clone(),
+        http_api_events_rx,
+    )?;
+
+    config.http_api_address = resolved_http_api_addr;
+
+    info!("HTTP API server address: {}", config.http_api_address);
+
+    runtime_manager.register_component("HTTP API".to_string(), http_api_server_handle);
+
     if config.enable_block_indexing {
         let _handle = setup_indexer_module(
             &config,
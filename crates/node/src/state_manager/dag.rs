@@ -95,6 +95,10 @@ impl DagModule {
         self.last_confirmed_block_header.clone()
     }
 
+    pub fn last_confirmed_block(&self) -> Option<Block> {
+        self.last_confirmed_block.clone()
+    }
+
     pub fn set_quorum_members(&mut self, quorum_members: QuorumMembers) {
         self.quorum_members = Some(quorum_members);
     }
@@ -237,6 +241,44 @@ impl DagModule {
         Ok(None)
     }
 
+    /// Appends previously validated blocks to the DAG without validating them
+    /// again, e.g. blocks loaded from the block store on restart. Blocks must
+    /// come after the blocks they reference.
+    pub fn rebuild(&mut self, blocks: Vec<Block>) -> GraphResult<()> {
+        for block in blocks {
+            match block {
+                Block::Genesis { block: ref genesis } => {
+                    self.write_genesis(&block.clone().into())?;
+                    self.last_confirmed_block_header = Some(genesis.header.clone());
+                    self.last_confirmed_block = Some(block.clone());
+                },
+                Block::Proposal {
+                    block: ref proposal,
+                } => {
+                    let ref_block = self.get_reference_block(&proposal.ref_block)?;
+                    let vtx: Vertex<Block, String> = block.clone().into();
+                    self.write_edge((&ref_block, &vtx))?;
+                },
+                Block::Convergence {
+                    block: ref convergence,
+                } => {
+                    let vtx: Vertex<Block, String> = block.clone().into();
+                    let edges: Edges = self
+                        .get_convergence_reference_blocks(convergence)
+                        .into_iter()
+                        .map(|ref_block| (ref_block, vtx.clone()))
+                        .collect();
+                    self.extend_edges(edges)?;
+
+                    self.last_confirmed_block_header = Some(convergence.header.clone());
+                    self.last_confirmed_block = Some(block.clone());
+                },
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn get_convergence_reference_blocks(
         &self,
         convergence: &ConvergenceBlock,
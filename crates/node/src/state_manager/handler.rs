@@ -42,11 +42,8 @@ impl Handler<EventMessage> for StateManager {
             Event::NewTxnCreated(txn) => {
                 info!("Storing transaction in mempool for validation");
 
-                let txn_hash = txn.id();
-
-                let _mempool_size = self
-                    .mempool
-                    .insert(txn)
+                let txn_hash = self
+                    .insert_txn_to_mempool(txn)
                     .map_err(|err| TheaterError::Other(err.to_string()))?;
 
                 self.events_tx
@@ -4,7 +4,8 @@ use std::{
 };
 
 use block::{
-    Block, BlockHash, Certificate, ClaimHash, ConvergenceBlock, GenesisBlock, ProposalBlock,
+    header::StateRoots, Block, BlockHash, Certificate, ClaimHash, ConvergenceBlock, GenesisBlock,
+    ProposalBlock,
 };
 use bulldag::{
     graph::{BullDag, GraphError},
@@ -13,9 +14,9 @@ use bulldag::{
 use ethereum_types::U256;
 use events::Event;
 use mempool::{LeftRightMempool, MempoolReadHandleFactory};
-use primitives::{Address, NodeId, Round};
+use primitives::{Address, ChainParameters, NodeId, Round};
 use signer::engine::{QuorumMembers, SignerEngine};
-use storage::vrrbdb::{types::*, ApplyBlockResult};
+use storage::vrrbdb::{types::*, ApplyBlockResult, ProgramRecord};
 use storage::{
     storage_utils::StorageError,
     vrrbdb::{Claims, VrrbDb, VrrbDbReadHandle},
@@ -25,7 +26,7 @@ use theater::{ActorId, ActorState};
 use vrrb_core::{account::Account, claim::Claim};
 use vrrb_core::{
     account::UpdateArgs,
-    transactions::{Transaction, TransactionDigest, TransactionKind},
+    transactions::{Transaction, TransactionDigest, TransactionKind, TxAmount, TxNonce},
 };
 
 use crate::{data_store::DataStore, state_reader::StateReader};
@@ -36,6 +37,10 @@ use super::{
     DagModule, GraphResult,
 };
 
+/// Number of senders whose next nonce the state manager remembers for the
+/// mempool before it starts over
+const MAX_CACHED_MEMPOOL_NONCES: usize = 10_000;
+
 /// Provides a convenient configuration struct for building a
 /// StateManager
 #[derive(Debug, Clone)]
@@ -53,6 +58,11 @@ pub struct StateManager {
     pub(crate) dag: DagModule,
     pub(crate) database: VrrbDb,
     pub(crate) mempool: LeftRightMempool,
+    /// Next nonce of the senders the mempool was told about, kept so that
+    /// inserting a transaction doesn't read its sender's account every time
+    pub(crate) mempool_nonces: HashMap<Address, TxNonce>,
+    /// Parameters of the chain, known once a genesis block was applied
+    pub(crate) chain_parameters: Option<ChainParameters>,
 }
 
 impl StateManager {
@@ -65,14 +75,22 @@ impl StateManager {
             _status: ActorState::Stopped,
             dag: dag_module,
             mempool: config.mempool,
+            mempool_nonces: HashMap::new(),
+            chain_parameters: None,
         }
     }
 
+    /// Parameters set by the genesis block this node applied, if any
+    pub fn chain_parameters(&self) -> Option<ChainParameters> {
+        self.chain_parameters
+    }
+
     pub fn append_genesis(
         &mut self,
         genesis_block: &GenesisBlock,
     ) -> GraphResult<ApplyBlockResult> {
         self.dag.append_genesis(genesis_block)?;
+        self.persist_block(genesis_block.clone().into())?;
         self.apply_block(Block::Genesis {
             block: genesis_block.to_owned(),
         })
@@ -83,8 +101,13 @@ impl StateManager {
         &mut self,
         convergence: &ConvergenceBlock,
     ) -> GraphResult<ApplyBlockResult> {
+        self.database
+            .verify_state_roots(&convergence.header)
+            .map_err(|err| GraphError::Other(err.to_string()))?;
+
         let opt = self.dag.append_convergence(convergence)?;
         if let Some(cblock) = opt {
+            self.persist_block(cblock.clone().into())?;
             let ref_blocks = self.dag.get_convergence_reference_blocks(convergence);
             let proposals: Vec<ProposalBlock> = ref_blocks
                 .iter()
@@ -112,6 +135,14 @@ impl StateManager {
             .database
             .apply_convergence_block(convergence, proposals)
             .map_err(|err| GraphError::Other(err.to_string()))?;
+
+        let senders = proposals
+            .iter()
+            .flat_map(|block| block.txns.iter().map(|(_, txn)| txn.sender_address()))
+            .collect::<HashSet<Address>>();
+
+        self.refresh_mempool_nonces(senders);
+
         Ok(res)
     }
 
@@ -119,8 +150,15 @@ impl StateManager {
         &mut self,
         certificate: &Certificate,
     ) -> GraphResult<Option<ConvergenceBlock>> {
-        self.dag
-            .append_certificate_to_convergence_block(certificate)
+        let block = self
+            .dag
+            .append_certificate_to_convergence_block(certificate)?;
+
+        if let Some(block) = &block {
+            self.persist_block(block.clone().into())?;
+        }
+
+        Ok(block)
     }
 
     pub fn append_certificate_to_genesis_block(
@@ -128,12 +166,62 @@ impl StateManager {
         block_hash: &str,
         certificate: &Certificate,
     ) -> GraphResult<Option<GenesisBlock>> {
+        let block = self
+            .dag
+            .append_certificate_to_genesis_block(block_hash, certificate)?;
+
+        if let Some(block) = &block {
+            self.persist_block(block.clone().into())?;
+        }
+
+        Ok(block)
+    }
+
+    /// Appends a proposal block to the DAG, persisting it if it passed
+    /// validation
+    pub fn append_proposal(
+        &mut self,
+        proposal: &ProposalBlock,
+        sig_engine: SignerEngine,
+    ) -> GraphResult<()> {
+        self.dag.append_proposal(proposal, sig_engine)?;
+
+        let appended = self
+            .dag
+            .dag()
+            .read()
+            .map_err(|err| GraphError::Other(err.to_string()))?
+            .get_vertex(proposal.hash.clone())
+            .is_some();
+
+        if appended {
+            self.persist_block(proposal.clone().into())?;
+        }
+
+        Ok(())
+    }
+
+    /// Rebuilds the DAG from the blocks persisted in the block store,
+    /// returning the number of blocks appended
+    pub fn rebuild_dag(&mut self) -> Result<usize> {
+        let blocks = self.database.blocks_in_dag_order()?;
+        let count = blocks.len();
+
+        if let Some(Block::Genesis { block }) = blocks.first() {
+            self.chain_parameters = Some(block.chain_parameters);
+        }
+
         self.dag
-            .append_certificate_to_genesis_block(block_hash, certificate)
+            .rebuild(blocks)
+            .map_err(|err| NodeError::Other(format!("unable to rebuild DAG: {err:?}")))?;
+
+        Ok(count)
     }
 
-    pub fn export_state(&self) {
-        self.database.export_state();
+    fn persist_block(&mut self, block: Block) -> GraphResult<()> {
+        self.database
+            .insert_block(&block)
+            .map_err(|err| GraphError::Other(format!("unable to persist block: {err}")))
     }
 
     /// Produces the read handle for the VrrbDb instance in this
@@ -165,6 +253,12 @@ impl StateManager {
         Ok(root_hash_hex)
     }
 
+    /// Returns the roots of the state, transaction and claim tries the next
+    /// convergence block has to commit to
+    pub fn state_roots(&self) -> Result<StateRoots> {
+        Ok(self.database.state_roots()?)
+    }
+
     //TODO: Move to test configured trait
     pub fn write_vertex(&mut self, vertex: &Vertex<Block, BlockHash>) -> Result<()> {
         self.dag
@@ -209,7 +303,7 @@ impl StateManager {
             let proposals = round_blocks.proposals.clone();
 
             self.update_txn_trie(&proposals);
-            self.update_claim_store(&proposals);
+            self.update_claim_store(&proposals)?;
 
             return Ok(());
         }
@@ -239,7 +333,7 @@ impl StateManager {
     /// Provided a reference to an array of `ProposalBlock`s
     /// making up the current round's `ConvergenceBlock`, writes
     /// all the new, conflict resolved, claims into the `ClaimStore`
-    fn update_claim_store(&mut self, proposals: &[ProposalBlock]) {
+    fn update_claim_store(&mut self, proposals: &[ProposalBlock]) -> Result<()> {
         let consolidated: HashSet<(U256, Option<Claim>)> = {
             let nested: Vec<HashSet<(U256, Option<Claim>)>> = {
                 proposals
@@ -258,7 +352,9 @@ impl StateManager {
         };
 
         self.database
-            .extend_claims(consolidated.into_iter().collect());
+            .extend_claims(consolidated.into_iter().collect())?;
+
+        Ok(())
     }
 
     /// Provides a method to convert a `RoundBlocks` wrapper struct into
@@ -294,6 +390,14 @@ impl StateManager {
             .map_err(|err| NodeError::Other(err.to_string()))
     }
 
+    /// Registers a program in the `VrrbDb` `ProgramStore` and returns the
+    /// content ID it can be called by
+    pub fn insert_program(&mut self, program: ProgramRecord) -> Result<String> {
+        self.database
+            .insert_program(&program)
+            .map_err(|err| NodeError::Other(err.to_string()))
+    }
+
     pub fn extend_accounts(&mut self, accounts: Vec<(Address, Option<Account>)>) -> Result<()> {
         self.database.extend_accounts(accounts);
         Ok(())
@@ -370,17 +474,31 @@ impl StateManager {
                     let err_note = format!("Encountered GraphError: {e:?}");
                     return Err(NodeError::Other(err_note));
                 };
+
+                if let Err(e) = self.persist_block(block.clone().into()) {
+                    return Err(NodeError::Other(format!("{e:?}")));
+                }
             },
             Block::Proposal { ref mut block } => {
-                if let Err(e) = self.dag.append_proposal(block, sig_engine.clone()) {
+                if let Err(e) = self.append_proposal(block, sig_engine.clone()) {
                     let err_note = format!("Encountered GraphError: {e:?}");
                     return Err(NodeError::Other(err_note));
                 }
             },
             Block::Convergence { ref mut block } => {
-                if let Err(e) = self.dag.append_convergence(block) {
-                    let err_note = format!("Encountered GraphError: {e:?}");
-                    return Err(NodeError::Other(err_note));
+                self.database.verify_state_roots(&block.header)?;
+
+                match self.dag.append_convergence(block) {
+                    Ok(Some(confirmed)) => {
+                        if let Err(e) = self.persist_block(confirmed.into()) {
+                            return Err(NodeError::Other(format!("{e:?}")));
+                        }
+                    },
+                    Ok(None) => {},
+                    Err(e) => {
+                        let err_note = format!("Encountered GraphError: {e:?}");
+                        return Err(NodeError::Other(err_note));
+                    },
                 }
 
                 if block.certificate.is_none() {
@@ -400,31 +518,118 @@ impl StateManager {
     }
 
     pub fn apply_block(&mut self, block: Block) -> Result<ApplyBlockResult> {
+        let chain_parameters = match &block {
+            Block::Genesis { block } => Some(block.chain_parameters),
+            _ => None,
+        };
+
         let apply_result = self
             .database
             .apply_block(block)
             .map_err(|err| NodeError::Other(err.to_string()))?;
 
+        if chain_parameters.is_some() {
+            self.chain_parameters = chain_parameters;
+        }
+
         Ok(apply_result)
     }
 
+    /// Adds a transaction to the mempool, provided its sender can pay for it
+    /// along with the rest of its pending transactions
     pub fn insert_txn_to_mempool(&mut self, txn: TransactionKind) -> Result<TransactionDigest> {
         let txn_hash = txn.id();
+        let sender = txn.sender_address();
+        let balance = self.spendable_balance(&sender);
 
         self.mempool
-            .insert(txn)
+            .insert_funded(txn, balance)
             .map_err(|err| NodeError::Other(err.to_string()))?;
 
+        self.sync_mempool_nonces([sender]);
+
         Ok(txn_hash)
     }
 
+    /// Adds a batch of transactions to the mempool, skipping the ones their
+    /// senders cannot pay for
     pub fn extend_mempool(&mut self, txns: &[TransactionKind]) -> Result<()> {
         let txn_batch = txns.iter().map(|txn| txn.to_owned()).collect();
-        self.mempool
-            .extend(txn_batch)
-            .map_err(|err| NodeError::Other(err.to_string()))?;
+        let senders = txns
+            .iter()
+            .map(|txn| txn.sender_address())
+            .collect::<HashSet<Address>>();
+        let balances = senders
+            .iter()
+            .map(|sender| (sender.clone(), self.spendable_balance(sender)))
+            .collect::<HashMap<Address, TxAmount>>();
 
-        Ok(())
+        let res = self
+            .mempool
+            .extend_funded(txn_batch, &balances)
+            .map_err(|err| NodeError::Other(err.to_string()));
+
+        self.sync_mempool_nonces(senders);
+
+        res
+    }
+
+    /// Returns what `address` can spend, nothing if it has no account
+    fn spendable_balance(&self, address: &Address) -> TxAmount {
+        self.get_account(address)
+            .map(|account| account.credits().saturating_sub(account.debits()))
+            .unwrap_or_default()
+    }
+
+    /// Lets the mempool know the next nonce expected from each of the given
+    /// senders, so it can tell ready transactions apart from the ones waiting
+    /// for a nonce gap to close. Transactions are expected to carry a nonce
+    /// one higher than their sender's account nonce.
+    ///
+    /// Senders the mempool already knows are skipped, and accounts are only
+    /// read for senders whose next nonce isn't cached yet.
+    fn sync_mempool_nonces(&mut self, senders: impl IntoIterator<Item = Address>) {
+        for sender in senders {
+            if self.mempool.sender_next_nonce(&sender).is_some() {
+                continue;
+            }
+
+            let next_nonce = match self.mempool_nonces.get(&sender) {
+                Some(next_nonce) => *next_nonce,
+                None => match self.get_account(&sender) {
+                    Ok(account) => self.cache_mempool_nonce(&sender, account.nonce() + 1),
+                    Err(_) => continue,
+                },
+            };
+
+            if let Err(err) = self.mempool.update_sender_nonce(&sender, next_nonce) {
+                telemetry::error!("error updating mempool nonce of {sender}: {err}");
+            }
+        }
+    }
+
+    /// Reads the accounts of the given senders after a block changed their
+    /// nonces and passes their next nonces on to the mempool
+    fn refresh_mempool_nonces(&mut self, senders: impl IntoIterator<Item = Address>) {
+        for sender in senders {
+            if let Ok(account) = self.get_account(&sender) {
+                let next_nonce = self.cache_mempool_nonce(&sender, account.nonce() + 1);
+                if let Err(err) = self.mempool.update_sender_nonce(&sender, next_nonce) {
+                    telemetry::error!("error updating mempool nonce of {sender}: {err}");
+                }
+            }
+        }
+    }
+
+    fn cache_mempool_nonce(&mut self, sender: &Address, next_nonce: TxNonce) -> TxNonce {
+        if self.mempool_nonces.len() >= MAX_CACHED_MEMPOOL_NONCES
+            && !self.mempool_nonces.contains_key(sender)
+        {
+            self.mempool_nonces.clear();
+        }
+
+        self.mempool_nonces.insert(sender.clone(), next_nonce);
+        next_nonce
     }
 
     /// Return the number of key-value pairs in the map.
@@ -43,7 +43,7 @@ mod tests {
 
         let db_config = VrrbDbConfig::default();
 
-        let db = VrrbDb::new(db_config);
+        let db = VrrbDb::new(db_config).unwrap();
         let mempool = LeftRightMempool::default();
 
         let dag: Arc<RwLock<BullDag<Block, String>>> = Arc::new(RwLock::new(BullDag::new()));
@@ -73,7 +73,7 @@ mod tests {
     #[serial]
     async fn vrrbdb_should_update_with_new_block() {
         let db_config = VrrbDbConfig::default().with_path(std::env::temp_dir().join("db"));
-        let db = VrrbDb::new(db_config);
+        let db = VrrbDb::new(db_config).unwrap();
         let mempool = LeftRightMempool::default();
 
         let accounts: Vec<(Address, Option<Account>)> = produce_accounts(5);
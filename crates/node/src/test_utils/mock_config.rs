@@ -39,8 +39,8 @@ use vrrb_core::{
 use vrrb_rpc::rpc::{api::RpcApiClient, client::create_client};
 
 pub fn create_mock_full_node_config() -> NodeConfig {
-    let data_dir = env::temp_dir();
     let id = Uuid::new_v4().simple().to_string();
+    let data_dir = env::temp_dir().join(&id);
 
     let temp_dir_path = std::env::temp_dir();
     let db_path = temp_dir_path.join(vrrb_core::helpers::generate_random_string());
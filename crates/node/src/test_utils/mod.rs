@@ -720,11 +720,16 @@ pub fn dummy_convergence_block() -> ConvergenceBlock {
             block_reward: Default::default(),
             next_block_reward: Default::default(),
             miner_signature: signature,
+            state_roots: Default::default(),
+            election_proof: Default::default(),
+            slashing_evidence_root: Default::default(),
         },
         txns: Default::default(),
         claims: Default::default(),
         hash: "dummy_convergence_block".into(),
         certificate: None,
+        slashing_evidence: vec![],
+        fees: Default::default(),
     }
 }
 
@@ -101,6 +101,7 @@ async fn harvester_nodes_form_certificate() {
         })
         .collect();
     let mut convergence_block = dummy_convergence_block();
+    convergence_block.header.state_roots = harvesters[0].state_driver.state_roots().unwrap();
     let mut chosen_harvester = harvesters.pop().unwrap();
     let _ = chosen_harvester
         .state_driver
@@ -155,6 +156,7 @@ async fn certificate_formed_includes_pending_quorum() {
         .collect();
 
     let mut convergence_block = dummy_convergence_block();
+    convergence_block.header.state_roots = harvesters[0].state_driver.state_roots().unwrap();
     let mut chosen_harvester = harvesters.pop().unwrap();
 
     let _ = chosen_harvester
@@ -244,6 +246,7 @@ async fn all_nodes_append_certificate_to_convergence_block() {
         })
         .collect();
     let mut convergence_block = dummy_convergence_block();
+    convergence_block.header.state_roots = harvesters[0].state_driver.state_roots().unwrap();
     harvesters.iter_mut().for_each(|node| {
         node.state_driver
             .handle_block_received(
@@ -340,6 +343,7 @@ async fn all_nodes_append_certified_convergence_block_to_dag() {
     let sig_engine = all_nodes[0].consensus_driver.sig_engine();
     let proposal_block = dummy_proposal_block(sig_engine);
     let mut convergence_block = dummy_convergence_block();
+    convergence_block.header.state_roots = harvesters[0].state_driver.state_roots().unwrap();
     convergence_block.header.ref_hashes = vec![proposal_block.hash.clone()];
     let pblock: Block = proposal_block.into();
     let vtx = pblock.into();
@@ -477,13 +481,22 @@ async fn all_nodes_update_state_upon_successfully_appending_certified_convergenc
         node.state_driver.write_vertex(&vtx).unwrap();
     });
 
+    harvesters
+        .iter_mut()
+        .chain(all_nodes.iter_mut())
+        .for_each(|node| {
+            node.state_driver
+                .insert_account(address1.clone(), account1.clone())
+                .unwrap();
+            node.state_driver
+                .insert_account(address2.clone(), account2.clone())
+                .unwrap();
+        });
+
+    // The convergence block is mined on top of the accounts
+    convergence_block.header.state_roots = harvesters[0].state_driver.state_roots().unwrap();
+
     harvesters.iter_mut().for_each(|node| {
-        node.state_driver
-            .insert_account(address1.clone(), account1.clone())
-            .unwrap();
-        node.state_driver
-            .insert_account(address2.clone(), account2.clone())
-            .unwrap();
         node.consensus_driver.miner_election_results = Some(miner_election_results.clone());
         node.state_driver.write_vertex(&vtx).unwrap();
         node.state_driver
@@ -497,12 +510,6 @@ async fn all_nodes_update_state_upon_successfully_appending_certified_convergenc
     });
 
     all_nodes.iter_mut().for_each(|node| {
-        node.state_driver
-            .insert_account(address1.clone(), account1.clone())
-            .unwrap();
-        node.state_driver
-            .insert_account(address2.clone(), account2.clone())
-            .unwrap();
         node.consensus_driver.miner_election_results = Some(miner_election_results.clone());
         node.state_driver.write_vertex(&vtx).unwrap();
         node.state_driver
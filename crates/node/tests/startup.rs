@@ -45,3 +45,20 @@ async fn bootstrap_node_can_add_newly_joined_peers_to_peer_list() {
     let is_cancelled = vrrb_node.stop().await.unwrap();
     assert!(is_cancelled);
 }
+
+#[tokio::test]
+#[serial]
+async fn node_serves_the_http_api() {
+    remove_vrrb_data_dir();
+    let node_config = create_mock_bootstrap_node_config();
+
+    let vrrb_node = Node::start(node_config).await.unwrap();
+
+    let response = reqwest::get(format!("http://{}/health", vrrb_node.http_api_address()))
+        .await
+        .unwrap();
+    assert!(response.status().is_success());
+
+    let is_cancelled = vrrb_node.stop().await.unwrap();
+    assert!(is_cancelled);
+}
@@ -77,7 +77,7 @@ impl std::fmt::Display for VersionNumber {
     }
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ServiceStatusResponse {
     /// Type of service (see above)
     pub service_type: ServiceType,
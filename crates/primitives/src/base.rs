@@ -13,6 +13,9 @@ pub type Epoch = u128;
 pub type Round = u128;
 pub type Seed = u64;
 pub const GENESIS_EPOCH: Epoch = 0;
+/// Number of rounds an epoch lasts for on chains whose genesis block does
+/// not set it
+pub const DEFAULT_EPOCH_LENGTH: Round = 10_000;
 pub const GROSS_UTILITY_PERCENTAGE: f64 = 0.01;
 pub const PERCENTAGE_CHANGE_SUPPLY_CAP: f64 = 0.25;
 
@@ -120,6 +123,66 @@ impl Display for QuorumKind {
     }
 }
 
+/// Strategies miners can pick the proposal block that keeps a transaction
+/// included by more than one proposal block with
+#[derive(
+    Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictResolutionKind {
+    /// The proposer with the lowest pointer sum for the round seed wins
+    #[default]
+    PointerSum,
+    /// The proposal block paying the lowest fee per byte loses
+    FeePerByte,
+    /// Proposers win with a probability proportional to their stake
+    StakeWeighted,
+}
+
+impl Display for ConflictResolutionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConflictResolutionKind::PointerSum => write!(f, "pointer_sum"),
+            ConflictResolutionKind::FeePerByte => write!(f, "fee_per_byte"),
+            ConflictResolutionKind::StakeWeighted => write!(f, "stake_weighted"),
+        }
+    }
+}
+
+/// Protocol parameters fixed by the genesis block of a chain. Every node
+/// follows the parameters of the genesis block it applied, so they cannot
+/// drift apart between the nodes of a network.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ChainParameters {
+    /// Strategy miners resolve conflicts between proposal blocks with
+    #[serde(default)]
+    pub conflict_resolution: ConflictResolutionKind,
+
+    /// Number of rounds an epoch lasts for. Quorums are re-elected, block
+    /// rewards adjusted and unbonded stake released at every epoch boundary
+    #[serde(default = "default_epoch_length")]
+    pub epoch_length: Round,
+}
+
+impl Default for ChainParameters {
+    fn default() -> Self {
+        Self {
+            conflict_resolution: ConflictResolutionKind::default(),
+            epoch_length: DEFAULT_EPOCH_LENGTH,
+        }
+    }
+}
+
+fn default_epoch_length() -> Round {
+    DEFAULT_EPOCH_LENGTH
+}
+
+/// Returns the epoch `round` belongs to on a chain whose epochs last for
+/// `epoch_length` rounds. Epochs last for at least one round.
+pub fn epoch_of(round: Round, epoch_length: Round) -> Epoch {
+    GENESIS_EPOCH + round / epoch_length.max(1)
+}
+
 /// A hashed [PublicKeySet].
 #[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, PartialOrd, Ord)]
 pub struct QuorumId(String);
@@ -0,0 +1,292 @@
+use std::{path::Path, sync::Arc};
+
+use block::{Block, BlockHash};
+use primitives::Round;
+use rocksdb::{ColumnFamily, Direction, IteratorMode, WriteBatch, DB};
+use serde::{Deserialize, Serialize};
+use storage_utils::{Result, StorageError};
+
+use crate::{base_db_options, BlockHeight};
+
+/// Column family holding every block, keyed by its hash
+pub const BLOCKS_COLUMN_FAMILY: &str = "blocks";
+
+/// Column family mapping the height of genesis and convergence blocks to
+/// their hash
+pub const BLOCK_HEIGHT_INDEX_COLUMN_FAMILY: &str = "block_height_index";
+
+/// Column family indexing every block by the round it was produced in
+pub const BLOCK_ROUND_INDEX_COLUMN_FAMILY: &str = "block_round_index";
+
+/// Maximum number of blocks returned by a single call to `BlockStore::range`
+pub const MAX_BLOCKS_PER_PAGE: usize = 100;
+
+/// A page of blocks ordered by height
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockPage {
+    pub blocks: Vec<Block>,
+    /// Height to request the next page from, if there are more blocks
+    pub next_height: Option<BlockHeight>,
+}
+
+/// Persistent store of genesis, proposal and convergence blocks.
+///
+/// Blocks are indexed by hash, by round and, for genesis and convergence
+/// blocks, by height. Clones share the same underlying database.
+#[derive(Debug, Clone)]
+pub struct BlockStore {
+    db: Arc<DB>,
+}
+
+impl BlockStore {
+    /// Opens the block store under `path`, creating it if missing
+    pub fn new(path: &Path) -> Result<Self> {
+        let path = path.join("blocks");
+
+        let mut options = base_db_options();
+        options.set_error_if_exists(false);
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+
+        let db = DB::open_cf(
+            &options,
+            path,
+            [
+                BLOCKS_COLUMN_FAMILY,
+                BLOCK_HEIGHT_INDEX_COLUMN_FAMILY,
+                BLOCK_ROUND_INDEX_COLUMN_FAMILY,
+            ],
+        )
+        .map_err(block_store_error)?;
+
+        Ok(Self { db: Arc::new(db) })
+    }
+
+    /// Stores a block along with its indexes. Inserting a block that is
+    /// already stored overwrites it.
+    pub fn insert(&self, block: &Block) -> Result<()> {
+        let hash = block.hash();
+        let value = bincode::serialize(block).map_err(block_store_error)?;
+
+        let mut batch = WriteBatch::default();
+        batch.put_cf(self.cf(BLOCKS_COLUMN_FAMILY)?, hash.as_bytes(), value);
+        batch.put_cf(
+            self.cf(BLOCK_ROUND_INDEX_COLUMN_FAMILY)?,
+            round_index_key(block, &hash),
+            [],
+        );
+
+        if let Some(height) = block_height(block) {
+            batch.put_cf(
+                self.cf(BLOCK_HEIGHT_INDEX_COLUMN_FAMILY)?,
+                height.to_be_bytes(),
+                hash.as_bytes(),
+            );
+        }
+
+        self.db.write(batch).map_err(block_store_error)
+    }
+
+    pub fn get_by_hash(&self, hash: &str) -> Result<Option<Block>> {
+        let value = self
+            .db
+            .get_cf(self.cf(BLOCKS_COLUMN_FAMILY)?, hash.as_bytes())
+            .map_err(block_store_error)?;
+
+        value
+            .map(|value| bincode::deserialize(&value).map_err(block_store_error))
+            .transpose()
+    }
+
+    /// Returns the genesis or convergence block at the given height
+    pub fn get_by_height(&self, height: BlockHeight) -> Result<Option<Block>> {
+        let hash = self
+            .db
+            .get_cf(
+                self.cf(BLOCK_HEIGHT_INDEX_COLUMN_FAMILY)?,
+                height.to_be_bytes(),
+            )
+            .map_err(block_store_error)?;
+
+        match hash {
+            Some(hash) => self.get_by_hash(&decode_hash(&hash)?),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns every block produced in the given round, genesis first, then
+    /// proposals, then convergence blocks
+    pub fn get_by_round(&self, round: Round) -> Result<Vec<Block>> {
+        let prefix = round.to_be_bytes();
+        let mut blocks = vec![];
+
+        for hash in self.round_index_hashes(&prefix)? {
+            if let Some(block) = self.get_by_hash(&hash)? {
+                blocks.push(block);
+            }
+        }
+
+        Ok(blocks)
+    }
+
+    /// Returns up to `limit` genesis and convergence blocks starting at
+    /// `from_height`. `limit` is kept between 1 and `MAX_BLOCKS_PER_PAGE`.
+    pub fn range(&self, from_height: BlockHeight, limit: usize) -> Result<BlockPage> {
+        let limit = limit.clamp(1, MAX_BLOCKS_PER_PAGE);
+        let from = from_height.to_be_bytes();
+
+        let mut page = BlockPage::default();
+        let iter = self.db.iterator_cf(
+            self.cf(BLOCK_HEIGHT_INDEX_COLUMN_FAMILY)?,
+            IteratorMode::From(&from, Direction::Forward),
+        );
+
+        for entry in iter {
+            let (key, hash) = entry.map_err(block_store_error)?;
+
+            if page.blocks.len() == limit {
+                page.next_height = Some(decode_height(&key)?);
+                break;
+            }
+
+            if let Some(block) = self.get_by_hash(&decode_hash(&hash)?)? {
+                page.blocks.push(block);
+            }
+        }
+
+        Ok(page)
+    }
+
+    /// Returns the `limit` highest genesis and convergence blocks ordered by
+    /// height. `limit` is kept between 1 and `MAX_BLOCKS_PER_PAGE`.
+    pub fn latest(&self, limit: usize) -> Result<Vec<Block>> {
+        let limit = limit.clamp(1, MAX_BLOCKS_PER_PAGE);
+        let iter = self.db.iterator_cf(
+            self.cf(BLOCK_HEIGHT_INDEX_COLUMN_FAMILY)?,
+            IteratorMode::End,
+        );
+
+        let mut blocks = vec![];
+        for entry in iter.take(limit) {
+            let (_, hash) = entry.map_err(block_store_error)?;
+
+            if let Some(block) = self.get_by_hash(&decode_hash(&hash)?)? {
+                blocks.push(block);
+            }
+        }
+        blocks.reverse();
+
+        Ok(blocks)
+    }
+
+    /// Returns every stored block in an order in which it can be appended to
+    /// the DAG: by round, with the genesis and proposal blocks of a round
+    /// ahead of the convergence blocks that reference them
+    pub fn blocks_in_dag_order(&self) -> Result<Vec<Block>> {
+        let mut blocks = vec![];
+
+        for hash in self.round_index_hashes(&[])? {
+            if let Some(block) = self.get_by_hash(&hash)? {
+                blocks.push(block);
+            }
+        }
+
+        Ok(blocks)
+    }
+
+    /// Returns the height of the highest genesis or convergence block stored
+    pub fn latest_height(&self) -> Result<Option<BlockHeight>> {
+        let mut iter = self.db.iterator_cf(
+            self.cf(BLOCK_HEIGHT_INDEX_COLUMN_FAMILY)?,
+            IteratorMode::End,
+        );
+
+        match iter.next() {
+            Some(entry) => {
+                let (key, _) = entry.map_err(block_store_error)?;
+                Ok(Some(decode_height(&key)?))
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the number of blocks stored
+    pub fn len(&self) -> Result<usize> {
+        Ok(self
+            .db
+            .iterator_cf(self.cf(BLOCKS_COLUMN_FAMILY)?, IteratorMode::Start)
+            .count())
+    }
+
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    fn round_index_hashes(&self, prefix: &[u8]) -> Result<Vec<BlockHash>> {
+        let iter = self.db.iterator_cf(
+            self.cf(BLOCK_ROUND_INDEX_COLUMN_FAMILY)?,
+            IteratorMode::From(prefix, Direction::Forward),
+        );
+
+        let mut hashes = vec![];
+        for entry in iter {
+            let (key, _) = entry.map_err(block_store_error)?;
+
+            if !key.starts_with(prefix) {
+                break;
+            }
+
+            hashes.push(decode_hash(&key[ROUND_INDEX_PREFIX_LEN..])?);
+        }
+
+        Ok(hashes)
+    }
+
+    fn cf(&self, name: &str) -> Result<&ColumnFamily> {
+        self.db
+            .cf_handle(name)
+            .ok_or_else(|| StorageError::Other(format!("missing {name} column family")))
+    }
+}
+
+/// Round index keys are the big endian round, followed by a byte ordering the
+/// kinds of block within the round, followed by the block hash
+const ROUND_INDEX_PREFIX_LEN: usize = std::mem::size_of::<Round>() + 1;
+
+fn round_index_key(block: &Block, hash: &str) -> Vec<u8> {
+    let (round, kind) = match block {
+        Block::Genesis { block } => (block.header.round, 0u8),
+        Block::Proposal { block } => (block.round, 1u8),
+        Block::Convergence { block } => (block.header.round, 2u8),
+    };
+
+    let mut key = round.to_be_bytes().to_vec();
+    key.push(kind);
+    key.extend(hash.as_bytes());
+
+    key
+}
+
+fn block_height(block: &Block) -> Option<BlockHeight> {
+    match block {
+        Block::Genesis { block } => Some(block.header.block_height),
+        Block::Convergence { block } => Some(block.header.block_height),
+        Block::Proposal { .. } => None,
+    }
+}
+
+fn decode_height(key: &[u8]) -> Result<BlockHeight> {
+    let bytes = key
+        .try_into()
+        .map_err(|_| StorageError::Other("invalid block height index key".to_string()))?;
+
+    Ok(BlockHeight::from_be_bytes(bytes))
+}
+
+fn decode_hash(bytes: &[u8]) -> Result<BlockHash> {
+    String::from_utf8(bytes.to_vec()).map_err(block_store_error)
+}
+
+fn block_store_error(err: impl std::fmt::Display) -> StorageError {
+    StorageError::Other(format!("block store error: {err}"))
+}
@@ -0,0 +1,85 @@
+use std::{path::Path, sync::Arc};
+
+use ethereum_types::U256;
+use primitives::Address;
+use rocksdb::{ColumnFamily, WriteBatch, DB};
+use storage_utils::{Result, StorageError};
+
+use crate::base_db_options;
+
+/// Column family mapping the address of every claim to the hash the claim is
+/// stored under
+pub const CLAIM_ADDRESSES_COLUMN_FAMILY: &str = "claim_addresses";
+
+/// Secondary index of the claim store so that claims can be looked up by
+/// their address without scanning every claim. Clones share the same
+/// underlying database.
+#[derive(Debug, Clone)]
+pub struct ClaimAddressIndex {
+    db: Arc<DB>,
+}
+
+impl ClaimAddressIndex {
+    /// Opens the index under `path`, creating it if missing
+    pub fn new(path: &Path) -> Result<Self> {
+        let path = path.join("claim_address_index");
+
+        let mut options = base_db_options();
+        options.set_error_if_exists(false);
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+
+        let db = DB::open_cf(&options, path, [CLAIM_ADDRESSES_COLUMN_FAMILY])
+            .map_err(claim_address_index_error)?;
+
+        Ok(Self { db: Arc::new(db) })
+    }
+
+    /// Records the hash of the claim registered under every given address
+    pub fn insert<'a>(
+        &self,
+        claims: impl IntoIterator<Item = (&'a Address, &'a U256)>,
+    ) -> Result<()> {
+        let cf = self.cf()?;
+        let mut batch = WriteBatch::default();
+
+        for (address, claim_hash) in claims {
+            let mut value = [0u8; 32];
+            claim_hash.to_big_endian(&mut value);
+            batch.put_cf(cf, address.0, value);
+        }
+
+        self.db.write(batch).map_err(claim_address_index_error)
+    }
+
+    /// Returns the hash of the claim registered under `address`, if any
+    pub fn get(&self, address: &Address) -> Result<Option<U256>> {
+        let value = self
+            .db
+            .get_cf(self.cf()?, address.0)
+            .map_err(claim_address_index_error)?;
+
+        value
+            .map(|value| {
+                if value.len() != 32 {
+                    return Err(claim_address_index_error("invalid claim hash"));
+                }
+                Ok(U256::from_big_endian(&value))
+            })
+            .transpose()
+    }
+
+    fn cf(&self) -> Result<&ColumnFamily> {
+        self.db
+            .cf_handle(CLAIM_ADDRESSES_COLUMN_FAMILY)
+            .ok_or_else(|| {
+                StorageError::Other(format!(
+                    "missing {CLAIM_ADDRESSES_COLUMN_FAMILY} column family"
+                ))
+            })
+    }
+}
+
+fn claim_address_index_error(err: impl std::fmt::Display) -> StorageError {
+    StorageError::Other(format!("claim address index error: {err}"))
+}
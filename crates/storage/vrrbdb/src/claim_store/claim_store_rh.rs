@@ -1,13 +1,16 @@
 use std::collections::HashMap;
 
+use ethereum_types::U256;
 use integral_db::{JellyfishMerkleTreeWrapper, ReadHandleFactory};
 use patriecia::{JellyfishMerkleTree, Version};
-use primitives::NodeId;
+use primitives::{Address, NodeId};
 use sha2::Sha256;
 use storage_utils::{Result, StorageError};
-use vrrb_core::claim::Claim;
+use vrrb_core::{claim::Claim, staking::UnbondingEntry};
 
-use crate::RocksDbAdapter;
+use crate::{
+    ClaimAddressIndex, ClaimNodeIndex, MerkleProof, ProofStore, RocksDbAdapter, UnbondingIndex,
+};
 
 #[derive(Debug, Clone)]
 pub struct ClaimStoreReadHandle {
@@ -27,6 +30,11 @@ impl ClaimStoreReadHandle {
             .map_err(|err| StorageError::Other(err.to_string()))
     }
 
+    /// Returns the latest version of the claim trie
+    pub fn version(&self) -> Version {
+        self.inner.version()
+    }
+
     /// Get a batch of claims by providing Vec of PublicKeysHash
     ///
     /// Returns HashMap indexed by PublicKeys and containing either
@@ -76,11 +84,24 @@ impl ClaimStoreReadHandle {
 #[derive(Debug, Clone)]
 pub struct ClaimStoreReadHandleFactory {
     inner: ReadHandleFactory<JellyfishMerkleTree<RocksDbAdapter, Sha256>>,
+    node_index: ClaimNodeIndex,
+    address_index: ClaimAddressIndex,
+    unbonding_index: UnbondingIndex,
 }
 
 impl ClaimStoreReadHandleFactory {
-    pub fn new(inner: ReadHandleFactory<JellyfishMerkleTree<RocksDbAdapter, Sha256>>) -> Self {
-        Self { inner }
+    pub fn new(
+        inner: ReadHandleFactory<JellyfishMerkleTree<RocksDbAdapter, Sha256>>,
+        node_index: ClaimNodeIndex,
+        address_index: ClaimAddressIndex,
+        unbonding_index: UnbondingIndex,
+    ) -> Self {
+        Self {
+            inner,
+            node_index,
+            address_index,
+            unbonding_index,
+        }
     }
 
     pub fn handle(&self) -> ClaimStoreReadHandle {
@@ -95,4 +116,60 @@ impl ClaimStoreReadHandleFactory {
 
         ClaimStoreReadHandle { inner }
     }
+
+    /// Returns the claim stored under `hash` in the latest version of the
+    /// trie, if any
+    pub fn get_by_hash(&self, hash: &U256) -> Result<Option<Claim>> {
+        MerkleProof::latest(ProofStore::Claims, &self.inner, hash)?.value()
+    }
+
+    /// Returns the claim registered by `node_id` in the latest version of
+    /// the trie, if any
+    pub fn get_by_node_id(&self, node_id: &NodeId) -> Result<Option<Claim>> {
+        match self.node_index.get(node_id)? {
+            Some(hash) => self.get_by_hash(&hash),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the claim registered under `address` in the latest version of
+    /// the trie, if any
+    pub fn get_by_address(&self, address: &Address) -> Result<Option<Claim>> {
+        match self.address_index.get(address)? {
+            Some(hash) => self.get_by_hash(&hash),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the stake `owner` withdrew that is still unbonding, ordered by
+    /// release epoch
+    pub fn get_unbonding_entries(&self, owner: &Address) -> Result<Vec<UnbondingEntry>> {
+        let mut entries = vec![];
+        for node_id in self.unbonding_index.claims_of(owner)? {
+            if let Some(claim) = self.get_by_node_id(&node_id)? {
+                entries.extend(
+                    claim
+                        .unbonding()
+                        .iter()
+                        .filter(|entry| &entry.owner == owner)
+                        .cloned(),
+                );
+            }
+        }
+        entries.sort_by_key(|entry| entry.release_epoch);
+
+        Ok(entries)
+    }
+
+    /// Returns a proof of the claim stored under `key`, or of its absence,
+    /// against the latest claims root
+    pub fn get_proof(&self, key: &U256) -> Result<MerkleProof> {
+        MerkleProof::latest(ProofStore::Claims, &self.inner, key)
+    }
+
+    /// Returns a proof of the claim stored under `key`, or of its absence,
+    /// against `version` of the trie
+    pub fn get_proof_at(&self, key: &U256, version: Version) -> Result<MerkleProof> {
+        MerkleProof::at_version(ProofStore::Claims, &self.inner, key, version)
+    }
 }
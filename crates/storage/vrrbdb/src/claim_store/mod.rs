@@ -1,16 +1,25 @@
-use std::{path::Path, sync::Arc};
+use std::{collections::HashSet, path::Path, sync::Arc};
 
 use ethereum_types::U256;
 use integral_db::LeftRightTrie;
-use patriecia::RootHash;
+use patriecia::{RootHash, Version};
+use primitives::{Address, Epoch, NodeId};
 use sha2::Sha256;
 use storage_utils::{Result, StorageError};
-use vrrb_core::claim::Claim;
+use vrrb_core::{claim::Claim, staking::UnbondingEntry};
 
 use crate::RocksDbAdapter;
 
+mod address_index;
 mod claim_store_rh;
+mod node_index;
+mod payout_index;
+mod unbonding_index;
+pub use address_index::*;
 pub use claim_store_rh::*;
+pub use node_index::*;
+pub use payout_index::*;
+pub use unbonding_index::*;
 
 pub type Claims = Vec<Claim>;
 pub type FailedClaimUpdates = Vec<(U256, Claims, Result<()>)>;
@@ -18,31 +27,33 @@ pub type FailedClaimUpdates = Vec<(U256, Claims, Result<()>)>;
 #[derive(Debug, Clone)]
 pub struct ClaimStore {
     trie: LeftRightTrie<'static, U256, Claim, RocksDbAdapter, Sha256>,
-}
-
-impl Default for ClaimStore {
-    fn default() -> Self {
-        let db_path = storage_utils::get_node_data_dir()
-            .unwrap_or_default()
-            .join("db")
-            .join("claim");
-
-        let db_adapter = RocksDbAdapter::new(db_path, "claims").unwrap_or_default();
-
-        let trie = LeftRightTrie::new(Arc::new(db_adapter));
-
-        Self { trie }
-    }
+    db: Arc<RocksDbAdapter>,
+    node_index: ClaimNodeIndex,
+    address_index: ClaimAddressIndex,
+    unbonding_index: UnbondingIndex,
+    payout_index: PayoutIndex,
 }
 
 impl ClaimStore {
-    /// Returns new, empty instance of ClaimDb
-    pub fn new(path: &Path) -> Self {
+    /// Opens the claim store under `path`, creating it if missing
+    pub fn new(path: &Path) -> Result<Self> {
+        let node_index = ClaimNodeIndex::new(path)?;
+        let address_index = ClaimAddressIndex::new(path)?;
+        let unbonding_index = UnbondingIndex::new(path)?;
+        let payout_index = PayoutIndex::new(path)?;
         let path = path.join("claims");
-        let db_adapter = RocksDbAdapter::new(path, "claims").unwrap_or_default();
-        let trie = LeftRightTrie::new(Arc::new(db_adapter));
+        let db_adapter = RocksDbAdapter::new(path, "claims")?;
+        let db = Arc::new(db_adapter);
+        let trie = LeftRightTrie::new(db.clone());
 
-        Self { trie }
+        Ok(Self {
+            trie,
+            db,
+            node_index,
+            address_index,
+            unbonding_index,
+            payout_index,
+        })
     }
 
     /// Returns new ReadHandle to the VrrDb data. As long as the returned value
@@ -59,7 +70,7 @@ impl ClaimStore {
     }
 
     // Maybe initialize is better name for that?
-    fn insert_uncommited(&mut self, claim: Claim) -> Result<()> {
+    pub(crate) fn insert_uncommited(&mut self, claim: Claim) -> Result<()> {
         //        if claim.debits != 0 {
         //            return Err(StorageError::Other(
         //                "cannot insert claim with debit".to_string(),
@@ -72,6 +83,10 @@ impl ClaimStore {
         //            ));
         //        }
 
+        self.node_index.insert([(&claim.node_id, &claim.hash)])?;
+        self.address_index.insert([(&claim.address, &claim.hash)])?;
+        self.unbonding_index.insert([&claim])?;
+        self.payout_index.insert([&claim])?;
         self.trie.insert(claim.hash, claim);
 
         Ok(())
@@ -281,13 +296,115 @@ impl ClaimStore {
             .map_err(|e| StorageError::Other(e.to_string()))
     }
 
-    pub fn extend(&mut self, claims: Vec<(U256, Option<Claim>)>) {
-        self.trie.extend(claims)
+    /// Returns the latest version of the claim trie
+    pub fn version(&self) -> Version {
+        self.read_handle().version()
+    }
+
+    pub fn extend(&mut self, claims: Vec<(U256, Option<Claim>)>) -> Result<()> {
+        self.node_index.insert(
+            claims
+                .iter()
+                .filter_map(|(hash, claim)| claim.as_ref().map(|claim| (&claim.node_id, hash))),
+        )?;
+        self.address_index.insert(
+            claims
+                .iter()
+                .filter_map(|(hash, claim)| claim.as_ref().map(|claim| (&claim.address, hash))),
+        )?;
+        self.unbonding_index
+            .insert(claims.iter().filter_map(|(_, claim)| claim.as_ref()))?;
+        self.payout_index
+            .insert(claims.iter().filter_map(|(_, claim)| claim.as_ref()))?;
+        self.trie.extend(claims);
+
+        Ok(())
+    }
+
+    /// Takes every unbonding entry that is due by `epoch` off the claims that
+    /// hold it and returns them. The claims found through the unbonding
+    /// index are updated and committed at once.
+    pub fn release_unbonded(&mut self, epoch: Epoch) -> Result<Vec<UnbondingEntry>> {
+        let due = self.unbonding_index.due(epoch)?;
+        if due.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let factory = self.factory();
+        let mut released = vec![];
+        let mut released_owners: HashSet<(Address, NodeId)> = HashSet::new();
+        for node_id in due {
+            let mut claim = match factory.get_by_node_id(&node_id)? {
+                Some(claim) => claim,
+                None => continue,
+            };
+
+            let entries = claim.release_unbonded(epoch);
+            for entry in &entries {
+                let still_unbonding = claim
+                    .unbonding()
+                    .iter()
+                    .any(|pending| pending.owner == entry.owner);
+                if !still_unbonding {
+                    released_owners.insert((entry.owner.clone(), node_id.clone()));
+                }
+            }
+
+            released.extend(entries);
+            self.trie.insert(claim.hash, claim);
+        }
+
+        self.unbonding_index.remove_released(
+            epoch,
+            released_owners
+                .iter()
+                .map(|(owner, node_id)| (owner, node_id)),
+        )?;
+        self.commit();
+
+        Ok(released)
+    }
+
+    /// Takes the rewards claims accrued before `epoch` off of them and returns
+    /// the payouts owed to their owners and delegators. The claims found
+    /// through the payout index are updated and committed at once.
+    pub fn pay_out_rewards(&mut self, epoch: Epoch) -> Result<Vec<(Address, u128)>> {
+        let due = self.payout_index.due(epoch)?;
+        if due.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let factory = self.factory();
+        let mut payouts = vec![];
+        for node_id in due {
+            let mut claim = match factory.get_by_node_id(&node_id)? {
+                Some(claim) if claim.rewards_due(epoch) => claim,
+                _ => continue,
+            };
+
+            payouts.extend(claim.distribute_rewards());
+            self.trie.insert(claim.hash, claim);
+        }
+
+        self.payout_index.remove_paid(epoch)?;
+        self.commit();
+
+        Ok(payouts)
+    }
+
+    /// Returns the database backing the store's trie
+    pub(crate) fn db(&self) -> Arc<RocksDbAdapter> {
+        self.db.clone()
     }
 
     pub fn factory(&self) -> ClaimStoreReadHandleFactory {
         let inner = self.trie.factory();
 
-        ClaimStoreReadHandleFactory::new(inner)
+        ClaimStoreReadHandleFactory::new(
+            inner,
+            self.node_index.clone(),
+            self.address_index.clone(),
+            self.unbonding_index.clone(),
+        )
     }
 }
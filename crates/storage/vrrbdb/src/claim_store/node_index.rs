@@ -0,0 +1,85 @@
+use std::{path::Path, sync::Arc};
+
+use ethereum_types::U256;
+use primitives::NodeId;
+use rocksdb::{ColumnFamily, WriteBatch, DB};
+use storage_utils::{Result, StorageError};
+
+use crate::base_db_options;
+
+/// Column family mapping the id of every node that registered a claim to the
+/// hash the claim is stored under
+pub const CLAIM_NODE_IDS_COLUMN_FAMILY: &str = "claim_node_ids";
+
+/// Secondary index of the claim store so that claims can be looked up by the
+/// id of the node that registered them. Clones share the same underlying
+/// database.
+#[derive(Debug, Clone)]
+pub struct ClaimNodeIndex {
+    db: Arc<DB>,
+}
+
+impl ClaimNodeIndex {
+    /// Opens the index under `path`, creating it if missing
+    pub fn new(path: &Path) -> Result<Self> {
+        let path = path.join("claim_index");
+
+        let mut options = base_db_options();
+        options.set_error_if_exists(false);
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+
+        let db = DB::open_cf(&options, path, [CLAIM_NODE_IDS_COLUMN_FAMILY])
+            .map_err(claim_index_error)?;
+
+        Ok(Self { db: Arc::new(db) })
+    }
+
+    /// Records the hash of the claim registered by every given node
+    pub fn insert<'a>(
+        &self,
+        claims: impl IntoIterator<Item = (&'a NodeId, &'a U256)>,
+    ) -> Result<()> {
+        let cf = self.cf()?;
+        let mut batch = WriteBatch::default();
+
+        for (node_id, claim_hash) in claims {
+            let mut value = [0u8; 32];
+            claim_hash.to_big_endian(&mut value);
+            batch.put_cf(cf, node_id.as_bytes(), value);
+        }
+
+        self.db.write(batch).map_err(claim_index_error)
+    }
+
+    /// Returns the hash of the claim registered by `node_id`, if any
+    pub fn get(&self, node_id: &NodeId) -> Result<Option<U256>> {
+        let value = self
+            .db
+            .get_cf(self.cf()?, node_id.as_bytes())
+            .map_err(claim_index_error)?;
+
+        value
+            .map(|value| {
+                if value.len() != 32 {
+                    return Err(claim_index_error("invalid claim hash"));
+                }
+                Ok(U256::from_big_endian(&value))
+            })
+            .transpose()
+    }
+
+    fn cf(&self) -> Result<&ColumnFamily> {
+        self.db
+            .cf_handle(CLAIM_NODE_IDS_COLUMN_FAMILY)
+            .ok_or_else(|| {
+                StorageError::Other(format!(
+                    "missing {CLAIM_NODE_IDS_COLUMN_FAMILY} column family"
+                ))
+            })
+    }
+}
+
+fn claim_index_error(err: impl std::fmt::Display) -> StorageError {
+    StorageError::Other(format!("claim index error: {err}"))
+}
@@ -0,0 +1,106 @@
+use std::{path::Path, sync::Arc};
+
+use primitives::{Epoch, NodeId};
+use rocksdb::{ColumnFamily, IteratorMode, WriteBatch, DB};
+use storage_utils::{Result, StorageError};
+use vrrb_core::claim::Claim;
+
+use super::unbonding_index::{decode_epoch_key, epoch_key};
+use crate::base_db_options;
+
+/// Column family keyed by the epoch a claim last accrued rewards in followed
+/// by the id of the node that registered the claim
+pub const PENDING_REWARDS_COLUMN_FAMILY: &str = "pending_rewards";
+
+/// Secondary index of the claim store so that the claims with rewards to pay
+/// out can be found without scanning every claim. Clones share the same
+/// underlying database.
+#[derive(Debug, Clone)]
+pub struct PayoutIndex {
+    db: Arc<DB>,
+}
+
+impl PayoutIndex {
+    /// Opens the index under `path`, creating it if missing
+    pub fn new(path: &Path) -> Result<Self> {
+        let path = path.join("payout_index");
+
+        let mut options = base_db_options();
+        options.set_error_if_exists(false);
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+
+        let db = DB::open_cf(&options, path, [PENDING_REWARDS_COLUMN_FAMILY])
+            .map_err(payout_index_error)?;
+
+        Ok(Self { db: Arc::new(db) })
+    }
+
+    /// Records every given claim that has rewards pending
+    pub fn insert<'a>(&self, claims: impl IntoIterator<Item = &'a Claim>) -> Result<()> {
+        let cf = self.cf()?;
+        let mut batch = WriteBatch::default();
+
+        for claim in claims {
+            if claim.get_pending_rewards() > 0 {
+                batch.put_cf(
+                    cf,
+                    epoch_key(claim.get_rewards_epoch(), &claim.node_id),
+                    b"",
+                );
+            }
+        }
+
+        self.db.write(batch).map_err(payout_index_error)
+    }
+
+    /// Returns the ids of the nodes whose claims accrued rewards before
+    /// `epoch`
+    pub fn due(&self, epoch: Epoch) -> Result<Vec<NodeId>> {
+        let mut node_ids = vec![];
+        for entry in self.db.iterator_cf(self.cf()?, IteratorMode::Start) {
+            let (key, _) = entry.map_err(payout_index_error)?;
+            let (rewards_epoch, node_id) = decode_epoch_key(&key)?;
+            if rewards_epoch >= epoch {
+                break;
+            }
+
+            if !node_ids.contains(&node_id) {
+                node_ids.push(node_id);
+            }
+        }
+
+        Ok(node_ids)
+    }
+
+    /// Drops every claim that accrued rewards before `epoch`, in a single
+    /// write
+    pub fn remove_paid(&self, epoch: Epoch) -> Result<()> {
+        let cf = self.cf()?;
+        let mut batch = WriteBatch::default();
+
+        for entry in self.db.iterator_cf(cf, IteratorMode::Start) {
+            let (key, _) = entry.map_err(payout_index_error)?;
+            if decode_epoch_key(&key)?.0 >= epoch {
+                break;
+            }
+            batch.delete_cf(cf, key);
+        }
+
+        self.db.write(batch).map_err(payout_index_error)
+    }
+
+    fn cf(&self) -> Result<&ColumnFamily> {
+        self.db
+            .cf_handle(PENDING_REWARDS_COLUMN_FAMILY)
+            .ok_or_else(|| {
+                StorageError::Other(format!(
+                    "missing {PENDING_REWARDS_COLUMN_FAMILY} column family"
+                ))
+            })
+    }
+}
+
+fn payout_index_error(err: impl std::fmt::Display) -> StorageError {
+    StorageError::Other(format!("payout index error: {err}"))
+}
@@ -0,0 +1,174 @@
+use std::{path::Path, sync::Arc};
+
+use primitives::{Address, Epoch, NodeId};
+use rocksdb::{ColumnFamily, IteratorMode, WriteBatch, DB};
+use storage_utils::{Result, StorageError};
+use vrrb_core::claim::Claim;
+
+use crate::base_db_options;
+
+/// Column family keyed by the release epoch of every unbonding entry followed
+/// by the id of the node whose claim holds it
+pub const UNBONDING_RELEASE_EPOCHS_COLUMN_FAMILY: &str = "unbonding_release_epochs";
+
+/// Column family keyed by the owner of every unbonding entry followed by the
+/// id of the node whose claim holds it
+pub const UNBONDING_OWNERS_COLUMN_FAMILY: &str = "unbonding_owners";
+
+const EPOCH_KEY_LEN: usize = std::mem::size_of::<Epoch>();
+
+/// Secondary index of the claim store so that unbonding entries can be found
+/// by release epoch and by owner without scanning every claim. Clones share
+/// the same underlying database.
+#[derive(Debug, Clone)]
+pub struct UnbondingIndex {
+    db: Arc<DB>,
+}
+
+impl UnbondingIndex {
+    /// Opens the index under `path`, creating it if missing
+    pub fn new(path: &Path) -> Result<Self> {
+        let path = path.join("unbonding_index");
+
+        let mut options = base_db_options();
+        options.set_error_if_exists(false);
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+
+        let db = DB::open_cf(
+            &options,
+            path,
+            [
+                UNBONDING_RELEASE_EPOCHS_COLUMN_FAMILY,
+                UNBONDING_OWNERS_COLUMN_FAMILY,
+            ],
+        )
+        .map_err(unbonding_index_error)?;
+
+        Ok(Self { db: Arc::new(db) })
+    }
+
+    /// Records the unbonding entries held by every given claim
+    pub fn insert<'a>(&self, claims: impl IntoIterator<Item = &'a Claim>) -> Result<()> {
+        let epochs = self.cf(UNBONDING_RELEASE_EPOCHS_COLUMN_FAMILY)?;
+        let owners = self.cf(UNBONDING_OWNERS_COLUMN_FAMILY)?;
+        let mut batch = WriteBatch::default();
+
+        for claim in claims {
+            for entry in claim.unbonding() {
+                batch.put_cf(epochs, epoch_key(entry.release_epoch, &claim.node_id), b"");
+                batch.put_cf(owners, owner_key(&entry.owner, &claim.node_id), b"");
+            }
+        }
+
+        self.db.write(batch).map_err(unbonding_index_error)
+    }
+
+    /// Returns the ids of the nodes whose claims hold entries that are due by
+    /// `epoch`
+    pub fn due(&self, epoch: Epoch) -> Result<Vec<NodeId>> {
+        let iter = self.db.iterator_cf(
+            self.cf(UNBONDING_RELEASE_EPOCHS_COLUMN_FAMILY)?,
+            IteratorMode::Start,
+        );
+
+        let mut node_ids = vec![];
+        for entry in iter {
+            let (key, _) = entry.map_err(unbonding_index_error)?;
+            let (release_epoch, node_id) = decode_epoch_key(&key)?;
+            if release_epoch > epoch {
+                break;
+            }
+
+            if !node_ids.contains(&node_id) {
+                node_ids.push(node_id);
+            }
+        }
+
+        Ok(node_ids)
+    }
+
+    /// Returns the ids of the nodes whose claims hold entries owned by `owner`
+    pub fn claims_of(&self, owner: &Address) -> Result<Vec<NodeId>> {
+        let prefix = owner.0.as_slice();
+        let iter = self
+            .db
+            .prefix_iterator_cf(self.cf(UNBONDING_OWNERS_COLUMN_FAMILY)?, prefix);
+
+        let mut node_ids = vec![];
+        for entry in iter {
+            let (key, _) = entry.map_err(unbonding_index_error)?;
+            if !key.starts_with(prefix) {
+                break;
+            }
+
+            let node_id =
+                String::from_utf8(key[prefix.len()..].to_vec()).map_err(unbonding_index_error)?;
+            node_ids.push(node_id);
+        }
+
+        Ok(node_ids)
+    }
+
+    /// Drops every entry that is due by `epoch` along with the owners that no
+    /// longer have stake unbonding from the given claims, in a single write
+    pub fn remove_released<'a>(
+        &self,
+        epoch: Epoch,
+        released_owners: impl IntoIterator<Item = (&'a Address, &'a NodeId)>,
+    ) -> Result<()> {
+        let epochs = self.cf(UNBONDING_RELEASE_EPOCHS_COLUMN_FAMILY)?;
+        let owners = self.cf(UNBONDING_OWNERS_COLUMN_FAMILY)?;
+        let mut batch = WriteBatch::default();
+
+        for entry in self.db.iterator_cf(epochs, IteratorMode::Start) {
+            let (key, _) = entry.map_err(unbonding_index_error)?;
+            if decode_epoch_key(&key)?.0 > epoch {
+                break;
+            }
+            batch.delete_cf(epochs, key);
+        }
+
+        for (owner, node_id) in released_owners {
+            batch.delete_cf(owners, owner_key(owner, node_id));
+        }
+
+        self.db.write(batch).map_err(unbonding_index_error)
+    }
+
+    fn cf(&self, name: &str) -> Result<&ColumnFamily> {
+        self.db
+            .cf_handle(name)
+            .ok_or_else(|| StorageError::Other(format!("missing {name} column family")))
+    }
+}
+
+/// Epochs are encoded big endian so keys are ordered by epoch
+pub(super) fn epoch_key(epoch: Epoch, node_id: &NodeId) -> Vec<u8> {
+    let mut key = epoch.to_be_bytes().to_vec();
+    key.extend_from_slice(node_id.as_bytes());
+    key
+}
+
+pub(super) fn decode_epoch_key(key: &[u8]) -> Result<(Epoch, NodeId)> {
+    if key.len() < EPOCH_KEY_LEN {
+        return Err(unbonding_index_error("invalid epoch key"));
+    }
+
+    let (epoch, node_id) = key.split_at(EPOCH_KEY_LEN);
+    let mut bytes = [0u8; EPOCH_KEY_LEN];
+    bytes.copy_from_slice(epoch);
+    let node_id = String::from_utf8(node_id.to_vec()).map_err(unbonding_index_error)?;
+
+    Ok((Epoch::from_be_bytes(bytes), node_id))
+}
+
+fn owner_key(owner: &Address, node_id: &NodeId) -> Vec<u8> {
+    let mut key = owner.0.to_vec();
+    key.extend_from_slice(node_id.as_bytes());
+    key
+}
+
+fn unbonding_index_error(err: impl std::fmt::Display) -> StorageError {
+    StorageError::Other(format!("unbonding index error: {err}"))
+}
@@ -0,0 +1,187 @@
+use std::{collections::BTreeSet, path::Path, sync::Arc};
+
+use block::{ConvergenceBlock, ProposalBlock};
+use primitives::{Address, Epoch, Round};
+use rocksdb::{IteratorMode, WriteBatch, DB};
+use serde::{Deserialize, Serialize};
+use storage_utils::{Result, StorageError};
+
+use crate::base_db_options;
+
+/// Column family holding the summary of every epoch that is over, keyed by
+/// epoch
+pub const EPOCH_SUMMARY_COLUMN_FAMILY: &str = "epoch_summaries";
+
+/// Column family holding the summary of the epoch in progress, so a node
+/// picks it up where it left it after a restart
+pub const EPOCH_IN_PROGRESS_COLUMN_FAMILY: &str = "epoch_in_progress";
+
+const EPOCH_IN_PROGRESS_KEY: &[u8] = b"current";
+
+/// Record of what happened during an epoch, written once the epoch is over
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EpochSummary {
+    pub epoch: Epoch,
+    /// Round of the first convergence block applied during the epoch
+    pub start_round: Round,
+    /// Round of the last convergence block applied during the epoch
+    pub end_round: Round,
+    /// Number of convergence blocks applied during the epoch
+    pub blocks: u64,
+    /// Addresses of the claims that mined or proposed blocks during the epoch
+    pub participants: BTreeSet<Address>,
+    /// Block rewards issued to miners during the epoch
+    pub issued_rewards: u128,
+    /// Part of the transaction fees paid during the epoch that was burned
+    pub fees_burned: u128,
+}
+
+impl EpochSummary {
+    pub fn new(epoch: Epoch, start_round: Round) -> Self {
+        Self {
+            epoch,
+            start_round,
+            end_round: start_round,
+            ..Default::default()
+        }
+    }
+
+    /// Accounts for a convergence block applied during the epoch, given the
+    /// proposal blocks it references
+    pub fn record_block(&mut self, convergence: &ConvergenceBlock, proposals: &[ProposalBlock]) {
+        self.blocks += 1;
+        self.end_round = self.end_round.max(convergence.header.round);
+        self.issued_rewards = self
+            .issued_rewards
+            .saturating_add(convergence.header.block_reward.amount);
+        self.fees_burned = self.fees_burned.saturating_add(convergence.fees.burned);
+        self.participants
+            .insert(convergence.header.miner_claim.address.clone());
+
+        for proposal in proposals
+            .iter()
+            .filter(|block| convergence.txns.contains_key(&block.hash))
+        {
+            self.participants.insert(proposal.from.address.clone());
+        }
+    }
+}
+
+/// Persistent store of the summaries of the epochs that are over. Clones
+/// share the same underlying database.
+#[derive(Debug, Clone)]
+pub struct EpochSummaryStore {
+    db: Arc<DB>,
+}
+
+impl EpochSummaryStore {
+    /// Opens the epoch summary store under `path`, creating it if missing
+    pub fn new(path: &Path) -> Result<Self> {
+        let path = path.join("epoch_summaries");
+
+        let mut options = base_db_options();
+        options.set_error_if_exists(false);
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+
+        let db = DB::open_cf(
+            &options,
+            path,
+            [EPOCH_SUMMARY_COLUMN_FAMILY, EPOCH_IN_PROGRESS_COLUMN_FAMILY],
+        )
+        .map_err(epoch_summary_store_error)?;
+
+        Ok(Self { db: Arc::new(db) })
+    }
+
+    /// Stores the summary of an epoch, overwriting any previous summary of
+    /// the same epoch
+    pub fn insert(&self, summary: &EpochSummary) -> Result<()> {
+        let value = bincode::serialize(summary).map_err(epoch_summary_store_error)?;
+
+        self.db
+            .put_cf(
+                self.cf(EPOCH_SUMMARY_COLUMN_FAMILY)?,
+                summary.epoch.to_be_bytes(),
+                value,
+            )
+            .map_err(epoch_summary_store_error)
+    }
+
+    /// Stores the summary of the epoch in progress along with the summary of
+    /// the epoch it just ended, if any, in a single write
+    pub fn record(&self, current: &EpochSummary, finished: Option<&EpochSummary>) -> Result<()> {
+        let mut batch = WriteBatch::default();
+
+        if let Some(finished) = finished {
+            let value = bincode::serialize(finished).map_err(epoch_summary_store_error)?;
+            batch.put_cf(
+                self.cf(EPOCH_SUMMARY_COLUMN_FAMILY)?,
+                finished.epoch.to_be_bytes(),
+                value,
+            );
+        }
+
+        let value = bincode::serialize(current).map_err(epoch_summary_store_error)?;
+        batch.put_cf(
+            self.cf(EPOCH_IN_PROGRESS_COLUMN_FAMILY)?,
+            EPOCH_IN_PROGRESS_KEY,
+            value,
+        );
+
+        self.db.write(batch).map_err(epoch_summary_store_error)
+    }
+
+    /// Returns the summary of the epoch in progress so far
+    pub fn in_progress(&self) -> Result<Option<EpochSummary>> {
+        let value = self
+            .db
+            .get_cf(
+                self.cf(EPOCH_IN_PROGRESS_COLUMN_FAMILY)?,
+                EPOCH_IN_PROGRESS_KEY,
+            )
+            .map_err(epoch_summary_store_error)?;
+
+        value
+            .map(|value| bincode::deserialize(&value).map_err(epoch_summary_store_error))
+            .transpose()
+    }
+
+    pub fn get(&self, epoch: Epoch) -> Result<Option<EpochSummary>> {
+        let value = self
+            .db
+            .get_cf(self.cf(EPOCH_SUMMARY_COLUMN_FAMILY)?, epoch.to_be_bytes())
+            .map_err(epoch_summary_store_error)?;
+
+        value
+            .map(|value| bincode::deserialize(&value).map_err(epoch_summary_store_error))
+            .transpose()
+    }
+
+    /// Returns the summary of the most recent epoch that is over
+    pub fn latest(&self) -> Result<Option<EpochSummary>> {
+        let mut iter = self
+            .db
+            .iterator_cf(self.cf(EPOCH_SUMMARY_COLUMN_FAMILY)?, IteratorMode::End);
+
+        match iter.next() {
+            Some(entry) => {
+                let (_, value) = entry.map_err(epoch_summary_store_error)?;
+                Ok(Some(
+                    bincode::deserialize(&value).map_err(epoch_summary_store_error)?,
+                ))
+            },
+            None => Ok(None),
+        }
+    }
+
+    fn cf(&self, name: &str) -> Result<&rocksdb::ColumnFamily> {
+        self.db
+            .cf_handle(name)
+            .ok_or_else(|| StorageError::Other(format!("missing {name} column family")))
+    }
+}
+
+fn epoch_summary_store_error(err: impl std::fmt::Display) -> StorageError {
+    StorageError::Other(format!("epoch summary store error: {err}"))
+}
@@ -1,6 +1,14 @@
+mod block_store;
 mod claim_store;
+mod epoch_summary;
+mod program_store;
+mod proof;
+mod pruner;
 pub mod result;
 mod rocksdb_adapter;
+mod slashing_store;
+mod snapshot;
+mod state_history;
 mod state_store;
 pub mod test_utils;
 mod transaction_store;
@@ -9,8 +17,16 @@ mod vrrbdb;
 mod vrrbdb_read_handle;
 mod vrrbdb_serialized_values;
 
+pub use block_store::*;
 pub use claim_store::*;
+pub use epoch_summary::*;
+pub use program_store::*;
+pub use proof::*;
+pub use pruner::*;
 pub use rocksdb_adapter::*;
+pub use slashing_store::*;
+pub use snapshot::*;
+pub use state_history::*;
 pub use state_store::*;
 pub use transaction_store::*;
 pub use types::*;
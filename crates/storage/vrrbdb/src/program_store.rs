@@ -0,0 +1,120 @@
+use std::{path::Path, sync::Arc};
+
+use rocksdb::{ColumnFamily, DB};
+use serde::{Deserialize, Serialize};
+use storage_utils::{Result, StorageError};
+use web3_pkg::web3_pkg::{Web3ContentId, Web3Package};
+
+use crate::base_db_options;
+
+/// Column family holding every deployed program, keyed by content ID
+pub const PROGRAMS_COLUMN_FAMILY: &str = "programs";
+
+/// A deployed program: the package describing it along with the WASM module
+/// it runs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProgramRecord {
+    pub package: Web3Package,
+    pub wasm: Vec<u8>,
+}
+
+impl ProgramRecord {
+    pub fn new(package: Web3Package, wasm: Vec<u8>) -> Self {
+        Self { package, wasm }
+    }
+
+    /// Returns the content ID the program is registered under, which is the
+    /// CID of its WASM module
+    pub fn content_id(&self) -> String {
+        Web3ContentId::of_raw_block(&self.wasm).cid
+    }
+
+    /// Checks that the package's WASM object is the module of the program and
+    /// returns its content ID
+    pub fn verify(&self) -> Result<String> {
+        let object = self.package.wasm_object().ok_or_else(|| {
+            StorageError::Other("program package has no wasm32-wasi object".to_string())
+        })?;
+
+        let content_id = self.content_id();
+        if object.object_cid.cid != content_id {
+            return Err(StorageError::Other(format!(
+                "program package points at {} but its wasm module is {content_id}",
+                object.object_cid.cid
+            )));
+        }
+
+        Ok(content_id)
+    }
+}
+
+/// Persistent registry of deployed programs. Clones share the same
+/// underlying database.
+#[derive(Debug, Clone)]
+pub struct ProgramStore {
+    db: Arc<DB>,
+}
+
+impl ProgramStore {
+    /// Opens the program store under `path`, creating it if missing
+    pub fn new(path: &Path) -> Result<Self> {
+        let path = path.join("programs");
+
+        let mut options = base_db_options();
+        options.set_error_if_exists(false);
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+
+        let db =
+            DB::open_cf(&options, path, [PROGRAMS_COLUMN_FAMILY]).map_err(program_store_error)?;
+
+        Ok(Self { db: Arc::new(db) })
+    }
+
+    /// Registers a program under the content ID of its WASM module and
+    /// returns the ID. Registered programs cannot be replaced.
+    pub fn insert(&self, program: &ProgramRecord) -> Result<String> {
+        let content_id = program.verify()?;
+
+        let cf = self.cf()?;
+        let registered = self
+            .db
+            .get_pinned_cf(cf, content_id.as_bytes())
+            .map_err(program_store_error)?;
+
+        if registered.is_some() {
+            return Err(StorageError::Other(format!(
+                "program {content_id} is already registered"
+            )));
+        }
+
+        let value = bincode::serialize(program).map_err(program_store_error)?;
+
+        self.db
+            .put_cf(cf, content_id.as_bytes(), value)
+            .map_err(program_store_error)?;
+
+        Ok(content_id)
+    }
+
+    pub fn get(&self, content_id: &str) -> Result<Option<ProgramRecord>> {
+        let value = self
+            .db
+            .get_cf(self.cf()?, content_id.as_bytes())
+            .map_err(program_store_error)?;
+
+        value
+            .map(|value| bincode::deserialize(&value).map_err(program_store_error))
+            .transpose()
+    }
+
+    fn cf(&self) -> Result<&ColumnFamily> {
+        self.db.cf_handle(PROGRAMS_COLUMN_FAMILY).ok_or_else(|| {
+            StorageError::Other(format!("missing {PROGRAMS_COLUMN_FAMILY} column family"))
+        })
+    }
+}
+
+fn program_store_error(err: impl std::fmt::Display) -> StorageError {
+    StorageError::Other(format!("program store error: {err}"))
+}
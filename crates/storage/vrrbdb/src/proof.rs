@@ -0,0 +1,137 @@
+use block::header::BlockHeader;
+use integral_db::{JellyfishMerkleTreeWrapper, ReadHandleFactory};
+use patriecia::{JellyfishMerkleTree, KeyHash, RootHash, SparseMerkleProof, Version};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::Sha256;
+use storage_utils::{Result, StorageError};
+
+use crate::{parse_root_hash, RocksDbAdapter};
+
+/// The `VrrbDb` trie a proof was generated from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ProofStore {
+    State,
+    Transactions,
+    Claims,
+}
+
+/// Merkle proof that a key holds a given value, or no value at all, within
+/// one of the `VrrbDb` tries.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MerkleProof {
+    pub store: ProofStore,
+    pub version: Version,
+    /// Encoded key the proof is about
+    pub key: Vec<u8>,
+    /// Encoded value stored under `key`, `None` if the proof shows the key is
+    /// not part of the trie
+    pub value: Option<Vec<u8>>,
+    /// Hex encoded root hash of the trie the proof was generated against
+    pub root_hash: String,
+    pub proof: SparseMerkleProof<Sha256>,
+}
+
+impl MerkleProof {
+    fn generate<K: Serialize>(
+        store: ProofStore,
+        tree: &JellyfishMerkleTree<RocksDbAdapter, Sha256>,
+        key: &K,
+        version: Version,
+    ) -> Result<Self> {
+        let key = bincode::serialize(key).map_err(|err| StorageError::Other(err.to_string()))?;
+
+        let (value, proof) = tree
+            .get_with_proof(KeyHash::with::<Sha256>(&key), version)
+            .map_err(|err| StorageError::Other(format!("unable to generate proof: {err}")))?;
+
+        let root_hash = tree
+            .get_root_hash(version)
+            .map_err(|err| StorageError::Other(format!("unable to read root hash: {err}")))?;
+
+        Ok(Self {
+            store,
+            version,
+            key,
+            value,
+            root_hash: hex::encode(root_hash.0),
+            proof,
+        })
+    }
+
+    /// Generates a proof for `key` against the latest version of the trie
+    /// behind `factory`
+    pub(crate) fn latest<K: Serialize>(
+        store: ProofStore,
+        factory: &ReadHandleFactory<JellyfishMerkleTree<RocksDbAdapter, Sha256>>,
+        key: &K,
+    ) -> Result<Self> {
+        let tree = Self::tree(factory);
+        let version = JellyfishMerkleTreeWrapper::new(tree.clone()).version();
+
+        Self::generate(store, &tree, key, version)
+    }
+
+    /// Generates a proof for `key` against a past `version` of the trie
+    /// behind `factory`
+    pub(crate) fn at_version<K: Serialize>(
+        store: ProofStore,
+        factory: &ReadHandleFactory<JellyfishMerkleTree<RocksDbAdapter, Sha256>>,
+        key: &K,
+        version: Version,
+    ) -> Result<Self> {
+        Self::generate(store, &Self::tree(factory), key, version)
+    }
+
+    fn tree(
+        factory: &ReadHandleFactory<JellyfishMerkleTree<RocksDbAdapter, Sha256>>,
+    ) -> JellyfishMerkleTree<RocksDbAdapter, Sha256> {
+        factory
+            .handle()
+            .enter()
+            .map(|guard| guard.clone())
+            .unwrap_or_default()
+    }
+
+    /// Decodes the value the proof is about
+    pub fn value<V: DeserializeOwned>(&self) -> Result<Option<V>> {
+        self.value
+            .as_ref()
+            .map(|value| bincode::deserialize(value))
+            .transpose()
+            .map_err(|err| StorageError::Other(err.to_string()))
+    }
+
+    /// Checks the proof against a root hash of the trie it was generated from
+    pub fn verify(&self, root_hash: RootHash) -> Result<()> {
+        self.proof
+            .verify(
+                root_hash,
+                KeyHash::with::<Sha256>(&self.key),
+                self.value.as_ref(),
+            )
+            .map_err(|err| StorageError::Other(format!("invalid proof: {err}")))
+    }
+}
+
+/// Checks a proof against the root of its trie committed to by `header`,
+/// without needing access to any `VrrbDb`.
+///
+/// Headers commit to the state their block was mined on, so the proof must
+/// have been generated at the height of `header`, see
+/// `VrrbDbReadHandle::get_account_proof_at`.
+pub fn verify_proof(proof: &MerkleProof, header: &BlockHeader) -> Result<()> {
+    let root_hash = match proof.store {
+        ProofStore::State => &header.state_roots.state_root_hash,
+        ProofStore::Transactions => &header.state_roots.transactions_root_hash,
+        ProofStore::Claims => &header.state_roots.claims_root_hash,
+    };
+
+    if root_hash.is_empty() {
+        return Err(StorageError::Other(format!(
+            "block header at height {} does not commit to a {:?} root",
+            header.block_height, proof.store
+        )));
+    }
+
+    proof.verify(parse_root_hash(root_hash)?)
+}
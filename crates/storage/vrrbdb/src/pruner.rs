@@ -0,0 +1,215 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        mpsc::{self, RecvTimeoutError},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use patriecia::Version;
+use serde::{Deserialize, Serialize};
+use storage_utils::{Result, StorageError};
+
+use crate::{
+    ClaimStoreReadHandleFactory, RocksDbAdapter, StateStoreReadHandleFactory,
+    TransactionStoreReadHandleFactory, VrrbDb,
+};
+
+/// Counters describing the work done by a `StatePruner`
+#[derive(Debug, Default)]
+pub struct PrunerMetrics {
+    runs: AtomicU64,
+    pruned_nodes: AtomicU64,
+    pending_stale_nodes: AtomicUsize,
+}
+
+/// Point in time copy of `PrunerMetrics`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrunerStats {
+    /// Number of pruning passes completed
+    pub runs: u64,
+    /// Total number of stale nodes deleted
+    pub pruned_nodes: u64,
+    /// Stale nodes left after the last pass, including the ones still
+    /// within the retention window
+    pub pending_stale_nodes: usize,
+}
+
+impl PrunerMetrics {
+    pub fn stats(&self) -> PrunerStats {
+        PrunerStats {
+            runs: self.runs.load(Ordering::Relaxed),
+            pruned_nodes: self.pruned_nodes.load(Ordering::Relaxed),
+            pending_stale_nodes: self.pending_stale_nodes.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Read side of one of the `VrrbDb` tries, used to find its current version
+#[derive(Debug, Clone)]
+pub(crate) enum PrunedTrie {
+    State(StateStoreReadHandleFactory),
+    Transactions(TransactionStoreReadHandleFactory),
+    Claims(ClaimStoreReadHandleFactory),
+}
+
+impl PrunedTrie {
+    fn label(&self) -> &'static str {
+        match self {
+            PrunedTrie::State(_) => "state",
+            PrunedTrie::Transactions(_) => "transactions",
+            PrunedTrie::Claims(_) => "claims",
+        }
+    }
+
+    fn version(&self) -> Version {
+        match self {
+            PrunedTrie::State(factory) => factory.handle().version(),
+            PrunedTrie::Transactions(factory) => factory.handle().version(),
+            PrunedTrie::Claims(factory) => factory.handle().version(),
+        }
+    }
+}
+
+/// Deletes tree nodes of the `VrrbDb` tries that are no longer reachable from
+/// any of the most recent `retained_versions` versions.
+///
+/// Versions are counted back from the current version of each trie. Older
+/// versions, and the proofs and historical reads that depend on them, stop
+/// being available once pruned.
+#[derive(Debug, Clone)]
+pub struct StatePruner {
+    stores: Vec<(PrunedTrie, Arc<RocksDbAdapter>)>,
+    retained_versions: Version,
+    batch_size: usize,
+    metrics: Arc<PrunerMetrics>,
+}
+
+impl StatePruner {
+    pub fn new(db: &VrrbDb, retained_versions: Version, batch_size: usize) -> Result<Self> {
+        if batch_size == 0 {
+            return Err(StorageError::Other(
+                "pruning batch size must be greater than zero".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            stores: db.store_databases(),
+            retained_versions,
+            batch_size,
+            metrics: Arc::new(PrunerMetrics::default()),
+        })
+    }
+
+    pub fn metrics(&self) -> Arc<PrunerMetrics> {
+        self.metrics.clone()
+    }
+
+    pub fn stats(&self) -> PrunerStats {
+        self.metrics.stats()
+    }
+
+    /// Runs a single pruning pass, deleting at most `batch_size` nodes from
+    /// each trie. Returns the number of nodes deleted.
+    pub fn prune(&self) -> Result<usize> {
+        let mut pruned = 0;
+        let mut pending = 0;
+
+        for (trie, db) in &self.stores {
+            if let Some(min_readable_version) = trie.version().checked_sub(self.retained_versions) {
+                pruned += db
+                    .prune_stale_nodes(min_readable_version, self.batch_size)
+                    .map_err(|err| StorageError::Other(format!("{}: {err}", trie.label())))?;
+            }
+
+            pending += db.stale_node_count();
+        }
+
+        self.metrics.runs.fetch_add(1, Ordering::Relaxed);
+        self.metrics
+            .pruned_nodes
+            .fetch_add(pruned as u64, Ordering::Relaxed);
+        self.metrics
+            .pending_stale_nodes
+            .store(pending, Ordering::Relaxed);
+
+        Ok(pruned)
+    }
+
+    /// Runs pruning passes until there is nothing left to prune outside of
+    /// the retention window. Returns the number of nodes deleted.
+    pub fn prune_all(&self) -> Result<usize> {
+        let mut pruned = 0;
+
+        loop {
+            match self.prune()? {
+                0 => return Ok(pruned),
+                count => pruned += count,
+            }
+        }
+    }
+
+    /// Runs a pruning pass every `interval` on a background thread until the
+    /// returned handle is stopped or dropped.
+    pub fn spawn(self, interval: Duration) -> PrunerHandle {
+        let (stop_tx, stop_rx) = mpsc::channel::<()>();
+        let metrics = self.metrics();
+
+        let thread = thread::spawn(move || loop {
+            match stop_rx.recv_timeout(interval) {
+                Err(RecvTimeoutError::Timeout) => match self.prune() {
+                    Ok(0) => {},
+                    Ok(pruned) => telemetry::info!(
+                        "pruned {pruned} stale state nodes, {} pending",
+                        self.stats().pending_stale_nodes
+                    ),
+                    Err(err) => telemetry::error!("failed to prune stale state nodes: {err}"),
+                },
+                _ => break,
+            }
+        });
+
+        PrunerHandle {
+            stop_tx,
+            thread: Some(thread),
+            metrics,
+        }
+    }
+}
+
+/// Handle to a `StatePruner` running in the background
+#[derive(Debug)]
+pub struct PrunerHandle {
+    stop_tx: mpsc::Sender<()>,
+    thread: Option<JoinHandle<()>>,
+    metrics: Arc<PrunerMetrics>,
+}
+
+impl PrunerHandle {
+    pub fn stats(&self) -> PrunerStats {
+        self.metrics.stats()
+    }
+
+    /// Stops the pruner, waiting for any pass in progress to finish
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        let _ = self.stop_tx.send(());
+
+        if let Some(thread) = self.thread.take() {
+            if thread.join().is_err() {
+                telemetry::error!("state pruner thread panicked");
+            }
+        }
+    }
+}
+
+impl Drop for PrunerHandle {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
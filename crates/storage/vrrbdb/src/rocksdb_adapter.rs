@@ -1,18 +1,22 @@
 use std::collections::hash_map::IntoIter;
-use std::collections::{hash_map::Entry, BTreeSet, HashMap};
+use std::collections::{hash_map::Entry, BTreeMap, BTreeSet, HashMap};
 
 use anyhow::{bail, Result};
 use parking_lot::RwLock;
 use patriecia::{
     KeyHash, LeafNode, Node, NodeBatch, NodeKey, OwnedValue, Preimage, StaleNodeIndex, TreeReader,
-    TreeUpdateBatch, TreeWriter, Vers, VersionedDatabase,
+    TreeUpdateBatch, TreeWriter, Vers, Version, VersionedDatabase,
 };
 use primitives::{get_vrrb_environment, Environment, DEFAULT_VRRB_DB_PATH};
-use rocksdb::{IteratorMode, DB, DEFAULT_COLUMN_FAMILY_NAME};
+use rocksdb::{IteratorMode, WriteBatch, DB, DEFAULT_COLUMN_FAMILY_NAME};
 use std::sync::Arc;
 use storage_utils::{get_node_data_dir, StorageError};
 use telemetry::error;
 
+/// Column family holding the index of tree nodes that are no longer part of
+/// the latest version of the tree, keyed by the version they became stale at
+pub const STALE_NODE_INDEX_COLUMN_FAMILY: &str = "stale_node_index";
+
 #[derive(Debug)]
 pub struct RocksDbAdapter {
     data: Arc<RwLock<RocksDbInner>>,
@@ -22,21 +26,43 @@ pub struct RocksDbAdapter {
 pub struct RocksDbInner {
     db: DB,
     stale_nodes: BTreeSet<StaleNodeIndex>,
+    /// Number of entries in the stale node index column family
+    stale_node_count: usize,
     value_history: HashMap<KeyHash, Vec<(Vers, Option<OwnedValue>)>>,
     preimages: HashMap<KeyHash, Preimage>,
+    /// Values written along with the next batch of tree nodes, keyed by
+    /// column family and key
+    staged: BTreeMap<(String, Vec<u8>), Vec<u8>>,
 }
 impl RocksDbInner {
     fn new(instance: DB) -> Self {
+        let stale_node_count = count_stale_nodes(&instance);
+
         Self {
             db: instance,
             stale_nodes: BTreeSet::new(),
+            stale_node_count,
             value_history: HashMap::new(),
             preimages: HashMap::new(),
+            staged: BTreeMap::new(),
+        }
+    }
+
+    /// Adds the staged values to `batch`, leaving none staged
+    fn drain_staged(&mut self, batch: &mut WriteBatch) -> Result<()> {
+        for ((column_family, key), value) in std::mem::take(&mut self.staged) {
+            let cf = self
+                .db
+                .cf_handle(&column_family)
+                .ok_or_else(|| anyhow::anyhow!("missing {column_family} column family"))?;
+            batch.put_cf(cf, key, value);
         }
+
+        Ok(())
     }
 }
 
-fn base_db_options() -> rocksdb::Options {
+pub(crate) fn base_db_options() -> rocksdb::Options {
     let mut options = rocksdb::Options::default();
 
     let environ = get_vrrb_environment();
@@ -65,6 +91,7 @@ fn new_db_instance(
     options: rocksdb::Options,
     path: std::path::PathBuf,
     column_family: &str,
+    extra_column_families: &[&str],
 ) -> storage_utils::Result<DB> {
     let cfs = match rocksdb::DB::list_cf(&options, &path) {
         Ok(cfs) => cfs,
@@ -80,7 +107,7 @@ fn new_db_instance(
 
     let column_family_exists = cfs.iter().any(|cf| cf == column_family);
 
-    let mut instance = rocksdb::DB::open_cf(&options, &path, cfs)
+    let mut instance = rocksdb::DB::open_cf(&options, &path, &cfs)
         .map_err(|err| StorageError::Other(err.to_string()))?;
 
     if !column_family_exists && column_family != DEFAULT_COLUMN_FAMILY_NAME {
@@ -89,17 +116,37 @@ fn new_db_instance(
             .map_err(|err| StorageError::Other(err.to_string()))?;
     }
 
+    for extra in
+        std::iter::once(STALE_NODE_INDEX_COLUMN_FAMILY).chain(extra_column_families.iter().copied())
+    {
+        if !cfs.iter().any(|cf| cf == extra) {
+            instance
+                .create_cf(extra, &options)
+                .map_err(|err| StorageError::Other(err.to_string()))?;
+        }
+    }
+
     Ok(instance)
 }
 
 impl RocksDbAdapter {
     pub fn new(path: std::path::PathBuf, column_family: &str) -> storage_utils::Result<Self> {
+        Self::with_column_families(path, column_family, &[])
+    }
+
+    /// Opens the database like `RocksDbAdapter::new`, along with column
+    /// families the tree does not use, such as those of secondary indexes
+    pub fn with_column_families(
+        path: std::path::PathBuf,
+        column_family: &str,
+        extra_column_families: &[&str],
+    ) -> storage_utils::Result<Self> {
         let mut options = base_db_options();
         options.set_error_if_exists(false);
         options.create_if_missing(true);
         options.create_missing_column_families(true);
 
-        let instance = new_db_instance(options, path, column_family)
+        let instance = new_db_instance(options, path, column_family, extra_column_families)
             .map_err(|err| StorageError::Other(err.to_string()))?;
 
         Ok(Self {
@@ -108,21 +155,170 @@ impl RocksDbAdapter {
         })
     }
 
+    /// Writes the nodes and stale node index of a tree update, along with the
+    /// staged values, in a single batch
     pub fn write_tree_update_batch(&self, batch: TreeUpdateBatch) -> Result<()> {
-        self.write_node_batch(&batch.node_batch)?;
-        batch
-            .stale_node_index_batch
-            .into_iter()
-            .map(|i| self.put_stale_node_index(i))
-            .collect::<Result<Vec<_>>>()?;
+        let mut locked = self.data.write();
+        let mut write_batch = WriteBatch::default();
+
+        locked.put_node_batch(&mut write_batch, &batch.node_batch)?;
+
+        let cf = stale_node_index_cf(&locked.db)?;
+        let mut stale_node_keys = vec![];
+        for index in &batch.stale_node_index_batch {
+            anyhow::ensure!(!locked.stale_nodes.contains(index), "Duplicated retire log");
+            let key = stale_node_index_key(index.stale_since_version, &index.node_key)?;
+            stale_node_keys.push(key);
+        }
+        for key in stale_node_keys {
+            write_batch.put_cf(cf, key, []);
+        }
+
+        locked.drain_staged(&mut write_batch)?;
+        locked.db.write(write_batch)?;
+
+        let stale_node_count = batch.stale_node_index_batch.len();
+        locked.stale_nodes.extend(batch.stale_node_index_batch);
+        locked.stale_node_count += stale_node_count;
+
         Ok(())
     }
 
     pub fn put_stale_node_index(&self, index: StaleNodeIndex) -> Result<()> {
-        let is_new_entry = self.data.write().stale_nodes.insert(index);
+        let key = stale_node_index_key(index.stale_since_version, &index.node_key)?;
+
+        let mut locked = self.data.write();
+        let is_new_entry = locked.stale_nodes.insert(index);
         anyhow::ensure!(is_new_entry, "Duplicated retire log");
+
+        let cf = stale_node_index_cf(&locked.db)?;
+        locked.db.put_cf(cf, key, [])?;
+        locked.stale_node_count += 1;
+
         Ok(())
     }
+
+    /// Stages a value to be written to `column_family` along with the next
+    /// batch of tree nodes, so that it is persisted if, and only if, the
+    /// tree is
+    pub fn stage(&self, column_family: &str, key: Vec<u8>, value: Vec<u8>) {
+        self.data
+            .write()
+            .staged
+            .insert((column_family.to_string(), key), value);
+    }
+
+    /// Writes the values still staged once the tree has nothing to write
+    pub fn write_staged(&self) -> Result<()> {
+        let mut locked = self.data.write();
+        if locked.staged.is_empty() {
+            return Ok(());
+        }
+
+        let mut batch = WriteBatch::default();
+        locked.drain_staged(&mut batch)?;
+        locked.db.write(batch)?;
+
+        Ok(())
+    }
+
+    /// Returns the value of `key` in `column_family`, staged or written
+    pub fn get_cf(&self, column_family: &str, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        let locked = self.data.read();
+
+        if let Some(value) = locked
+            .staged
+            .get(&(column_family.to_string(), key.to_vec()))
+        {
+            return Ok(Some(value.clone()));
+        }
+
+        let cf = locked
+            .db
+            .cf_handle(column_family)
+            .ok_or_else(|| anyhow::anyhow!("missing {column_family} column family"))?;
+
+        Ok(locked.db.get_cf(cf, key)?)
+    }
+
+    /// Runs `f` against the underlying database, which only holds values that
+    /// were written
+    pub fn with_db<R>(&self, f: impl FnOnce(&DB) -> R) -> R {
+        f(&self.data.read().db)
+    }
+
+    /// Returns the number of stale nodes waiting to be pruned
+    pub fn stale_node_count(&self) -> usize {
+        self.data.read().stale_node_count
+    }
+
+    /// Deletes up to `limit` nodes that became stale at or before
+    /// `min_readable_version`, oldest first, returning how many were
+    /// deleted. Versions from `min_readable_version` onwards remain readable.
+    pub fn prune_stale_nodes(&self, min_readable_version: Version, limit: usize) -> Result<usize> {
+        let mut locked = self.data.write();
+        let cf = stale_node_index_cf(&locked.db)?;
+
+        let mut batch = WriteBatch::default();
+        let mut pruned = vec![];
+
+        for entry in locked.db.iterator_cf(cf, IteratorMode::Start).take(limit) {
+            let (key, _) = entry?;
+            let (stale_since_version, node_key_bytes) = decode_stale_node_index_key(&key)?;
+
+            if stale_since_version > min_readable_version {
+                break;
+            }
+
+            batch.delete(node_key_bytes);
+            batch.delete_cf(cf, &key);
+            pruned.push(StaleNodeIndex {
+                stale_since_version,
+                node_key: bincode::deserialize(node_key_bytes)?,
+            });
+        }
+
+        locked.db.write(batch)?;
+
+        for index in &pruned {
+            locked.stale_nodes.remove(index);
+        }
+        locked.stale_node_count = locked.stale_node_count.saturating_sub(pruned.len());
+
+        Ok(pruned.len())
+    }
+}
+
+/// Counts the entries of the stale node index, only done when opening the
+/// database since the count is kept up to date from then on
+fn count_stale_nodes(db: &DB) -> usize {
+    match db.cf_handle(STALE_NODE_INDEX_COLUMN_FAMILY) {
+        Some(cf) => db.iterator_cf(cf, IteratorMode::Start).count(),
+        None => 0,
+    }
+}
+
+fn stale_node_index_cf(db: &DB) -> Result<&rocksdb::ColumnFamily> {
+    db.cf_handle(STALE_NODE_INDEX_COLUMN_FAMILY)
+        .ok_or_else(|| anyhow::anyhow!("missing {STALE_NODE_INDEX_COLUMN_FAMILY} column family"))
+}
+
+/// Stale node index keys start with the big endian version the node became
+/// stale at, so iterating the column family visits the oldest entries first.
+/// The rest of the key is the node key as stored in the default column family.
+fn stale_node_index_key(stale_since_version: Version, node_key: &NodeKey) -> Result<Vec<u8>> {
+    let mut key = stale_since_version.to_be_bytes().to_vec();
+    key.extend(bincode::serialize(node_key)?);
+
+    Ok(key)
+}
+
+fn decode_stale_node_index_key(key: &[u8]) -> Result<(Version, &[u8])> {
+    anyhow::ensure!(key.len() > 8, "invalid stale node index key");
+    let (version, node_key_bytes) = key.split_at(8);
+    let version = Version::from_be_bytes(version.try_into()?);
+
+    Ok((version, node_key_bytes))
 }
 
 // TODO: handle these unwrap
@@ -132,14 +328,17 @@ impl Clone for RocksDbAdapter {
         let mut options = base_db_options();
         options.set_error_if_exists(false);
 
-        let db = new_db_instance(options, locked.db.path().into(), self.column.as_str()).unwrap();
+        let db =
+            new_db_instance(options, locked.db.path().into(), self.column.as_str(), &[]).unwrap();
 
         Self {
             data: Arc::new(RwLock::new(RocksDbInner {
                 db,
                 stale_nodes: locked.stale_nodes.clone(),
+                stale_node_count: locked.stale_node_count,
                 value_history: locked.value_history.clone(),
                 preimages: locked.preimages.clone(),
+                staged: locked.staged.clone(),
             })),
             column: self.column.clone(),
         }
@@ -159,15 +358,18 @@ impl Default for RocksDbAdapter {
             options,
             DEFAULT_VRRB_DB_PATH.into(),
             DEFAULT_COLUMN_FAMILY_NAME,
+            &[],
         )
         .unwrap();
 
         Self {
             data: Arc::new(RwLock::new(RocksDbInner {
+                stale_node_count: count_stale_nodes(&db),
                 db,
                 stale_nodes: BTreeSet::new(),
                 value_history: HashMap::new(),
                 preimages: HashMap::new(),
+                staged: BTreeMap::new(),
             })),
             column: DEFAULT_COLUMN_FAMILY_NAME.to_string(),
         }
@@ -270,15 +472,32 @@ impl TreeReader for RocksDbAdapter {
 impl TreeWriter for RocksDbAdapter {
     fn write_node_batch(&self, node_batch: &NodeBatch) -> Result<()> {
         let mut locked = self.data.write();
+        let mut write_batch = WriteBatch::default();
+
+        locked.put_node_batch(&mut write_batch, node_batch)?;
+        locked.db.write(write_batch)?;
+
+        Ok(())
+    }
+}
+
+impl RocksDbInner {
+    /// Adds the nodes of `node_batch` to `write_batch` and records the values
+    /// it holds in the value history
+    fn put_node_batch(
+        &mut self,
+        write_batch: &mut WriteBatch,
+        node_batch: &NodeBatch,
+    ) -> Result<()> {
         for (node_key, node) in node_batch.nodes() {
             let node_key_bytes = bincode::serialize(&node_key)?;
             let node_bytes = bincode::serialize(&node)?;
-            locked.db.put(node_key_bytes, node_bytes)?;
+            write_batch.put(node_key_bytes, node_bytes);
         }
 
         for ((version, key_hash), value) in node_batch.values() {
             put_value(
-                &mut locked.value_history,
+                &mut self.value_history,
                 version.into(),
                 *key_hash,
                 value.clone(),
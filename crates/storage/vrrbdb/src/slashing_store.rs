@@ -0,0 +1,71 @@
+use std::{path::Path, sync::Arc};
+
+use block::{slashing::SlashingEvidenceId, BlockHash};
+use rocksdb::DB;
+use storage_utils::{Result, StorageError};
+
+use crate::base_db_options;
+
+/// Column family mapping the id of every piece of slashing evidence that was
+/// applied to the hash of the block that applied it
+pub const SLASHING_EVIDENCE_INDEX_COLUMN_FAMILY: &str = "slashing_evidence_index";
+
+/// Persistent record of the slashing evidence applied to the claim store.
+/// Clones share the same underlying database.
+#[derive(Debug, Clone)]
+pub struct SlashingEvidenceStore {
+    db: Arc<DB>,
+}
+
+impl SlashingEvidenceStore {
+    /// Opens the slashing evidence store under `path`, creating it if missing
+    pub fn new(path: &Path) -> Result<Self> {
+        let path = path.join("slashing_evidence");
+
+        let mut options = base_db_options();
+        options.set_error_if_exists(false);
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+
+        let db = DB::open_cf(&options, path, [SLASHING_EVIDENCE_INDEX_COLUMN_FAMILY])
+            .map_err(slashing_store_error)?;
+
+        Ok(Self { db: Arc::new(db) })
+    }
+
+    /// Returns the hash of the block that applied the given slashing
+    /// evidence, if it was applied
+    pub fn get_applying_block(
+        &self,
+        evidence_id: &SlashingEvidenceId,
+    ) -> Result<Option<BlockHash>> {
+        let hash = self
+            .db
+            .get_cf(self.cf()?, evidence_id.as_bytes())
+            .map_err(slashing_store_error)?;
+
+        hash.map(|hash| String::from_utf8(hash).map_err(slashing_store_error))
+            .transpose()
+    }
+
+    /// Records that the given slashing evidence was applied by `block_hash`
+    pub fn insert(&self, evidence_id: &SlashingEvidenceId, block_hash: &str) -> Result<()> {
+        self.db
+            .put_cf(self.cf()?, evidence_id.as_bytes(), block_hash.as_bytes())
+            .map_err(slashing_store_error)
+    }
+
+    fn cf(&self) -> Result<&rocksdb::ColumnFamily> {
+        self.db
+            .cf_handle(SLASHING_EVIDENCE_INDEX_COLUMN_FAMILY)
+            .ok_or_else(|| {
+                StorageError::Other(format!(
+                    "missing {SLASHING_EVIDENCE_INDEX_COLUMN_FAMILY} column family"
+                ))
+            })
+    }
+}
+
+fn slashing_store_error(err: impl std::fmt::Display) -> StorageError {
+    StorageError::Other(format!("slashing evidence store error: {err}"))
+}
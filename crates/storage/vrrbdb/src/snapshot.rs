@@ -0,0 +1,248 @@
+use std::{
+    fs::{self, File},
+    io::{BufReader, BufWriter, Write},
+    path::Path,
+};
+
+use block::header::StateRoots;
+use primitives::{Address, Round};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use storage_utils::{Result, StorageError};
+use vrrb_core::{account::Account, claim::Claim, transactions::TransactionKind};
+
+use crate::BlockHeight;
+
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 2;
+
+/// Maximum number of entries stored within a single snapshot chunk
+pub const DEFAULT_SNAPSHOT_CHUNK_SIZE: usize = 1_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SnapshotChunkKind {
+    Accounts,
+    Claims,
+    Transactions,
+}
+
+/// Describes one chunk of a snapshot file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotChunkInfo {
+    pub kind: SnapshotChunkKind,
+    /// Number of entries within the chunk
+    pub len: usize,
+    /// Hex encoded SHA-256 checksum of the encoded chunk
+    pub checksum: String,
+}
+
+/// Written at the start of every snapshot file. Pins the snapshot to the
+/// convergence block whose state it holds and lists the chunks that follow.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    pub format_version: u32,
+    pub block_hash: String,
+    pub block_height: BlockHeight,
+    /// Hex encoded state trie root hash the snapshot must rebuild
+    pub state_root_hash: String,
+    /// Hex encoded transaction trie root hash the snapshot must rebuild
+    pub transactions_root_hash: String,
+    /// Hex encoded claim trie root hash the snapshot must rebuild
+    pub claims_root_hash: String,
+    pub chunks: Vec<SnapshotChunkInfo>,
+}
+
+impl SnapshotManifest {
+    /// Returns the number of entries of the given kind in the snapshot
+    pub fn len_of(&self, kind: SnapshotChunkKind) -> usize {
+        self.chunks
+            .iter()
+            .filter(|chunk| chunk.kind == kind)
+            .map(|chunk| chunk.len)
+            .sum()
+    }
+}
+
+/// A transaction of the ledger along with the round of the convergence block
+/// that applied it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfirmedTransaction {
+    pub round: Round,
+    pub txn: TransactionKind,
+}
+
+/// Accounts, claims and confirmed transactions as of a convergence block,
+/// used to bootstrap a node without replaying every block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateSnapshot {
+    pub block_hash: String,
+    pub block_height: BlockHeight,
+    pub state_root_hash: String,
+    pub transactions_root_hash: String,
+    pub claims_root_hash: String,
+    pub accounts: Vec<(Address, Account)>,
+    pub claims: Vec<Claim>,
+    pub transactions: Vec<ConfirmedTransaction>,
+}
+
+impl StateSnapshot {
+    /// Returns the roots the snapshot must rebuild, which are the ones the
+    /// header of the block following the snapshot's block commits to
+    pub fn state_roots(&self) -> StateRoots {
+        StateRoots {
+            state_root_hash: self.state_root_hash.clone(),
+            transactions_root_hash: self.transactions_root_hash.clone(),
+            claims_root_hash: self.claims_root_hash.clone(),
+        }
+    }
+
+    /// Writes the snapshot to `path`, splitting its entries into chunks of at
+    /// most `chunk_size` entries. The file is written next to `path` first
+    /// and moved into place once complete.
+    pub fn write(&self, path: &Path, chunk_size: usize) -> Result<SnapshotManifest> {
+        if chunk_size == 0 {
+            return Err(StorageError::Other(
+                "snapshot chunk size must be greater than zero".to_string(),
+            ));
+        }
+
+        let mut chunks = vec![];
+        encode_chunks(
+            SnapshotChunkKind::Accounts,
+            &self.accounts,
+            chunk_size,
+            &mut chunks,
+        )?;
+        encode_chunks(
+            SnapshotChunkKind::Claims,
+            &self.claims,
+            chunk_size,
+            &mut chunks,
+        )?;
+        encode_chunks(
+            SnapshotChunkKind::Transactions,
+            &self.transactions,
+            chunk_size,
+            &mut chunks,
+        )?;
+
+        let manifest = SnapshotManifest {
+            format_version: SNAPSHOT_FORMAT_VERSION,
+            block_hash: self.block_hash.clone(),
+            block_height: self.block_height,
+            state_root_hash: self.state_root_hash.clone(),
+            transactions_root_hash: self.transactions_root_hash.clone(),
+            claims_root_hash: self.claims_root_hash.clone(),
+            chunks: chunks.iter().map(|(info, _)| info.clone()).collect(),
+        };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let tmp_path = path.with_extension("tmp");
+        let mut writer = BufWriter::new(File::create(&tmp_path)?);
+
+        bincode::serialize_into(&mut writer, &manifest).map_err(snapshot_error)?;
+        for (_, payload) in &chunks {
+            bincode::serialize_into(&mut writer, payload).map_err(snapshot_error)?;
+        }
+        writer.flush()?;
+
+        let file = writer.into_inner().map_err(snapshot_error)?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, path)?;
+
+        Ok(manifest)
+    }
+
+    /// Reads a snapshot back, checking the checksum of every chunk
+    pub fn read(path: &Path) -> Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+
+        let manifest: SnapshotManifest =
+            bincode::deserialize_from(&mut reader).map_err(snapshot_error)?;
+
+        if manifest.format_version != SNAPSHOT_FORMAT_VERSION {
+            return Err(StorageError::Other(format!(
+                "unsupported snapshot format version {}, expected {}",
+                manifest.format_version, SNAPSHOT_FORMAT_VERSION
+            )));
+        }
+
+        let mut snapshot = StateSnapshot {
+            block_hash: manifest.block_hash.clone(),
+            block_height: manifest.block_height,
+            state_root_hash: manifest.state_root_hash.clone(),
+            transactions_root_hash: manifest.transactions_root_hash.clone(),
+            claims_root_hash: manifest.claims_root_hash.clone(),
+            accounts: Vec::with_capacity(manifest.len_of(SnapshotChunkKind::Accounts)),
+            claims: Vec::with_capacity(manifest.len_of(SnapshotChunkKind::Claims)),
+            transactions: Vec::with_capacity(manifest.len_of(SnapshotChunkKind::Transactions)),
+        };
+
+        for (index, info) in manifest.chunks.iter().enumerate() {
+            let payload: Vec<u8> = bincode::deserialize_from(&mut reader).map_err(|err| {
+                StorageError::Other(format!("unable to read snapshot chunk {index}: {err}"))
+            })?;
+
+            if checksum(&payload) != info.checksum {
+                return Err(StorageError::Other(format!(
+                    "snapshot chunk {index} is corrupt: checksum mismatch"
+                )));
+            }
+
+            let len = match info.kind {
+                SnapshotChunkKind::Accounts => decode_chunk(&payload, &mut snapshot.accounts)?,
+                SnapshotChunkKind::Claims => decode_chunk(&payload, &mut snapshot.claims)?,
+                SnapshotChunkKind::Transactions => {
+                    decode_chunk(&payload, &mut snapshot.transactions)?
+                },
+            };
+
+            if len != info.len {
+                return Err(StorageError::Other(format!(
+                    "snapshot chunk {index} holds {len} entries, expected {}",
+                    info.len
+                )));
+            }
+        }
+
+        Ok(snapshot)
+    }
+}
+
+fn encode_chunks<T: Serialize>(
+    kind: SnapshotChunkKind,
+    entries: &[T],
+    chunk_size: usize,
+    chunks: &mut Vec<(SnapshotChunkInfo, Vec<u8>)>,
+) -> Result<()> {
+    for entries in entries.chunks(chunk_size) {
+        let payload = bincode::serialize(entries).map_err(snapshot_error)?;
+        let info = SnapshotChunkInfo {
+            kind,
+            len: entries.len(),
+            checksum: checksum(&payload),
+        };
+
+        chunks.push((info, payload));
+    }
+
+    Ok(())
+}
+
+fn decode_chunk<T: DeserializeOwned>(payload: &[u8], entries: &mut Vec<T>) -> Result<usize> {
+    let decoded: Vec<T> = bincode::deserialize(payload).map_err(snapshot_error)?;
+    let len = decoded.len();
+    entries.extend(decoded);
+
+    Ok(len)
+}
+
+fn checksum(payload: &[u8]) -> String {
+    hex::encode(Sha256::digest(payload))
+}
+
+fn snapshot_error(err: impl std::fmt::Display) -> StorageError {
+    StorageError::Other(format!("snapshot error: {err}"))
+}
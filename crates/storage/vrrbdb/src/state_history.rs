@@ -0,0 +1,232 @@
+use std::{collections::BTreeMap, path::Path, sync::Arc};
+
+use parking_lot::RwLock;
+use patriecia::{RootHash, Version};
+use rocksdb::{IteratorMode, DB};
+use storage_utils::{Result, StorageError};
+
+use crate::base_db_options;
+
+pub type BlockHeight = u128;
+
+/// Column family holding every state checkpoint, keyed by block height
+pub const STATE_CHECKPOINTS_COLUMN_FAMILY: &str = "state_checkpoints";
+
+/// Identifies a past version of the state trie, either by the height of the
+/// block that produced it or by the root hash the trie had at that point.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HeightOrRoot {
+    Height(BlockHeight),
+    Root(RootHash),
+}
+
+impl HeightOrRoot {
+    /// Parses a hex encoded state root hash
+    pub fn root_from_hex(root_hash: &str) -> Result<Self> {
+        parse_root_hash(root_hash).map(HeightOrRoot::Root)
+    }
+}
+
+/// Parses a hex encoded trie root hash
+pub fn parse_root_hash(root_hash: &str) -> Result<RootHash> {
+    let bytes = hex::decode(root_hash.trim_start_matches("0x"))
+        .map_err(|err| StorageError::Other(format!("invalid root hash: {err}")))?;
+
+    let root_hash = <[u8; 32]>::try_from(bytes.as_slice()).map_err(|_| {
+        StorageError::Other(format!("root hash must be 32 bytes, got {}", bytes.len()))
+    })?;
+
+    Ok(RootHash(root_hash))
+}
+
+/// Versions and root hashes of the state, transaction and claim tries right
+/// after a block was applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StateCheckpoint {
+    pub height: BlockHeight,
+    pub version: Version,
+    pub root_hash: RootHash,
+    pub transactions_version: Version,
+    pub transactions_root_hash: RootHash,
+    pub claims_version: Version,
+    pub claims_root_hash: RootHash,
+}
+
+/// Size of the version and root hash of a trie within an encoded checkpoint
+const ENCODED_TRIE_LEN: usize = 8 + 32;
+
+/// Size of an encoded checkpoint: the height followed by the version and
+/// root hash of each trie
+const ENCODED_CHECKPOINT_LEN: usize = 16 + 3 * ENCODED_TRIE_LEN;
+
+impl StateCheckpoint {
+    fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(ENCODED_CHECKPOINT_LEN);
+        bytes.extend_from_slice(&self.height.to_be_bytes());
+
+        for (version, root_hash) in [
+            (self.version, self.root_hash),
+            (self.transactions_version, self.transactions_root_hash),
+            (self.claims_version, self.claims_root_hash),
+        ] {
+            bytes.extend_from_slice(&version.to_be_bytes());
+            bytes.extend_from_slice(&root_hash.0);
+        }
+
+        bytes
+    }
+
+    fn decode(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() != ENCODED_CHECKPOINT_LEN {
+            return Err(state_history_error(format!(
+                "checkpoints must be {ENCODED_CHECKPOINT_LEN} bytes, got {}",
+                bytes.len()
+            )));
+        }
+
+        let (height, tries) = bytes.split_at(16);
+        let trie = |index: usize| -> Result<(Version, RootHash)> {
+            let start = index * ENCODED_TRIE_LEN;
+            let (version, root_hash) = tries[start..start + ENCODED_TRIE_LEN].split_at(8);
+
+            Ok((
+                Version::from_be_bytes(version.try_into().map_err(state_history_error)?),
+                RootHash(root_hash.try_into().map_err(state_history_error)?),
+            ))
+        };
+
+        let (version, root_hash) = trie(0)?;
+        let (transactions_version, transactions_root_hash) = trie(1)?;
+        let (claims_version, claims_root_hash) = trie(2)?;
+
+        Ok(Self {
+            height: BlockHeight::from_be_bytes(height.try_into().map_err(state_history_error)?),
+            version,
+            root_hash,
+            transactions_version,
+            transactions_root_hash,
+            claims_version,
+            claims_root_hash,
+        })
+    }
+}
+
+/// Index of the state trie versions produced by each applied block. The
+/// underlying trie keeps every version around, this index is what maps block
+/// heights and root hashes back to them.
+///
+/// Histories opened with `StateHistory::open` persist their checkpoints and
+/// load them back when reopened. Clones share the same index, so read handles
+/// see the checkpoints recorded after they were created.
+#[derive(Debug, Clone, Default)]
+pub struct StateHistory {
+    checkpoints: Arc<RwLock<BTreeMap<BlockHeight, StateCheckpoint>>>,
+    db: Option<Arc<DB>>,
+}
+
+impl StateHistory {
+    /// Creates a history that only lives in memory
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Opens the history stored under `path`, creating it if missing, and
+    /// loads every checkpoint recorded in it
+    pub fn open(path: &Path) -> Result<Self> {
+        let path = path.join("state_history");
+
+        let mut options = base_db_options();
+        options.set_error_if_exists(false);
+        options.create_if_missing(true);
+        options.create_missing_column_families(true);
+
+        let db = DB::open_cf(&options, path, [STATE_CHECKPOINTS_COLUMN_FAMILY])
+            .map_err(state_history_error)?;
+
+        let mut checkpoints = BTreeMap::new();
+        {
+            let cf = db
+                .cf_handle(STATE_CHECKPOINTS_COLUMN_FAMILY)
+                .ok_or_else(|| state_history_error("missing state checkpoints column family"))?;
+
+            for entry in db.iterator_cf(cf, IteratorMode::Start) {
+                let (_, value) = entry.map_err(state_history_error)?;
+                let checkpoint = StateCheckpoint::decode(&value)?;
+                checkpoints.insert(checkpoint.height, checkpoint);
+            }
+        }
+
+        Ok(Self {
+            checkpoints: Arc::new(RwLock::new(checkpoints)),
+            db: Some(Arc::new(db)),
+        })
+    }
+
+    /// Records the state produced by the block at `checkpoint.height`,
+    /// overwriting any previous checkpoint for that height
+    pub fn record(&self, checkpoint: StateCheckpoint) -> Result<()> {
+        if let Some(db) = &self.db {
+            let cf = db
+                .cf_handle(STATE_CHECKPOINTS_COLUMN_FAMILY)
+                .ok_or_else(|| state_history_error("missing state checkpoints column family"))?;
+
+            db.put_cf(cf, checkpoint.height.to_be_bytes(), checkpoint.encode())
+                .map_err(state_history_error)?;
+        }
+
+        self.checkpoints
+            .write()
+            .insert(checkpoint.height, checkpoint);
+
+        Ok(())
+    }
+
+    /// Returns the checkpoint of the most recently applied block
+    pub fn latest(&self) -> Option<StateCheckpoint> {
+        self.checkpoints.read().values().next_back().copied()
+    }
+
+    /// Returns the state as it was at `height`, that is, right after the last
+    /// block applied at or below that height. Heights past the latest applied
+    /// block have no state yet.
+    pub fn at_height(&self, height: BlockHeight) -> Option<StateCheckpoint> {
+        let checkpoints = self.checkpoints.read();
+
+        match checkpoints.keys().next_back() {
+            Some(latest) if height <= *latest => checkpoints
+                .range(..=height)
+                .next_back()
+                .map(|(_, checkpoint)| *checkpoint),
+            _ => None,
+        }
+    }
+
+    /// Returns the most recent checkpoint whose state root matches `root_hash`
+    pub fn with_root(&self, root_hash: &RootHash) -> Option<StateCheckpoint> {
+        self.checkpoints
+            .read()
+            .values()
+            .rev()
+            .find(|checkpoint| &checkpoint.root_hash == root_hash)
+            .copied()
+    }
+
+    pub fn resolve(&self, at: &HeightOrRoot) -> Option<StateCheckpoint> {
+        match at {
+            HeightOrRoot::Height(height) => self.at_height(*height),
+            HeightOrRoot::Root(root_hash) => self.with_root(root_hash),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.checkpoints.read().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.checkpoints.read().is_empty()
+    }
+}
+
+fn state_history_error(err: impl std::fmt::Display) -> StorageError {
+    StorageError::Other(format!("state history error: {err}"))
+}
@@ -1,7 +1,7 @@
 use std::{collections::HashMap, path::Path, sync::Arc};
 
 use integral_db::LeftRightTrie;
-use patriecia::RootHash;
+use patriecia::{RootHash, Version};
 use primitives::Address;
 use sha2::Sha256;
 use storage_utils::{Result, StorageError};
@@ -18,6 +18,7 @@ pub type FailedAccountUpdates = Vec<(Address, Vec<UpdateArgs>, Result<()>)>;
 #[derive(Debug, Clone)]
 pub struct StateStore {
     trie: LeftRightTrie<'static, Address, Account, RocksDbAdapter, Sha256>,
+    db: Arc<RocksDbAdapter>,
 }
 
 impl Default for StateStore {
@@ -29,9 +30,10 @@ impl Default for StateStore {
 
         let db_adapter = RocksDbAdapter::new(db_path, "state").unwrap_or_default();
 
-        let trie = LeftRightTrie::new(Arc::new(db_adapter));
+        let db = Arc::new(db_adapter);
+        let trie = LeftRightTrie::new(db.clone());
 
-        Self { trie }
+        Self { trie, db }
     }
 }
 
@@ -41,9 +43,10 @@ impl StateStore {
     pub fn new(path: &Path) -> Self {
         let path = path.join("state");
         let db_adapter = RocksDbAdapter::new(path, "state").unwrap_or_default();
-        let trie = LeftRightTrie::new(Arc::new(db_adapter));
+        let db = Arc::new(db_adapter);
+        let trie = LeftRightTrie::new(db.clone());
 
-        Self { trie }
+        Self { trie, db }
     }
 
     /// Returns new ReadHandle to the VrrDb data. As long as the returned value
@@ -288,10 +291,20 @@ impl StateStore {
             .map_err(|e| StorageError::Other(e.to_string()))
     }
 
+    /// Returns the latest version of the state trie
+    pub fn version(&self) -> Version {
+        self.read_handle().version()
+    }
+
     pub fn extend(&mut self, accounts: Vec<(Address, Option<Account>)>) {
         self.trie.extend(accounts)
     }
 
+    /// Returns the database backing the store's trie
+    pub(crate) fn db(&self) -> Arc<RocksDbAdapter> {
+        self.db.clone()
+    }
+
     pub fn factory(&self) -> StateStoreReadHandleFactory {
         let inner = self.trie.factory();
 
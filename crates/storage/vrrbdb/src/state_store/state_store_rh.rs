@@ -1,13 +1,13 @@
 use std::collections::HashMap;
 
 use integral_db::{JellyfishMerkleTreeWrapper, ReadHandleFactory};
-use patriecia::JellyfishMerkleTree;
+use patriecia::{JellyfishMerkleTree, Version};
 use primitives::Address;
 use sha2::Sha256;
 use storage_utils::{Result, StorageError};
 use vrrb_core::account::Account;
 
-use crate::RocksDbAdapter;
+use crate::{MerkleProof, ProofStore, RocksDbAdapter};
 
 #[derive(Debug, Clone)]
 pub struct StateStoreReadHandle {
@@ -28,6 +28,18 @@ impl StateStoreReadHandle {
             .map_err(|err| StorageError::Other(err.to_string()))
     }
 
+    /// Returns the account stored under `key` as of the given trie version
+    pub fn get_at(&self, key: &Address, version: Version) -> Result<Account> {
+        self.inner
+            .get(key, version)
+            .map_err(|err| StorageError::Other(err.to_string()))
+    }
+
+    /// Returns the latest version of the state trie
+    pub fn version(&self) -> Version {
+        self.inner.version()
+    }
+
     /// Get a batch of accounts by providing Vec of PublicKeysHash
     ///
     /// Returns HashMap indexed by PublicKeys and containing either
@@ -95,4 +107,16 @@ impl StateStoreReadHandleFactory {
 
         StateStoreReadHandle { inner }
     }
+
+    /// Returns a proof of the account stored under `key`, or of its absence,
+    /// against the latest state root
+    pub fn get_proof(&self, key: &Address) -> Result<MerkleProof> {
+        MerkleProof::latest(ProofStore::State, &self.inner, key)
+    }
+
+    /// Returns a proof of the account stored under `key`, or of its absence,
+    /// against `version` of the trie
+    pub fn get_proof_at(&self, key: &Address, version: Version) -> Result<MerkleProof> {
+        MerkleProof::at_version(ProofStore::State, &self.inner, key, version)
+    }
 }
@@ -0,0 +1,313 @@
+use std::sync::Arc;
+
+use primitives::{Address, Round};
+use rocksdb::{Direction, IteratorMode};
+use serde::{Deserialize, Serialize};
+use storage_utils::{Result, StorageError};
+use vrrb_core::transactions::TransactionDigest;
+
+use crate::RocksDbAdapter;
+
+/// Column family indexing the transactions every account took part in, keyed
+/// by account, round, direction and digest
+pub const ACCOUNT_TRANSACTIONS_COLUMN_FAMILY: &str = "account_transactions";
+
+/// Column family holding the round every indexed transaction was applied in,
+/// keyed by digest
+pub const TRANSACTION_ROUNDS_COLUMN_FAMILY: &str = "transaction_rounds";
+
+/// Column family holding the number of transactions every account took part
+/// in, keyed by account and direction
+pub const ACCOUNT_TRANSACTION_COUNTS_COLUMN_FAMILY: &str = "account_transaction_counts";
+
+/// Column families of the index, kept in the database of the transaction
+/// store
+pub const ACCOUNT_INDEX_COLUMN_FAMILIES: [&str; 3] = [
+    ACCOUNT_TRANSACTIONS_COLUMN_FAMILY,
+    TRANSACTION_ROUNDS_COLUMN_FAMILY,
+    ACCOUNT_TRANSACTION_COUNTS_COLUMN_FAMILY,
+];
+
+/// Maximum number of transactions returned by a single call to
+/// `AccountTransactionIndex::page`
+pub const MAX_ACCOUNT_TRANSACTIONS_PER_PAGE: usize = 100;
+
+/// Length of the account prefix of every index key
+const ACCOUNT_PREFIX_LEN: usize = 20;
+
+/// Length of the round and direction that follow the account prefix
+const POSITION_LEN: usize = 17;
+
+const DIRECTIONS: [TransactionDirection; 3] = [
+    TransactionDirection::Sent,
+    TransactionDirection::Received,
+    TransactionDirection::Stake,
+];
+
+/// The side of a transaction an account took part in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TransactionDirection {
+    /// The account sent the transaction
+    Sent,
+    /// The account received the transaction
+    Received,
+    /// The account staked or registered a claim with the transaction
+    Stake,
+}
+
+impl TransactionDirection {
+    fn to_byte(self) -> u8 {
+        match self {
+            TransactionDirection::Sent => 0,
+            TransactionDirection::Received => 1,
+            TransactionDirection::Stake => 2,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Result<Self> {
+        match byte {
+            0 => Ok(TransactionDirection::Sent),
+            1 => Ok(TransactionDirection::Received),
+            2 => Ok(TransactionDirection::Stake),
+            _ => Err(StorageError::Other(format!(
+                "invalid transaction direction {byte}"
+            ))),
+        }
+    }
+}
+
+/// A transaction an account took part in, as recorded by the index
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountTransactionEntry {
+    pub digest: TransactionDigest,
+    /// Round of the convergence block that applied the transaction
+    pub round: Round,
+    pub direction: TransactionDirection,
+}
+
+/// A page of the transactions an account took part in, ordered by round
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountTransactionPage {
+    pub entries: Vec<AccountTransactionEntry>,
+    /// Cursor to request the next page with, if there are more transactions.
+    /// Cursors stay valid as new transactions are indexed.
+    pub next_cursor: Option<String>,
+}
+
+/// Secondary index of the transaction store mapping every account to the
+/// transactions it sent, received or staked with.
+///
+/// The index lives in the database of the transaction store. Its entries are
+/// staged until the transaction trie is published, and written in the same
+/// batch as the trie, so the index never gets ahead of, or behind, the
+/// transactions it points to. Clones share the same underlying database.
+#[derive(Debug, Clone)]
+pub struct AccountTransactionIndex {
+    db: Arc<RocksDbAdapter>,
+}
+
+impl AccountTransactionIndex {
+    /// Creates the index of the transaction store backed by `db`, which must
+    /// have been opened with `ACCOUNT_INDEX_COLUMN_FAMILIES`
+    pub fn new(db: Arc<RocksDbAdapter>) -> Self {
+        Self { db }
+    }
+
+    /// Records that `address` took part in a transaction applied in `round`.
+    /// Indexing the same transaction twice has no effect.
+    pub fn insert(
+        &self,
+        address: &Address,
+        direction: TransactionDirection,
+        round: Round,
+        digest: &TransactionDigest,
+    ) -> Result<()> {
+        let mut key = address.raw_address().to_vec();
+        key.extend(round.to_be_bytes());
+        key.push(direction.to_byte());
+        key.extend(digest.digest_string().as_bytes());
+
+        if self
+            .get(ACCOUNT_TRANSACTIONS_COLUMN_FAMILY, &key)?
+            .is_some()
+        {
+            return Ok(());
+        }
+
+        let count_key = count_key(address, direction);
+        let count = self.count_of(&count_key)? + 1;
+
+        self.db
+            .stage(ACCOUNT_TRANSACTIONS_COLUMN_FAMILY, key, vec![]);
+        self.db.stage(
+            TRANSACTION_ROUNDS_COLUMN_FAMILY,
+            digest.digest_string().into_bytes(),
+            round.to_be_bytes().to_vec(),
+        );
+        self.db.stage(
+            ACCOUNT_TRANSACTION_COUNTS_COLUMN_FAMILY,
+            count_key,
+            count.to_be_bytes().to_vec(),
+        );
+
+        Ok(())
+    }
+
+    /// Returns the round the transaction with the given digest was applied
+    /// in, if it was indexed
+    pub fn round_of(&self, digest: &TransactionDigest) -> Result<Option<Round>> {
+        self.get(
+            TRANSACTION_ROUNDS_COLUMN_FAMILY,
+            digest.digest_string().as_bytes(),
+        )?
+        .map(|bytes| {
+            <[u8; 16]>::try_from(bytes.as_slice())
+                .map(Round::from_be_bytes)
+                .map_err(account_index_error)
+        })
+        .transpose()
+    }
+
+    /// Returns up to `limit` transactions `address` took part in, optionally
+    /// only those of one `direction`, starting at `from_round` or right after
+    /// the transaction `cursor` points to. `limit` is kept between 1 and
+    /// `MAX_ACCOUNT_TRANSACTIONS_PER_PAGE`.
+    pub fn page(
+        &self,
+        address: &Address,
+        direction: Option<TransactionDirection>,
+        from_round: Round,
+        limit: usize,
+        cursor: Option<&str>,
+    ) -> Result<AccountTransactionPage> {
+        let limit = limit.clamp(1, MAX_ACCOUNT_TRANSACTIONS_PER_PAGE);
+        let prefix = address.raw_address();
+
+        let mut from = prefix.to_vec();
+        match cursor {
+            Some(cursor) => from.extend(decode_cursor(cursor)?),
+            None => from.extend(from_round.to_be_bytes()),
+        }
+
+        self.db.with_db(|db| {
+            let cf = db
+                .cf_handle(ACCOUNT_TRANSACTIONS_COLUMN_FAMILY)
+                .ok_or_else(|| missing_column_family(ACCOUNT_TRANSACTIONS_COLUMN_FAMILY))?;
+
+            let mut page = AccountTransactionPage::default();
+            let mut last_suffix = None;
+
+            for entry in db.iterator_cf(cf, IteratorMode::From(&from, Direction::Forward)) {
+                let (key, _) = entry.map_err(account_index_error)?;
+                if !key.starts_with(&prefix) {
+                    break;
+                }
+
+                let suffix = key[ACCOUNT_PREFIX_LEN..].to_vec();
+                let entry = decode_entry(&suffix)?;
+
+                if cursor.is_some() && from[ACCOUNT_PREFIX_LEN..] == suffix[..] {
+                    continue;
+                }
+
+                if direction.map_or(false, |direction| direction != entry.direction) {
+                    continue;
+                }
+
+                if page.entries.len() == limit {
+                    page.next_cursor = last_suffix.map(hex::encode);
+                    break;
+                }
+
+                page.entries.push(entry);
+                last_suffix = Some(suffix);
+            }
+
+            Ok(page)
+        })
+    }
+
+    /// Returns the number of transactions `address` took part in, optionally
+    /// only those of one `direction`
+    pub fn count(
+        &self,
+        address: &Address,
+        direction: Option<TransactionDirection>,
+    ) -> Result<usize> {
+        let directions = match direction {
+            Some(direction) => vec![direction],
+            None => DIRECTIONS.to_vec(),
+        };
+
+        let mut count = 0;
+        for direction in directions {
+            count += self.count_of(&count_key(address, direction))?;
+        }
+
+        usize::try_from(count).map_err(account_index_error)
+    }
+
+    fn count_of(&self, count_key: &[u8]) -> Result<u64> {
+        self.get(ACCOUNT_TRANSACTION_COUNTS_COLUMN_FAMILY, count_key)?
+            .map_or(Ok(0), |bytes| {
+                <[u8; 8]>::try_from(bytes.as_slice())
+                    .map(u64::from_be_bytes)
+                    .map_err(account_index_error)
+            })
+    }
+
+    fn get(&self, column_family: &str, key: &[u8]) -> Result<Option<Vec<u8>>> {
+        self.db
+            .get_cf(column_family, key)
+            .map_err(account_index_error)
+    }
+}
+
+fn count_key(address: &Address, direction: TransactionDirection) -> Vec<u8> {
+    let mut key = address.raw_address().to_vec();
+    key.push(direction.to_byte());
+    key
+}
+
+fn missing_column_family(name: &str) -> StorageError {
+    StorageError::Other(format!("missing {name} column family"))
+}
+
+fn decode_entry(suffix: &[u8]) -> Result<AccountTransactionEntry> {
+    if suffix.len() <= POSITION_LEN {
+        return Err(StorageError::Other(
+            "invalid account transaction index key".to_string(),
+        ));
+    }
+
+    let (position, digest) = suffix.split_at(POSITION_LEN);
+    let round = position[..16]
+        .try_into()
+        .map(Round::from_be_bytes)
+        .map_err(account_index_error)?;
+    let direction = TransactionDirection::from_byte(position[16])?;
+    let digest = String::from_utf8(digest.to_vec())
+        .map_err(account_index_error)?
+        .parse::<TransactionDigest>()
+        .map_err(|_| StorageError::Other("invalid transaction digest in index".to_string()))?;
+
+    Ok(AccountTransactionEntry {
+        digest,
+        round,
+        direction,
+    })
+}
+
+fn decode_cursor(cursor: &str) -> Result<Vec<u8>> {
+    let suffix =
+        hex::decode(cursor).map_err(|_| StorageError::Other(format!("invalid cursor {cursor}")))?;
+
+    decode_entry(&suffix).map_err(|_| StorageError::Other(format!("invalid cursor {cursor}")))?;
+
+    Ok(suffix)
+}
+
+fn account_index_error(err: impl std::fmt::Display) -> StorageError {
+    StorageError::Other(format!("account transaction index error: {err}"))
+}
@@ -1,19 +1,24 @@
 use std::{path::Path, sync::Arc};
 
-use integral_db::{LeftRightTrie, Proof, H256};
-use patriecia::RootHash;
+use integral_db::LeftRightTrie;
+use patriecia::{RootHash, Version};
+use primitives::{Address, Round};
 use sha2::Sha256;
 use storage_utils::{Result, StorageError};
 
-use crate::RocksDbAdapter;
+use crate::{MerkleProof, RocksDbAdapter};
 
+mod account_index;
 mod transaction_store_rh;
+pub use account_index::*;
 pub use transaction_store_rh::*;
 use vrrb_core::transactions::{Transaction, TransactionDigest, TransactionKind};
 
 #[derive(Debug, Clone)]
 pub struct TransactionStore {
     trie: LeftRightTrie<'static, TransactionDigest, TransactionKind, RocksDbAdapter, Sha256>,
+    db: Arc<RocksDbAdapter>,
+    account_index: AccountTransactionIndex,
 }
 
 impl Default for TransactionStore {
@@ -23,32 +28,65 @@ impl Default for TransactionStore {
             .join("db")
             .join("transactions");
 
-        let db_adapter = RocksDbAdapter::new(db_path, "transactions").unwrap_or_default();
-
-        let trie = LeftRightTrie::new(Arc::new(db_adapter));
-
-        Self { trie }
+        let db_adapter = RocksDbAdapter::with_column_families(
+            db_path,
+            "transactions",
+            &ACCOUNT_INDEX_COLUMN_FAMILIES,
+        )
+        .unwrap_or_default();
+
+        let db = Arc::new(db_adapter);
+        let trie = LeftRightTrie::new(db.clone());
+        let account_index = AccountTransactionIndex::new(db.clone());
+
+        Self {
+            trie,
+            db,
+            account_index,
+        }
     }
 }
 
 impl TransactionStore {
-    /// Returns new, empty instance of TransactionStore
-    pub fn new(path: &Path) -> Self {
+    /// Opens the store, along with its account transaction index, under
+    /// `path`
+    pub fn new(path: &Path) -> Result<Self> {
         let path = path.join("transactions");
-        let db_adapter = RocksDbAdapter::new(path, "transactions").unwrap_or_default();
-        let trie = LeftRightTrie::new(Arc::new(db_adapter));
+        let db_adapter = RocksDbAdapter::with_column_families(
+            path,
+            "transactions",
+            &ACCOUNT_INDEX_COLUMN_FAMILIES,
+        )?;
+        let db = Arc::new(db_adapter);
+        let trie = LeftRightTrie::new(db.clone());
+        let account_index = AccountTransactionIndex::new(db.clone());
+
+        Ok(Self {
+            trie,
+            db,
+            account_index,
+        })
+    }
 
-        Self { trie }
+    /// Returns the database backing the store's trie
+    pub(crate) fn db(&self) -> Arc<RocksDbAdapter> {
+        self.db.clone()
     }
 
     pub fn factory(&self) -> TransactionStoreReadHandleFactory {
         let inner = self.trie.factory();
 
-        TransactionStoreReadHandleFactory::new(inner)
+        TransactionStoreReadHandleFactory::new(inner, self.account_index.clone())
     }
 
-    pub fn commit(&mut self) {
+    /// Publishes the pending changes of the trie. Transactions indexed since
+    /// the last commit are written in the same batch as the trie.
+    pub fn commit(&mut self) -> Result<()> {
         self.trie.publish();
+
+        self.db
+            .write_staged()
+            .map_err(|err| StorageError::Other(err.to_string()))
     }
 
     pub fn read_handle(&self) -> TransactionStoreReadHandle {
@@ -76,11 +114,33 @@ impl TransactionStore {
             .map_err(|e| StorageError::Other(e.to_string()))
     }
 
-    pub fn get_proof(&self) -> Result<Vec<Proof>> {
-        todo!()
+    /// Returns the latest version of the transaction trie
+    pub fn version(&self) -> Version {
+        self.read_handle().version()
+    }
+
+    /// Records that the account at `address` took part in a transaction
+    /// applied in `round`, so it shows up in the account's history once the
+    /// store is committed
+    pub fn index_account_transaction(
+        &self,
+        address: &Address,
+        direction: TransactionDirection,
+        round: Round,
+        digest: &TransactionDigest,
+    ) -> Result<()> {
+        self.account_index.insert(address, direction, round, digest)
+    }
+
+    /// Returns the round the transaction with the given digest was applied
+    /// in, if it was indexed
+    pub fn round_of(&self, digest: &TransactionDigest) -> Result<Option<Round>> {
+        self.account_index.round_of(digest)
     }
 
-    pub fn verify_proof(&self) -> Option<H256> {
-        todo!()
+    /// Returns a proof of the inclusion, or absence, of a transaction in the
+    /// latest version of the ledger
+    pub fn get_proof(&self, digest: &TransactionDigest) -> Result<MerkleProof> {
+        self.factory().get_proof(digest)
     }
 }
@@ -2,11 +2,15 @@ use std::collections::HashMap;
 
 use integral_db::{JellyfishMerkleTreeWrapper, ReadHandleFactory};
 use patriecia::{JellyfishMerkleTree, Version};
+use primitives::{Address, Round};
 use sha2::Sha256;
 use storage_utils::{Result, StorageError};
 use vrrb_core::transactions::{Transaction, TransactionDigest, TransactionKind};
 
-use crate::RocksDbAdapter;
+use crate::{
+    AccountTransactionIndex, AccountTransactionPage, MerkleProof, ProofStore, RocksDbAdapter,
+    TransactionDirection,
+};
 
 #[derive(Debug, Clone)]
 pub struct TransactionStoreReadHandle {
@@ -24,6 +28,22 @@ impl TransactionStoreReadHandle {
             .map_err(|err| StorageError::Other(err.to_string()))
     }
 
+    /// Returns the latest version of the transaction trie
+    pub fn version(&self) -> Version {
+        self.inner.version()
+    }
+
+    /// Returns the transaction as of the latest version of the ledger
+    pub fn get_latest(&self, key: &TransactionDigest) -> Result<TransactionKind> {
+        self.get(key, self.inner.version())
+    }
+
+    /// Returns true if the transaction is part of the ledger at its latest
+    /// version
+    pub fn contains(&self, key: &TransactionDigest) -> bool {
+        self.get_latest(key).is_ok()
+    }
+
     pub fn batch_get(
         &self,
         keys: Vec<TransactionDigest>,
@@ -72,11 +92,18 @@ impl TransactionStoreReadHandle {
 #[derive(Debug, Clone)]
 pub struct TransactionStoreReadHandleFactory {
     inner: ReadHandleFactory<JellyfishMerkleTree<RocksDbAdapter, Sha256>>,
+    account_index: AccountTransactionIndex,
 }
 
 impl TransactionStoreReadHandleFactory {
-    pub fn new(inner: ReadHandleFactory<JellyfishMerkleTree<RocksDbAdapter, Sha256>>) -> Self {
-        Self { inner }
+    pub fn new(
+        inner: ReadHandleFactory<JellyfishMerkleTree<RocksDbAdapter, Sha256>>,
+        account_index: AccountTransactionIndex,
+    ) -> Self {
+        Self {
+            inner,
+            account_index,
+        }
     }
 
     pub fn handle(&self) -> TransactionStoreReadHandle {
@@ -91,4 +118,40 @@ impl TransactionStoreReadHandleFactory {
 
         TransactionStoreReadHandle { inner }
     }
+
+    /// Returns a page of the transactions the account at `address` took part
+    /// in. See `AccountTransactionIndex::page`.
+    pub fn account_transactions(
+        &self,
+        address: &Address,
+        direction: Option<TransactionDirection>,
+        from_round: Round,
+        limit: usize,
+        cursor: Option<&str>,
+    ) -> Result<AccountTransactionPage> {
+        self.account_index
+            .page(address, direction, from_round, limit, cursor)
+    }
+
+    /// Returns the number of transactions the account at `address` took part
+    /// in
+    pub fn count_account_transactions(
+        &self,
+        address: &Address,
+        direction: Option<TransactionDirection>,
+    ) -> Result<usize> {
+        self.account_index.count(address, direction)
+    }
+
+    /// Returns a proof of the inclusion, or absence, of a transaction in the
+    /// latest version of the ledger
+    pub fn get_proof(&self, key: &TransactionDigest) -> Result<MerkleProof> {
+        MerkleProof::latest(ProofStore::Transactions, &self.inner, key)
+    }
+
+    /// Returns a proof of the inclusion, or absence, of a transaction in
+    /// `version` of the ledger
+    pub fn get_proof_at(&self, key: &TransactionDigest, version: Version) -> Result<MerkleProof> {
+        MerkleProof::at_version(ProofStore::Transactions, &self.inner, key, version)
+    }
 }
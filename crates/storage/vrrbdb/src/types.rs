@@ -3,6 +3,7 @@ use std::{collections::HashSet, str::FromStr};
 use block::{ConvergenceBlock, ProposalBlock};
 use primitives::Address;
 use vrrb_core::account::{AccountDigests, UpdateArgs};
+use vrrb_core::staking::StakeUpdate;
 use vrrb_core::transactions::{Token, Transaction, TransactionDigest, TransactionKind};
 
 /// Provides a wrapper around the current rounds `ConvergenceBlock` and
@@ -151,36 +152,21 @@ impl From<StateUpdate> for UpdateArgs {
 /// Converts a `ProposalBlock` into a `HashSet` of
 /// `StateUpdate`s which can then be easily converted into
 /// a `HashSet` of `UpdateArgs` to update Accounts, Claims, etc.
+///
+/// Fees are neither charged nor paid out here.
+/// `VrrbDb::apply_convergence_block` charges them to senders and accrues the
+/// proposer and validator shares on the claims that earned them, so they can
+/// be shared with delegators.
 impl FromBlock for HashSet<StateUpdate> {
     fn from_block(block: ProposalBlock) -> Self {
         let mut set = HashSet::new();
-        let mut proposer_fees = 0u128;
 
         block.txns.into_iter().for_each(|(_digest, txn)| {
-            let fee = txn.proposer_fee_share();
-            proposer_fees += fee;
-
-            let updates = IntoUpdates::from_txn(txn.clone());
+            let updates = IntoUpdates::from_txn(txn);
             set.insert(updates.sender_update);
             set.insert(updates.receiver_update);
-
-            let validator_fees = HashSet::<StateUpdate>::from_txn(txn.clone());
-            set.extend(validator_fees);
         });
 
-        let fee_update = StateUpdate {
-            address: block.from.address,
-            token: Some(Token::default()),
-            nonce: None,
-            amount: proposer_fees,
-            storage: None,
-            package_address: None,
-            digest: TransactionDigest::default(),
-            update_account: UpdateAccount::Fee,
-        };
-
-        set.insert(fee_update);
-
         set
     }
 }
@@ -190,10 +176,22 @@ impl FromBlock for HashSet<StateUpdate> {
 /// one for the sender and one for the receiver
 impl FromTxn for IntoUpdates {
     fn from_txn(txn: TransactionKind) -> IntoUpdates {
+        // Stakes and claim registrations don't move funds between accounts,
+        // the receiver is a claim. Stake balances are settled against the
+        // claim itself by `VrrbDb::apply_stake`.
+        let (sender_amount, receiver_amount, receiver_update_account) = match &txn {
+            TransactionKind::Stake(stake_txn) => match stake_txn.stake().get_amount() {
+                StakeUpdate::Add(amount) => (amount, 0, UpdateAccount::Claim),
+                _ => (0, 0, UpdateAccount::Claim),
+            },
+            TransactionKind::RegisterClaim(_) => (0, 0, UpdateAccount::Claim),
+            _ => (txn.amount(), txn.amount(), UpdateAccount::Receiver),
+        };
+
         let sender_update = StateUpdate {
             address: txn.sender_address(),
             token: Some(txn.token()),
-            amount: txn.amount(),
+            amount: sender_amount,
             nonce: Some(txn.nonce()),
             storage: None,
             package_address: None,
@@ -204,12 +202,12 @@ impl FromTxn for IntoUpdates {
         let receiver_update = StateUpdate {
             address: txn.receiver_address(),
             token: Some(txn.token()),
-            amount: txn.amount(),
+            amount: receiver_amount,
             nonce: None,
             storage: None,
             package_address: None,
             digest: txn.id(),
-            update_account: UpdateAccount::Receiver,
+            update_account: receiver_update_account,
         };
 
         IntoUpdates {
@@ -223,28 +221,36 @@ impl FromTxn for IntoUpdates {
 /// for fee distribution among the validators of a given tx
 impl FromTxn for HashSet<StateUpdate> {
     fn from_txn(txn: TransactionKind) -> HashSet<StateUpdate> {
-        let mut set = HashSet::new();
-        let fees = txn.validator_fee_share();
-        if let Some(mut validator_set) = txn.validators() {
-            validator_set.retain(|_, vote| *vote);
-            let validator_share = fees / (validator_set.len() as u128);
-            validator_set.iter().for_each(|(k, _v)| {
-                let address = Address::from_str(k);
-                if let Ok(addr) = address {
-                    set.insert(StateUpdate {
-                        address: addr,
-                        token: None,
-                        amount: validator_share,
-                        nonce: None,
-                        storage: None,
-                        package_address: None,
-                        digest: TransactionDigest::default(),
-                        update_account: UpdateAccount::Fee,
-                    });
-                }
-            });
+        validator_fee_updates(&txn, txn.validator_fee_share())
+    }
+}
+
+/// Splits `fees` evenly among the validators that voted for `txn`
+fn validator_fee_updates(txn: &TransactionKind, fees: u128) -> HashSet<StateUpdate> {
+    let mut set = HashSet::new();
+    if let Some(mut validator_set) = txn.validators() {
+        validator_set.retain(|_, vote| *vote);
+        if validator_set.is_empty() {
+            return set;
         }
 
-        set
+        let validator_share = fees / (validator_set.len() as u128);
+        validator_set.iter().for_each(|(k, _v)| {
+            let address = Address::from_str(k);
+            if let Ok(addr) = address {
+                set.insert(StateUpdate {
+                    address: addr,
+                    token: None,
+                    amount: validator_share,
+                    nonce: None,
+                    storage: None,
+                    package_address: None,
+                    digest: TransactionDigest::default(),
+                    update_account: UpdateAccount::Fee,
+                });
+            }
+        });
     }
+
+    set
 }
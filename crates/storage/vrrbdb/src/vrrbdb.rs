@@ -1,21 +1,37 @@
-use std::path::PathBuf;
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
 
-use block::{Block, ConvergenceBlock, GenesisBlock, GenesisRewards, ProposalBlock};
+use block::{
+    header::{BlockHeader, StateRoots},
+    slashing::SlashingEvidence,
+    Block, ConvergenceBlock, GenesisBlock, GenesisRewards, ProposalBlock,
+};
 use ethereum_types::U256;
 use patriecia::RootHash;
-use primitives::Address;
+use primitives::{Address, Epoch, Round};
 use ritelinked::LinkedHashMap;
 use storage_utils::{Result, StorageError};
-use vrrb_core::transactions::{Transaction, TransactionKind, Transfer};
+use vrrb_core::transactions::{
+    CallProgram, RegisterClaim, StakeTransaction, Transaction, TransactionKind, Transfer,
+};
 use vrrb_core::{
-    account::{Account, UpdateArgs},
+    account::{Account, AccountDigests, UpdateArgs},
     claim::Claim,
+    staking::{StakeUpdate, DEFAULT_UNBONDING_EPOCHS},
 };
 
 use crate::{
-    ClaimStore, ClaimStoreReadHandleFactory, FromTxn, IntoUpdates, StateStore,
-    StateStoreReadHandleFactory, StateUpdate, TransactionStore, TransactionStoreReadHandleFactory,
-    VrrbDbReadHandle,
+    BlockHeight, BlockPage, BlockStore, ClaimStore, ClaimStoreReadHandleFactory,
+    ConfirmedTransaction, EpochSummary, EpochSummaryStore, FromTxn, HeightOrRoot, IntoUpdates,
+    ProgramRecord, ProgramStore, PrunedTrie, RocksDbAdapter, SlashingEvidenceStore,
+    SnapshotManifest, StateCheckpoint, StateHistory, StateSnapshot, StateStore,
+    StateStoreReadHandleFactory, StateUpdate, TransactionDirection, TransactionStore,
+    TransactionStoreReadHandleFactory, VrrbDbReadHandle, DEFAULT_SNAPSHOT_CHUNK_SIZE,
 };
 
 #[derive(Debug, Clone)]
@@ -25,6 +41,8 @@ pub struct VrrbDbConfig {
     pub transaction_store_path: Option<String>,
     pub event_store_path: Option<String>,
     pub claim_store_path: Option<String>,
+    /// Number of epochs withdrawn stake stays locked before being released
+    pub unbonding_epochs: Epoch,
 }
 
 impl VrrbDbConfig {
@@ -33,6 +51,12 @@ impl VrrbDbConfig {
 
         self.clone()
     }
+
+    pub fn with_unbonding_epochs(&mut self, unbonding_epochs: Epoch) -> Self {
+        self.unbonding_epochs = unbonding_epochs;
+
+        self.clone()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -71,36 +95,216 @@ impl Default for VrrbDbConfig {
             transaction_store_path: None,
             event_store_path: None,
             claim_store_path: None,
+            unbonding_epochs: DEFAULT_UNBONDING_EPOCHS,
         }
     }
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct VrrbDb {
     state_store: StateStore,
     transaction_store: TransactionStore,
     claim_store: ClaimStore,
+    block_store: BlockStore,
+    program_store: ProgramStore,
+    slashing_evidence_store: SlashingEvidenceStore,
+    epoch_summary_store: EpochSummaryStore,
+    state_history: StateHistory,
+    unbonding_epochs: Epoch,
 }
 
 impl VrrbDb {
-    pub fn new(config: VrrbDbConfig) -> Self {
+    /// Opens every store under the configured path, creating the ones that
+    /// are missing
+    pub fn new(config: VrrbDbConfig) -> Result<Self> {
         let state_store = StateStore::new(&config.path);
-        let transaction_store = TransactionStore::new(&config.path);
-        let claim_store = ClaimStore::new(&config.path);
-
-        Self {
+        let transaction_store = TransactionStore::new(&config.path)?;
+        let claim_store = ClaimStore::new(&config.path)?;
+        let block_store = BlockStore::new(&config.path)?;
+        let program_store = ProgramStore::new(&config.path)?;
+        let slashing_evidence_store = SlashingEvidenceStore::new(&config.path)?;
+        let epoch_summary_store = EpochSummaryStore::new(&config.path)?;
+        let state_history = StateHistory::open(&config.path)?;
+
+        Ok(Self {
             state_store,
             transaction_store,
             claim_store,
+            block_store,
+            program_store,
+            slashing_evidence_store,
+            epoch_summary_store,
+            state_history,
+            unbonding_epochs: config.unbonding_epochs,
+        })
+    }
+
+    /// Produces a snapshot of the current accounts, claims and confirmed
+    /// transactions, pinned to the convergence block that produced them.
+    /// The block must be stored at `block_height` and be the last block
+    /// applied to the database.
+    pub fn snapshot_state(
+        &self,
+        block_hash: String,
+        block_height: BlockHeight,
+    ) -> Result<StateSnapshot> {
+        self.verify_snapshot_block(&block_hash, block_height)?;
+
+        let read_handle = self.read_handle();
+
+        let mut accounts = read_handle
+            .state_store_values()?
+            .into_iter()
+            .collect::<Vec<_>>();
+        accounts.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let mut claims = read_handle
+            .claim_store_values()?
+            .into_values()
+            .collect::<Vec<_>>();
+        claims.sort_by_key(|claim| claim.hash);
+
+        let mut transactions = read_handle
+            .transaction_store_values()?
+            .into_iter()
+            .map(|(digest, txn)| {
+                let round = self.transaction_store.round_of(&digest)?.ok_or_else(|| {
+                    StorageError::Other(format!("no round recorded for transaction {digest}"))
+                })?;
+
+                Ok(ConfirmedTransaction { round, txn })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        transactions.sort_by_key(|confirmed| (confirmed.round, confirmed.txn.id()));
+
+        let state_roots = self.state_roots()?;
+
+        Ok(StateSnapshot {
+            block_hash,
+            block_height,
+            state_root_hash: state_roots.state_root_hash,
+            transactions_root_hash: state_roots.transactions_root_hash,
+            claims_root_hash: state_roots.claims_root_hash,
+            accounts,
+            claims,
+            transactions,
+        })
+    }
+
+    /// Checks that the block with the given hash is stored at `block_height`
+    /// and that the state was not changed since it was applied
+    fn verify_snapshot_block(&self, block_hash: &str, block_height: BlockHeight) -> Result<()> {
+        let stored_hash = self
+            .get_block_by_height(block_height)?
+            .map(|block| block.hash());
+
+        if stored_hash.as_deref() != Some(block_hash) {
+            return Err(StorageError::Other(format!(
+                "block {block_hash} is not stored at height {block_height}"
+            )));
         }
+
+        let applied = self.state_history.latest().filter(|checkpoint| {
+            checkpoint.height == block_height
+                && self.state_root_hash().ok() == Some(checkpoint.root_hash)
+                && self.transactions_root_hash().ok() == Some(checkpoint.transactions_root_hash)
+                && self.claims_root_hash().ok() == Some(checkpoint.claims_root_hash)
+        });
+
+        if applied.is_none() {
+            return Err(StorageError::Other(format!(
+                "the state does not match the block at height {block_height}"
+            )));
+        }
+
+        Ok(())
     }
 
-    pub fn export_state(&self) {
-        todo!("implement once integral-db is ready to be consumed");
+    /// Writes a snapshot of the current state to `path`. See
+    /// `VrrbDb::snapshot_state`.
+    pub fn export_state(
+        &self,
+        block_hash: String,
+        block_height: BlockHeight,
+        path: &Path,
+    ) -> Result<SnapshotManifest> {
+        self.snapshot_state(block_hash, block_height)?
+            .write(path, DEFAULT_SNAPSHOT_CHUNK_SIZE)
     }
 
-    pub fn commit_transactions(&mut self) {
-        self.transaction_store.commit();
+    /// Rebuilds the state, transaction and claim tries, along with the
+    /// account transaction index, from the snapshot at `path`. Only empty
+    /// databases can be bootstrapped from a snapshot.
+    ///
+    /// `trusted_header` is the header of the block following the snapshot's
+    /// block, obtained from a source the caller trusts. The snapshot must
+    /// rebuild the state roots that header commits to. The tries are rebuilt
+    /// in a staging database next to `path` first, and nothing is written to
+    /// this database unless the rebuilt roots match.
+    pub fn import_state(
+        &mut self,
+        path: &Path,
+        trusted_header: &BlockHeader,
+    ) -> Result<StateSnapshot> {
+        if !self.state_store.is_empty()?
+            || !self.claim_store.is_empty()?
+            || !self.transaction_store.read_handle().is_empty()
+        {
+            return Err(StorageError::Other(
+                "snapshots can only be imported into an empty database".to_string(),
+            ));
+        }
+
+        let snapshot = StateSnapshot::read(path)?;
+
+        if trusted_header.block_height != snapshot.block_height + 1 {
+            return Err(StorageError::Other(format!(
+                "snapshot of the block at height {} cannot be verified against the header at \
+                 height {}",
+                snapshot.block_height, trusted_header.block_height
+            )));
+        }
+
+        if trusted_header.state_roots != snapshot.state_roots() {
+            return Err(StorageError::Other(format!(
+                "snapshot state roots {:?} do not match the trusted header's {:?}",
+                snapshot.state_roots(),
+                trusted_header.state_roots
+            )));
+        }
+
+        let staging_path = path.with_extension("staging");
+        let rebuilt = rebuild_state_roots(&snapshot, &staging_path);
+        fs::remove_dir_all(&staging_path)?;
+
+        let rebuilt = rebuilt?;
+        if rebuilt != snapshot.state_roots() {
+            return Err(StorageError::Other(format!(
+                "snapshot rebuilt state roots {rebuilt:?}, expected {:?}",
+                snapshot.state_roots()
+            )));
+        }
+
+        self.state_store.extend(snapshot_accounts(&snapshot));
+        self.state_store.commit();
+
+        self.claim_store.extend(snapshot_claims(&snapshot))?;
+        self.claim_store.commit();
+
+        self.transaction_store
+            .extend(snapshot_transactions(&snapshot));
+        for confirmed in &snapshot.transactions {
+            self.index_account_transaction(confirmed.round, &confirmed.txn)?;
+        }
+        self.transaction_store.commit()?;
+
+        self.checkpoint_state(snapshot.block_height)?;
+
+        Ok(snapshot)
+    }
+
+    pub fn commit_transactions(&mut self) -> Result<()> {
+        self.transaction_store.commit()
     }
 
     pub fn commit_state(&mut self) {
@@ -116,6 +320,10 @@ impl VrrbDb {
             self.state_store.factory(),
             self.transaction_store_factory(),
             self.claim_store_factory(),
+            self.block_store.clone(),
+            self.program_store.clone(),
+            self.epoch_summary_store.clone(),
+            self.state_history.clone(),
         )
     }
 
@@ -123,11 +331,21 @@ impl VrrbDb {
         state_store: StateStore,
         transaction_store: TransactionStore,
         claim_store: ClaimStore,
+        block_store: BlockStore,
+        program_store: ProgramStore,
+        slashing_evidence_store: SlashingEvidenceStore,
+        epoch_summary_store: EpochSummaryStore,
     ) -> Self {
         Self {
             state_store,
             transaction_store,
             claim_store,
+            block_store,
+            program_store,
+            slashing_evidence_store,
+            epoch_summary_store,
+            state_history: StateHistory::new(),
+            unbonding_epochs: DEFAULT_UNBONDING_EPOCHS,
         }
     }
 
@@ -136,6 +354,36 @@ impl VrrbDb {
         self.state_store.root_hash()
     }
 
+    /// Returns the state root hash right after the block at `height` was
+    /// applied.
+    pub fn state_root_at(&self, height: BlockHeight) -> Result<RootHash> {
+        self.read_handle().state_root_at(height)
+    }
+
+    /// Returns an account as it was at a past block height or state root.
+    pub fn get_account_at(&self, address: &Address, at: HeightOrRoot) -> Result<Account> {
+        self.read_handle().get_account_at(address, at)
+    }
+
+    /// Records the current versions of the state, transaction and claim tries
+    /// as the state produced by the block at `height`, so it can be queried
+    /// later on.
+    pub fn checkpoint_state(&mut self, height: BlockHeight) -> Result<StateCheckpoint> {
+        let checkpoint = StateCheckpoint {
+            height,
+            version: self.state_store.version(),
+            root_hash: self.state_store.root_hash()?,
+            transactions_version: self.transaction_store.version(),
+            transactions_root_hash: self.transaction_store.root_hash()?,
+            claims_version: self.claim_store.version(),
+            claims_root_hash: self.claim_store.root_hash()?,
+        };
+
+        self.state_history.record(checkpoint)?;
+
+        Ok(checkpoint)
+    }
+
     /// Returns the transaction store trie's root hash.
     pub fn transactions_root_hash(&self) -> Result<RootHash> {
         self.transaction_store.root_hash()
@@ -146,6 +394,51 @@ impl VrrbDb {
         self.claim_store.root_hash()
     }
 
+    /// Returns the hex encoded roots of the state, transaction and claim
+    /// tries, which the header of the next block must commit to.
+    pub fn state_roots(&self) -> Result<StateRoots> {
+        Ok(StateRoots {
+            state_root_hash: hex::encode(self.state_root_hash()?.0),
+            transactions_root_hash: hex::encode(self.transactions_root_hash()?.0),
+            claims_root_hash: hex::encode(self.claims_root_hash()?.0),
+        })
+    }
+
+    /// Checks that `header` commits to the current roots of the tries, that
+    /// is, that its block was mined on top of the state held by this
+    /// database.
+    pub fn verify_state_roots(&self, header: &BlockHeader) -> Result<()> {
+        let state_roots = self.state_roots()?;
+
+        if header.state_roots != state_roots {
+            return Err(StorageError::Other(format!(
+                "block at height {} commits to state roots {:?}, expected {:?}",
+                header.block_height, header.state_roots, state_roots
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Returns the databases backing each of the tries, along with the trie
+    /// they back
+    pub(crate) fn store_databases(&self) -> Vec<(PrunedTrie, Arc<RocksDbAdapter>)> {
+        vec![
+            (
+                PrunedTrie::State(self.state_store_factory()),
+                self.state_store.db(),
+            ),
+            (
+                PrunedTrie::Transactions(self.transaction_store_factory()),
+                self.transaction_store.db(),
+            ),
+            (
+                PrunedTrie::Claims(self.claim_store_factory()),
+                self.claim_store.db(),
+            ),
+        ]
+    }
+
     /// Produces a reader factory that can be used to generate read handles into
     /// the state trie.
     pub fn state_store_factory(&self) -> StateStoreReadHandleFactory {
@@ -164,6 +457,31 @@ impl VrrbDb {
         self.claim_store.factory()
     }
 
+    /// Persists a block to the block store
+    pub fn insert_block(&mut self, block: &Block) -> Result<()> {
+        self.block_store.insert(block)
+    }
+
+    pub fn get_block_by_hash(&self, hash: &str) -> Result<Option<Block>> {
+        self.block_store.get_by_hash(hash)
+    }
+
+    pub fn get_block_by_height(&self, height: BlockHeight) -> Result<Option<Block>> {
+        self.block_store.get_by_height(height)
+    }
+
+    /// Returns a page of genesis and convergence blocks starting at
+    /// `from_height`
+    pub fn get_blocks_range(&self, from_height: BlockHeight, limit: usize) -> Result<BlockPage> {
+        self.block_store.range(from_height, limit)
+    }
+
+    /// Returns every persisted block in the order it should be appended to
+    /// the DAG when rebuilding it
+    pub fn blocks_in_dag_order(&self) -> Result<Vec<Block>> {
+        self.block_store.blocks_in_dag_order()
+    }
+
     /// Inserts an account to current state tree.
     pub fn insert_account(&mut self, key: Address, account: Account) -> Result<()> {
         self.state_store.insert(key, account)
@@ -211,7 +529,7 @@ impl VrrbDb {
     }
 
     /// Adds multiple claims to the current claim tree.  
-    pub fn extend_claims_unchecked(&mut self, claims: Vec<(U256, Option<Claim>)>) {
+    pub fn extend_claims_unchecked(&mut self, claims: Vec<(U256, Option<Claim>)>) -> Result<()> {
         self.claim_store.extend(claims)
     }
 
@@ -221,7 +539,7 @@ impl VrrbDb {
     }
 
     /// Inserts multiple claims into the current claim trie
-    pub fn extend_claims(&mut self, claims: Vec<(U256, Option<Claim>)>) {
+    pub fn extend_claims(&mut self, claims: Vec<(U256, Option<Claim>)>) -> Result<()> {
         self.claim_store.extend(claims)
     }
 
@@ -231,8 +549,27 @@ impl VrrbDb {
     }
 
     fn apply_transfer(&mut self, read_handle: VrrbDbReadHandle, txn: Transfer) -> Result<()> {
-        let txn = TransactionKind::Transfer(txn);
+        self.apply_balance_transfer(read_handle, TransactionKind::Transfer(txn))
+    }
+
+    /// Moves the value attached to a program call from the sender to the
+    /// program's account. Execution of the program itself happens outside
+    /// of the state store.
+    fn apply_call_program(
+        &mut self,
+        read_handle: VrrbDbReadHandle,
+        txn: CallProgram,
+    ) -> Result<()> {
+        self.apply_balance_transfer(read_handle, TransactionKind::CallProgram(txn))
+    }
 
+    /// Debits `amount` from the sender and credits it to the receiver,
+    /// creating the receiver's account if it does not exist yet.
+    fn apply_balance_transfer(
+        &mut self,
+        read_handle: VrrbDbReadHandle,
+        txn: TransactionKind,
+    ) -> Result<()> {
         let sender_address = txn.sender_address();
         let receiver_address = txn.receiver_address();
 
@@ -287,9 +624,407 @@ impl VrrbDb {
         &mut self,
         read_handle: VrrbDbReadHandle,
         txn_kind: TransactionKind,
+        epoch: Epoch,
     ) -> Result<()> {
         match txn_kind {
             TransactionKind::Transfer(txn) => self.apply_transfer(read_handle, txn),
+            TransactionKind::Stake(txn) => self.apply_stake(read_handle, txn, epoch),
+            TransactionKind::RegisterClaim(txn) => self.apply_register_claim(read_handle, txn),
+            TransactionKind::CallProgram(txn) => self.apply_call_program(read_handle, txn),
+        }
+    }
+
+    /// Applies a stake update to the receiving claim and moves the
+    /// difference in stake between the sender's balance and the claim.
+    /// Only the amount that actually changed the claim's stake is debited
+    /// or credited, so a withdrawal larger than the stake cannot mint funds.
+    ///
+    /// Withdrawals are not credited right away, they are queued on the claim
+    /// and released by `release_unbonded_stake` once the unbonding period is
+    /// over.
+    fn apply_stake(
+        &mut self,
+        read_handle: VrrbDbReadHandle,
+        txn: StakeTransaction,
+        epoch: Epoch,
+    ) -> Result<()> {
+        let sender_address = txn.sender_address();
+        read_handle.get_account_by_address(&sender_address)?;
+
+        let stake = txn.stake().clone();
+        let mut claim = read_handle.get_claim_by_address(&stake.get_receiver())?;
+
+        let prev_stake = claim.get_stake_of(&sender_address);
+        let unbonding = matches!(stake.get_amount(), StakeUpdate::Withdrawal(_));
+        if unbonding {
+            claim.unbond(stake, epoch, epoch.saturating_add(self.unbonding_epochs))
+        } else {
+            claim.update_stake(stake)
+        }
+        .map_err(|err| StorageError::Other(err.to_string()))?;
+        let next_stake = claim.get_stake_of(&sender_address);
+
+        let mut digests = AccountDigests::default();
+        digests.insert_stake(txn.id());
+
+        let args = UpdateArgs {
+            address: sender_address.clone(),
+            nonce: Some(txn.nonce()),
+            credits: prev_stake
+                .checked_sub(next_stake)
+                .filter(|delta| *delta > 0 && !unbonding),
+            debits: next_stake
+                .checked_sub(prev_stake)
+                .filter(|delta| *delta > 0),
+            storage: None,
+            package_address: None,
+            digests: Some(digests),
+        };
+
+        self.state_store.update_uncommited(sender_address, args)?;
+        self.state_store.commit();
+
+        self.claim_store.insert(claim)?;
+        self.transaction_store.insert(TransactionKind::Stake(txn))?;
+
+        Ok(())
+    }
+
+    /// Registers a new claim for the sender. Claims are keyed by their hash
+    /// so registering the same claim twice is rejected.
+    fn apply_register_claim(
+        &mut self,
+        read_handle: VrrbDbReadHandle,
+        txn: RegisterClaim,
+    ) -> Result<()> {
+        let sender_address = txn.sender_address();
+        read_handle.get_account_by_address(&sender_address)?;
+
+        let claim = txn.claim().clone();
+        if read_handle.get_claim(&claim.hash)?.is_some() {
+            return Err(StorageError::RecordExists);
+        }
+
+        let args = UpdateArgs {
+            address: sender_address.clone(),
+            nonce: Some(txn.nonce()),
+            credits: None,
+            debits: None,
+            storage: None,
+            package_address: None,
+            digests: None,
+        };
+
+        self.state_store.update_uncommited(sender_address, args)?;
+        self.state_store.commit();
+
+        self.claim_store.insert(claim)?;
+        self.transaction_store
+            .insert(TransactionKind::RegisterClaim(txn))?;
+
+        Ok(())
+    }
+
+    /// Checks the slashing evidence of a block before any of the block is
+    /// applied, returning the evidence that still has to be applied. Evidence
+    /// that was already applied, by this or any other block, or that shows up
+    /// twice in the block is skipped so that an offense is only ever punished
+    /// once.
+    fn verify_slashing_evidence<'a>(
+        &self,
+        read_handle: &VrrbDbReadHandle,
+        convergence: &'a ConvergenceBlock,
+    ) -> Result<Vec<&'a SlashingEvidence>> {
+        convergence
+            .verify_slashing_evidence_root()
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+
+        let mut pending = vec![];
+        let mut seen = HashSet::new();
+        for evidence in &convergence.slashing_evidence {
+            let evidence_id = evidence.id();
+            if !seen.insert(evidence_id.clone()) {
+                continue;
+            }
+
+            if let Some(applied_by) = self
+                .slashing_evidence_store
+                .get_applying_block(&evidence_id)?
+            {
+                telemetry::info!(
+                    "slashing evidence {evidence_id} was already applied by block {applied_by}"
+                );
+                continue;
+            }
+
+            let claim = read_handle.get_claim_by_node_id(&evidence.offender)?;
+            evidence
+                .verify(claim.public_key(), claim.address())
+                .map_err(|err| StorageError::Other(err.to_string()))?;
+
+            pending.push(evidence);
+        }
+
+        Ok(pending)
+    }
+
+    /// Slashes the stake of the claim named by evidence checked by
+    /// `verify_slashing_evidence`
+    fn apply_slashing_evidence(
+        &mut self,
+        read_handle: VrrbDbReadHandle,
+        block_hash: &str,
+        evidence: &SlashingEvidence,
+    ) -> Result<()> {
+        let mut claim = read_handle.get_claim_by_node_id(&evidence.offender)?;
+
+        claim
+            .slash(evidence.penalty.clone())
+            .map_err(|err| StorageError::Other(err.to_string()))?;
+
+        self.claim_store.insert(claim)?;
+        self.slashing_evidence_store
+            .insert(&evidence.id(), block_hash)
+    }
+
+    /// Credits the owners of every unbonding entry that is due by `epoch`.
+    /// Due entries are found through the claim store's unbonding index and
+    /// the credits are committed once for the whole block.
+    fn release_unbonded_stake(
+        &mut self,
+        read_handle: VrrbDbReadHandle,
+        epoch: Epoch,
+    ) -> Result<()> {
+        let mut credits: HashMap<Address, u128> = HashMap::new();
+        for entry in self.claim_store.release_unbonded(epoch)? {
+            let credit = credits.entry(entry.owner).or_default();
+            *credit = credit.saturating_add(entry.amount);
+        }
+
+        self.credit_accounts(&read_handle, credits)
+    }
+
+    /// Checks the balance of every sender covers what all of its
+    /// transactions in a block cost, amounts and fees included, before any
+    /// of them is applied, so that a block is either applied in full or not
+    /// at all
+    fn verify_senders_can_pay(
+        &self,
+        read_handle: &VrrbDbReadHandle,
+        txns: &[(Address, TransactionKind)],
+    ) -> Result<()> {
+        let mut costs: HashMap<Address, u128> = HashMap::new();
+        for (_, txn) in txns {
+            let cost = costs.entry(txn.sender_address()).or_default();
+            *cost = cost.saturating_add(txn.cost());
+        }
+
+        for (address, cost) in costs {
+            let account = read_handle.get_account_by_address(&address)?;
+            let balance = account.credits().saturating_sub(account.debits());
+            if balance < cost {
+                return Err(StorageError::Other(format!(
+                    "account {address} cannot pay {cost} for its transactions with a balance of {balance}"
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Debits the fees paid by the transactions of a block from their
+    /// senders. `verify_senders_can_pay` has already checked they can afford
+    /// them. The fees are split between validators and proposers by
+    /// `accrue_rewards`, the burned part of them is credited to no one.
+    ///
+    /// This is the only place fees are charged, the debits are committed
+    /// once for the whole block.
+    fn charge_fees(&mut self, fees: HashMap<Address, u128>) -> Result<()> {
+        for (address, fee) in fees {
+            if fee == 0 {
+                continue;
+            }
+
+            let args = UpdateArgs {
+                address: address.clone(),
+                nonce: None,
+                credits: None,
+                debits: Some(fee),
+                storage: None,
+                package_address: None,
+                digests: None,
+            };
+
+            self.state_store.update_uncommited(address, args)?;
+        }
+
+        self.state_store.commit();
+
+        Ok(())
+    }
+
+    /// Adds the block reward to the miner's claim, the proposer fee share of
+    /// every transaction to the claim of the proposer that included it and
+    /// the validator fee share to the claims of the validators that voted for
+    /// it. Fee shares are net of the block's burn rate. Rewards earned by
+    /// addresses without a claim are dropped.
+    fn accrue_rewards(
+        &mut self,
+        read_handle: VrrbDbReadHandle,
+        convergence: &ConvergenceBlock,
+        txns: &[(Address, TransactionKind)],
+    ) -> Result<()> {
+        let mut rewards: HashMap<Address, u128> = HashMap::new();
+        *rewards
+            .entry(convergence.header.miner_claim.address.clone())
+            .or_default() += convergence.header.block_reward.amount;
+
+        for (proposer, txn) in txns {
+            let split = convergence.fees.split(txn);
+            *rewards.entry(proposer.clone()).or_default() += split.proposer;
+
+            if let Some(mut validators) = txn.validators() {
+                validators.retain(|_, vote| *vote);
+                if validators.is_empty() {
+                    continue;
+                }
+
+                let share = split.validators / (validators.len() as u128);
+                for validator in validators.keys() {
+                    if let Ok(address) = Address::from_str(validator) {
+                        *rewards.entry(address).or_default() += share;
+                    }
+                }
+            }
+        }
+
+        for (address, amount) in rewards {
+            if amount == 0 {
+                continue;
+            }
+
+            if let Ok(mut claim) = read_handle.get_claim_by_address(&address) {
+                claim.accrue_rewards(amount, convergence.header.epoch);
+                self.claim_store.insert_uncommited(claim)?;
+            }
+        }
+        self.claim_store.commit();
+
+        Ok(())
+    }
+
+    /// Pays out the rewards claims earned during previous epochs to their
+    /// owners and delegators. Claims with rewards due are found through the
+    /// claim store's payout index and the credits are committed once.
+    fn distribute_rewards(&mut self, read_handle: VrrbDbReadHandle, epoch: Epoch) -> Result<()> {
+        let mut credits: HashMap<Address, u128> = HashMap::new();
+        for (address, amount) in self.claim_store.pay_out_rewards(epoch)? {
+            let credit = credits.entry(address).or_default();
+            *credit = credit.saturating_add(amount);
+        }
+
+        self.credit_accounts(&read_handle, credits)
+    }
+
+    /// Credits every given account, creating the ones that do not exist yet,
+    /// and commits the state store once. Credits must be grouped by address
+    /// beforehand since uncommitted updates to the same account overwrite
+    /// each other.
+    fn credit_accounts(
+        &mut self,
+        read_handle: &VrrbDbReadHandle,
+        credits: HashMap<Address, u128>,
+    ) -> Result<()> {
+        let mut new_accounts = vec![];
+        for (address, amount) in credits {
+            if amount == 0 {
+                continue;
+            }
+
+            match read_handle.get_account_by_address(&address) {
+                Ok(_) => {
+                    let args = UpdateArgs {
+                        address: address.clone(),
+                        nonce: None,
+                        credits: Some(amount),
+                        debits: None,
+                        storage: None,
+                        package_address: None,
+                        digests: None,
+                    };
+                    self.state_store.update_uncommited(address, args)?;
+                },
+                Err(StorageError::Other(_)) => {
+                    let mut account = Account::new(address.clone());
+                    account.set_credits(amount);
+                    new_accounts.push((address, Some(account)));
+                },
+                Err(err) => return Err(err),
+            }
+        }
+
+        self.state_store.extend(new_accounts);
+        self.state_store.commit();
+
+        Ok(())
+    }
+
+    /// Persists the summary of an epoch that is over so it can be queried
+    /// later on
+    pub fn insert_epoch_summary(&self, summary: &EpochSummary) -> Result<()> {
+        self.epoch_summary_store.insert(summary)
+    }
+
+    /// Persists the summary of the epoch in progress, along with the summary
+    /// of the epoch that just ended when `current` opened a new one
+    pub fn record_epoch(
+        &self,
+        current: &EpochSummary,
+        finished: Option<&EpochSummary>,
+    ) -> Result<()> {
+        self.epoch_summary_store.record(current, finished)
+    }
+
+    /// Returns the summary of the epoch in progress, as last recorded
+    pub fn epoch_in_progress(&self) -> Result<Option<EpochSummary>> {
+        self.epoch_summary_store.in_progress()
+    }
+
+    /// Registers a program so it can be called by its content ID, which is
+    /// returned
+    pub fn insert_program(&self, program: &ProgramRecord) -> Result<String> {
+        self.program_store.insert(program)
+    }
+
+    /// Adds a transaction to the history of every account that took part in
+    /// it
+    fn index_account_transaction(&self, round: Round, txn: &TransactionKind) -> Result<()> {
+        let digest = txn.id();
+        let sender_address = txn.sender_address();
+
+        match txn {
+            TransactionKind::Transfer(_) | TransactionKind::CallProgram(_) => {
+                self.transaction_store.index_account_transaction(
+                    &sender_address,
+                    TransactionDirection::Sent,
+                    round,
+                    &digest,
+                )?;
+                self.transaction_store.index_account_transaction(
+                    &txn.receiver_address(),
+                    TransactionDirection::Received,
+                    round,
+                    &digest,
+                )
+            },
+            TransactionKind::Stake(_) | TransactionKind::RegisterClaim(_) => {
+                self.transaction_store.index_account_transaction(
+                    &sender_address,
+                    TransactionDirection::Stake,
+                    round,
+                    &digest,
+                )
+            },
         }
     }
 
@@ -298,7 +1033,12 @@ impl VrrbDb {
         convergence: &ConvergenceBlock,
         proposals: &[ProposalBlock],
     ) -> Result<ApplyBlockResult> {
+        self.verify_state_roots(&convergence.header)?;
+
         let read_handle = self.read_handle();
+        let slashing_evidence = self.verify_slashing_evidence(&read_handle, convergence)?;
+
+        let mut applied_txns = vec![];
         for (proposal, txn_set) in &convergence.txns {
             let block = proposals
                 .iter()
@@ -311,14 +1051,46 @@ impl VrrbDb {
             let mut txns = block.txns.clone();
             txns.retain(|digest, _| txn_set.contains(digest));
             for (_digest, txn_kind) in txns {
-                self.apply_txn(read_handle.clone(), txn_kind)?;
+                applied_txns.push((block.from.address.clone(), txn_kind));
             }
         }
 
-        self.transaction_store.commit();
+        self.verify_senders_can_pay(&read_handle, &applied_txns)?;
+
+        let mut fees: HashMap<Address, u128> = HashMap::new();
+        for (_, txn_kind) in &applied_txns {
+            let fee = fees.entry(txn_kind.sender_address()).or_default();
+            *fee = fee.saturating_add(txn_kind.fee());
+
+            self.apply_txn(
+                read_handle.clone(),
+                txn_kind.clone(),
+                convergence.header.epoch,
+            )?;
+        }
+
+        self.charge_fees(fees)?;
+
+        for evidence in slashing_evidence {
+            self.apply_slashing_evidence(read_handle.clone(), &convergence.hash, evidence)?;
+        }
+
+        self.release_unbonded_stake(read_handle.clone(), convergence.header.epoch)?;
+        self.distribute_rewards(read_handle.clone(), convergence.header.epoch)?;
+        self.accrue_rewards(read_handle.clone(), convergence, &applied_txns)?;
+
+        // The index is written along with the transactions it points to when
+        // the transaction store is committed
+        for (_, txn) in &applied_txns {
+            self.index_account_transaction(convergence.header.round, txn)?;
+        }
+
+        self.transaction_store.commit()?;
         self.state_store.commit();
 
-        let state_root_hash = self.state_store.root_hash()?;
+        let state_root_hash = self
+            .checkpoint_state(convergence.header.block_height)?
+            .root_hash;
         let transactions_root_hash = self.transaction_store.root_hash()?;
 
         Ok(ApplyBlockResult {
@@ -337,10 +1109,10 @@ impl VrrbDb {
         }
         self.apply_genesis_rewards(read_handle.clone(), &block.genesis_rewards)?;
 
-        self.transaction_store.commit();
+        self.transaction_store.commit()?;
         self.state_store.commit();
 
-        let state_root_hash = self.state_store.root_hash()?;
+        let state_root_hash = self.checkpoint_state(block.header.block_height)?.root_hash;
         let transactions_root_hash = RootHash(Default::default());
 
         Ok(ApplyBlockResult {
@@ -364,12 +1136,64 @@ impl VrrbDb {
     }
 }
 
+/// Rebuilds the tries of `snapshot` in a scratch database under `path` and
+/// returns their roots
+fn rebuild_state_roots(snapshot: &StateSnapshot, path: &Path) -> Result<StateRoots> {
+    let mut state_store = StateStore::new(path);
+    state_store.extend(snapshot_accounts(snapshot));
+    state_store.commit();
+
+    let mut transaction_store = TransactionStore::new(path)?;
+    transaction_store.extend(snapshot_transactions(snapshot));
+    transaction_store.commit()?;
+
+    let mut claim_store = ClaimStore::new(path)?;
+    claim_store.extend(snapshot_claims(snapshot))?;
+    claim_store.commit();
+
+    Ok(StateRoots {
+        state_root_hash: hex::encode(state_store.root_hash()?.0),
+        transactions_root_hash: hex::encode(transaction_store.root_hash()?.0),
+        claims_root_hash: hex::encode(claim_store.root_hash()?.0),
+    })
+}
+
+fn snapshot_accounts(snapshot: &StateSnapshot) -> Vec<(Address, Option<Account>)> {
+    snapshot
+        .accounts
+        .iter()
+        .map(|(address, account)| (address.clone(), Some(account.clone())))
+        .collect()
+}
+
+fn snapshot_claims(snapshot: &StateSnapshot) -> Vec<(U256, Option<Claim>)> {
+    snapshot
+        .claims
+        .iter()
+        .map(|claim| (claim.hash, Some(claim.clone())))
+        .collect()
+}
+
+fn snapshot_transactions(snapshot: &StateSnapshot) -> Vec<TransactionKind> {
+    snapshot
+        .transactions
+        .iter()
+        .map(|confirmed| confirmed.txn.clone())
+        .collect()
+}
+
 impl Clone for VrrbDb {
     fn clone(&self) -> VrrbDb {
         Self {
             state_store: self.state_store.clone(),
             transaction_store: self.transaction_store.clone(),
             claim_store: self.claim_store.clone(),
+            block_store: self.block_store.clone(),
+            program_store: self.program_store.clone(),
+            slashing_evidence_store: self.slashing_evidence_store.clone(),
+            epoch_summary_store: self.epoch_summary_store.clone(),
+            state_history: self.state_history.clone(),
+            unbonding_epochs: self.unbonding_epochs,
         }
     }
 }
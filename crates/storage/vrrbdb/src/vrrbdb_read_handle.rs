@@ -1,13 +1,23 @@
 use std::collections::HashMap;
 
-use primitives::{Address, NodeId};
+use block::Block;
+use ethereum_types::U256;
+use patriecia::RootHash;
+use primitives::{Address, Epoch, NodeId, Round};
 use storage_utils::StorageError;
 use vrrb_core::transactions::{TransactionDigest, TransactionKind};
-use vrrb_core::{account::Account, claim::Claim};
+use vrrb_core::{
+    account::Account,
+    claim::Claim,
+    staking::{Delegation, UnbondingEntry},
+};
 
 use crate::result::Result;
 use crate::{
-    ClaimStoreReadHandleFactory, StateStoreReadHandleFactory, TransactionStoreReadHandleFactory,
+    AccountTransactionPage, BlockHeight, BlockPage, BlockStore, ClaimStoreReadHandleFactory,
+    EpochSummary, EpochSummaryStore, HeightOrRoot, MerkleProof, ProgramRecord, ProgramStore,
+    StateCheckpoint, StateHistory, StateStoreReadHandleFactory, TransactionDirection,
+    TransactionStoreReadHandleFactory,
 };
 
 #[derive(Debug, Clone)]
@@ -15,6 +25,10 @@ pub struct VrrbDbReadHandle {
     state_store_handle_factory: StateStoreReadHandleFactory,
     transaction_store_handle_factory: TransactionStoreReadHandleFactory,
     claim_store_handle_factory: ClaimStoreReadHandleFactory,
+    block_store: BlockStore,
+    program_store: ProgramStore,
+    epoch_summary_store: EpochSummaryStore,
+    state_history: StateHistory,
 }
 
 impl VrrbDbReadHandle {
@@ -22,11 +36,19 @@ impl VrrbDbReadHandle {
         state_store_handle_factory: StateStoreReadHandleFactory,
         transaction_store_handle_factory: TransactionStoreReadHandleFactory,
         claim_store_handle_factory: ClaimStoreReadHandleFactory,
+        block_store: BlockStore,
+        program_store: ProgramStore,
+        epoch_summary_store: EpochSummaryStore,
+        state_history: StateHistory,
     ) -> Self {
         Self {
             state_store_handle_factory,
             transaction_store_handle_factory,
             claim_store_handle_factory,
+            block_store,
+            program_store,
+            epoch_summary_store,
+            state_history,
         }
     }
 
@@ -48,6 +70,18 @@ impl VrrbDbReadHandle {
         self.claim_store_handle_factory.handle().entries()
     }
 
+    /// Returns the claim with the given hash, if any
+    pub fn get_claim(&self, claim_hash: &U256) -> Result<Option<Claim>> {
+        self.claim_store_handle_factory.get_by_hash(claim_hash)
+    }
+
+    /// Returns the claim registered by the node with the given id
+    pub fn get_claim_by_node_id(&self, node_id: &NodeId) -> Result<Claim> {
+        self.claim_store_handle_factory
+            .get_by_node_id(node_id)?
+            .ok_or_else(|| StorageError::NotFound(format!("no claim found for node {node_id}")))
+    }
+
     pub fn get_account_by_address(&self, address: &Address) -> Result<Account> {
         self.state_store_handle_factory
             .handle()
@@ -56,4 +90,195 @@ impl VrrbDbReadHandle {
                 StorageError::Other(format!("Failed to get account by address: {:?}", err))
             })
     }
+
+    /// Returns the state root hash right after the block at `height` was
+    /// applied
+    pub fn state_root_at(&self, height: BlockHeight) -> Result<RootHash> {
+        self.state_history
+            .at_height(height)
+            .map(|checkpoint| checkpoint.root_hash)
+            .ok_or_else(|| StorageError::Other(format!("no state recorded at height {height}")))
+    }
+
+    /// Returns an account as it was at a past block height or state root
+    pub fn get_account_at(&self, address: &Address, at: HeightOrRoot) -> Result<Account> {
+        let checkpoint = self
+            .state_history
+            .resolve(&at)
+            .ok_or_else(|| StorageError::Other(format!("no state recorded at {at:?}")))?;
+
+        self.state_store_handle_factory
+            .handle()
+            .get_at(address, checkpoint.version)
+            .map_err(|err| {
+                StorageError::Other(format!(
+                    "Failed to get account {address} at height {}: {:?}",
+                    checkpoint.height, err
+                ))
+            })
+    }
+
+    /// Returns a proof of the account stored under `address`, or of its
+    /// absence, against the latest state root
+    pub fn get_account_proof(&self, address: &Address) -> Result<MerkleProof> {
+        self.state_store_handle_factory.get_proof(address)
+    }
+
+    /// Returns a proof of the inclusion, or absence, of a transaction in the
+    /// latest version of the ledger
+    pub fn get_transaction_proof(&self, digest: &TransactionDigest) -> Result<MerkleProof> {
+        self.transaction_store_handle_factory.get_proof(digest)
+    }
+
+    /// Returns a proof of the claim with the given hash, or of its absence,
+    /// against the latest claims root
+    pub fn get_claim_proof(&self, claim_hash: &U256) -> Result<MerkleProof> {
+        self.claim_store_handle_factory.get_proof(claim_hash)
+    }
+
+    /// Returns a proof of the account stored under `address`, or of its
+    /// absence, against the state root committed to by the header of the
+    /// block at `block_height`
+    pub fn get_account_proof_at(
+        &self,
+        address: &Address,
+        block_height: BlockHeight,
+    ) -> Result<MerkleProof> {
+        let checkpoint = self.checkpoint_committed_at(block_height)?;
+
+        self.state_store_handle_factory
+            .get_proof_at(address, checkpoint.version)
+    }
+
+    /// Returns a proof of the inclusion, or absence, of a transaction against
+    /// the transactions root committed to by the header of the block at
+    /// `block_height`
+    pub fn get_transaction_proof_at(
+        &self,
+        digest: &TransactionDigest,
+        block_height: BlockHeight,
+    ) -> Result<MerkleProof> {
+        let checkpoint = self.checkpoint_committed_at(block_height)?;
+
+        self.transaction_store_handle_factory
+            .get_proof_at(digest, checkpoint.transactions_version)
+    }
+
+    /// Returns a proof of the claim with the given hash, or of its absence,
+    /// against the claims root committed to by the header of the block at
+    /// `block_height`
+    pub fn get_claim_proof_at(
+        &self,
+        claim_hash: &U256,
+        block_height: BlockHeight,
+    ) -> Result<MerkleProof> {
+        let checkpoint = self.checkpoint_committed_at(block_height)?;
+
+        self.claim_store_handle_factory
+            .get_proof_at(claim_hash, checkpoint.claims_version)
+    }
+
+    /// Returns the state the header of the block at `block_height` commits
+    /// to, that is, the state right after the block before it was applied
+    fn checkpoint_committed_at(&self, block_height: BlockHeight) -> Result<StateCheckpoint> {
+        block_height
+            .checked_sub(1)
+            .and_then(|height| self.state_history.at_height(height))
+            .ok_or_else(|| {
+                StorageError::Other(format!(
+                    "no state recorded for the block at height {block_height}"
+                ))
+            })
+    }
+
+    /// Returns the claim registered by the given address, if any
+    pub fn get_claim_by_address(&self, address: &Address) -> Result<Claim> {
+        self.claim_store_handle_factory
+            .get_by_address(address)?
+            .ok_or_else(|| StorageError::Other(format!("no claim found for address {address}")))
+    }
+
+    /// Returns the stake `owner` withdrew that is still unbonding, ordered by
+    /// release epoch
+    pub fn get_unbonding_entries(&self, owner: &Address) -> Result<Vec<UnbondingEntry>> {
+        self.claim_store_handle_factory.get_unbonding_entries(owner)
+    }
+
+    /// Returns the stake delegated to the claim of `claim_address`
+    pub fn get_delegations(&self, claim_address: &Address) -> Result<Vec<Delegation>> {
+        Ok(self
+            .get_claim_by_address(claim_address)?
+            .delegations()
+            .to_vec())
+    }
+
+    pub fn get_block_by_hash(&self, hash: &str) -> Result<Option<Block>> {
+        self.block_store.get_by_hash(hash)
+    }
+
+    /// Returns the genesis or convergence block at the given height
+    pub fn get_block_by_height(&self, height: BlockHeight) -> Result<Option<Block>> {
+        self.block_store.get_by_height(height)
+    }
+
+    /// Returns a page of genesis and convergence blocks starting at
+    /// `from_height`
+    pub fn get_blocks_range(&self, from_height: BlockHeight, limit: usize) -> Result<BlockPage> {
+        self.block_store.range(from_height, limit)
+    }
+
+    /// Returns the `limit` highest genesis and convergence blocks ordered by
+    /// height
+    pub fn get_latest_blocks(&self, limit: usize) -> Result<Vec<Block>> {
+        self.block_store.latest(limit)
+    }
+
+    /// Returns the summary of `epoch`, once the epoch is over
+    pub fn get_epoch_summary(&self, epoch: Epoch) -> Result<Option<EpochSummary>> {
+        self.epoch_summary_store.get(epoch)
+    }
+
+    /// Returns the summary of the most recent epoch that is over
+    pub fn latest_epoch_summary(&self) -> Result<Option<EpochSummary>> {
+        self.epoch_summary_store.latest()
+    }
+
+    /// Returns a confirmed transaction
+    pub fn get_transaction(&self, digest: &TransactionDigest) -> Result<TransactionKind> {
+        self.transaction_store_handle_factory
+            .handle()
+            .get_latest(digest)
+    }
+
+    /// Returns a page of the confirmed transactions the account at
+    /// `address` took part in, ordered by the round they were applied in.
+    /// Pages after the first one are requested with the `next_cursor` of the
+    /// previous page.
+    pub fn get_account_transactions(
+        &self,
+        address: &Address,
+        direction: Option<TransactionDirection>,
+        from_round: Round,
+        limit: usize,
+        cursor: Option<&str>,
+    ) -> Result<AccountTransactionPage> {
+        self.transaction_store_handle_factory
+            .account_transactions(address, direction, from_round, limit, cursor)
+    }
+
+    /// Returns the number of confirmed transactions the account at `address`
+    /// took part in
+    pub fn count_account_transactions(
+        &self,
+        address: &Address,
+        direction: Option<TransactionDirection>,
+    ) -> Result<usize> {
+        self.transaction_store_handle_factory
+            .count_account_transactions(address, direction)
+    }
+
+    /// Returns the program registered under `content_id`
+    pub fn get_program(&self, content_id: &str) -> Result<Option<ProgramRecord>> {
+        self.program_store.get(content_id)
+    }
 }
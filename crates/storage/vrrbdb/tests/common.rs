@@ -1,10 +1,15 @@
-use std::net::SocketAddr;
+use std::{env, net::SocketAddr};
 
 use primitives::{Address, SecretKey};
 use rand::{distributions::Alphanumeric, thread_rng, Rng};
 use secp256k1::{Message, Secp256k1};
 use vrrb_core::transactions::{NewTransferArgs, TransactionKind, Transfer};
-use vrrb_core::{claim::Claim, keypair::Keypair};
+use vrrb_core::{
+    claim::Claim,
+    keypair::Keypair,
+    staking::{Stake, StakeUpdate},
+};
+use vrrbdb::{VrrbDb, VrrbDbConfig};
 
 // NOTE: this is used to generate random filenames so files created by tests
 // don't get overwritten
@@ -87,3 +92,31 @@ pub fn _generate_random_claim() -> Claim {
     )
     .unwrap()
 }
+
+pub fn _random_db() -> VrrbDb {
+    let path = env::temp_dir().join(_generate_random_string());
+
+    VrrbDb::new(VrrbDbConfig::default().with_path(path)).unwrap()
+}
+
+/// Builds a stake update signed by `keypair` and certified by a dummy quorum
+pub fn _certified_stake(keypair: &Keypair, amount: StakeUpdate, to: Option<Address>) -> Stake {
+    let mut stake = Stake::new(
+        amount,
+        keypair.miner_kp.0,
+        keypair.miner_kp.1,
+        Address::new(keypair.miner_kp.1),
+        to,
+    )
+    .unwrap();
+    stake.certify((vec![0; 96], vec![0; 96])).unwrap();
+
+    stake
+}
+
+pub fn _stake_of(db: &VrrbDb, node_id: &str) -> u128 {
+    db.read_handle()
+        .get_claim_by_node_id(&node_id.to_string())
+        .unwrap()
+        .get_stake()
+}
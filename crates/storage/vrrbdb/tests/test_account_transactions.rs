@@ -0,0 +1,248 @@
+use std::{env, path::PathBuf};
+
+use block::{fees::BlockFees, header::BlockHeader, ConvergenceBlock, ProposalBlock};
+use primitives::{Address, Round};
+use ritelinked::{LinkedHashMap, LinkedHashSet};
+use serial_test::serial;
+use vrrb_core::{
+    account::Account,
+    transactions::{Transaction, TransactionDigest, TransactionKind},
+};
+use vrrbdb::{TransactionDirection, VrrbDb, VrrbDbConfig};
+
+mod common;
+use common::{
+    _generate_random_address, _generate_random_claim, _generate_random_string,
+    _generate_random_transaction,
+};
+
+fn funded_db(address: &Address) -> VrrbDb {
+    funded_db_at(env::temp_dir().join(_generate_random_string()), address)
+}
+
+fn funded_db_at(path: PathBuf, address: &Address) -> VrrbDb {
+    let mut db = VrrbDb::new(VrrbDbConfig::default().with_path(path)).unwrap();
+
+    let mut account = Account::new(address.clone());
+    account.set_credits(u64::MAX as u128);
+    db.insert_account(address.clone(), account).unwrap();
+
+    db
+}
+
+fn apply_block(db: &mut VrrbDb, round: Round, txns: Vec<TransactionKind>) {
+    let proposal = ProposalBlock {
+        ref_block: String::new(),
+        round,
+        epoch: 0,
+        txns: txns.iter().map(|txn| (txn.id(), txn.clone())).collect(),
+        claims: LinkedHashMap::new(),
+        from: _generate_random_claim(),
+        hash: _generate_random_string(),
+        signature: None,
+    };
+
+    let (secret_key, _) = _generate_random_address();
+    let mut header =
+        BlockHeader::genesis(0, 0, 0, _generate_random_claim(), secret_key, String::new());
+    header.round = round;
+    header.block_height = round;
+    header.state_roots = db.state_roots().unwrap();
+
+    let mut convergence_txns = LinkedHashMap::new();
+    convergence_txns.insert(
+        proposal.hash.clone(),
+        txns.iter()
+            .map(|txn| txn.id())
+            .collect::<LinkedHashSet<_>>(),
+    );
+    let fees = BlockFees::new(&convergence_txns, &[proposal.clone()], 0);
+
+    let convergence = ConvergenceBlock {
+        header,
+        txns: convergence_txns,
+        claims: LinkedHashMap::new(),
+        hash: _generate_random_string(),
+        certificate: None,
+        slashing_evidence: vec![],
+        fees,
+    };
+
+    db.apply_convergence_block(&convergence, &[proposal])
+        .unwrap();
+}
+
+/// Applies one transfer from `sender` per round, starting at round 1, and
+/// returns their digests in the order they were applied
+fn apply_transfers(db: &mut VrrbDb, sender: &Address, rounds: Round) -> Vec<TransactionDigest> {
+    let (secret_key, _) = _generate_random_address();
+    let mut digests = vec![];
+
+    for round in 1..=rounds {
+        let (_, receiver) = _generate_random_address();
+        let txn = _generate_random_transaction(secret_key, sender.clone(), receiver);
+        digests.push(txn.id());
+        apply_block(db, round, vec![txn]);
+    }
+
+    digests
+}
+
+#[test]
+#[serial]
+fn account_transactions_are_paged_by_round() {
+    let (_, sender) = _generate_random_address();
+    let mut db = funded_db(&sender);
+    let digests = apply_transfers(&mut db, &sender, 5);
+
+    let read_handle = db.read_handle();
+    let first = read_handle
+        .get_account_transactions(&sender, None, 0, 2, None)
+        .unwrap();
+    assert_eq!(
+        first
+            .entries
+            .iter()
+            .map(|entry| entry.digest.clone())
+            .collect::<Vec<_>>(),
+        digests[..2]
+    );
+    assert_eq!(first.entries[0].round, 1);
+    assert_eq!(first.entries[0].direction, TransactionDirection::Sent);
+
+    let second = read_handle
+        .get_account_transactions(&sender, None, 0, 2, first.next_cursor.as_deref())
+        .unwrap();
+    assert_eq!(second.entries[0].digest, digests[2]);
+
+    let last = read_handle
+        .get_account_transactions(&sender, None, 0, 2, second.next_cursor.as_deref())
+        .unwrap();
+    assert_eq!(last.entries.len(), 1);
+    assert_eq!(last.entries[0].digest, digests[4]);
+    assert!(last.next_cursor.is_none());
+
+    let from_round = read_handle
+        .get_account_transactions(&sender, None, 4, 10, None)
+        .unwrap();
+    assert_eq!(from_round.entries.len(), 2);
+    assert_eq!(from_round.entries[0].round, 4);
+
+    let txn = read_handle.get_transaction(&digests[0]).unwrap();
+    assert_eq!(txn.sender_address(), sender);
+}
+
+#[test]
+#[serial]
+fn cursors_stay_valid_as_transactions_are_applied() {
+    let (_, sender) = _generate_random_address();
+    let mut db = funded_db(&sender);
+    apply_transfers(&mut db, &sender, 2);
+
+    let first = db
+        .read_handle()
+        .get_account_transactions(&sender, None, 0, 1, None)
+        .unwrap();
+
+    let (secret_key, _) = _generate_random_address();
+    let (_, receiver) = _generate_random_address();
+    let txn = _generate_random_transaction(secret_key, sender.clone(), receiver);
+    apply_block(&mut db, 3, vec![txn.clone()]);
+
+    let rest = db
+        .read_handle()
+        .get_account_transactions(&sender, None, 0, 10, first.next_cursor.as_deref())
+        .unwrap();
+    assert_eq!(rest.entries.len(), 2);
+    assert_eq!(rest.entries[1].digest, txn.id());
+}
+
+#[test]
+#[serial]
+fn account_transactions_are_filtered_and_counted_by_direction() {
+    let (_, sender) = _generate_random_address();
+    let mut db = funded_db(&sender);
+    apply_transfers(&mut db, &sender, 3);
+
+    let (secret_key, _) = _generate_random_address();
+    let (_, receiver) = _generate_random_address();
+    let txn = _generate_random_transaction(secret_key, sender.clone(), receiver.clone());
+    apply_block(&mut db, 4, vec![txn.clone()]);
+
+    let read_handle = db.read_handle();
+    let received = read_handle
+        .get_account_transactions(&receiver, Some(TransactionDirection::Received), 0, 10, None)
+        .unwrap();
+    assert_eq!(received.entries.len(), 1);
+    assert_eq!(received.entries[0].digest, txn.id());
+    assert_eq!(received.entries[0].round, 4);
+
+    let sent = read_handle
+        .get_account_transactions(&receiver, Some(TransactionDirection::Sent), 0, 10, None)
+        .unwrap();
+    assert!(sent.entries.is_empty());
+
+    assert_eq!(
+        read_handle
+            .count_account_transactions(&sender, None)
+            .unwrap(),
+        4
+    );
+    assert_eq!(
+        read_handle
+            .count_account_transactions(&sender, Some(TransactionDirection::Received))
+            .unwrap(),
+        0
+    );
+    assert_eq!(
+        read_handle
+            .count_account_transactions(&receiver, None)
+            .unwrap(),
+        1
+    );
+}
+
+#[test]
+#[serial]
+fn account_transactions_are_persisted_with_the_transactions() {
+    let path = env::temp_dir().join(_generate_random_string());
+    let (_, sender) = _generate_random_address();
+    let mut db = funded_db_at(path.clone(), &sender);
+    let digests = apply_transfers(&mut db, &sender, 3);
+    drop(db);
+
+    // The index lives in the database of the transaction store
+    assert!(!path.join("transaction_index").exists());
+
+    let db = VrrbDb::new(VrrbDbConfig::default().with_path(path)).unwrap();
+    let read_handle = db.read_handle();
+
+    let page = read_handle
+        .get_account_transactions(&sender, None, 0, 10, None)
+        .unwrap();
+    assert_eq!(
+        page.entries
+            .iter()
+            .map(|entry| entry.digest.clone())
+            .collect::<Vec<_>>(),
+        digests
+    );
+    assert_eq!(
+        read_handle
+            .count_account_transactions(&sender, Some(TransactionDirection::Sent))
+            .unwrap(),
+        3
+    );
+}
+
+#[test]
+#[serial]
+fn invalid_cursors_are_rejected() {
+    let (_, sender) = _generate_random_address();
+    let db = funded_db(&sender);
+
+    assert!(db
+        .read_handle()
+        .get_account_transactions(&sender, None, 0, 10, Some("not a cursor"))
+        .is_err());
+}
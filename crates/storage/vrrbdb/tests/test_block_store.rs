@@ -0,0 +1,164 @@
+use std::env;
+
+use block::{
+    header::BlockHeader, Block, ConvergenceBlock, GenesisBlock, GenesisRewards, ProposalBlock,
+};
+use primitives::ChainParameters;
+use ritelinked::LinkedHashMap;
+use vrrbdb::{BlockStore, MAX_BLOCKS_PER_PAGE};
+
+mod common;
+use common::{_generate_random_address, _generate_random_claim, _generate_random_string};
+
+fn new_block_store() -> BlockStore {
+    let path = env::temp_dir().join(_generate_random_string());
+    BlockStore::new(&path).unwrap()
+}
+
+fn header(round: u128, block_height: u128) -> BlockHeader {
+    let (secret_key, _) = _generate_random_address();
+    let mut header =
+        BlockHeader::genesis(0, 0, 0, _generate_random_claim(), secret_key, String::new());
+    header.round = round;
+    header.block_height = block_height;
+
+    header
+}
+
+fn genesis() -> Block {
+    Block::Genesis {
+        block: GenesisBlock {
+            header: header(0, 0),
+            genesis_rewards: GenesisRewards(LinkedHashMap::new()),
+            claims: LinkedHashMap::new(),
+            chain_parameters: ChainParameters::default(),
+            hash: "genesis".to_string(),
+            certificate: None,
+        },
+    }
+}
+
+fn proposal(round: u128, ref_block: &str, hash: &str) -> Block {
+    Block::Proposal {
+        block: ProposalBlock {
+            ref_block: ref_block.to_string(),
+            round,
+            epoch: 0,
+            txns: LinkedHashMap::new(),
+            claims: LinkedHashMap::new(),
+            from: _generate_random_claim(),
+            hash: hash.to_string(),
+            signature: None,
+        },
+    }
+}
+
+fn convergence(round: u128, block_height: u128, hash: &str) -> Block {
+    Block::Convergence {
+        block: ConvergenceBlock {
+            header: header(round, block_height),
+            txns: LinkedHashMap::new(),
+            claims: LinkedHashMap::new(),
+            hash: hash.to_string(),
+            certificate: None,
+            slashing_evidence: vec![],
+            fees: Default::default(),
+        },
+    }
+}
+
+#[test]
+fn blocks_can_be_read_back_by_hash_height_and_round() {
+    let store = new_block_store();
+
+    let genesis = genesis();
+    let proposal = proposal(1, "genesis", "proposal_1");
+    let convergence = convergence(1, 1, "convergence_1");
+
+    for block in [&genesis, &proposal, &convergence] {
+        store.insert(block).unwrap();
+    }
+
+    assert_eq!(store.len().unwrap(), 3);
+    assert_eq!(
+        store.get_by_hash("proposal_1").unwrap(),
+        Some(proposal.clone())
+    );
+    assert_eq!(store.get_by_hash("missing").unwrap(), None);
+
+    assert_eq!(store.get_by_height(0).unwrap(), Some(genesis));
+    assert_eq!(store.get_by_height(1).unwrap(), Some(convergence.clone()));
+    assert_eq!(store.get_by_height(2).unwrap(), None);
+    assert_eq!(store.latest_height().unwrap(), Some(1));
+
+    assert_eq!(store.get_by_round(1).unwrap(), vec![proposal, convergence]);
+}
+
+#[test]
+fn blocks_are_returned_in_dag_order() {
+    let store = new_block_store();
+
+    let blocks = vec![
+        genesis(),
+        proposal(1, "genesis", "proposal_1"),
+        convergence(1, 1, "convergence_1"),
+        proposal(2, "convergence_1", "proposal_2"),
+        convergence(2, 2, "convergence_2"),
+    ];
+
+    for block in blocks.iter().rev() {
+        store.insert(block).unwrap();
+    }
+
+    assert_eq!(store.blocks_in_dag_order().unwrap(), blocks);
+}
+
+#[test]
+fn block_ranges_are_paginated_by_height() {
+    let store = new_block_store();
+
+    store.insert(&genesis()).unwrap();
+    for height in 1..=5 {
+        store
+            .insert(&convergence(
+                height,
+                height,
+                &format!("convergence_{height}"),
+            ))
+            .unwrap();
+    }
+
+    let page = store.range(1, 2).unwrap();
+    let hashes: Vec<String> = page.blocks.iter().map(Block::hash).collect();
+    assert_eq!(hashes, vec!["convergence_1", "convergence_2"]);
+    assert_eq!(page.next_height, Some(3));
+
+    let page = store.range(4, 10).unwrap();
+    assert_eq!(page.blocks.len(), 2);
+    assert_eq!(page.next_height, None);
+
+    let page = store.range(0, usize::MAX).unwrap();
+    assert!(page.blocks.len() <= MAX_BLOCKS_PER_PAGE);
+
+    let page = store.range(2, 0).unwrap();
+    let hashes: Vec<String> = page.blocks.iter().map(Block::hash).collect();
+    assert_eq!(hashes, vec!["convergence_2"]);
+    assert_eq!(page.next_height, Some(3));
+
+    let latest: Vec<String> = store.latest(2).unwrap().iter().map(Block::hash).collect();
+    assert_eq!(latest, vec!["convergence_4", "convergence_5"]);
+}
+
+#[test]
+fn blocks_survive_reopening_the_store() {
+    let path = env::temp_dir().join(_generate_random_string());
+    let genesis = genesis();
+
+    {
+        let store = BlockStore::new(&path).unwrap();
+        store.insert(&genesis).unwrap();
+    }
+
+    let store = BlockStore::new(&path).unwrap();
+    assert_eq!(store.get_by_height(0).unwrap(), Some(genesis));
+}
@@ -11,7 +11,7 @@ use serial_test::serial;
 #[test]
 #[serial]
 fn claims_can_be_added() {
-    let mut db = VrrbDb::new(VrrbDbConfig::default());
+    let mut db = VrrbDb::new(VrrbDbConfig::default()).unwrap();
 
     let claim1 = _generate_random_claim();
     let claim2 = _generate_random_claim();
@@ -31,9 +31,39 @@ fn claims_can_be_added() {
         (claim3.hash, Some(claim3)),
         (claim4.hash, Some(claim4)),
         (claim5.hash, Some(claim5)),
-    ]);
+    ])
+    .unwrap();
 
     let entries = db.claim_store_factory().handle().entries().unwrap();
 
     assert_eq!(entries.len(), 5);
 }
+
+#[test]
+#[serial]
+fn claims_can_be_looked_up_by_address() {
+    let mut db = VrrbDb::new(VrrbDbConfig::default()).unwrap();
+
+    let claim1 = _generate_random_claim();
+    let claim2 = _generate_random_claim();
+    let claim3 = _generate_random_claim();
+
+    db.insert_claim(claim1.clone()).unwrap();
+    db.extend_claims(vec![
+        (claim2.hash, Some(claim2.clone())),
+        (claim3.hash, Some(claim3.clone())),
+    ])
+    .unwrap();
+
+    let read_handle = db.read_handle();
+
+    for claim in [claim1, claim2, claim3] {
+        assert_eq!(
+            read_handle.get_claim_by_address(&claim.address).unwrap(),
+            claim
+        );
+    }
+
+    let unknown = _generate_random_claim();
+    assert!(read_handle.get_claim_by_address(&unknown.address).is_err());
+}
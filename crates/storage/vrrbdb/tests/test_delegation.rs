@@ -0,0 +1,179 @@
+use std::{env, net::SocketAddr};
+
+use block::{header::BlockHeader, ConvergenceBlock, ProposalBlock};
+use primitives::{Address, Epoch};
+use ritelinked::{LinkedHashMap, LinkedHashSet};
+use secp256k1::Message;
+use serial_test::serial;
+use sha2::{Digest, Sha256};
+use vrrb_core::{
+    account::Account,
+    claim::Claim,
+    keypair::Keypair,
+    staking::{Delegation, StakeUpdate},
+    transactions::{StakeTransaction, Transaction, TransactionKind, BASE_FEE},
+};
+use vrrbdb::{VrrbDb, VrrbDbConfig};
+
+mod common;
+use common::{
+    _certified_stake, _generate_random_address, _generate_random_claim, _generate_random_string,
+};
+
+fn validator_claim(keypair: &Keypair, amount: u128, commission_rate: u8) -> Claim {
+    let public_key = keypair.miner_kp.1;
+    let ip_address = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+    let signature = Claim::signature_for_valid_claim(
+        public_key,
+        ip_address,
+        keypair.miner_kp.0.secret_bytes().to_vec(),
+    )
+    .unwrap();
+
+    let mut claim = Claim::new(
+        public_key,
+        Address::new(public_key),
+        ip_address,
+        signature,
+        "validator".to_string(),
+    )
+    .unwrap();
+    claim.set_commission_rate(commission_rate).unwrap();
+    claim
+        .update_stake(_certified_stake(keypair, StakeUpdate::Add(amount), None))
+        .unwrap();
+
+    claim
+}
+
+fn delegation_txn(delegator: &Keypair, validator: &Address, amount: u128) -> TransactionKind {
+    let message = Message::from_slice(&Sha256::digest(b"vrrb")).unwrap();
+
+    StakeTransaction::builder()
+        .timestamp(0)
+        .sender_address(Address::new(delegator.miner_kp.1))
+        .sender_public_key(delegator.miner_kp.1)
+        .stake(_certified_stake(
+            delegator,
+            StakeUpdate::Add(amount),
+            Some(validator.clone()),
+        ))
+        .signature(delegator.miner_kp.0.sign_ecdsa(message))
+        .nonce(1)
+        .build_kind()
+        .unwrap()
+}
+
+fn apply_block(
+    db: &mut VrrbDb,
+    epoch: Epoch,
+    miner_claim: Claim,
+    reward: u128,
+    txns: Vec<TransactionKind>,
+) {
+    let proposal = ProposalBlock {
+        ref_block: String::new(),
+        round: 1,
+        epoch,
+        txns: txns.iter().map(|txn| (txn.id(), txn.clone())).collect(),
+        claims: LinkedHashMap::new(),
+        from: _generate_random_claim(),
+        hash: _generate_random_string(),
+        signature: None,
+    };
+
+    let (secret_key, _) = _generate_random_address();
+    let mut header = BlockHeader::genesis(0, 0, 0, miner_claim, secret_key, String::new());
+    header.epoch = epoch;
+    header.block_height = 1;
+    header.block_reward.amount = reward;
+    header.state_roots = db.state_roots().unwrap();
+
+    let mut convergence_txns = LinkedHashMap::new();
+    convergence_txns.insert(
+        proposal.hash.clone(),
+        txns.iter()
+            .map(|txn| txn.id())
+            .collect::<LinkedHashSet<_>>(),
+    );
+
+    let convergence = ConvergenceBlock {
+        header,
+        txns: convergence_txns,
+        claims: LinkedHashMap::new(),
+        hash: _generate_random_string(),
+        certificate: None,
+        slashing_evidence: vec![],
+        fees: Default::default(),
+    };
+
+    db.apply_convergence_block(&convergence, &[proposal])
+        .unwrap();
+}
+
+fn credits_of(db: &VrrbDb, address: &Address) -> u128 {
+    db.read_handle()
+        .get_account_by_address(address)
+        .unwrap()
+        .credits()
+}
+
+#[test]
+#[serial]
+fn block_rewards_are_shared_with_delegators_at_the_end_of_the_epoch() {
+    let path = env::temp_dir().join(_generate_random_string());
+    let mut db = VrrbDb::new(VrrbDbConfig::default().with_path(path)).unwrap();
+
+    let validator = Keypair::random();
+    let validator_address = Address::new(validator.miner_kp.1);
+    let claim = validator_claim(&validator, 10_000, 10);
+    db.insert_account(
+        validator_address.clone(),
+        Account::new(validator_address.clone()),
+    )
+    .unwrap();
+    db.insert_claim(claim.clone()).unwrap();
+
+    let delegator = Keypair::random();
+    let delegator_address = Address::new(delegator.miner_kp.1);
+    let mut account = Account::new(delegator_address.clone());
+    account.set_credits(50_000 + BASE_FEE);
+    db.insert_account(delegator_address.clone(), account)
+        .unwrap();
+
+    apply_block(
+        &mut db,
+        1,
+        claim,
+        1_000,
+        vec![delegation_txn(&delegator, &validator_address, 30_000)],
+    );
+
+    assert_eq!(
+        db.read_handle()
+            .get_delegations(&validator_address)
+            .unwrap(),
+        vec![Delegation {
+            delegator: delegator_address.clone(),
+            amount: 30_000,
+        }]
+    );
+    assert_eq!(
+        db.read_handle()
+            .get_account_by_address(&delegator_address)
+            .unwrap()
+            .debits(),
+        30_000 + BASE_FEE
+    );
+    assert_eq!(credits_of(&db, &validator_address), 0);
+
+    apply_block(&mut db, 1, _generate_random_claim(), 1_000, vec![]);
+    assert_eq!(credits_of(&db, &validator_address), 0);
+
+    apply_block(&mut db, 2, _generate_random_claim(), 1_000, vec![]);
+
+    // 100 commission, then 900 split 1:3 between the validator and the
+    // delegator
+    assert_eq!(credits_of(&db, &validator_address), 325);
+    assert_eq!(credits_of(&db, &delegator_address), 50_675 + BASE_FEE);
+}
@@ -0,0 +1,143 @@
+use block::{fees::BlockFees, header::BlockHeader, ConvergenceBlock, ProposalBlock};
+use primitives::Round;
+use ritelinked::{LinkedHashMap, LinkedHashSet};
+use serial_test::serial;
+use vrrb_core::transactions::{Transaction, TransactionKind};
+use vrrbdb::EpochSummary;
+
+mod common;
+use common::{
+    _generate_random_address, _generate_random_claim, _generate_random_string,
+    _generate_random_valid_transaction, _random_db,
+};
+
+fn proposal_block(txns: &[TransactionKind]) -> ProposalBlock {
+    ProposalBlock {
+        ref_block: String::new(),
+        round: 1,
+        epoch: 0,
+        txns: txns.iter().map(|txn| (txn.id(), txn.clone())).collect(),
+        claims: LinkedHashMap::new(),
+        from: _generate_random_claim(),
+        hash: _generate_random_string(),
+        signature: None,
+    }
+}
+
+fn convergence_block(round: Round, proposal: &ProposalBlock) -> ConvergenceBlock {
+    let (secret_key, _) = _generate_random_address();
+    let mut header =
+        BlockHeader::genesis(0, 0, 0, _generate_random_claim(), secret_key, String::new());
+    header.round = round;
+
+    let mut txns = LinkedHashMap::new();
+    txns.insert(
+        proposal.hash.clone(),
+        proposal
+            .txns
+            .keys()
+            .take(1)
+            .cloned()
+            .collect::<LinkedHashSet<_>>(),
+    );
+
+    let fees = BlockFees::new(&txns, &[proposal.clone()], 50);
+
+    ConvergenceBlock {
+        header,
+        txns,
+        claims: LinkedHashMap::new(),
+        hash: _generate_random_string(),
+        certificate: None,
+        slashing_evidence: vec![],
+        fees,
+    }
+}
+
+#[test]
+fn summaries_account_for_the_blocks_of_the_epoch() {
+    let txns = vec![
+        _generate_random_valid_transaction(),
+        _generate_random_valid_transaction(),
+    ];
+    let proposal = proposal_block(&txns);
+    let first = convergence_block(10, &proposal);
+    let second = convergence_block(14, &proposal);
+
+    let mut summary = EpochSummary::new(1, 10);
+    summary.record_block(&first, &[proposal.clone()]);
+    summary.record_block(&second, &[proposal.clone()]);
+
+    assert_eq!(summary.blocks, 2);
+    assert_eq!(summary.start_round, 10);
+    assert_eq!(summary.end_round, 14);
+    assert_eq!(
+        summary.issued_rewards,
+        first.header.block_reward.amount + second.header.block_reward.amount
+    );
+
+    assert_eq!(summary.participants.len(), 3);
+    assert!(summary
+        .participants
+        .contains(&first.header.miner_claim.address));
+    assert!(summary
+        .participants
+        .contains(&second.header.miner_claim.address));
+    assert!(summary.participants.contains(&proposal.from.address));
+
+    // Only the transaction included by each convergence block is accounted for
+    assert_eq!(summary.fees_burned, txns[0].fee());
+}
+
+#[test]
+#[serial]
+fn summaries_can_be_queried_by_epoch() {
+    let db = _random_db();
+    let read_handle = db.read_handle();
+
+    assert_eq!(read_handle.latest_epoch_summary().unwrap(), None);
+
+    let proposal = proposal_block(&[_generate_random_valid_transaction()]);
+    for epoch in 1..=3 {
+        let mut summary = EpochSummary::new(epoch, epoch * 10);
+        summary.record_block(
+            &convergence_block(epoch * 10, &proposal),
+            &[proposal.clone()],
+        );
+        db.insert_epoch_summary(&summary).unwrap();
+    }
+
+    let second = read_handle.get_epoch_summary(2).unwrap().unwrap();
+    assert_eq!(second.epoch, 2);
+    assert_eq!(second.start_round, 20);
+    assert_eq!(second.blocks, 1);
+
+    assert_eq!(read_handle.get_epoch_summary(4).unwrap(), None);
+    assert_eq!(
+        read_handle.latest_epoch_summary().unwrap().unwrap().epoch,
+        3
+    );
+}
+
+#[test]
+#[serial]
+fn epoch_in_progress_is_recorded_apart_from_finished_epochs() {
+    let db = _random_db();
+    let read_handle = db.read_handle();
+
+    assert_eq!(db.epoch_in_progress().unwrap(), None);
+
+    let proposal = proposal_block(&[_generate_random_valid_transaction()]);
+    let mut current = EpochSummary::new(1, 10);
+    current.record_block(&convergence_block(10, &proposal), &[proposal.clone()]);
+    db.record_epoch(&current, None).unwrap();
+
+    assert_eq!(db.epoch_in_progress().unwrap(), Some(current.clone()));
+    assert_eq!(read_handle.latest_epoch_summary().unwrap(), None);
+
+    let next = EpochSummary::new(2, 20);
+    db.record_epoch(&next, Some(&current)).unwrap();
+
+    assert_eq!(db.epoch_in_progress().unwrap(), Some(next));
+    assert_eq!(read_handle.latest_epoch_summary().unwrap(), Some(current));
+}
@@ -0,0 +1,200 @@
+use std::{collections::HashSet, env};
+
+use block::{fees::BlockFees, header::BlockHeader, ConvergenceBlock, ProposalBlock};
+use primitives::{Address, Epoch};
+use ritelinked::{LinkedHashMap, LinkedHashSet};
+use serial_test::serial;
+use storage_utils::Result;
+use vrrb_core::{
+    account::Account,
+    claim::Claim,
+    transactions::{Transaction, TransactionKind, BASE_FEE},
+};
+use vrrbdb::{ApplyBlockResult, FromBlock, StateUpdate, VrrbDb, VrrbDbConfig};
+
+mod common;
+use common::{
+    _generate_random_address, _generate_random_claim, _generate_random_string,
+    _generate_random_transaction,
+};
+
+const BURN_RATE: u8 = 10;
+
+fn funded_db(address: &Address, credits: u128) -> VrrbDb {
+    let path = env::temp_dir().join(_generate_random_string());
+    let mut db = VrrbDb::new(VrrbDbConfig::default().with_path(path)).unwrap();
+
+    let mut account = Account::new(address.clone());
+    account.set_credits(credits);
+    db.insert_account(address.clone(), account).unwrap();
+
+    db
+}
+
+fn apply_block(
+    db: &mut VrrbDb,
+    epoch: Epoch,
+    proposer: Claim,
+    txns: Vec<TransactionKind>,
+) -> Result<ApplyBlockResult> {
+    let proposal = ProposalBlock {
+        ref_block: String::new(),
+        round: 1,
+        epoch,
+        txns: txns.iter().map(|txn| (txn.id(), txn.clone())).collect(),
+        claims: LinkedHashMap::new(),
+        from: proposer,
+        hash: _generate_random_string(),
+        signature: None,
+    };
+
+    let (secret_key, _) = _generate_random_address();
+    let mut header =
+        BlockHeader::genesis(0, 0, 0, _generate_random_claim(), secret_key, String::new());
+    header.epoch = epoch;
+    header.block_height = epoch + 1;
+    header.state_roots = db.state_roots().unwrap();
+
+    let mut convergence_txns = LinkedHashMap::new();
+    convergence_txns.insert(
+        proposal.hash.clone(),
+        txns.iter()
+            .map(|txn| txn.id())
+            .collect::<LinkedHashSet<_>>(),
+    );
+    let fees = BlockFees::new(&convergence_txns, &[proposal.clone()], BURN_RATE);
+
+    let convergence = ConvergenceBlock {
+        header,
+        txns: convergence_txns,
+        claims: LinkedHashMap::new(),
+        hash: _generate_random_string(),
+        certificate: None,
+        slashing_evidence: vec![],
+        fees,
+    };
+
+    db.apply_convergence_block(&convergence, &[proposal])
+}
+
+fn account_of(db: &VrrbDb, address: &Address) -> Account {
+    db.read_handle().get_account_by_address(address).unwrap()
+}
+
+#[test]
+#[serial]
+fn fees_are_paid_by_senders_and_shared_with_proposers() {
+    let (secret_key, sender) = _generate_random_address();
+    let (_, receiver) = _generate_random_address();
+    let mut db = funded_db(&sender, BASE_FEE * 2);
+
+    let proposer = _generate_random_claim();
+    db.insert_claim(proposer.clone()).unwrap();
+
+    let txn = _generate_random_transaction(secret_key, sender.clone(), receiver.clone());
+    apply_block(&mut db, 1, proposer.clone(), vec![txn.clone()]).unwrap();
+
+    assert_eq!(account_of(&db, &sender).debits(), txn.amount() + txn.fee());
+    assert_eq!(account_of(&db, &receiver).credits(), txn.amount());
+
+    // The proposer's share is paid out along with the other rewards of its
+    // claim once the epoch is over, net of the burned part of the fee
+    apply_block(&mut db, 2, _generate_random_claim(), vec![]).unwrap();
+    assert_eq!(
+        account_of(&db, &proposer.address).credits(),
+        txn.proposer_fee_share() / 10 * 9
+    );
+}
+
+#[test]
+#[serial]
+fn blocks_are_rejected_when_senders_cannot_pay_fees() {
+    let (secret_key, sender) = _generate_random_address();
+    let (_, receiver) = _generate_random_address();
+    let txn = _generate_random_transaction(secret_key, sender.clone(), receiver);
+    let mut db = funded_db(&sender, txn.amount());
+
+    assert!(apply_block(&mut db, 1, _generate_random_claim(), vec![txn]).is_err());
+}
+
+#[test]
+#[serial]
+fn blocks_senders_cannot_pay_for_leave_the_state_untouched() {
+    let (secret_key, sender) = _generate_random_address();
+    let (_, first_receiver) = _generate_random_address();
+    let (_, second_receiver) = _generate_random_address();
+
+    let first = _generate_random_transaction(secret_key, sender.clone(), first_receiver.clone());
+    let second = _generate_random_transaction(secret_key, sender.clone(), second_receiver);
+    let mut db = funded_db(&sender, first.amount() + first.fee());
+    let state_roots = db.state_roots().unwrap();
+
+    assert!(apply_block(
+        &mut db,
+        1,
+        _generate_random_claim(),
+        vec![first.clone(), second.clone()],
+    )
+    .is_err());
+
+    assert_eq!(account_of(&db, &sender).debits(), 0);
+    assert!(db
+        .read_handle()
+        .get_account_by_address(&first_receiver)
+        .is_err());
+    assert_eq!(db.state_roots().unwrap(), state_roots);
+
+    apply_block(&mut db, 1, _generate_random_claim(), vec![first.clone()]).unwrap();
+    assert_eq!(account_of(&db, &sender).debits(), first.cost());
+}
+
+#[test]
+#[serial]
+fn fees_of_a_sender_are_charged_once_per_transaction() {
+    let (secret_key, sender) = _generate_random_address();
+    let (_, first_receiver) = _generate_random_address();
+    let (_, second_receiver) = _generate_random_address();
+    let mut db = funded_db(&sender, BASE_FEE * 4);
+
+    let first = _generate_random_transaction(secret_key, sender.clone(), first_receiver);
+    let second = _generate_random_transaction(secret_key, sender.clone(), second_receiver);
+    apply_block(
+        &mut db,
+        1,
+        _generate_random_claim(),
+        vec![first.clone(), second.clone()],
+    )
+    .unwrap();
+
+    assert_eq!(
+        account_of(&db, &sender).debits(),
+        first.amount() + first.fee() + second.amount() + second.fee()
+    );
+}
+
+#[test]
+#[serial]
+fn block_updates_leave_fees_to_block_application() {
+    let (secret_key, sender) = _generate_random_address();
+    let (_, receiver) = _generate_random_address();
+    let txn = _generate_random_transaction(secret_key, sender.clone(), receiver);
+
+    let proposal = ProposalBlock {
+        ref_block: String::new(),
+        round: 1,
+        epoch: 1,
+        txns: LinkedHashMap::from_iter([(txn.id(), txn.clone())]),
+        claims: LinkedHashMap::new(),
+        from: _generate_random_claim(),
+        hash: _generate_random_string(),
+        signature: None,
+    };
+
+    let debits: u128 = HashSet::<StateUpdate>::from_block(proposal)
+        .into_iter()
+        .filter(|update| update.address == sender)
+        .map(|update| update.amount)
+        .sum();
+
+    assert_eq!(debits, txn.amount());
+}
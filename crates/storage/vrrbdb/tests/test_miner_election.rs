@@ -0,0 +1,203 @@
+use std::net::SocketAddr;
+
+use block::{
+    election::{elect_miner, verify_miner_election, ClaimInclusionProof, ElectionError},
+    header::BlockHeader,
+    Block, GenesisBlock, GenesisRewards,
+};
+use primitives::{Address, ChainParameters, DEFAULT_EPOCH_LENGTH};
+use ritelinked::LinkedHashMap;
+use vrrb_core::{
+    claim::{Claim, Eligibility},
+    keypair::Keypair,
+};
+use vrrbdb::{VrrbDb, VrrbDbConfig};
+
+mod common;
+use common::_generate_random_claim;
+use serial_test::serial;
+
+fn miner_claim(keypair: &Keypair) -> Claim {
+    let (secret_key, public_key) = keypair.miner_kp;
+    let ip_address = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+    let signature = Claim::signature_for_valid_claim(
+        public_key,
+        ip_address,
+        secret_key.secret_bytes().to_vec(),
+    )
+    .unwrap();
+
+    let mut claim = Claim::new(
+        public_key,
+        Address::new(public_key),
+        ip_address,
+        signature,
+        public_key.to_string(),
+    )
+    .unwrap();
+    claim.eligibility = Eligibility::Miner;
+
+    claim
+}
+
+fn genesis(keypair: &Keypair) -> GenesisBlock {
+    let header = BlockHeader::genesis(
+        0,
+        0,
+        0,
+        miner_claim(keypair),
+        keypair.miner_kp.0,
+        String::new(),
+    );
+
+    GenesisBlock {
+        hash: header.hash(),
+        header,
+        genesis_rewards: GenesisRewards(LinkedHashMap::new()),
+        claims: LinkedHashMap::new(),
+        chain_parameters: ChainParameters::default(),
+        certificate: None,
+    }
+}
+
+/// Header of the block mined by `keypair` on top of `last_block`, carrying
+/// the inclusion proof of its claim in `db`
+fn mine_header(db: &VrrbDb, keypair: &Keypair, last_block: &GenesisBlock) -> BlockHeader {
+    let claim = miner_claim(keypair);
+    let mut header = BlockHeader::new(
+        Block::Genesis {
+            block: last_block.clone(),
+        },
+        vec![],
+        claim.clone(),
+        keypair.miner_kp.0,
+        String::new(),
+        String::new(),
+        DEFAULT_EPOCH_LENGTH,
+        0,
+    )
+    .unwrap();
+
+    let proof = db.read_handle().get_claim_proof(&claim.hash).unwrap();
+    header.election_proof.claim_inclusion = ClaimInclusionProof {
+        proof: bincode::serialize(&proof.proof).unwrap(),
+        value: proof.value.unwrap(),
+    };
+
+    header
+}
+
+/// Miners with their claims stored in a fresh database, the winner of the
+/// election of the block following `last_block` first
+fn miners(db: &mut VrrbDb, last_block: &GenesisBlock) -> Vec<(Keypair, Claim)> {
+    let mut miners = (0..4)
+        .map(|_| {
+            let keypair = Keypair::random();
+            let claim = miner_claim(&keypair);
+            db.insert_claim(claim.clone()).unwrap();
+            (keypair, claim)
+        })
+        .collect::<Vec<_>>();
+
+    let seed = last_block.header.next_block_seed;
+    let winner = elect_miner(miners.iter().map(|(_, claim)| claim), seed).unwrap();
+    miners.sort_by_key(|(_, claim)| claim.hash != winner);
+
+    miners
+}
+
+#[test]
+#[serial]
+fn miner_election_verifies_against_the_claims_root() {
+    let mut db = VrrbDb::new(VrrbDbConfig::default()).unwrap();
+    let last_block = genesis(&Keypair::random());
+    let miners = miners(&mut db, &last_block);
+    let claims = miners.iter().map(|(_, claim)| claim).collect::<Vec<_>>();
+    let claims_root = hex::encode(db.claims_root_hash().unwrap().0);
+
+    let header = mine_header(&db, &miners[0].0, &last_block);
+    verify_miner_election(&header, &last_block.header, &claims_root, claims.clone()).unwrap();
+
+    let mut missing_inclusion = header.clone();
+    missing_inclusion.election_proof.claim_inclusion = ClaimInclusionProof::default();
+    assert!(matches!(
+        verify_miner_election(
+            &missing_inclusion,
+            &last_block.header,
+            &claims_root,
+            claims.clone()
+        ),
+        Err(ElectionError::InvalidClaimProof(_))
+    ));
+
+    let mut tampered = header.clone();
+    tampered.next_block_seed += 1;
+    assert_eq!(
+        verify_miner_election(&tampered, &last_block.header, &claims_root, claims.clone()),
+        Err(ElectionError::SeedMismatch)
+    );
+
+    let mut tampered = header.clone();
+    tampered.miner_claim = _generate_random_claim();
+    assert!(
+        verify_miner_election(&tampered, &last_block.header, &claims_root, claims.clone()).is_err()
+    );
+
+    let other_root = hex::encode([7u8; 32]);
+    assert!(matches!(
+        verify_miner_election(&header, &last_block.header, &other_root, claims),
+        Err(ElectionError::InvalidClaimProof(_))
+    ));
+}
+
+#[test]
+#[serial]
+fn miners_that_lost_the_election_are_rejected() {
+    let mut db = VrrbDb::new(VrrbDbConfig::default()).unwrap();
+    let last_block = genesis(&Keypair::random());
+    let miners = miners(&mut db, &last_block);
+    let claims = miners.iter().map(|(_, claim)| claim).collect::<Vec<_>>();
+    let claims_root = hex::encode(db.claims_root_hash().unwrap().0);
+
+    for (keypair, claim) in miners.iter().skip(1) {
+        let header = mine_header(&db, keypair, &last_block);
+
+        assert_eq!(
+            verify_miner_election(&header, &last_block.header, &claims_root, claims.clone()),
+            Err(ElectionError::NotElected(claim.hash))
+        );
+    }
+}
+
+#[test]
+#[serial]
+fn miner_election_is_bound_to_the_previous_block() {
+    let mut db = VrrbDb::new(VrrbDbConfig::default()).unwrap();
+    let last_block = genesis(&Keypair::random());
+    let miners = miners(&mut db, &last_block);
+    let claims = miners.iter().map(|(_, claim)| claim).collect::<Vec<_>>();
+    let claims_root = hex::encode(db.claims_root_hash().unwrap().0);
+
+    let header = mine_header(&db, &miners[0].0, &last_block);
+
+    // A proof made for one block cannot be replayed on top of another one
+    let other_block = genesis(&Keypair::random());
+    assert_eq!(
+        verify_miner_election(&header, &other_block.header, &claims_root, claims.clone()),
+        Err(ElectionError::PreviousBlockMismatch)
+    );
+
+    let mut tampered = header.clone();
+    tampered.block_seed += 1;
+    assert_eq!(
+        verify_miner_election(&tampered, &last_block.header, &claims_root, claims.clone()),
+        Err(ElectionError::PreviousBlockMismatch)
+    );
+
+    let mut tampered = header;
+    tampered.election_proof.vrf_message = b"chosen by the miner".to_vec();
+    assert_eq!(
+        verify_miner_election(&tampered, &last_block.header, &claims_root, claims),
+        Err(ElectionError::PreviousBlockMismatch)
+    );
+}
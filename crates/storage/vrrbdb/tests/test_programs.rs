@@ -0,0 +1,91 @@
+use std::env;
+
+use serial_test::serial;
+use vrrbdb::{ProgramRecord, VrrbDb, VrrbDbConfig};
+use web3_pkg::web3_pkg::{Web3ContentId, Web3Package, Web3PackageArchitecture, Web3PackageObject};
+
+mod common;
+use common::_generate_random_string;
+
+const WASM: [u8; 4] = [0, 97, 115, 109];
+
+fn program(arch: Web3PackageArchitecture, cid: &str) -> ProgramRecord {
+    let package = Web3Package {
+        pkg_name: "counter".to_string(),
+        pkg_objects: vec![Web3PackageObject {
+            object_arch: arch,
+            object_cid: Web3ContentId {
+                cid: cid.to_string(),
+            },
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+
+    ProgramRecord::new(package, WASM.to_vec())
+}
+
+fn wasm_cid() -> String {
+    Web3ContentId::of_raw_block(&WASM).cid
+}
+
+#[test]
+#[serial]
+fn programs_can_be_queried_by_content_id() {
+    let path = env::temp_dir().join(_generate_random_string());
+    let db = VrrbDb::new(VrrbDbConfig::default().with_path(path)).unwrap();
+    let read_handle = db.read_handle();
+
+    let content_id = db
+        .insert_program(&program(Web3PackageArchitecture::Wasm32Wasi, &wasm_cid()))
+        .unwrap();
+    assert_eq!(content_id, wasm_cid());
+
+    let stored = read_handle.get_program(&content_id).unwrap().unwrap();
+    assert_eq!(stored.package.pkg_name, "counter");
+    assert_eq!(stored.wasm, WASM.to_vec());
+
+    assert!(read_handle.get_program("QmMissing").unwrap().is_none());
+}
+
+#[test]
+#[serial]
+fn programs_without_a_wasm_object_are_rejected() {
+    let path = env::temp_dir().join(_generate_random_string());
+    let db = VrrbDb::new(VrrbDbConfig::default().with_path(path)).unwrap();
+
+    assert!(db
+        .insert_program(&program(Web3PackageArchitecture::Amd64Linux, &wasm_cid()))
+        .is_err());
+    assert!(db.read_handle().get_program(&wasm_cid()).unwrap().is_none());
+}
+
+#[test]
+#[serial]
+fn programs_must_point_at_their_wasm_module() {
+    let path = env::temp_dir().join(_generate_random_string());
+    let db = VrrbDb::new(VrrbDbConfig::default().with_path(path)).unwrap();
+
+    assert!(db
+        .insert_program(&program(Web3PackageArchitecture::Wasm32Wasi, "QmCounter"))
+        .is_err());
+    assert!(db.read_handle().get_program("QmCounter").unwrap().is_none());
+    assert!(db.read_handle().get_program(&wasm_cid()).unwrap().is_none());
+}
+
+#[test]
+#[serial]
+fn registered_programs_cannot_be_replaced() {
+    let path = env::temp_dir().join(_generate_random_string());
+    let db = VrrbDb::new(VrrbDbConfig::default().with_path(path)).unwrap();
+
+    db.insert_program(&program(Web3PackageArchitecture::Wasm32Wasi, &wasm_cid()))
+        .unwrap();
+
+    let mut replacement = program(Web3PackageArchitecture::Wasm32Wasi, &wasm_cid());
+    replacement.package.pkg_name = "replacement".to_string();
+    assert!(db.insert_program(&replacement).is_err());
+
+    let stored = db.read_handle().get_program(&wasm_cid()).unwrap().unwrap();
+    assert_eq!(stored.package.pkg_name, "counter");
+}
@@ -0,0 +1,99 @@
+use block::header::BlockHeader;
+use vrrb_core::account::Account;
+use vrrbdb::{parse_root_hash, verify_proof, ProofStore, VrrbDb, VrrbDbConfig};
+
+mod common;
+use common::{_generate_random_address, _generate_random_claim};
+use serial_test::serial;
+
+#[test]
+#[serial]
+fn account_proofs_verify_against_the_state_root() {
+    let mut db = VrrbDb::new(VrrbDbConfig::default()).unwrap();
+    let (_, address) = _generate_random_address();
+    let (_, missing_address) = _generate_random_address();
+
+    db.insert_account(address.clone(), Account::new(address.clone()))
+        .unwrap();
+
+    let read_handle = db.read_handle();
+    let proof = read_handle.get_account_proof(&address).unwrap();
+
+    assert_eq!(proof.store, ProofStore::State);
+    assert_eq!(
+        proof.value::<Account>().unwrap(),
+        Some(Account::new(address.clone()))
+    );
+    assert_eq!(
+        proof.root_hash,
+        hex::encode(db.state_root_hash().unwrap().0)
+    );
+    proof
+        .verify(parse_root_hash(&proof.root_hash).unwrap())
+        .unwrap();
+
+    let absence = read_handle.get_account_proof(&missing_address).unwrap();
+    assert_eq!(absence.value, None);
+    absence
+        .verify(parse_root_hash(&absence.root_hash).unwrap())
+        .unwrap();
+}
+
+#[test]
+#[serial]
+fn tampered_proofs_are_rejected() {
+    let mut db = VrrbDb::new(VrrbDbConfig::default()).unwrap();
+    let (_, address) = _generate_random_address();
+
+    db.insert_account(address.clone(), Account::new(address.clone()))
+        .unwrap();
+
+    let mut proof = db.read_handle().get_account_proof(&address).unwrap();
+    let root_hash = parse_root_hash(&proof.root_hash).unwrap();
+
+    let mut tampered = Account::new(address);
+    tampered.set_credits(1_000);
+    proof.value = Some(bincode::serialize(&tampered).unwrap());
+
+    assert!(proof.verify(root_hash).is_err());
+}
+
+#[test]
+#[serial]
+fn proofs_verify_against_block_header_roots() {
+    let mut db = VrrbDb::new(VrrbDbConfig::default()).unwrap();
+    let (_, address) = _generate_random_address();
+
+    db.insert_account(address.clone(), Account::new(address.clone()))
+        .unwrap();
+    db.checkpoint_state(1).unwrap();
+
+    // The header of the block at height 2 commits to the state left by the
+    // block at height 1
+    let (secret_key, _) = _generate_random_address();
+    let mut header =
+        BlockHeader::genesis(0, 0, 0, _generate_random_claim(), secret_key, String::new());
+    header.block_height = 2;
+    header.state_roots = db.state_roots().unwrap();
+    db.verify_state_roots(&header).unwrap();
+
+    let mut account = Account::new(address.clone());
+    account.set_credits(1_000);
+    db.insert_account(address.clone(), account).unwrap();
+    db.checkpoint_state(2).unwrap();
+
+    assert!(db.verify_state_roots(&header).is_err());
+
+    let read_handle = db.read_handle();
+    let proof = read_handle.get_account_proof_at(&address, 2).unwrap();
+    assert_eq!(
+        proof.value::<Account>().unwrap(),
+        Some(Account::new(address.clone()))
+    );
+    verify_proof(&proof, &header).unwrap();
+
+    let latest = read_handle.get_account_proof(&address).unwrap();
+    assert!(verify_proof(&latest, &header).is_err());
+
+    assert!(read_handle.get_account_proof_at(&address, 0).is_err());
+}
@@ -0,0 +1,64 @@
+use std::env;
+
+use vrrb_core::account::{Account, UpdateArgs};
+use vrrbdb::{StatePruner, VrrbDb, VrrbDbConfig};
+
+mod common;
+use common::{_generate_random_address, _generate_random_string};
+use serial_test::serial;
+
+fn db_with_history(updates: u128) -> (VrrbDb, primitives::Address) {
+    let path = env::temp_dir().join(_generate_random_string());
+    let mut db = VrrbDb::new(VrrbDbConfig::default().with_path(path)).unwrap();
+    let (_, address) = _generate_random_address();
+
+    db.insert_account(address.clone(), Account::new(address.clone()))
+        .unwrap();
+
+    for credits in 1..=updates {
+        db.update_account(UpdateArgs {
+            address: address.clone(),
+            nonce: None,
+            credits: Some(credits),
+            debits: None,
+            storage: None,
+            package_address: None,
+            digests: None,
+        })
+        .unwrap();
+    }
+
+    (db, address)
+}
+
+#[test]
+#[serial]
+fn stale_nodes_outside_the_retention_window_are_pruned() {
+    let (db, address) = db_with_history(10);
+    let root_hash = db.state_root_hash().unwrap();
+
+    let pruner = StatePruner::new(&db, 2, 1).unwrap();
+    let pruned = pruner.prune_all().unwrap();
+    let stats = pruner.stats();
+
+    assert!(pruned > 0);
+    assert_eq!(stats.pruned_nodes, pruned as u64);
+    assert!(stats.runs > 1);
+
+    assert_eq!(db.state_root_hash().unwrap(), root_hash);
+    let account = db.read_handle().get_account_by_address(&address).unwrap();
+    assert_eq!(account.credits(), (1..=10).sum::<u128>());
+
+    assert_eq!(pruner.prune().unwrap(), 0);
+}
+
+#[test]
+#[serial]
+fn nothing_is_pruned_within_the_retention_window() {
+    let (db, _) = db_with_history(3);
+
+    let pruner = StatePruner::new(&db, 1_000, 100).unwrap();
+
+    assert_eq!(pruner.prune_all().unwrap(), 0);
+    assert!(pruner.stats().pending_stale_nodes > 0);
+}
@@ -0,0 +1,218 @@
+use std::net::SocketAddr;
+
+use block::{
+    header::BlockHeader,
+    slashing::{evidence_root, Offense, SignedVote, SlashingEvidence},
+    ConvergenceBlock,
+};
+use primitives::{Address, NodeId, SecretKey, Signature};
+use ritelinked::LinkedHashMap;
+use secp256k1::Message;
+use sha2::{Digest, Sha256};
+use vrrb_core::{
+    claim::Claim,
+    keypair::Keypair,
+    staking::StakeUpdate,
+    transactions::{Transaction, TransactionDigest},
+};
+use vrrbdb::VrrbDb;
+
+mod common;
+use common::{
+    _certified_stake, _generate_random_address, _generate_random_claim,
+    _generate_random_valid_transaction, _random_db, _stake_of,
+};
+use serial_test::serial;
+
+fn staked_claim(keypair: &Keypair, node_id: NodeId, amount: u128) -> Claim {
+    let public_key = keypair.miner_kp.1;
+    let address = Address::new(public_key);
+    let ip_address = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+    let signature = Claim::signature_for_valid_claim(
+        public_key,
+        ip_address,
+        keypair.miner_kp.0.secret_bytes().to_vec(),
+    )
+    .unwrap();
+
+    let mut claim = Claim::new(public_key, address, ip_address, signature, node_id).unwrap();
+
+    claim
+        .update_stake(_certified_stake(keypair, StakeUpdate::Add(amount), None))
+        .unwrap();
+
+    claim
+}
+
+fn sign(secret_key: &SecretKey, data: &[u8]) -> Signature {
+    let message = Message::from_slice(&Sha256::digest(data)).unwrap();
+    secret_key.sign_ecdsa(message)
+}
+
+fn vote(secret_key: &SecretKey, txn_id: &TransactionDigest, is_txn_valid: bool) -> SignedVote {
+    SignedVote {
+        txn_id: txn_id.clone(),
+        is_txn_valid,
+        signature: sign(secret_key, &SignedVote::payload(txn_id, is_txn_valid)),
+    }
+}
+
+fn double_vote_evidence(offender: &Keypair, node_id: &str, first: bool) -> SlashingEvidence {
+    let reporter = Keypair::random();
+    let txn_id = _generate_random_valid_transaction().id();
+
+    SlashingEvidence::new(
+        node_id.to_string(),
+        Address::new(offender.miner_kp.1),
+        Offense::DoubleVote {
+            first: vote(&offender.miner_kp.0, &txn_id, first),
+            second: vote(&offender.miner_kp.0, &txn_id, !first),
+        },
+        reporter.miner_kp.0,
+        reporter.miner_kp.1,
+    )
+    .unwrap()
+}
+
+fn convergence_block(
+    db: &VrrbDb,
+    hash: &str,
+    slashing_evidence: Vec<SlashingEvidence>,
+) -> ConvergenceBlock {
+    let (secret_key, _) = _generate_random_address();
+    let mut header =
+        BlockHeader::genesis(0, 0, 0, _generate_random_claim(), secret_key, String::new());
+    header.round = 1;
+    header.block_height = 1;
+    header.state_roots = db.state_roots().unwrap();
+    header.slashing_evidence_root = evidence_root(&slashing_evidence);
+
+    ConvergenceBlock {
+        header,
+        txns: LinkedHashMap::new(),
+        claims: LinkedHashMap::new(),
+        hash: hash.to_string(),
+        certificate: None,
+        slashing_evidence,
+        fees: Default::default(),
+    }
+}
+
+#[test]
+#[serial]
+fn double_votes_slash_the_offenders_stake() {
+    let mut db = _random_db();
+    let offender = Keypair::random();
+    db.insert_claim(staked_claim(&offender, "farmer".to_string(), 10_000))
+        .unwrap();
+
+    let evidence = double_vote_evidence(&offender, "farmer", true);
+    db.apply_convergence_block(&convergence_block(&db, "block_1", vec![evidence]), &[])
+        .unwrap();
+
+    assert_eq!(_stake_of(&db, "farmer"), 9_000);
+}
+
+#[test]
+#[serial]
+fn evidence_is_only_applied_once() {
+    let mut db = _random_db();
+    let offender = Keypair::random();
+    db.insert_claim(staked_claim(&offender, "farmer".to_string(), 10_000))
+        .unwrap();
+
+    let evidence = double_vote_evidence(&offender, "farmer", true);
+    let first = convergence_block(&db, "block_1", vec![evidence.clone()]);
+    db.apply_convergence_block(&first, &[]).unwrap();
+    let second = convergence_block(&db, "block_2", vec![evidence]);
+    db.apply_convergence_block(&second, &[]).unwrap();
+
+    assert_eq!(_stake_of(&db, "farmer"), 9_000);
+}
+
+#[test]
+#[serial]
+fn duplicate_evidence_within_a_block_is_applied_once() {
+    let mut db = _random_db();
+    let offender = Keypair::random();
+    db.insert_claim(staked_claim(&offender, "farmer".to_string(), 10_000))
+        .unwrap();
+
+    let evidence = double_vote_evidence(&offender, "farmer", true);
+    let block = convergence_block(&db, "block_1", vec![evidence.clone(), evidence]);
+    db.apply_convergence_block(&block, &[]).unwrap();
+
+    assert_eq!(_stake_of(&db, "farmer"), 9_000);
+}
+
+#[test]
+#[serial]
+fn evidence_not_committed_in_the_header_is_rejected() {
+    let mut db = _random_db();
+    let offender = Keypair::random();
+    db.insert_claim(staked_claim(&offender, "farmer".to_string(), 10_000))
+        .unwrap();
+
+    let mut block = convergence_block(&db, "block_1", vec![]);
+    block.slashing_evidence = vec![double_vote_evidence(&offender, "farmer", true)];
+
+    assert!(db.apply_convergence_block(&block, &[]).is_err());
+    assert_eq!(_stake_of(&db, "farmer"), 10_000);
+}
+
+#[test]
+fn evidence_is_the_same_regardless_of_vote_order() {
+    let offender = Keypair::random();
+
+    let first = double_vote_evidence(&offender, "farmer", true);
+    let second = double_vote_evidence(&offender, "farmer", false);
+
+    assert!(matches!(
+        first.offense,
+        Offense::DoubleVote { ref first, .. } if !first.is_txn_valid
+    ));
+    assert!(matches!(
+        second.offense,
+        Offense::DoubleVote { ref first, .. } if !first.is_txn_valid
+    ));
+}
+
+#[test]
+#[serial]
+fn evidence_signed_by_another_node_is_rejected() {
+    let mut db = _random_db();
+    let farmer = Keypair::random();
+    let impostor = Keypair::random();
+    db.insert_claim(staked_claim(&farmer, "farmer".to_string(), 10_000))
+        .unwrap();
+
+    let mut evidence = double_vote_evidence(&impostor, "farmer", true);
+    evidence.penalty = double_vote_evidence(&farmer, "farmer", true).penalty;
+
+    assert!(db
+        .apply_convergence_block(&convergence_block(&db, "block_1", vec![evidence]), &[])
+        .is_err());
+    assert_eq!(_stake_of(&db, "farmer"), 10_000);
+}
+
+#[test]
+fn agreeing_votes_are_not_evidence() {
+    let offender = Keypair::random();
+    let txn_id = _generate_random_valid_transaction().id();
+    let reporter = Keypair::random();
+    let address = Address::new(offender.miner_kp.1);
+
+    let evidence = SlashingEvidence::new(
+        "farmer".to_string(),
+        address.clone(),
+        Offense::DoubleVote {
+            first: vote(&offender.miner_kp.0, &txn_id, true),
+            second: vote(&offender.miner_kp.0, &txn_id, true),
+        },
+        reporter.miner_kp.0,
+        reporter.miner_kp.1,
+    )
+    .unwrap();
+
+    assert!(evidence.verify(&offender.miner_kp.1, &address).is_err());
+}
@@ -0,0 +1,247 @@
+use std::env;
+
+use block::{fees::BlockFees, header::BlockHeader, Block, ConvergenceBlock, ProposalBlock};
+use primitives::Address;
+use ritelinked::{LinkedHashMap, LinkedHashSet};
+use vrrb_core::{
+    account::Account,
+    transactions::{Transaction, TransactionKind},
+};
+use vrrbdb::{StateSnapshot, TransactionDirection, VrrbDb};
+
+mod common;
+use common::{
+    _generate_random_address, _generate_random_claim, _generate_random_string,
+    _generate_random_transaction, _random_db,
+};
+use serial_test::serial;
+
+/// Height of the block the snapshots of `populated_db` are pinned to
+const SNAPSHOT_HEIGHT: u128 = 1;
+
+fn header(block_height: u128) -> BlockHeader {
+    let (secret_key, _) = _generate_random_address();
+    let mut header =
+        BlockHeader::genesis(0, 0, 0, _generate_random_claim(), secret_key, String::new());
+    header.round = block_height;
+    header.block_height = block_height;
+
+    header
+}
+
+/// Applies and stores a convergence block at `SNAPSHOT_HEIGHT` including
+/// `txn`, returning the block's hash
+fn apply_block(db: &mut VrrbDb, txn: TransactionKind) -> String {
+    let proposal = ProposalBlock {
+        ref_block: String::new(),
+        round: SNAPSHOT_HEIGHT,
+        epoch: 0,
+        txns: [(txn.id(), txn.clone())].into_iter().collect(),
+        claims: LinkedHashMap::new(),
+        from: _generate_random_claim(),
+        hash: _generate_random_string(),
+        signature: None,
+    };
+
+    let mut header = header(SNAPSHOT_HEIGHT);
+    header.state_roots = db.state_roots().unwrap();
+
+    let mut txns = LinkedHashMap::new();
+    txns.insert(
+        proposal.hash.clone(),
+        [txn.id()].into_iter().collect::<LinkedHashSet<_>>(),
+    );
+    let fees = BlockFees::new(&txns, &[proposal.clone()], 0);
+
+    let convergence = ConvergenceBlock {
+        header,
+        txns,
+        claims: LinkedHashMap::new(),
+        hash: _generate_random_string(),
+        certificate: None,
+        slashing_evidence: vec![],
+        fees,
+    };
+
+    db.apply_convergence_block(&convergence, &[proposal])
+        .unwrap();
+    db.insert_block(&Block::Convergence {
+        block: convergence.clone(),
+    })
+    .unwrap();
+
+    convergence.hash
+}
+
+/// Returns a database holding accounts, claims and one transfer from the
+/// returned sender, along with the hash of the block that applied it
+fn populated_db() -> (VrrbDb, Address, String) {
+    let mut db = _random_db();
+
+    for _ in 0..5 {
+        let (_, address) = _generate_random_address();
+        let mut account = Account::new(address.clone());
+        account.set_credits(100);
+        db.insert_account(address, account).unwrap();
+    }
+
+    db.insert_claim(_generate_random_claim()).unwrap();
+    db.insert_claim(_generate_random_claim()).unwrap();
+
+    let (secret_key, sender) = _generate_random_address();
+    let mut account = Account::new(sender.clone());
+    account.set_credits(u64::MAX as u128);
+    db.insert_account(sender.clone(), account).unwrap();
+
+    let (_, receiver) = _generate_random_address();
+    let txn = _generate_random_transaction(secret_key, sender.clone(), receiver);
+    let block_hash = apply_block(&mut db, txn);
+
+    (db, sender, block_hash)
+}
+
+/// Returns the header of the block following the snapshot's block, which
+/// commits to the state of `db`
+fn trusted_header(db: &VrrbDb) -> BlockHeader {
+    let mut header = header(SNAPSHOT_HEIGHT + 1);
+    header.state_roots = db.state_roots().unwrap();
+
+    header
+}
+
+fn assert_empty(db: &VrrbDb) {
+    let read_handle = db.read_handle();
+
+    assert!(read_handle.state_store_values().unwrap().is_empty());
+    assert!(read_handle.claim_store_values().unwrap().is_empty());
+    assert!(read_handle.transaction_store_values().unwrap().is_empty());
+    assert!(db.state_root_at(SNAPSHOT_HEIGHT).is_err());
+}
+
+#[test]
+#[serial]
+fn snapshots_rebuild_the_same_state() {
+    let (db, sender, block_hash) = populated_db();
+    let path = env::temp_dir().join(format!("{}.snapshot", _generate_random_string()));
+
+    let snapshot = db.snapshot_state(block_hash, SNAPSHOT_HEIGHT).unwrap();
+    let manifest = snapshot.write(&path, 2).unwrap();
+
+    assert_eq!(snapshot.transactions.len(), 1);
+    assert_eq!(snapshot.transactions[0].round, SNAPSHOT_HEIGHT);
+    assert_eq!(manifest.chunks.len(), (snapshot.accounts.len() + 1) / 2 + 2);
+    assert_eq!(StateSnapshot::read(&path).unwrap(), snapshot);
+
+    let mut bootstrapped = _random_db();
+    let imported = bootstrapped
+        .import_state(&path, &trusted_header(&db))
+        .unwrap();
+
+    assert_eq!(imported.block_height, SNAPSHOT_HEIGHT);
+    assert_eq!(
+        bootstrapped.state_roots().unwrap(),
+        db.state_roots().unwrap()
+    );
+    assert_eq!(
+        bootstrapped.read_handle().state_store_values().unwrap(),
+        db.read_handle().state_store_values().unwrap()
+    );
+    assert_eq!(
+        bootstrapped.state_root_at(SNAPSHOT_HEIGHT).unwrap(),
+        db.state_root_hash().unwrap()
+    );
+
+    let digest = snapshot.transactions[0].txn.id();
+    assert!(bootstrapped.read_handle().get_transaction(&digest).is_ok());
+
+    let page = bootstrapped
+        .read_handle()
+        .get_account_transactions(&sender, None, 0, 10, None)
+        .unwrap();
+    assert_eq!(page.entries.len(), 1);
+    assert_eq!(page.entries[0].digest, digest);
+    assert_eq!(page.entries[0].round, SNAPSHOT_HEIGHT);
+    assert_eq!(page.entries[0].direction, TransactionDirection::Sent);
+}
+
+#[test]
+#[serial]
+fn snapshots_are_pinned_to_the_last_applied_block() {
+    let (mut db, _, block_hash) = populated_db();
+
+    assert!(db
+        .snapshot_state(_generate_random_string(), SNAPSHOT_HEIGHT)
+        .is_err());
+    assert!(db
+        .snapshot_state(block_hash.clone(), SNAPSHOT_HEIGHT + 1)
+        .is_err());
+
+    let (_, address) = _generate_random_address();
+    db.insert_account(address.clone(), Account::new(address))
+        .unwrap();
+
+    assert!(db.snapshot_state(block_hash, SNAPSHOT_HEIGHT).is_err());
+}
+
+#[test]
+#[serial]
+fn corrupt_chunks_are_rejected() {
+    let (db, _, block_hash) = populated_db();
+    let path = env::temp_dir().join(format!("{}.snapshot", _generate_random_string()));
+
+    db.export_state(block_hash, SNAPSHOT_HEIGHT, &path).unwrap();
+
+    let mut bytes = std::fs::read(&path).unwrap();
+    let last = bytes.len() - 1;
+    bytes[last] ^= 0xff;
+    std::fs::write(&path, bytes).unwrap();
+
+    assert!(StateSnapshot::read(&path).is_err());
+
+    let mut bootstrapped = _random_db();
+    assert!(bootstrapped
+        .import_state(&path, &trusted_header(&db))
+        .is_err());
+    assert_empty(&bootstrapped);
+}
+
+#[test]
+#[serial]
+fn imports_fail_unless_the_trusted_header_matches() {
+    let (db, _, block_hash) = populated_db();
+    let path = env::temp_dir().join(format!("{}.snapshot", _generate_random_string()));
+
+    db.export_state(block_hash, SNAPSHOT_HEIGHT, &path).unwrap();
+
+    let mut bootstrapped = _random_db();
+
+    let mut other_height = trusted_header(&db);
+    other_height.block_height = SNAPSHOT_HEIGHT;
+    assert!(bootstrapped.import_state(&path, &other_height).is_err());
+
+    let mut other_roots = trusted_header(&db);
+    other_roots.state_roots.claims_root_hash = hex::encode([0u8; 32]);
+    assert!(bootstrapped.import_state(&path, &other_roots).is_err());
+
+    assert_empty(&bootstrapped);
+}
+
+#[test]
+#[serial]
+fn imports_fail_when_the_rebuilt_root_does_not_match() {
+    let (db, _, block_hash) = populated_db();
+    let path = env::temp_dir().join(format!("{}.snapshot", _generate_random_string()));
+
+    let mut snapshot = db.snapshot_state(block_hash, SNAPSHOT_HEIGHT).unwrap();
+    snapshot.state_root_hash = hex::encode([0u8; 32]);
+    snapshot.write(&path, 2).unwrap();
+
+    // The header agrees with the snapshot, only rebuilding the tries tells
+    // the snapshot apart from the state it claims to hold
+    let mut header = trusted_header(&db);
+    header.state_roots = snapshot.state_roots();
+
+    let mut bootstrapped = _random_db();
+    assert!(bootstrapped.import_state(&path, &header).is_err());
+    assert_empty(&bootstrapped);
+}
@@ -0,0 +1,109 @@
+use std::env;
+
+use patriecia::RootHash;
+use vrrb_core::account::{Account, UpdateArgs};
+use vrrbdb::{HeightOrRoot, StateCheckpoint, StateHistory, VrrbDb, VrrbDbConfig};
+
+mod common;
+use common::{_generate_random_address, _generate_random_string};
+use serial_test::serial;
+
+fn checkpoint(height: u128, version: u64) -> StateCheckpoint {
+    StateCheckpoint {
+        height,
+        version,
+        root_hash: RootHash([height as u8; 32]),
+        transactions_version: version,
+        transactions_root_hash: RootHash([height as u8 + 1; 32]),
+        claims_version: version,
+        claims_root_hash: RootHash([height as u8 + 2; 32]),
+    }
+}
+
+#[test]
+fn heights_between_blocks_resolve_to_the_previous_block() {
+    let history = StateHistory::new();
+    history.record(checkpoint(0, 1)).unwrap();
+    history.record(checkpoint(2, 5)).unwrap();
+    history.record(checkpoint(5, 9)).unwrap();
+
+    assert_eq!(history.at_height(0), Some(checkpoint(0, 1)));
+    assert_eq!(history.at_height(3), Some(checkpoint(2, 5)));
+    assert_eq!(history.at_height(5), Some(checkpoint(5, 9)));
+    assert_eq!(history.at_height(6), None);
+    assert_eq!(history.latest(), Some(checkpoint(5, 9)));
+}
+
+#[test]
+fn root_hashes_resolve_to_their_checkpoint() {
+    let history = StateHistory::new();
+    history.record(checkpoint(0, 1)).unwrap();
+    history.record(checkpoint(1, 2)).unwrap();
+
+    assert_eq!(
+        history.resolve(&HeightOrRoot::Root(RootHash([1; 32]))),
+        Some(checkpoint(1, 2))
+    );
+    assert_eq!(
+        history.resolve(&HeightOrRoot::Root(RootHash([7; 32]))),
+        None
+    );
+}
+
+#[test]
+#[serial]
+fn accounts_can_be_read_at_past_heights() {
+    let mut db = VrrbDb::new(VrrbDbConfig::default()).unwrap();
+    let (_, address) = _generate_random_address();
+
+    db.insert_account(address.clone(), Account::new(address.clone()))
+        .unwrap();
+    let genesis = db.checkpoint_state(0).unwrap();
+
+    db.update_account(UpdateArgs {
+        address: address.clone(),
+        nonce: None,
+        credits: Some(100),
+        debits: None,
+        storage: None,
+        package_address: None,
+        digests: None,
+    })
+    .unwrap();
+    db.checkpoint_state(1).unwrap();
+
+    let at_genesis = db
+        .get_account_at(&address, HeightOrRoot::Height(0))
+        .unwrap();
+    let at_genesis_root = db
+        .get_account_at(&address, HeightOrRoot::Root(genesis.root_hash))
+        .unwrap();
+    let latest = db
+        .get_account_at(&address, HeightOrRoot::Height(1))
+        .unwrap();
+
+    assert_eq!(at_genesis.credits(), 0);
+    assert_eq!(at_genesis, at_genesis_root);
+    assert_eq!(latest.credits(), 100);
+    assert_eq!(db.state_root_at(0).unwrap(), genesis.root_hash);
+    assert!(db.state_root_at(2).is_err());
+}
+
+#[test]
+#[serial]
+fn checkpoints_survive_reopening_the_database() {
+    let path = env::temp_dir().join(_generate_random_string());
+    let (_, address) = _generate_random_address();
+
+    let genesis = {
+        let mut db = VrrbDb::new(VrrbDbConfig::default().with_path(path.clone())).unwrap();
+        db.insert_account(address.clone(), Account::new(address))
+            .unwrap();
+        db.checkpoint_state(0).unwrap()
+    };
+
+    let db = VrrbDb::new(VrrbDbConfig::default().with_path(path)).unwrap();
+
+    assert_eq!(db.state_root_at(0).unwrap(), genesis.root_hash);
+    assert!(db.state_root_at(1).is_err());
+}
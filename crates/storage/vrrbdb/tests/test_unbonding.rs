@@ -0,0 +1,198 @@
+use std::{env, net::SocketAddr};
+
+use block::{header::BlockHeader, ConvergenceBlock, ProposalBlock};
+use primitives::{Address, Epoch};
+use ritelinked::{LinkedHashMap, LinkedHashSet};
+use secp256k1::Message;
+use serial_test::serial;
+use sha2::{Digest, Sha256};
+use vrrb_core::{
+    account::Account,
+    claim::Claim,
+    keypair::Keypair,
+    staking::StakeUpdate,
+    transactions::{StakeTransaction, Transaction, TransactionKind, BASE_FEE},
+};
+use vrrbdb::{VrrbDb, VrrbDbConfig};
+
+mod common;
+use common::{
+    _certified_stake, _generate_random_address, _generate_random_claim, _generate_random_string,
+    _stake_of,
+};
+
+const UNBONDING_EPOCHS: Epoch = 2;
+
+/// Credits the staker starts with to pay the fee of its withdrawal
+const FEE_FUNDS: u128 = BASE_FEE;
+
+fn staked_db(keypair: &Keypair, amount: u128) -> VrrbDb {
+    let path = env::temp_dir().join(_generate_random_string());
+    let mut db = VrrbDb::new(
+        VrrbDbConfig::default()
+            .with_path(path)
+            .with_unbonding_epochs(UNBONDING_EPOCHS),
+    )
+    .unwrap();
+
+    let public_key = keypair.miner_kp.1;
+    let address = Address::new(public_key);
+    let mut account = Account::new(address.clone());
+    account.set_credits(FEE_FUNDS);
+    db.insert_account(address.clone(), account).unwrap();
+
+    let ip_address = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+    let signature = Claim::signature_for_valid_claim(
+        public_key,
+        ip_address,
+        keypair.miner_kp.0.secret_bytes().to_vec(),
+    )
+    .unwrap();
+    let mut claim = Claim::new(
+        public_key,
+        address.clone(),
+        ip_address,
+        signature,
+        "farmer".to_string(),
+    )
+    .unwrap();
+
+    claim
+        .update_stake(_certified_stake(keypair, StakeUpdate::Add(amount), None))
+        .unwrap();
+    db.insert_claim(claim).unwrap();
+
+    db
+}
+
+fn withdrawal_txn(keypair: &Keypair, amount: u128) -> TransactionKind {
+    let message = Message::from_slice(&Sha256::digest(b"vrrb")).unwrap();
+
+    StakeTransaction::builder()
+        .timestamp(0)
+        .sender_address(Address::new(keypair.miner_kp.1))
+        .sender_public_key(keypair.miner_kp.1)
+        .stake(_certified_stake(
+            keypair,
+            StakeUpdate::Withdrawal(amount),
+            None,
+        ))
+        .signature(keypair.miner_kp.0.sign_ecdsa(message))
+        .nonce(1)
+        .build_kind()
+        .unwrap()
+}
+
+fn apply_block(db: &mut VrrbDb, epoch: Epoch, txns: Vec<TransactionKind>) {
+    let proposal = ProposalBlock {
+        ref_block: String::new(),
+        round: 1,
+        epoch,
+        txns: txns.iter().map(|txn| (txn.id(), txn.clone())).collect(),
+        claims: LinkedHashMap::new(),
+        from: _generate_random_claim(),
+        hash: _generate_random_string(),
+        signature: None,
+    };
+
+    let (secret_key, _) = _generate_random_address();
+    let mut header =
+        BlockHeader::genesis(0, 0, 0, _generate_random_claim(), secret_key, String::new());
+    header.epoch = epoch;
+    header.block_height = epoch + 1;
+    header.state_roots = db.state_roots().unwrap();
+
+    let mut convergence_txns = LinkedHashMap::new();
+    convergence_txns.insert(
+        proposal.hash.clone(),
+        txns.iter()
+            .map(|txn| txn.id())
+            .collect::<LinkedHashSet<_>>(),
+    );
+
+    let convergence = ConvergenceBlock {
+        header,
+        txns: convergence_txns,
+        claims: LinkedHashMap::new(),
+        hash: _generate_random_string(),
+        certificate: None,
+        slashing_evidence: vec![],
+        fees: Default::default(),
+    };
+
+    db.apply_convergence_block(&convergence, &[proposal])
+        .unwrap();
+}
+
+fn credits_of(db: &VrrbDb, keypair: &Keypair) -> u128 {
+    db.read_handle()
+        .get_account_by_address(&Address::new(keypair.miner_kp.1))
+        .unwrap()
+        .credits()
+}
+
+#[test]
+#[serial]
+fn withdrawals_are_released_after_the_unbonding_period() {
+    let keypair = Keypair::random();
+    let address = Address::new(keypair.miner_kp.1);
+    let mut db = staked_db(&keypair, 10_000);
+
+    apply_block(&mut db, 3, vec![withdrawal_txn(&keypair, 4_000)]);
+
+    assert_eq!(_stake_of(&db, "farmer"), 6_000);
+    assert_eq!(credits_of(&db, &keypair), FEE_FUNDS);
+
+    let entries = db.read_handle().get_unbonding_entries(&address).unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].amount, 4_000);
+    assert_eq!(entries[0].requested_epoch, 3);
+    assert_eq!(entries[0].release_epoch, 3 + UNBONDING_EPOCHS);
+
+    apply_block(&mut db, 4, vec![]);
+    assert_eq!(credits_of(&db, &keypair), FEE_FUNDS);
+
+    apply_block(&mut db, 5, vec![]);
+    assert_eq!(credits_of(&db, &keypair), FEE_FUNDS + 4_000);
+    assert_eq!(_stake_of(&db, "farmer"), 6_000);
+    assert!(db
+        .read_handle()
+        .get_unbonding_entries(&address)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+#[serial]
+fn withdrawals_larger_than_the_stake_are_not_unbonded() {
+    let keypair = Keypair::random();
+    let address = Address::new(keypair.miner_kp.1);
+    let mut db = staked_db(&keypair, 10_000);
+
+    apply_block(&mut db, 0, vec![withdrawal_txn(&keypair, 25_000)]);
+
+    assert_eq!(_stake_of(&db, "farmer"), 10_000);
+    assert!(db
+        .read_handle()
+        .get_unbonding_entries(&address)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+#[serial]
+fn overdue_withdrawals_are_released_by_the_next_block() {
+    let keypair = Keypair::random();
+    let address = Address::new(keypair.miner_kp.1);
+    let mut db = staked_db(&keypair, 10_000);
+
+    apply_block(&mut db, 0, vec![withdrawal_txn(&keypair, 4_000)]);
+    apply_block(&mut db, 7, vec![]);
+
+    assert_eq!(credits_of(&db, &keypair), FEE_FUNDS + 4_000);
+    assert!(db
+        .read_handle()
+        .get_unbonding_entries(&address)
+        .unwrap()
+        .is_empty());
+}
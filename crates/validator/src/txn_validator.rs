@@ -1,8 +1,10 @@
 use std::result::Result as StdResult;
 
+use primitives::Address;
 use sha2::{Digest, Sha256};
 use storage::vrrbdb::StateStoreReadHandleFactory;
-use vrrb_core::transactions::{Transaction, TransactionKind};
+use vrrb_core::staking::{Stake, StakeUpdate};
+use vrrb_core::transactions::{Transaction, TransactionKind, TxFee, BASE_FEE};
 
 pub type Result<T> = StdResult<T, TxnValidatorError>;
 
@@ -43,6 +45,9 @@ pub enum TxnValidatorError {
     #[error("invalid amount")]
     TxnAmountIncorrect,
 
+    #[error("fee {0} is below the minimum fee of {1}")]
+    TxnFeeTooLow(TxFee, TxFee),
+
     #[error("invalid signature")]
     TxnSignatureIncorrect(String),
 
@@ -52,6 +57,15 @@ pub enum TxnValidatorError {
     #[error("value not found")]
     NotFound,
 
+    #[error("invalid stake: {0}")]
+    InvalidStake(String),
+
+    #[error("invalid claim registration: {0}")]
+    InvalidClaim(String),
+
+    #[error("invalid program call: {0}")]
+    InvalidProgramCall(String),
+
     #[error("account not found within state state_snapshot: {0}")]
     AccountNotFound(String),
     #[error("transaction payload not valid")]
@@ -71,7 +85,7 @@ impl TxnValidator {
     }
 
     /// An entire Txn validator
-    // TODO: include fees and signature threshold.
+    // TODO: include signature threshold.
     pub fn validate(
         &self,
         state_reader: StateStoreReadHandleFactory,
@@ -86,12 +100,87 @@ impl TxnValidator {
         state_reader: StateStoreReadHandleFactory,
         txn: &TransactionKind,
     ) -> Result<()> {
-        self.validate_amount(state_reader, txn)
+        self.validate_fee(txn)
+            .and_then(|_| self.validate_amount(state_reader, txn))
             .and_then(|_| self.validate_public_key(txn))
             //           .and_then(|_| self.validate_sender_address(txn))
             //           .and_then(|_| self.validate_receiver_address(txn))
             .and_then(|_| self.validate_signature(txn))
             .and_then(|_| self.validate_timestamp(txn))
+            .and_then(|_| self.validate_kind(txn))
+    }
+
+    /// Validates the rules specific to each kind of transaction
+    pub fn validate_kind(&self, txn: &TransactionKind) -> Result<()> {
+        match txn {
+            TransactionKind::Transfer(_) => Ok(()),
+            TransactionKind::Stake(stake_txn) => {
+                self.validate_stake(&txn.sender_address(), stake_txn.stake())
+            },
+            TransactionKind::RegisterClaim(register_claim) => {
+                let claim = register_claim.claim();
+                if claim.address != txn.sender_address() {
+                    return Err(TxnValidatorError::InvalidClaim(
+                        "claim address does not match sender address".to_string(),
+                    ));
+                }
+
+                if claim.public_key != txn.sender_public_key() {
+                    return Err(TxnValidatorError::InvalidClaim(
+                        "claim public key does not match sender public key".to_string(),
+                    ));
+                }
+
+                claim
+                    .verify()
+                    .map_err(|err| TxnValidatorError::InvalidClaim(err.to_string()))
+            },
+            TransactionKind::CallProgram(call) => {
+                if call.program_id.is_empty() {
+                    return Err(TxnValidatorError::InvalidProgramCall(
+                        "missing program id".to_string(),
+                    ));
+                }
+
+                if call.entrypoint.is_empty() {
+                    return Err(TxnValidatorError::InvalidProgramCall(
+                        "missing entrypoint".to_string(),
+                    ));
+                }
+
+                Ok(())
+            },
+        }
+    }
+
+    /// Stake validator. Slashes are applied by the protocol and are never
+    /// accepted from users.
+    pub fn validate_stake(&self, sender: &Address, stake: &Stake) -> Result<()> {
+        if &stake.get_sender() != sender {
+            return Err(TxnValidatorError::InvalidStake(
+                "stake sender does not match transaction sender".to_string(),
+            ));
+        }
+
+        stake
+            .verify()
+            .map_err(|err| TxnValidatorError::InvalidStake(err.to_string()))?;
+
+        match stake.get_amount() {
+            StakeUpdate::Add(amount) | StakeUpdate::Withdrawal(amount) => {
+                if amount == 0 || amount > Stake::MAX {
+                    return Err(TxnValidatorError::OutOfBounds(
+                        "stake".to_string(),
+                        "1".to_string(),
+                        Stake::MAX.to_string(),
+                    ));
+                }
+                Ok(())
+            },
+            StakeUpdate::Slash(_) => Err(TxnValidatorError::InvalidStake(
+                "slashes cannot be submitted as transactions".to_string(),
+            )),
+        }
     }
 
     /// Txn signature validator.
@@ -160,8 +249,17 @@ impl TxnValidator {
         }
     }
 
-    /// Txn receiver validator
-    // TODO, to be synchronized with transaction fees.
+    /// Txn fee validator, every transaction has to pay at least `BASE_FEE`
+    pub fn validate_fee(&self, txn: &TransactionKind) -> Result<()> {
+        if txn.fee() < BASE_FEE {
+            return Err(TxnValidatorError::TxnFeeTooLow(txn.fee(), BASE_FEE));
+        }
+
+        Ok(())
+    }
+
+    /// Checks the sender's balance covers the amount the transaction moves
+    /// out of it along with its fee
     pub fn validate_amount(
         &self,
         state_reader: StateStoreReadHandleFactory,
@@ -173,7 +271,7 @@ impl TxnValidator {
             .get(&address)
             .map_err(|_| TxnValidatorError::SenderAddressIncorrect)?;
         if (account.credits() - account.debits())
-            .checked_sub(txn.amount())
+            .checked_sub(txn.cost())
             .is_none()
         {
             return Err(TxnValidatorError::TxnAmountIncorrect);
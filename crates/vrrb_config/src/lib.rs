@@ -1,6 +1,8 @@
 mod bootstrap;
 pub mod bootstrap_quorum;
+pub mod mempool_config;
 mod node_config;
+pub mod pruning_config;
 pub mod quorum;
 pub mod result;
 pub mod test_utils;
@@ -8,7 +10,9 @@ pub mod threshold_config;
 
 pub use bootstrap::*;
 pub use bootstrap_quorum::*;
+pub use mempool_config::*;
 pub use node_config::*;
+pub use pruning_config::*;
 pub use quorum::*;
 pub use result::*;
 pub use test_utils::*;
@@ -69,4 +73,39 @@ mod tests {
         let valid_config = valid_threshold_config();
         valid_config.validate().unwrap();
     }
+
+    #[test]
+    fn default_mempool_config_is_valid() {
+        MempoolConfig::default().validate().unwrap();
+    }
+
+    #[test]
+    fn mempool_config_rejects_sender_cap_above_max_size() {
+        let config = MempoolConfig {
+            max_size: 10,
+            max_txns_per_sender: 11,
+            ..Default::default()
+        };
+
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn archive_mode_skips_pruning_checks() {
+        let config = PruningConfig {
+            retained_versions: 0,
+            ..PruningConfig::archive()
+        };
+        config.validate().unwrap();
+
+        assert!(PruningConfig::prune(0).validate().is_err());
+    }
+
+    #[test]
+    fn pruning_is_opt_in() {
+        assert!(PruningConfig::default().is_archive());
+        PruningConfig::prune(DEFAULT_PRUNING_RETAINED_VERSIONS)
+            .validate()
+            .unwrap();
+    }
 }
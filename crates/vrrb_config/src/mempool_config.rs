@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+
+use crate::ConfigError;
+
+pub const DEFAULT_MEMPOOL_MAX_SIZE: usize = 100_000;
+pub const DEFAULT_MEMPOOL_MAX_TXNS_PER_SENDER: usize = 1_000;
+pub const DEFAULT_REPLACEMENT_FEE_BUMP_PERCENT: u64 = 10;
+pub const DEFAULT_MEMPOOL_JOURNAL_COMPACTION_THRESHOLD: usize = 10_000;
+pub const DEFAULT_MEMPOOL_JOURNAL_COMPACTION_BYTES: u64 = 64 * 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Eq)]
+#[serde(default)]
+pub struct MempoolConfig {
+    /// Maximum number of transactions kept in the mempool. Once reached, the
+    /// lowest fee transactions are evicted to make room for better paying ones
+    pub max_size: usize,
+
+    /// Maximum number of pending transactions a single sender can have in the
+    /// mempool at any given time
+    pub max_txns_per_sender: usize,
+
+    /// Minimum fee increase, as a percentage of the pending transaction's
+    /// fee, a transaction with the same sender and nonce has to pay in order
+    /// to replace it
+    pub replacement_fee_bump_percent: u64,
+
+    /// Number of operations recorded in the mempool journal after which it
+    /// gets compacted
+    pub journal_compaction_threshold: usize,
+
+    /// Number of bytes written to the mempool journal after which it gets
+    /// compacted, regardless of how many operations they hold
+    pub journal_compaction_bytes: u64,
+}
+
+impl Default for MempoolConfig {
+    fn default() -> Self {
+        MempoolConfig {
+            max_size: DEFAULT_MEMPOOL_MAX_SIZE,
+            max_txns_per_sender: DEFAULT_MEMPOOL_MAX_TXNS_PER_SENDER,
+            replacement_fee_bump_percent: DEFAULT_REPLACEMENT_FEE_BUMP_PERCENT,
+            journal_compaction_threshold: DEFAULT_MEMPOOL_JOURNAL_COMPACTION_THRESHOLD,
+            journal_compaction_bytes: DEFAULT_MEMPOOL_JOURNAL_COMPACTION_BYTES,
+        }
+    }
+}
+
+impl MempoolConfig {
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.max_size == 0 {
+            return Err(ConfigError::Other(
+                "mempool max size must be greater than 0".to_string(),
+            ));
+        }
+        if self.max_txns_per_sender == 0 || self.max_txns_per_sender > self.max_size {
+            return Err(ConfigError::Other(format!(
+                "mempool max txns per sender {} == 0 || > {}",
+                self.max_txns_per_sender, self.max_size
+            )));
+        }
+        if self.journal_compaction_threshold == 0 {
+            return Err(ConfigError::Other(
+                "mempool journal compaction threshold must be greater than 0".to_string(),
+            ));
+        }
+        if self.journal_compaction_bytes == 0 {
+            return Err(ConfigError::Other(
+                "mempool journal compaction bytes must be greater than 0".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
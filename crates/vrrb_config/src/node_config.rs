@@ -5,14 +5,18 @@ use std::{
 };
 
 use derive_builder::Builder;
-use primitives::{KademliaPeerId, NodeId, NodeType, DEFAULT_VRRB_DATA_DIR_PATH};
+use primitives::{Epoch, KademliaPeerId, NodeId, NodeType, DEFAULT_VRRB_DATA_DIR_PATH};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
-use vrrb_core::keypair::Keypair;
+use vrrb_core::{
+    keypair::Keypair,
+    staking::{DEFAULT_COMMISSION_RATE, DEFAULT_UNBONDING_EPOCHS},
+    transactions::DEFAULT_FEE_BURN_RATE,
+};
 
 use crate::{
-    bootstrap::BootstrapConfig, BootstrapPeerData, QuorumMember, QuorumMembershipConfig,
-    ThresholdConfig,
+    bootstrap::BootstrapConfig, BootstrapPeerData, MempoolConfig, PruningConfig, QuorumMember,
+    QuorumMembershipConfig, ThresholdConfig, DEFAULT_DKG_TIMEOUT,
 };
 
 #[derive(Builder, Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
@@ -107,6 +111,57 @@ pub struct NodeConfig {
     pub threshold_config: ThresholdConfig,
 
     pub whitelisted_nodes: Vec<QuorumMember>,
+
+    /// Size limits and eviction settings of the node's mempool
+    #[builder(default)]
+    #[serde(default)]
+    pub mempool_config: MempoolConfig,
+
+    /// Whether and how stale state trie nodes get pruned. Archive nodes keep
+    /// every version of the state
+    #[builder(default)]
+    #[serde(default)]
+    pub pruning_config: PruningConfig,
+
+    /// Number of epochs withdrawn stake stays locked before it is released
+    /// back to its owner
+    #[builder(default = "DEFAULT_UNBONDING_EPOCHS")]
+    #[serde(default = "default_unbonding_epochs")]
+    pub unbonding_epochs: Epoch,
+
+    /// Percentage of the rewards earned by this node's claim that it keeps
+    /// before sharing the rest with its delegators
+    #[builder(default = "DEFAULT_COMMISSION_RATE")]
+    #[serde(default = "default_commission_rate")]
+    pub commission_rate: u8,
+
+    /// Percentage of every transaction fee burned by the blocks this node
+    /// mines instead of being paid to validators and proposers
+    #[builder(default = "DEFAULT_FEE_BURN_RATE")]
+    #[serde(default = "default_fee_burn_rate")]
+    pub fee_burn_rate: u8,
+
+    /// How long quorum members get to complete a DKG round before the ones
+    /// that did not respond are excluded
+    #[builder(default = "DEFAULT_DKG_TIMEOUT")]
+    #[serde(default = "default_dkg_timeout")]
+    pub dkg_timeout: Duration,
+}
+
+fn default_unbonding_epochs() -> Epoch {
+    DEFAULT_UNBONDING_EPOCHS
+}
+
+fn default_commission_rate() -> u8 {
+    DEFAULT_COMMISSION_RATE
+}
+
+fn default_fee_burn_rate() -> u8 {
+    DEFAULT_FEE_BURN_RATE
+}
+
+fn default_dkg_timeout() -> Duration {
+    DEFAULT_DKG_TIMEOUT
 }
 
 impl NodeConfig {
@@ -188,6 +243,12 @@ impl Default for NodeConfig {
             threshold_config: ThresholdConfig::default(),
             enable_block_indexing: false,
             whitelisted_nodes: vec![],
+            mempool_config: MempoolConfig::default(),
+            pruning_config: PruningConfig::default(),
+            unbonding_epochs: DEFAULT_UNBONDING_EPOCHS,
+            commission_rate: DEFAULT_COMMISSION_RATE,
+            fee_burn_rate: DEFAULT_FEE_BURN_RATE,
+            dkg_timeout: DEFAULT_DKG_TIMEOUT,
         }
     }
 }
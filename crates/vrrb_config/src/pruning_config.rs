@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ConfigError;
+
+pub const DEFAULT_PRUNING_RETAINED_VERSIONS: u64 = 10_000;
+pub const DEFAULT_PRUNING_BATCH_SIZE: usize = 1_000;
+pub const DEFAULT_PRUNING_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PruningMode {
+    /// Keeps every version of the state tries around
+    Archive,
+
+    /// Periodically deletes tree nodes that are only reachable from versions
+    /// older than the retention window
+    Prune,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Eq)]
+#[serde(default)]
+pub struct PruningConfig {
+    pub mode: PruningMode,
+
+    /// Number of trie versions kept readable, counted back from the latest
+    pub retained_versions: u64,
+
+    /// Maximum number of stale nodes deleted from each trie per pruning pass
+    pub batch_size: usize,
+
+    /// Time between pruning passes
+    pub interval: Duration,
+}
+
+impl Default for PruningConfig {
+    fn default() -> Self {
+        PruningConfig {
+            mode: PruningMode::Archive,
+            retained_versions: DEFAULT_PRUNING_RETAINED_VERSIONS,
+            batch_size: DEFAULT_PRUNING_BATCH_SIZE,
+            interval: DEFAULT_PRUNING_INTERVAL,
+        }
+    }
+}
+
+impl PruningConfig {
+    /// Configuration of a node that never prunes its state
+    pub fn archive() -> Self {
+        PruningConfig {
+            mode: PruningMode::Archive,
+            ..Default::default()
+        }
+    }
+
+    /// Configuration of a node that prunes versions older than the most
+    /// recent `retained_versions`
+    pub fn prune(retained_versions: u64) -> Self {
+        PruningConfig {
+            mode: PruningMode::Prune,
+            retained_versions,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_archive(&self) -> bool {
+        self.mode == PruningMode::Archive
+    }
+
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.is_archive() {
+            return Ok(());
+        }
+        if self.retained_versions == 0 {
+            return Err(ConfigError::Other(
+                "pruning retained versions must be greater than 0".to_string(),
+            ));
+        }
+        if self.batch_size == 0 {
+            return Err(ConfigError::Other(
+                "pruning batch size must be greater than 0".to_string(),
+            ));
+        }
+        if self.interval.is_zero() {
+            return Err(ConfigError::Other(
+                "pruning interval must be greater than 0".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
@@ -1,7 +1,13 @@
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
 
 use crate::ConfigError;
 
+/// How long quorum members get to complete a DKG round before the ones that
+/// did not respond are excluded
+pub const DEFAULT_DKG_TIMEOUT: Duration = Duration::from_secs(30);
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Eq)]
 pub struct ThresholdConfig {
     pub upper_bound: u16,
@@ -1,7 +1,7 @@
 use std::net::SocketAddr;
 
 use ethereum_types::U256;
-use primitives::{Address, NodeId, PublicKey, SerializedSecretKey};
+use primitives::{Address, Epoch, NodeId, PublicKey, SerializedSecretKey};
 use serde::{Deserialize, Serialize};
 /// a Module for creating, maintaining, and using a claim in the fair,
 /// computationally inexpensive, collission proof, fully decentralized, fully
@@ -13,7 +13,9 @@ use thiserror::Error;
 use crate::{
     keypair::{KeyPairError, Keypair},
     ownable::Ownable,
-    staking::{Stake, StakeError, StakeUpdate},
+    staking::{
+        Delegation, Stake, StakeError, StakeUpdate, UnbondingEntry, DEFAULT_COMMISSION_RATE,
+    },
 };
 
 pub type Result<T> = std::result::Result<T, ClaimError>;
@@ -53,6 +55,20 @@ pub struct Claim {
     pub node_id: NodeId,
     stake: u128,
     stake_txns: Vec<Stake>,
+    #[serde(default)]
+    unbonding: Vec<UnbondingEntry>,
+    #[serde(default)]
+    delegations: Vec<Delegation>,
+    #[serde(default = "default_commission_rate")]
+    commission_rate: u8,
+    #[serde(default)]
+    pending_rewards: u128,
+    #[serde(default)]
+    rewards_epoch: Epoch,
+}
+
+fn default_commission_rate() -> u8 {
+    DEFAULT_COMMISSION_RATE
 }
 
 // TODO: Remove None variant and use Option<Eligibility>.
@@ -107,6 +123,11 @@ impl Claim {
                 node_id,
                 stake: 0,
                 stake_txns: vec![],
+                unbonding: vec![],
+                delegations: vec![],
+                commission_rate: DEFAULT_COMMISSION_RATE,
+                pending_rewards: 0,
+                rewards_epoch: 0,
             }),
             Err(e) => Err(e),
         };
@@ -218,6 +239,32 @@ impl Claim {
         Ok(())
     }
 
+    /// Verifies that the claim hash was derived from the claim's public key
+    /// and ip address and that the signature was produced by the claim's
+    /// public key.
+    pub fn verify(&self) -> Result<()> {
+        let mut hasher = Sha256::new();
+        hasher.update(self.public_key.to_string());
+        hasher.update(self.ip_address.to_string());
+        let result = hasher.finalize();
+        let hash = U256::from_big_endian(&result[..]);
+        if hash != self.hash {
+            return Err(ClaimError::Other(
+                "claim hash does not match public key and ip address".to_string(),
+            ));
+        }
+
+        let mut msg_hash: Vec<u8> = Vec::new();
+        hash.0.to_vec().iter().for_each(|x| {
+            msg_hash.extend(x.to_le_bytes().iter());
+        });
+        Claim::is_valid_claim(
+            msg_hash.as_slice(),
+            self.signature.clone(),
+            self.public_key.serialize().to_vec(),
+        )
+    }
+
     /// Uses XOR of the ClaimHash as a U256 against a block seed of u64
     /// U256 is represented as a [u64; 4] so we XOR each of the 4
     /// u64 values in the U256 against the block seed.
@@ -243,6 +290,10 @@ impl Claim {
         }
 
         if stake_txn.get_certificate().is_some() {
+            if stake_txn.get_sender() != self.address {
+                return self.update_delegation(stake_txn);
+            }
+
             let prev_stake = self.stake;
             self.stake_txns.push(stake_txn);
             self.stake = self.check_stake_utxo();
@@ -257,8 +308,198 @@ impl Claim {
         Err(StakeError::UncertifiedStake)
     }
 
+    /// Applies a `StakeUpdate::Slash` penalty to the claim's stake. Unlike
+    /// `update_stake` the penalty is not issued by the claim owner, it is
+    /// issued by the node that reported the offense and is only applied
+    /// alongside verified slashing evidence.
+    pub fn slash(&mut self, penalty: Stake) -> crate::staking::Result<()> {
+        if !matches!(penalty.get_amount(), StakeUpdate::Slash(_)) {
+            return Err(StakeError::Other(
+                "Only slash updates can be applied as a penalty".to_string(),
+            ));
+        }
+
+        if penalty.get_receiver() != self.address {
+            return Err(StakeError::Other(
+                "This claim is not the target of the penalty".to_string(),
+            ));
+        }
+
+        if let StakeUpdate::Slash(pct) = penalty.get_amount() {
+            for entry in self.unbonding.iter_mut() {
+                entry.amount = Self::slash_calculator(pct, entry.amount);
+            }
+
+            for delegation in self.delegations.iter_mut() {
+                delegation.amount = Self::slash_calculator(pct, delegation.amount);
+            }
+        }
+
+        self.stake_txns.push(penalty);
+        self.stake = self.check_stake_utxo();
+
+        Ok(())
+    }
+
+    /// Applies a `StakeUpdate::Withdrawal` to the claim's stake and queues the
+    /// withdrawn amount until `release_epoch`. The withdrawn amount stops
+    /// counting toward the claim's stake right away.
+    pub fn unbond(
+        &mut self,
+        stake_txn: Stake,
+        requested_epoch: Epoch,
+        release_epoch: Epoch,
+    ) -> crate::staking::Result<()> {
+        if !matches!(stake_txn.get_amount(), StakeUpdate::Withdrawal(_)) {
+            return Err(StakeError::Other(
+                "Only withdrawals can be unbonded".to_string(),
+            ));
+        }
+
+        let owner = stake_txn.get_sender();
+        let prev_stake = self.get_stake_of(&owner);
+        self.update_stake(stake_txn)?;
+
+        let amount = prev_stake.saturating_sub(self.get_stake_of(&owner));
+        if amount > 0 {
+            self.unbonding.push(UnbondingEntry {
+                owner,
+                amount,
+                requested_epoch,
+                release_epoch,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Removes and returns the unbonding entries that are due by `epoch`
+    pub fn release_unbonded(&mut self, epoch: Epoch) -> Vec<UnbondingEntry> {
+        let (released, pending) = self
+            .unbonding
+            .drain(..)
+            .partition(|entry| entry.release_epoch <= epoch);
+        self.unbonding = pending;
+
+        released
+    }
+
+    /// Sets the percentage of the claim's rewards the validator keeps before
+    /// sharing the rest with its delegators
+    pub fn set_commission_rate(&mut self, commission_rate: u8) -> crate::staking::Result<()> {
+        if commission_rate > 100 {
+            return Err(StakeError::Other(
+                "Commission rate cannot be higher than 100%".to_string(),
+            ));
+        }
+
+        self.commission_rate = commission_rate;
+
+        Ok(())
+    }
+
+    pub fn get_commission_rate(&self) -> u8 {
+        self.commission_rate
+    }
+
+    /// Adds `amount` to the rewards the claim has earned during `epoch`. They
+    /// are paid out by `distribute_rewards` once the epoch is over.
+    pub fn accrue_rewards(&mut self, amount: u128, epoch: Epoch) {
+        self.pending_rewards = self.pending_rewards.saturating_add(amount);
+        self.rewards_epoch = epoch;
+    }
+
+    pub fn get_pending_rewards(&self) -> u128 {
+        self.pending_rewards
+    }
+
+    /// Returns the epoch the claim last accrued rewards in
+    pub fn get_rewards_epoch(&self) -> Epoch {
+        self.rewards_epoch
+    }
+
+    /// Returns true if the claim has rewards left over from an epoch before
+    /// `epoch`
+    pub fn rewards_due(&self, epoch: Epoch) -> bool {
+        self.pending_rewards > 0 && self.rewards_epoch < epoch
+    }
+
+    /// Pays out the pending rewards of the claim. The validator keeps its
+    /// commission and the rest is split between the validator and its
+    /// delegators in proportion to their stake. Whatever is left after
+    /// rounding goes to the validator, which is always the first payout.
+    pub fn distribute_rewards(&mut self) -> Vec<(Address, u128)> {
+        let rewards = std::mem::take(&mut self.pending_rewards);
+        if rewards == 0 {
+            return vec![];
+        }
+
+        let commission = U256::from(rewards) * U256::from(self.commission_rate) / U256::from(100);
+        let shared = U256::from(rewards) - commission;
+        let total_stake = self.get_total_stake();
+
+        let mut payouts = vec![(self.address.clone(), 0)];
+        let mut distributed = 0u128;
+        if total_stake > 0 {
+            for delegation in self.delegations.iter() {
+                let share =
+                    (shared * U256::from(delegation.amount) / U256::from(total_stake)).as_u128();
+                if share > 0 {
+                    payouts.push((delegation.delegator.clone(), share));
+                    distributed += share;
+                }
+            }
+        }
+
+        payouts[0].1 = rewards - distributed;
+
+        payouts
+    }
+
+    fn update_delegation(&mut self, stake_txn: Stake) -> crate::staking::Result<()> {
+        let delegator = stake_txn.get_sender();
+        let position = self
+            .delegations
+            .iter()
+            .position(|delegation| delegation.delegator == delegator);
+
+        match (stake_txn.get_amount(), position) {
+            (StakeUpdate::Add(value), Some(idx)) => {
+                let delegation = &mut self.delegations[idx];
+                delegation.amount = delegation.amount.saturating_add(value);
+            },
+            (StakeUpdate::Add(value), None) => {
+                self.delegations.push(Delegation {
+                    delegator,
+                    amount: value,
+                });
+            },
+            (StakeUpdate::Withdrawal(value), Some(idx)) => {
+                if let Some(amount) = self.delegations[idx].amount.checked_sub(value) {
+                    self.delegations[idx].amount = amount;
+                }
+
+                if self.delegations[idx].amount == 0 {
+                    self.delegations.remove(idx);
+                }
+            },
+            (StakeUpdate::Withdrawal(_), None) => {},
+            (StakeUpdate::Slash(_), _) => {
+                return Err(StakeError::Other(
+                    "Delegators cannot slash the claim they delegate to".to_string(),
+                ));
+            },
+        }
+
+        Ok(())
+    }
+
+    /// A stake is deposited to the claim it is addressed to, which is the
+    /// sender's own claim unless the stake is delegated. Matching on the
+    /// sender instead would let a claim owner's delegation to another claim
+    /// be applied to its own claim.
     fn depositing_claim(&self, stake_txn: &Stake) -> bool {
-        stake_txn.get_sender() == self.address
+        stake_txn.get_receiver() == self.address
     }
 
     /// Checks the cumulative value of a nodes stake by calculating
@@ -279,25 +520,69 @@ impl Claim {
                     }
                     acc
                 },
-                StakeUpdate::Slash(pct) => self.slash_calculator(pct, acc),
+                StakeUpdate::Slash(pct) => Self::slash_calculator(pct, acc),
             })
     }
 
     /// Returns the slashed value of a nodes stake after a slashing
     /// event.
-    fn slash_calculator(&self, pct: u8, value: u128) -> u128 {
+    fn slash_calculator(pct: u8, value: u128) -> u128 {
         let slash = (value as f64) * (pct as f64 / 100f64);
         value - slash as u128
     }
 
+    /// Returns the bonded stake the claim owner put in its own claim. Stake
+    /// that is delegated or still unbonding is not included.
     pub fn get_stake(&self) -> u128 {
         self.stake
     }
 
+    /// Returns the stake `address` has bonded to the claim, either as the
+    /// claim owner or as a delegator
+    pub fn get_stake_of(&self, address: &Address) -> u128 {
+        if address == &self.address {
+            return self.stake;
+        }
+
+        self.delegations
+            .iter()
+            .find(|delegation| &delegation.delegator == address)
+            .map(|delegation| delegation.amount)
+            .unwrap_or_default()
+    }
+
+    /// Returns the total amount of stake delegated to the claim
+    pub fn get_delegated_stake(&self) -> u128 {
+        self.delegations
+            .iter()
+            .map(|delegation| delegation.amount)
+            .sum()
+    }
+
+    /// Returns the claim owner's stake along with the stake delegated to it
+    pub fn get_total_stake(&self) -> u128 {
+        self.stake.saturating_add(self.get_delegated_stake())
+    }
+
+    pub fn delegations(&self) -> &[Delegation] {
+        &self.delegations
+    }
+
     pub fn get_stake_txns(&self) -> Vec<Stake> {
         self.stake_txns.clone()
     }
 
+    /// Returns the withdrawals that are still waiting out their unbonding
+    /// period
+    pub fn unbonding(&self) -> &[UnbondingEntry] {
+        &self.unbonding
+    }
+
+    /// Returns the total amount of stake that is still unbonding
+    pub fn get_unbonding_stake(&self) -> u128 {
+        self.unbonding.iter().map(|entry| entry.amount).sum()
+    }
+
     #[deprecated(note = "Please use get_election_result")]
     pub fn get_pointer(&self, block_seed: u128) -> Option<u128> {
         let block_seed_hex = format!("{block_seed:x}");
@@ -450,6 +735,11 @@ mod tests {
             node_id: NodeId::default(),
             stake: 0,
             stake_txns: vec![],
+            unbonding: vec![],
+            delegations: vec![],
+            commission_rate: DEFAULT_COMMISSION_RATE,
+            pending_rewards: 0,
+            rewards_epoch: 0,
         };
         let claim = Claim::new(
             public_key,
@@ -967,4 +1257,353 @@ mod tests {
         assert_eq!(claim.get_stake(), 90_000u128);
         assert_eq!(claim.get_stake_txns().len(), 2);
     }
+
+    #[test]
+    fn should_slash_stake_with_penalty_from_another_node() {
+        let kp = KeyPair::random();
+        let public_key = kp.miner_kp.1;
+        let address = Address::new(public_key);
+        let ip_address = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+        let signature = Claim::signature_for_valid_claim(
+            public_key,
+            ip_address,
+            kp.get_miner_secret_key().secret_bytes().to_vec(),
+        )
+        .unwrap();
+        let mut claim = Claim::new(
+            public_key,
+            address.clone(),
+            ip_address,
+            signature,
+            NodeId::default(),
+        )
+        .unwrap();
+
+        let mut stake = Stake::new(
+            StakeUpdate::Add(10_000u128),
+            kp.miner_kp.0,
+            kp.miner_kp.1,
+            address.clone(),
+            None,
+        )
+        .unwrap();
+
+        stake.certify((vec![0; 96], vec![0; 96])).unwrap();
+        claim.update_stake(stake).unwrap();
+
+        let reporter = KeyPair::random();
+        let penalty = Stake::new(
+            StakeUpdate::Slash(10),
+            reporter.miner_kp.0,
+            reporter.miner_kp.1,
+            address,
+            None,
+        )
+        .unwrap();
+
+        assert!(claim.slash(penalty).is_ok());
+        assert_eq!(claim.get_stake(), 9_000u128);
+
+        let other = Address::new(reporter.miner_kp.1);
+        let penalty = Stake::new(
+            StakeUpdate::Slash(10),
+            reporter.miner_kp.0,
+            reporter.miner_kp.1,
+            other,
+            None,
+        )
+        .unwrap();
+
+        assert!(claim.slash(penalty).is_err());
+        assert_eq!(claim.get_stake(), 9_000u128);
+    }
+
+    fn staked_claim(kp: &KeyPair, amount: u128) -> Claim {
+        let public_key = kp.miner_kp.1;
+        let address = Address::new(public_key);
+        let ip_address = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+        let signature = Claim::signature_for_valid_claim(
+            public_key,
+            ip_address,
+            kp.get_miner_secret_key().secret_bytes().to_vec(),
+        )
+        .unwrap();
+        let mut claim = Claim::new(
+            public_key,
+            address.clone(),
+            ip_address,
+            signature,
+            NodeId::default(),
+        )
+        .unwrap();
+
+        let mut stake = Stake::new(
+            StakeUpdate::Add(amount),
+            kp.miner_kp.0,
+            kp.miner_kp.1,
+            address,
+            None,
+        )
+        .unwrap();
+
+        stake.certify((vec![0; 96], vec![0; 96])).unwrap();
+        claim.update_stake(stake).unwrap();
+
+        claim
+    }
+
+    fn withdrawal(kp: &KeyPair, amount: u128) -> Stake {
+        let mut stake = Stake::new(
+            StakeUpdate::Withdrawal(amount),
+            kp.miner_kp.0,
+            kp.miner_kp.1,
+            Address::new(kp.miner_kp.1),
+            None,
+        )
+        .unwrap();
+
+        stake.certify((vec![0; 96], vec![0; 96])).unwrap();
+
+        stake
+    }
+
+    #[test]
+    fn should_unbond_withdrawn_stake_until_release_epoch() {
+        let kp = KeyPair::random();
+        let mut claim = staked_claim(&kp, 10_000u128);
+
+        claim.unbond(withdrawal(&kp, 4_000u128), 3, 5).unwrap();
+
+        assert_eq!(claim.get_stake(), 6_000u128);
+        assert_eq!(claim.get_unbonding_stake(), 4_000u128);
+        assert_eq!(claim.unbonding()[0].release_epoch, 5);
+
+        assert!(claim.release_unbonded(4).is_empty());
+        assert_eq!(claim.get_unbonding_stake(), 4_000u128);
+
+        let released = claim.release_unbonded(5);
+        assert_eq!(released.len(), 1);
+        assert_eq!(released[0].amount, 4_000u128);
+        assert_eq!(released[0].owner, Address::new(kp.miner_kp.1));
+        assert!(claim.unbonding().is_empty());
+        assert_eq!(claim.get_stake(), 6_000u128);
+    }
+
+    #[test]
+    fn should_only_unbond_withdrawals() {
+        let kp = KeyPair::random();
+        let mut claim = staked_claim(&kp, 10_000u128);
+
+        let mut stake = Stake::new(
+            StakeUpdate::Add(1_000u128),
+            kp.miner_kp.0,
+            kp.miner_kp.1,
+            Address::new(kp.miner_kp.1),
+            None,
+        )
+        .unwrap();
+        stake.certify((vec![0; 96], vec![0; 96])).unwrap();
+
+        assert!(claim.unbond(stake, 0, 2).is_err());
+        assert_eq!(claim.get_stake(), 10_000u128);
+        assert!(claim.unbonding().is_empty());
+    }
+
+    #[test]
+    fn should_slash_unbonding_stake() {
+        let kp = KeyPair::random();
+        let mut claim = staked_claim(&kp, 10_000u128);
+        claim.unbond(withdrawal(&kp, 4_000u128), 0, 2).unwrap();
+
+        let reporter = KeyPair::random();
+        let penalty = Stake::new(
+            StakeUpdate::Slash(10),
+            reporter.miner_kp.0,
+            reporter.miner_kp.1,
+            Address::new(kp.miner_kp.1),
+            None,
+        )
+        .unwrap();
+
+        claim.slash(penalty).unwrap();
+
+        assert_eq!(claim.get_stake(), 5_400u128);
+        assert_eq!(claim.get_unbonding_stake(), 3_600u128);
+    }
+
+    fn delegation(delegator: &KeyPair, claim: &Claim, amount: StakeUpdate) -> Stake {
+        let mut stake = Stake::new(
+            amount,
+            delegator.miner_kp.0,
+            delegator.miner_kp.1,
+            Address::new(delegator.miner_kp.1),
+            Some(claim.address.clone()),
+        )
+        .unwrap();
+
+        stake.certify((vec![0; 96], vec![0; 96])).unwrap();
+
+        stake
+    }
+
+    #[test]
+    fn should_track_delegations_separately_from_own_stake() {
+        let kp = KeyPair::random();
+        let delegator = KeyPair::random();
+        let delegator_address = Address::new(delegator.miner_kp.1);
+        let mut claim = staked_claim(&kp, 10_000u128);
+
+        let stake = delegation(&delegator, &claim, StakeUpdate::Add(5_000u128));
+        claim.update_stake(stake).unwrap();
+        let stake = delegation(&delegator, &claim, StakeUpdate::Add(1_000u128));
+        claim.update_stake(stake).unwrap();
+
+        assert_eq!(claim.get_stake(), 10_000u128);
+        assert_eq!(claim.get_delegated_stake(), 6_000u128);
+        assert_eq!(claim.get_total_stake(), 16_000u128);
+        assert_eq!(claim.get_stake_of(&delegator_address), 6_000u128);
+
+        let stake = delegation(&delegator, &claim, StakeUpdate::Withdrawal(6_000u128));
+        claim.update_stake(stake).unwrap();
+
+        assert!(claim.delegations().is_empty());
+        assert_eq!(claim.get_total_stake(), 10_000u128);
+    }
+
+    #[test]
+    fn should_reject_stake_addressed_to_another_claim() {
+        let kp = KeyPair::random();
+        let mut claim = staked_claim(&kp, 10_000u128);
+        let other = staked_claim(&KeyPair::random(), 10_000u128);
+
+        let stake = delegation(&kp, &other, StakeUpdate::Add(5_000u128));
+
+        assert!(claim.update_stake(stake).is_err());
+        assert_eq!(claim.get_total_stake(), 10_000u128);
+    }
+
+    #[test]
+    fn should_not_let_delegators_slash_the_claim() {
+        let kp = KeyPair::random();
+        let delegator = KeyPair::random();
+        let mut claim = staked_claim(&kp, 10_000u128);
+
+        let stake = delegation(&delegator, &claim, StakeUpdate::Slash(50));
+
+        assert!(claim.update_stake(stake).is_err());
+        assert_eq!(claim.get_stake(), 10_000u128);
+    }
+
+    #[test]
+    fn should_unbond_delegated_stake_to_the_delegator() {
+        let kp = KeyPair::random();
+        let delegator = KeyPair::random();
+        let mut claim = staked_claim(&kp, 10_000u128);
+
+        let stake = delegation(&delegator, &claim, StakeUpdate::Add(5_000u128));
+        claim.update_stake(stake).unwrap();
+        let stake = delegation(&delegator, &claim, StakeUpdate::Withdrawal(2_000u128));
+        claim.unbond(stake, 0, 2).unwrap();
+
+        assert_eq!(claim.get_stake(), 10_000u128);
+        assert_eq!(claim.get_delegated_stake(), 3_000u128);
+        assert_eq!(claim.unbonding()[0].amount, 2_000u128);
+        assert_eq!(
+            claim.unbonding()[0].owner,
+            Address::new(delegator.miner_kp.1)
+        );
+    }
+
+    #[test]
+    fn should_slash_delegated_stake() {
+        let kp = KeyPair::random();
+        let delegator = KeyPair::random();
+        let mut claim = staked_claim(&kp, 10_000u128);
+
+        let stake = delegation(&delegator, &claim, StakeUpdate::Add(5_000u128));
+        claim.update_stake(stake).unwrap();
+
+        let reporter = KeyPair::random();
+        let penalty = Stake::new(
+            StakeUpdate::Slash(10),
+            reporter.miner_kp.0,
+            reporter.miner_kp.1,
+            Address::new(kp.miner_kp.1),
+            None,
+        )
+        .unwrap();
+
+        claim.slash(penalty).unwrap();
+
+        assert_eq!(claim.get_stake(), 9_000u128);
+        assert_eq!(claim.get_delegated_stake(), 4_500u128);
+    }
+
+    #[test]
+    fn should_distribute_rewards_to_delegators_after_commission() {
+        let kp = KeyPair::random();
+        let delegator = KeyPair::random();
+        let mut claim = staked_claim(&kp, 10_000u128);
+        claim.set_commission_rate(10).unwrap();
+
+        let stake = delegation(&delegator, &claim, StakeUpdate::Add(30_000u128));
+        claim.update_stake(stake).unwrap();
+
+        claim.accrue_rewards(600, 1);
+        claim.accrue_rewards(400, 1);
+
+        assert!(!claim.rewards_due(1));
+        assert!(claim.rewards_due(2));
+
+        let payouts = claim.distribute_rewards();
+
+        // 100 commission, then 900 split 1:3 between the validator and the
+        // delegator
+        assert_eq!(
+            payouts,
+            vec![
+                (Address::new(kp.miner_kp.1), 325u128),
+                (Address::new(delegator.miner_kp.1), 675u128),
+            ]
+        );
+        assert_eq!(claim.get_pending_rewards(), 0);
+        assert!(claim.distribute_rewards().is_empty());
+    }
+
+    #[test]
+    fn should_reject_commission_rates_above_100() {
+        let kp = KeyPair::random();
+        let mut claim = staked_claim(&kp, 10_000u128);
+
+        assert!(claim.set_commission_rate(101).is_err());
+        assert_eq!(claim.get_commission_rate(), DEFAULT_COMMISSION_RATE);
+    }
+
+    #[test]
+    fn should_verify_valid_claim() {
+        let kp = KeyPair::random();
+        let public_key = kp.miner_kp.1;
+        let address = Address::new(public_key);
+        let ip_address = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+        let signature = Claim::signature_for_valid_claim(
+            public_key,
+            ip_address,
+            kp.get_miner_secret_key().secret_bytes().to_vec(),
+        )
+        .unwrap();
+        let mut claim = Claim::new(
+            public_key,
+            address,
+            ip_address,
+            signature,
+            NodeId::default(),
+        )
+        .unwrap();
+
+        assert!(claim.verify().is_ok());
+
+        claim.ip_address = "127.0.0.1:9090".parse::<SocketAddr>().unwrap();
+
+        assert!(claim.verify().is_err());
+    }
 }
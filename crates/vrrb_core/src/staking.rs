@@ -1,4 +1,4 @@
-use primitives::{Address, PayloadHash, QuorumPublicKey, Signature};
+use primitives::{Address, Epoch, PayloadHash, QuorumPublicKey, Signature};
 use secp256k1::Message;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -12,6 +12,14 @@ pub type Certificate = (Vec<u8>, PayloadHash);
 pub const MIN_STAKE_FARMER: u128 = 10_000;
 pub const MIN_STAKE_VALIDATOR: u128 = 50_000;
 
+/// Number of epochs withdrawn stake stays locked before it is released back
+/// to its owner
+pub const DEFAULT_UNBONDING_EPOCHS: Epoch = 2;
+
+/// Percentage of a claim's rewards kept by the validator before the rest is
+/// shared with its delegators
+pub const DEFAULT_COMMISSION_RATE: u8 = 10;
+
 pub type Result<T> = std::result::Result<T, StakeError>;
 
 #[derive(Debug, Error, PartialEq, Clone, Serialize, Deserialize, Eq)]
@@ -49,6 +57,24 @@ pub enum StakeUpdate {
     Slash(u8),
 }
 
+/// Stake withdrawn from a claim that is waiting out its unbonding period.
+/// Unbonding stake no longer counts toward the claim's eligibility but can
+/// still be slashed until it is released to `owner` at `release_epoch`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct UnbondingEntry {
+    pub owner: Address,
+    pub amount: u128,
+    pub requested_epoch: Epoch,
+    pub release_epoch: Epoch,
+}
+
+/// Stake delegated to a claim by an address other than the claim's own
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Delegation {
+    pub delegator: Address,
+    pub amount: u128,
+}
+
 /// A struct thatt defines a stake, includes the public key (which
 /// can be converted into an address) an amount, which is an instance
 /// of the `StakeUpdate` enum, a timestamp to sequence it in the
@@ -0,0 +1,291 @@
+use std::collections::HashMap;
+
+use primitives::{Address, ByteVec, PublicKey, SecretKey};
+use secp256k1::{ecdsa::Signature, Message};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use utils::hash_data;
+
+use crate::transactions::{
+    Token, Transaction, TransactionDigest, TransactionKind, TxAmount, TxNonce, TxTimestamp,
+    BASE_FEE,
+};
+
+/// Content identifier of a program, as produced by `web3_pkg`
+pub type ProgramId = String;
+
+#[allow(clippy::too_many_arguments)]
+pub fn generate_call_program_digest_vec(
+    timestamp: TxTimestamp,
+    sender_address: String,
+    sender_public_key: PublicKey,
+    program_address: String,
+    program_id: &ProgramId,
+    entrypoint: &str,
+    args: &[String],
+    token: &Token,
+    amount: TxAmount,
+    nonce: TxNonce,
+) -> ByteVec {
+    let payload_string = format!(
+        "{},{},{},{},{},{},{:?},{:?},{},{}",
+        &timestamp,
+        &sender_address,
+        &sender_public_key,
+        &program_address,
+        program_id,
+        entrypoint,
+        args,
+        token,
+        &amount,
+        &nonce
+    );
+
+    let mut hasher = Sha256::new();
+    hasher.update(payload_string);
+    let hash = hasher.finalize();
+
+    hash.to_vec()
+}
+
+/// A transaction that invokes an entrypoint of a deployed program.
+/// `amount` is the value attached to the call, it is debited from the
+/// sender and credited to `program_address`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct CallProgram {
+    pub id: TransactionDigest,
+    pub timestamp: TxTimestamp,
+    pub sender_address: Address,
+    pub sender_public_key: PublicKey,
+    pub program_address: Address,
+    pub program_id: ProgramId,
+    pub entrypoint: String,
+    pub args: Vec<String>,
+    pub token: Token,
+    pub amount: TxAmount,
+    pub signature: Signature,
+    pub validators: Option<HashMap<String, bool>>,
+    pub nonce: TxNonce,
+}
+
+#[derive(Clone, Default)]
+pub struct CallProgramBuilder {
+    timestamp: Option<TxTimestamp>,
+    sender_address: Option<Address>,
+    sender_public_key: Option<PublicKey>,
+    program_address: Option<Address>,
+    program_id: Option<ProgramId>,
+    entrypoint: Option<String>,
+    args: Option<Vec<String>>,
+    token: Option<Token>,
+    amount: Option<TxAmount>,
+    signature: Option<Signature>,
+    validators: Option<HashMap<String, bool>>,
+    nonce: Option<TxNonce>,
+}
+
+impl CallProgramBuilder {
+    pub fn timestamp(mut self, timestamp: TxTimestamp) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    pub fn sender_address(mut self, sender_address: Address) -> Self {
+        self.sender_address = Some(sender_address);
+        self
+    }
+
+    pub fn sender_public_key(mut self, sender_public_key: PublicKey) -> Self {
+        self.sender_public_key = Some(sender_public_key);
+        self
+    }
+
+    pub fn program_address(mut self, program_address: Address) -> Self {
+        self.program_address = Some(program_address);
+        self
+    }
+
+    pub fn program_id(mut self, program_id: ProgramId) -> Self {
+        self.program_id = Some(program_id);
+        self
+    }
+
+    pub fn entrypoint(mut self, entrypoint: String) -> Self {
+        self.entrypoint = Some(entrypoint);
+        self
+    }
+
+    pub fn args(mut self, args: Vec<String>) -> Self {
+        self.args = Some(args);
+        self
+    }
+
+    pub fn token(mut self, token: Token) -> Self {
+        self.token = Some(token);
+        self
+    }
+
+    pub fn amount(mut self, amount: TxAmount) -> Self {
+        self.amount = Some(amount);
+        self
+    }
+
+    pub fn signature(mut self, signature: Signature) -> Self {
+        self.signature = Some(signature);
+        self
+    }
+
+    pub fn validators(mut self, validators: HashMap<String, bool>) -> Self {
+        self.validators = Some(validators);
+        self
+    }
+
+    pub fn nonce(mut self, nonce: TxNonce) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+
+    pub fn build(self) -> Result<CallProgram, &'static str> {
+        let timestamp = self.timestamp.ok_or("timestamp is missing")?;
+        let sender_address = self.sender_address.ok_or("sender_address is missing")?;
+        let sender_public_key = self
+            .sender_public_key
+            .ok_or("sender_public_key is missing")?;
+        let program_address = self.program_address.ok_or("program_address is missing")?;
+        let program_id = self.program_id.ok_or("program_id is missing")?;
+        let entrypoint = self.entrypoint.ok_or("entrypoint is missing")?;
+        let args = self.args.unwrap_or_default();
+        let token = self.token.unwrap_or_default();
+        let amount = self.amount.unwrap_or_default();
+        let nonce = self.nonce.ok_or("nonce is missing")?;
+
+        let id = generate_call_program_digest_vec(
+            timestamp,
+            sender_address.to_string(),
+            sender_public_key,
+            program_address.to_string(),
+            &program_id,
+            &entrypoint,
+            &args,
+            &token,
+            amount,
+            nonce,
+        );
+
+        Ok(CallProgram {
+            id: TransactionDigest::from(id),
+            timestamp,
+            sender_address,
+            sender_public_key,
+            program_address,
+            program_id,
+            entrypoint,
+            args,
+            token,
+            amount,
+            signature: self.signature.ok_or("signature is missing")?,
+            validators: self.validators,
+            nonce,
+        })
+    }
+
+    pub fn build_kind(self) -> Result<TransactionKind, &'static str> {
+        Ok(TransactionKind::CallProgram(self.build()?))
+    }
+}
+
+impl CallProgram {
+    pub fn builder() -> CallProgramBuilder {
+        CallProgramBuilder::default()
+    }
+}
+
+impl Transaction for CallProgram {
+    fn id(&self) -> TransactionDigest {
+        self.id.clone()
+    }
+
+    fn timestamp(&self) -> TxTimestamp {
+        self.timestamp
+    }
+
+    fn sender_address(&self) -> Address {
+        self.sender_address.clone()
+    }
+
+    fn sender_public_key(&self) -> PublicKey {
+        self.sender_public_key
+    }
+
+    fn receiver_address(&self) -> Address {
+        self.program_address.clone()
+    }
+
+    fn token(&self) -> Token {
+        self.token.clone()
+    }
+
+    fn amount(&self) -> TxAmount {
+        self.amount
+    }
+
+    fn signature(&self) -> Signature {
+        self.signature
+    }
+
+    fn validators(&self) -> Option<HashMap<String, bool>> {
+        self.validators.clone()
+    }
+
+    fn nonce(&self) -> TxNonce {
+        self.nonce
+    }
+
+    fn fee(&self) -> u128 {
+        BASE_FEE
+    }
+
+    fn validator_fee_share(&self) -> u128 {
+        BASE_FEE / 2u128
+    }
+
+    fn proposer_fee_share(&self) -> u128 {
+        BASE_FEE / 2u128
+    }
+
+    fn build_payload(&self) -> String {
+        format!(
+            "{:x}",
+            hash_data!(
+                self.sender_address.clone(),
+                self.sender_public_key.clone(),
+                self.program_address.clone(),
+                self.program_id.clone(),
+                self.entrypoint.clone(),
+                self.args.clone(),
+                self.token.clone(),
+                self.amount.clone(),
+                self.nonce.clone()
+            )
+        )
+    }
+
+    fn digest(&self) -> TransactionDigest {
+        self.id()
+    }
+
+    fn sign(&mut self, sk: &SecretKey) {
+        let mut hasher = sha2::Sha256::new();
+        hasher.update(self.build_payload().as_bytes());
+        let result = hasher.finalize().to_vec();
+        if let Ok(msg) = Message::from_slice(&result) {
+            self.signature = sk.sign_ecdsa(msg);
+        }
+    }
+}
+
+impl From<CallProgram> for TransactionDigest {
+    fn from(txn: CallProgram) -> Self {
+        txn.id()
+    }
+}
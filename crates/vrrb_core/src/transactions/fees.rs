@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+
+use crate::transactions::Transaction;
+
+/// Percentage of every fee that is burned unless configured otherwise
+pub const DEFAULT_FEE_BURN_RATE: u8 = 0;
+
+/// How the fee paid by a transaction is split once it is included in a
+/// block. The burn rate is taken out of both the validator and the proposer
+/// shares, whatever is left of the fee after paying them is burned.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct FeeSplit {
+    /// Fee debited from the sender
+    pub fee: u128,
+    /// Share of the fee credited to the validators that voted for the
+    /// transaction
+    pub validators: u128,
+    /// Share of the fee credited to the proposer of the block that included
+    /// the transaction
+    pub proposer: u128,
+    /// Share of the fee that is credited to no one
+    pub burned: u128,
+}
+
+impl FeeSplit {
+    /// Splits the fee of `txn`, burning `burn_rate` percent of it. Rates
+    /// above 100 burn the whole fee.
+    pub fn new<T: Transaction>(txn: &T, burn_rate: u8) -> Self {
+        let kept = 100 - u128::from(burn_rate.min(100));
+        let fee = txn.fee();
+        let validators = percent_of(txn.validator_fee_share(), kept).min(fee);
+        let proposer = percent_of(txn.proposer_fee_share(), kept).min(fee - validators);
+
+        Self {
+            fee,
+            validators,
+            proposer,
+            burned: fee - validators - proposer,
+        }
+    }
+}
+
+/// Returns `percent` percent of `amount` without overflowing
+fn percent_of(amount: u128, percent: u128) -> u128 {
+    amount / 100 * percent + amount % 100 * percent / 100
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transactions::{Transfer, BASE_FEE};
+
+    #[test]
+    fn fees_are_split_between_validators_and_proposer_without_burning() {
+        let split = FeeSplit::new(&Transfer::default(), 0);
+
+        assert_eq!(split.fee, BASE_FEE);
+        assert_eq!(split.validators, BASE_FEE / 2);
+        assert_eq!(split.proposer, BASE_FEE / 2);
+        assert_eq!(split.burned, 0);
+    }
+
+    #[test]
+    fn burn_rate_is_taken_out_of_every_share() {
+        let split = FeeSplit::new(&Transfer::default(), 10);
+
+        assert_eq!(split.validators, BASE_FEE / 2 / 10 * 9);
+        assert_eq!(split.proposer, BASE_FEE / 2 / 10 * 9);
+        assert_eq!(split.burned, BASE_FEE / 10);
+        assert_eq!(split.validators + split.proposer + split.burned, split.fee);
+
+        let split = FeeSplit::new(&Transfer::default(), 200);
+        assert_eq!(split.burned, BASE_FEE);
+    }
+}
@@ -1,7 +1,15 @@
+pub mod call_program;
+pub mod fees;
+pub mod register_claim;
+pub mod stake;
+pub mod transaction;
 pub mod transaction_kind;
 pub mod transfer;
-pub mod transaction;
 
+pub use call_program::*;
+pub use fees::*;
+pub use register_claim::*;
+pub use stake::*;
+pub use transaction::*;
 pub use transaction_kind::*;
 pub use transfer::*;
-pub use transaction::*;
\ No newline at end of file
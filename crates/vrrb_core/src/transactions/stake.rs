@@ -0,0 +1,235 @@
+use std::collections::HashMap;
+
+use primitives::{Address, ByteVec, PublicKey, SecretKey};
+use secp256k1::{ecdsa::Signature, Message};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use utils::hash_data;
+
+use crate::staking::{Stake, StakeUpdate};
+use crate::transactions::{
+    Token, Transaction, TransactionDigest, TransactionKind, TxAmount, TxNonce, TxTimestamp,
+    BASE_FEE,
+};
+
+pub fn generate_stake_digest_vec(
+    timestamp: TxTimestamp,
+    sender_address: String,
+    sender_public_key: PublicKey,
+    stake: &Stake,
+    nonce: TxNonce,
+) -> ByteVec {
+    let payload_string = format!(
+        "{},{},{},{},{}",
+        &timestamp,
+        &sender_address,
+        &sender_public_key,
+        hex::encode(stake.get_payload()),
+        &nonce
+    );
+
+    let mut hasher = Sha256::new();
+    hasher.update(payload_string);
+    let hash = hasher.finalize();
+
+    hash.to_vec()
+}
+
+/// A transaction that posts, withdraws or slashes stake on a `Claim`.
+/// The inner `Stake` carries its own signature and certificate, the
+/// outer transaction signature covers the nonce so the stake can be
+/// ordered and replay protected like any other transaction.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct StakeTransaction {
+    pub id: TransactionDigest,
+    pub timestamp: TxTimestamp,
+    pub sender_address: Address,
+    pub sender_public_key: PublicKey,
+    pub stake: Stake,
+    pub signature: Signature,
+    pub validators: Option<HashMap<String, bool>>,
+    pub nonce: TxNonce,
+}
+
+#[derive(Clone, Default)]
+pub struct StakeTransactionBuilder {
+    timestamp: Option<TxTimestamp>,
+    sender_address: Option<Address>,
+    sender_public_key: Option<PublicKey>,
+    stake: Option<Stake>,
+    signature: Option<Signature>,
+    validators: Option<HashMap<String, bool>>,
+    nonce: Option<TxNonce>,
+}
+
+impl StakeTransactionBuilder {
+    pub fn timestamp(mut self, timestamp: TxTimestamp) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    pub fn sender_address(mut self, sender_address: Address) -> Self {
+        self.sender_address = Some(sender_address);
+        self
+    }
+
+    pub fn sender_public_key(mut self, sender_public_key: PublicKey) -> Self {
+        self.sender_public_key = Some(sender_public_key);
+        self
+    }
+
+    pub fn stake(mut self, stake: Stake) -> Self {
+        self.stake = Some(stake);
+        self
+    }
+
+    pub fn signature(mut self, signature: Signature) -> Self {
+        self.signature = Some(signature);
+        self
+    }
+
+    pub fn validators(mut self, validators: HashMap<String, bool>) -> Self {
+        self.validators = Some(validators);
+        self
+    }
+
+    pub fn nonce(mut self, nonce: TxNonce) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+
+    pub fn build(self) -> Result<StakeTransaction, &'static str> {
+        let timestamp = self.timestamp.ok_or("timestamp is missing")?;
+        let sender_address = self.sender_address.ok_or("sender_address is missing")?;
+        let sender_public_key = self
+            .sender_public_key
+            .ok_or("sender_public_key is missing")?;
+        let stake = self.stake.ok_or("stake is missing")?;
+        let nonce = self.nonce.ok_or("nonce is missing")?;
+
+        let id = generate_stake_digest_vec(
+            timestamp,
+            sender_address.to_string(),
+            sender_public_key,
+            &stake,
+            nonce,
+        );
+
+        Ok(StakeTransaction {
+            id: TransactionDigest::from(id),
+            timestamp,
+            sender_address,
+            sender_public_key,
+            stake,
+            signature: self.signature.ok_or("signature is missing")?,
+            validators: self.validators,
+            nonce,
+        })
+    }
+
+    pub fn build_kind(self) -> Result<TransactionKind, &'static str> {
+        Ok(TransactionKind::Stake(self.build()?))
+    }
+}
+
+impl StakeTransaction {
+    pub fn builder() -> StakeTransactionBuilder {
+        StakeTransactionBuilder::default()
+    }
+
+    /// Returns the inner `Stake` carried by this transaction
+    pub fn stake(&self) -> &Stake {
+        &self.stake
+    }
+}
+
+impl Transaction for StakeTransaction {
+    fn id(&self) -> TransactionDigest {
+        self.id.clone()
+    }
+
+    fn timestamp(&self) -> TxTimestamp {
+        self.timestamp
+    }
+
+    fn sender_address(&self) -> Address {
+        self.sender_address.clone()
+    }
+
+    fn sender_public_key(&self) -> PublicKey {
+        self.sender_public_key
+    }
+
+    /// The address of the claim that receives the stake
+    fn receiver_address(&self) -> Address {
+        self.stake.get_receiver()
+    }
+
+    fn token(&self) -> Token {
+        Token::default()
+    }
+
+    /// Returns the amount of the stake update. Slashes don't move
+    /// any funds out of the sender's account so they report zero.
+    fn amount(&self) -> TxAmount {
+        match self.stake.get_amount() {
+            StakeUpdate::Add(amount) | StakeUpdate::Withdrawal(amount) => amount,
+            StakeUpdate::Slash(_) => 0,
+        }
+    }
+
+    fn signature(&self) -> Signature {
+        self.signature
+    }
+
+    fn validators(&self) -> Option<HashMap<String, bool>> {
+        self.validators.clone()
+    }
+
+    fn nonce(&self) -> TxNonce {
+        self.nonce
+    }
+
+    fn fee(&self) -> u128 {
+        BASE_FEE
+    }
+
+    fn validator_fee_share(&self) -> u128 {
+        BASE_FEE / 2u128
+    }
+
+    fn proposer_fee_share(&self) -> u128 {
+        BASE_FEE / 2u128
+    }
+
+    fn build_payload(&self) -> String {
+        format!(
+            "{:x}",
+            hash_data!(
+                self.sender_address.clone(),
+                self.sender_public_key.clone(),
+                self.stake.clone(),
+                self.nonce.clone()
+            )
+        )
+    }
+
+    fn digest(&self) -> TransactionDigest {
+        self.id()
+    }
+
+    fn sign(&mut self, sk: &SecretKey) {
+        let mut hasher = sha2::Sha256::new();
+        hasher.update(self.build_payload().as_bytes());
+        let result = hasher.finalize().to_vec();
+        if let Ok(msg) = Message::from_slice(&result) {
+            self.signature = sk.sign_ecdsa(msg);
+        }
+    }
+}
+
+impl From<StakeTransaction> for TransactionDigest {
+    fn from(txn: StakeTransaction) -> Self {
+        txn.id()
+    }
+}
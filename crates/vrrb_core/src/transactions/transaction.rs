@@ -224,4 +224,28 @@ mod tests {
 
         assert_eq!(txn_digest, txn_digest_recovered);
     }
+
+    #[test]
+    fn base_fee_transfers_sign_the_payload_they_signed_before_fees() {
+        let txn = Transfer::default();
+        let pre_fee_payload = format!(
+            "{:x}",
+            utils::hash_data!(
+                txn.sender_address.clone(),
+                txn.sender_public_key,
+                txn.receiver_address.clone(),
+                txn.token.clone(),
+                txn.amount,
+                txn.nonce
+            )
+        );
+
+        assert_eq!(txn.build_payload(), pre_fee_payload);
+
+        let higher_fee_txn = Transfer {
+            fee: txn.fee * 2,
+            ..txn.clone()
+        };
+        assert_ne!(higher_fee_txn.build_payload(), pre_fee_payload);
+    }
 }
@@ -1,6 +1,8 @@
+use crate::staking::StakeUpdate;
 use crate::transactions::{
-    Token, Transaction, TransactionDigest, Transfer, TransferBuilder, TxAmount, TxNonce,
-    TxTimestamp,
+    CallProgram, CallProgramBuilder, RegisterClaim, RegisterClaimBuilder, StakeTransaction,
+    StakeTransactionBuilder, Token, Transaction, TransactionDigest, Transfer, TransferBuilder,
+    TxAmount, TxNonce, TxTimestamp,
 };
 use primitives::{Address, PublicKey, SecretKey, Signature};
 use serde::{Deserialize, Serialize};
@@ -9,12 +11,54 @@ use std::collections::HashMap;
 #[derive(Hash, Debug, Deserialize, Clone, Serialize, Eq, PartialEq)]
 pub enum TransactionKind {
     Transfer(Transfer),
+    Stake(StakeTransaction),
+    RegisterClaim(RegisterClaim),
+    CallProgram(CallProgram),
 }
 
 impl TransactionKind {
     pub fn transfer_builder() -> TransferBuilder {
         Transfer::builder()
     }
+
+    pub fn stake_builder() -> StakeTransactionBuilder {
+        StakeTransaction::builder()
+    }
+
+    pub fn register_claim_builder() -> RegisterClaimBuilder {
+        RegisterClaim::builder()
+    }
+
+    pub fn call_program_builder() -> CallProgramBuilder {
+        CallProgram::builder()
+    }
+
+    /// Returns a short, human readable name for the variant
+    pub fn kind_str(&self) -> &'static str {
+        match self {
+            TransactionKind::Transfer(_) => "transfer",
+            TransactionKind::Stake(_) => "stake",
+            TransactionKind::RegisterClaim(_) => "register_claim",
+            TransactionKind::CallProgram(_) => "call_program",
+        }
+    }
+
+    /// Returns the most the transaction can take out of its sender's
+    /// balance, the amount it moves plus its fee. Stake withdrawals move
+    /// funds back into the account and claim registrations move none, so
+    /// they only cost their fee.
+    pub fn cost(&self) -> TxAmount {
+        let amount = match self {
+            TransactionKind::Stake(stake_txn) => match stake_txn.stake().get_amount() {
+                StakeUpdate::Add(amount) => amount,
+                _ => 0,
+            },
+            TransactionKind::RegisterClaim(_) => 0,
+            _ => self.amount(),
+        };
+
+        amount.saturating_add(self.fee())
+    }
 }
 
 impl Default for TransactionKind {
@@ -27,96 +71,311 @@ impl Transaction for TransactionKind {
     fn id(&self) -> TransactionDigest {
         match self {
             TransactionKind::Transfer(transfer) => transfer.id(),
+            TransactionKind::Stake(stake) => stake.id(),
+            TransactionKind::RegisterClaim(register_claim) => register_claim.id(),
+            TransactionKind::CallProgram(call_program) => call_program.id(),
         }
     }
 
     fn timestamp(&self) -> TxTimestamp {
         match self {
             TransactionKind::Transfer(transfer) => transfer.timestamp(),
+            TransactionKind::Stake(stake) => stake.timestamp(),
+            TransactionKind::RegisterClaim(register_claim) => register_claim.timestamp(),
+            TransactionKind::CallProgram(call_program) => call_program.timestamp(),
         }
     }
 
     fn sender_address(&self) -> Address {
         match self {
             TransactionKind::Transfer(transfer) => transfer.sender_address(),
+            TransactionKind::Stake(stake) => stake.sender_address(),
+            TransactionKind::RegisterClaim(register_claim) => register_claim.sender_address(),
+            TransactionKind::CallProgram(call_program) => call_program.sender_address(),
         }
     }
 
     fn sender_public_key(&self) -> PublicKey {
         match self {
             TransactionKind::Transfer(transfer) => transfer.sender_public_key(),
+            TransactionKind::Stake(stake) => stake.sender_public_key(),
+            TransactionKind::RegisterClaim(register_claim) => register_claim.sender_public_key(),
+            TransactionKind::CallProgram(call_program) => call_program.sender_public_key(),
         }
     }
 
     fn receiver_address(&self) -> Address {
         match self {
             TransactionKind::Transfer(transfer) => transfer.receiver_address(),
+            TransactionKind::Stake(stake) => stake.receiver_address(),
+            TransactionKind::RegisterClaim(register_claim) => register_claim.receiver_address(),
+            TransactionKind::CallProgram(call_program) => call_program.receiver_address(),
         }
     }
 
     fn token(&self) -> Token {
         match self {
             TransactionKind::Transfer(transfer) => transfer.token(),
+            TransactionKind::Stake(stake) => stake.token(),
+            TransactionKind::RegisterClaim(register_claim) => register_claim.token(),
+            TransactionKind::CallProgram(call_program) => call_program.token(),
         }
     }
 
     fn amount(&self) -> TxAmount {
         match self {
             TransactionKind::Transfer(transfer) => transfer.amount(),
+            TransactionKind::Stake(stake) => stake.amount(),
+            TransactionKind::RegisterClaim(register_claim) => register_claim.amount(),
+            TransactionKind::CallProgram(call_program) => call_program.amount(),
         }
     }
 
     fn signature(&self) -> Signature {
         match self {
             TransactionKind::Transfer(transfer) => transfer.signature(),
+            TransactionKind::Stake(stake) => stake.signature(),
+            TransactionKind::RegisterClaim(register_claim) => register_claim.signature(),
+            TransactionKind::CallProgram(call_program) => call_program.signature(),
         }
     }
 
     fn validators(&self) -> Option<HashMap<String, bool>> {
         match self {
             TransactionKind::Transfer(transfer) => transfer.validators(),
+            TransactionKind::Stake(stake) => stake.validators(),
+            TransactionKind::RegisterClaim(register_claim) => register_claim.validators(),
+            TransactionKind::CallProgram(call_program) => call_program.validators(),
         }
     }
 
     fn nonce(&self) -> TxNonce {
         match self {
             TransactionKind::Transfer(transfer) => transfer.nonce(),
+            TransactionKind::Stake(stake) => stake.nonce(),
+            TransactionKind::RegisterClaim(register_claim) => register_claim.nonce(),
+            TransactionKind::CallProgram(call_program) => call_program.nonce(),
         }
     }
 
     fn fee(&self) -> u128 {
         match self {
             TransactionKind::Transfer(transfer) => transfer.fee(),
+            TransactionKind::Stake(stake) => stake.fee(),
+            TransactionKind::RegisterClaim(register_claim) => register_claim.fee(),
+            TransactionKind::CallProgram(call_program) => call_program.fee(),
         }
     }
 
     fn validator_fee_share(&self) -> u128 {
         match self {
             TransactionKind::Transfer(transfer) => transfer.validator_fee_share(),
+            TransactionKind::Stake(stake) => stake.validator_fee_share(),
+            TransactionKind::RegisterClaim(register_claim) => register_claim.validator_fee_share(),
+            TransactionKind::CallProgram(call_program) => call_program.validator_fee_share(),
         }
     }
 
     fn proposer_fee_share(&self) -> u128 {
         match self {
             TransactionKind::Transfer(transfer) => transfer.proposer_fee_share(),
+            TransactionKind::Stake(stake) => stake.proposer_fee_share(),
+            TransactionKind::RegisterClaim(register_claim) => register_claim.proposer_fee_share(),
+            TransactionKind::CallProgram(call_program) => call_program.proposer_fee_share(),
         }
     }
 
     fn build_payload(&self) -> String {
         match self {
             TransactionKind::Transfer(transfer) => transfer.build_payload(),
+            TransactionKind::Stake(stake) => stake.build_payload(),
+            TransactionKind::RegisterClaim(register_claim) => register_claim.build_payload(),
+            TransactionKind::CallProgram(call_program) => call_program.build_payload(),
         }
     }
 
     fn digest(&self) -> TransactionDigest {
         match self {
             TransactionKind::Transfer(transfer) => transfer.id(),
+            TransactionKind::Stake(stake) => stake.id(),
+            TransactionKind::RegisterClaim(register_claim) => register_claim.id(),
+            TransactionKind::CallProgram(call_program) => call_program.id(),
         }
     }
 
     fn sign(&mut self, sk: &SecretKey) {
         match self {
             TransactionKind::Transfer(transfer) => transfer.sign(sk),
+            TransactionKind::Stake(stake) => stake.sign(sk),
+            TransactionKind::RegisterClaim(register_claim) => register_claim.sign(sk),
+            TransactionKind::CallProgram(call_program) => call_program.sign(sk),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use primitives::{Address, NodeId};
+    use secp256k1::Message;
+
+    use crate::claim::Claim;
+    use crate::keypair::KeyPair;
+    use crate::staking::{Stake, StakeUpdate};
+    use crate::transactions::{Token, Transaction, TransactionKind, BASE_FEE};
+
+    fn dummy_signature(kp: &KeyPair) -> secp256k1::ecdsa::Signature {
+        let msg = Message::from_slice(&[1u8; 32]).unwrap();
+        kp.miner_kp.0.sign_ecdsa(msg)
+    }
+
+    fn verify_signature(txn: &TransactionKind) -> bool {
+        use sha2::Digest;
+
+        let mut hasher = sha2::Sha256::new();
+        hasher.update(txn.build_payload().as_bytes());
+        let msg = Message::from_slice(&hasher.finalize()).unwrap();
+
+        txn.signature()
+            .verify(&msg, &txn.sender_public_key())
+            .is_ok()
+    }
+
+    #[test]
+    fn should_build_signed_stake_transaction() {
+        let kp = KeyPair::random();
+        let pk = kp.miner_kp.1;
+        let address = Address::new(pk);
+        let stake = Stake::new(
+            StakeUpdate::Add(10_000),
+            kp.miner_kp.0,
+            pk,
+            address.clone(),
+            None,
+        )
+        .unwrap();
+
+        let mut txn = TransactionKind::stake_builder()
+            .timestamp(0)
+            .sender_address(address.clone())
+            .sender_public_key(pk)
+            .stake(stake)
+            .signature(dummy_signature(&kp))
+            .nonce(1)
+            .build_kind()
+            .unwrap();
+
+        txn.sign(&kp.miner_kp.0);
+
+        assert_eq!(txn.kind_str(), "stake");
+        assert_eq!(txn.amount(), 10_000);
+        assert_eq!(txn.cost(), 10_000 + BASE_FEE);
+        assert_eq!(txn.receiver_address(), address);
+        assert!(verify_signature(&txn));
+    }
+
+    #[test]
+    fn should_build_signed_register_claim_transaction() {
+        let kp = KeyPair::random();
+        let pk = kp.miner_kp.1;
+        let address = Address::new(pk);
+        let ip_address = "127.0.0.1:8080".parse::<SocketAddr>().unwrap();
+        let signature = Claim::signature_for_valid_claim(
+            pk,
+            ip_address,
+            kp.get_miner_secret_key().secret_bytes().to_vec(),
+        )
+        .unwrap();
+        let claim = Claim::new(
+            pk,
+            address.clone(),
+            ip_address,
+            signature,
+            NodeId::default(),
+        )
+        .unwrap();
+
+        let mut txn = TransactionKind::register_claim_builder()
+            .timestamp(0)
+            .sender_address(address.clone())
+            .sender_public_key(pk)
+            .claim(claim)
+            .signature(dummy_signature(&kp))
+            .nonce(1)
+            .build_kind()
+            .unwrap();
+
+        txn.sign(&kp.miner_kp.0);
+
+        assert_eq!(txn.kind_str(), "register_claim");
+        assert_eq!(txn.amount(), 0);
+        assert_eq!(txn.cost(), BASE_FEE);
+        assert_eq!(txn.receiver_address(), address);
+        assert!(verify_signature(&txn));
+    }
+
+    #[test]
+    fn call_program_ids_should_depend_on_entrypoint() {
+        let kp = KeyPair::random();
+        let pk = kp.miner_kp.1;
+        let address = Address::new(pk);
+        let builder = TransactionKind::call_program_builder()
+            .timestamp(0)
+            .sender_address(address.clone())
+            .sender_public_key(pk)
+            .program_address(address)
+            .program_id("bafyprogram".to_string())
+            .signature(dummy_signature(&kp))
+            .nonce(1);
+
+        let first = builder
+            .clone()
+            .entrypoint("main".to_string())
+            .build_kind()
+            .unwrap();
+        let second = builder.entrypoint("init".to_string()).build_kind().unwrap();
+
+        assert_eq!(first.kind_str(), "call_program");
+        assert_ne!(first.id(), second.id());
+    }
+
+    #[test]
+    fn call_program_ids_should_depend_on_program_address_and_token() {
+        let kp = KeyPair::random();
+        let pk = kp.miner_kp.1;
+        let address = Address::new(pk);
+        let other_address = Address::new(KeyPair::random().miner_kp.1);
+        let builder = TransactionKind::call_program_builder()
+            .timestamp(0)
+            .sender_address(address.clone())
+            .sender_public_key(pk)
+            .program_id("bafyprogram".to_string())
+            .entrypoint("main".to_string())
+            .signature(dummy_signature(&kp))
+            .nonce(1);
+
+        let first = builder
+            .clone()
+            .program_address(address.clone())
+            .build_kind()
+            .unwrap();
+        let second = builder
+            .clone()
+            .program_address(other_address)
+            .build_kind()
+            .unwrap();
+        let third = builder
+            .program_address(address)
+            .token(Token {
+                name: "Other".to_string(),
+                symbol: "OTHER".to_string(),
+                decimals: 18,
+            })
+            .build_kind()
+            .unwrap();
+
+        assert_ne!(first.id(), second.id());
+        assert_ne!(first.id(), third.id());
+    }
+}
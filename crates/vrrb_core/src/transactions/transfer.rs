@@ -38,11 +38,41 @@ pub fn generate_transfer_digest_vec(
     amount: TxAmount,
     nonce: TxNonce,
 ) -> ByteVec {
-    let payload_string = format!(
+    generate_transfer_digest_vec_with_fee(
+        timestamp,
+        sender_address,
+        sender_public_key,
+        receiver_address,
+        token,
+        amount,
+        BASE_FEE,
+        nonce,
+    )
+}
+
+/// Same as `generate_transfer_digest_vec` but for transfers that pay a fee
+/// other than `BASE_FEE`. Transfers paying the base fee keep the digest they
+/// had before fees became configurable.
+#[allow(clippy::too_many_arguments)]
+pub fn generate_transfer_digest_vec_with_fee(
+    timestamp: TxTimestamp,
+    sender_address: String,
+    sender_public_key: PublicKey,
+    receiver_address: String,
+    token: Token,
+    amount: TxAmount,
+    fee: TxFee,
+    nonce: TxNonce,
+) -> ByteVec {
+    let mut payload_string = format!(
         "{},{},{},{},{},{:?},{}",
         &timestamp, &sender_address, &sender_public_key, &receiver_address, &amount, &token, &nonce
     );
 
+    if fee != BASE_FEE {
+        payload_string.push_str(&format!(",{}", &fee));
+    }
+
     let mut hasher = Sha256::new();
     hasher.update(payload_string);
     let hash = hasher.finalize();
@@ -50,9 +80,48 @@ pub fn generate_transfer_digest_vec(
     hash.to_vec()
 }
 
+/// Builds the payload transfers are signed over. Like their digest, transfers
+/// paying `BASE_FEE` sign the payload they signed before fees became
+/// configurable so that their signatures stay valid, every other transfer
+/// also signs its fee.
+#[allow(clippy::too_many_arguments)]
+fn build_transfer_payload(
+    sender_address: Option<Address>,
+    sender_public_key: Option<PublicKey>,
+    receiver_address: Option<Address>,
+    token: Option<Token>,
+    amount: Option<TxAmount>,
+    fee: TxFee,
+    nonce: Option<TxNonce>,
+) -> String {
+    let hash = if fee == BASE_FEE {
+        hash_data!(
+            sender_address,
+            sender_public_key,
+            receiver_address,
+            token,
+            amount,
+            nonce
+        )
+    } else {
+        hash_data!(
+            sender_address,
+            sender_public_key,
+            receiver_address,
+            token,
+            amount,
+            fee,
+            nonce
+        )
+    };
+
+    format!("{:x}", hash)
+}
+
 pub type TxNonce = u128;
 pub type TxTimestamp = i64;
 pub type TxAmount = u128;
+pub type TxFee = u128;
 pub type TxSignature = Vec<u8>;
 
 #[derive(Clone, Debug, Serialize, Deserialize, Eq)]
@@ -64,11 +133,17 @@ pub struct Transfer {
     pub receiver_address: Address,
     pub token: Token,
     pub amount: TxAmount,
+    #[serde(default = "base_fee")]
+    pub fee: TxFee,
     pub signature: Signature,
     pub validators: Option<HashMap<String, bool>>,
     pub nonce: TxNonce,
 }
 
+fn base_fee() -> TxFee {
+    BASE_FEE
+}
+
 #[derive(Clone, Default)]
 pub struct TransferBuilder {
     timestamp: Option<TxTimestamp>,
@@ -77,6 +152,7 @@ pub struct TransferBuilder {
     receiver_address: Option<Address>,
     token: Option<Token>,
     amount: Option<TxAmount>,
+    fee: Option<TxFee>,
     signature: Option<Signature>,
     validators: Option<HashMap<String, bool>>,
     nonce: Option<TxNonce>,
@@ -113,21 +189,25 @@ impl TransferBuilder {
         self
     }
 
+    /// Sets the fee paid by the transfer, defaults to `BASE_FEE`
+    pub fn fee(mut self, fee: TxFee) -> Self {
+        self.fee = Some(fee);
+        self
+    }
+
     pub fn signature(mut self, signature: Signature) -> Self {
         self.signature = Some(signature);
         self
     }
     pub fn build_payload(&self) -> String {
-        format!(
-            "{:x}",
-            hash_data!(
-                self.sender_address.clone(),
-                self.sender_public_key.clone(),
-                self.receiver_address.clone(),
-                self.token.clone(),
-                self.amount.clone(),
-                self.nonce.clone()
-            )
+        build_transfer_payload(
+            self.sender_address.clone(),
+            self.sender_public_key,
+            self.receiver_address.clone(),
+            self.token.clone(),
+            self.amount,
+            self.fee.unwrap_or(BASE_FEE),
+            self.nonce,
         )
     }
 
@@ -142,7 +222,8 @@ impl TransferBuilder {
     }
 
     pub fn build(self) -> Result<Transfer, &'static str> {
-        let id = generate_transfer_digest_vec(
+        let fee = self.fee.unwrap_or(BASE_FEE);
+        let id = generate_transfer_digest_vec_with_fee(
             self.timestamp.ok_or("timestamp is missing")?,
             self.sender_address
                 .clone()
@@ -156,6 +237,7 @@ impl TransferBuilder {
                 .to_string(),
             self.token.clone().unwrap_or_default(),
             self.amount.ok_or("amount is missing")?,
+            fee,
             self.nonce.ok_or("nonce is missing")?,
         );
 
@@ -167,6 +249,7 @@ impl TransferBuilder {
             receiver_address: self.receiver_address.unwrap(),
             token: self.token.unwrap_or_default(),
             amount: self.amount.unwrap(),
+            fee,
             signature: self.signature.ok_or("signature is missing")?,
             validators: self.validators,
             nonce: self.nonce.unwrap(),
@@ -227,6 +310,7 @@ impl Transfer {
             receiver_address: args.receiver_address,
             token,
             amount: args.amount,
+            fee: BASE_FEE,
             signature: args.signature,
             validators: args.validators,
             nonce: args.nonce,
@@ -274,6 +358,7 @@ impl Transfer {
             receiver_address: address,
             token: Token::default(),
             amount: 0,
+            fee: BASE_FEE,
             signature,
             validators: None,
             nonce: 0,
@@ -281,13 +366,14 @@ impl Transfer {
     }
 
     pub fn build_payload_digest(&self) -> TransactionDigest {
-        let digest = generate_transfer_digest_vec(
+        let digest = generate_transfer_digest_vec_with_fee(
             self.timestamp(),
             self.sender_address().to_string(),
             self.sender_public_key(),
             self.receiver_address().to_string(),
             self.token(),
             self.amount(),
+            self.fee(),
             self.nonce(),
         );
 
@@ -314,13 +400,14 @@ impl Transfer {
     }
 
     pub fn generate_txn_digest_vec(&self) -> ByteVec {
-        generate_transfer_digest_vec(
+        generate_transfer_digest_vec_with_fee(
             self.timestamp(),
             self.sender_address().to_string(),
             self.sender_public_key(),
             self.receiver_address().to_string(),
             self.token(),
             self.amount(),
+            self.fee(),
             self.nonce(),
         )
     }
@@ -388,28 +475,27 @@ impl Transaction for Transfer {
     }
 
     fn fee(&self) -> u128 {
-        BASE_FEE
+        self.fee
     }
 
     fn validator_fee_share(&self) -> u128 {
-        BASE_FEE / 2u128
+        self.fee / 2u128
     }
 
+    /// The proposer gets the remainder when the fee can't be split evenly
     fn proposer_fee_share(&self) -> u128 {
-        BASE_FEE / 2u128
+        self.fee - self.validator_fee_share()
     }
 
     fn build_payload(&self) -> String {
-        format!(
-            "{:x}",
-            hash_data!(
-                self.sender_address.clone(),
-                self.sender_public_key.clone(),
-                self.receiver_address.clone(),
-                self.token.clone(),
-                self.amount.clone(),
-                self.nonce.clone()
-            )
+        build_transfer_payload(
+            Some(self.sender_address.clone()),
+            Some(self.sender_public_key),
+            Some(self.receiver_address.clone()),
+            Some(self.token.clone()),
+            Some(self.amount),
+            self.fee,
+            Some(self.nonce),
         )
     }
 
@@ -489,6 +575,7 @@ impl Hash for Transfer {
         self.receiver_address.hash(state);
         self.token.hash(state);
         self.amount.hash(state);
+        self.fee.hash(state);
         self.signature.hash(state);
         self.nonce.hash(state);
     }
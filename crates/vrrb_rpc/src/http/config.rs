@@ -1,6 +1,12 @@
 use std::{fmt::Debug, net::SocketAddr, time::Duration};
 
 use axum_server::tls_rustls::RustlsConfig;
+use events::{EventPublisher, DEFAULT_BUFFER};
+use mempool::{LeftRightMempool, MempoolReadHandleFactory};
+use storage::vrrbdb::{VrrbDb, VrrbDbConfig, VrrbDbReadHandle};
+use tokio::sync::mpsc::channel;
+
+use crate::rpc::RpcAccessConfig;
 
 /// Configuration store for an HttpApiServer
 // Source<: https://doc.rust-lang.org/1.0.0/style/ownership/builders.html
@@ -11,6 +17,35 @@ pub struct HttpApiServerConfig {
     pub api_version: String,
     pub server_timeout: Option<Duration>,
     pub tls_config: Option<RustlsConfig>,
+    pub vrrbdb_read_handle: VrrbDbReadHandle,
+    pub mempool_read_handle_factory: MempoolReadHandleFactory,
+    pub events_tx: EventPublisher,
+    /// Access policies of the admin routes, shared with the JSON-RPC API
+    pub access: RpcAccessConfig,
+}
+
+impl Default for HttpApiServerConfig {
+    fn default() -> Self {
+        let mut vrrbdb_config = VrrbDbConfig::default();
+        vrrbdb_config.path =
+            std::env::temp_dir().join(vrrb_core::helpers::generate_random_string());
+
+        let vrrbdb = VrrbDb::new(vrrbdb_config).expect("failed to open a temporary database");
+        let mempool = LeftRightMempool::default();
+        let (events_tx, _) = channel(DEFAULT_BUFFER);
+
+        Self {
+            address: "127.0.0.1:0".to_string(),
+            api_title: "Node HTTP API".to_string(),
+            api_version: "1.0".to_string(),
+            server_timeout: None,
+            tls_config: None,
+            vrrbdb_read_handle: vrrbdb.read_handle(),
+            mempool_read_handle_factory: mempool.factory(),
+            events_tx,
+            access: RpcAccessConfig::default(),
+        }
+    }
 }
 
 /// Configuration store for an HttpApiRouter
@@ -21,4 +56,9 @@ pub struct HttpApiRouterConfig {
     pub api_title: String,
     pub api_version: String,
     pub server_timeout: Option<Duration>,
+    pub vrrbdb_read_handle: VrrbDbReadHandle,
+    pub mempool_read_handle_factory: MempoolReadHandleFactory,
+    pub events_tx: EventPublisher,
+    /// Access policies of the admin routes, shared with the JSON-RPC API
+    pub access: RpcAccessConfig,
 }
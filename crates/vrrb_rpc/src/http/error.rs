@@ -0,0 +1,73 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::rpc::RpcAccessError;
+
+pub type HttpResult<T> = std::result::Result<T, HttpApiError>;
+
+/// Errors returned by the HTTP API. They are sent as an `HttpErrorBody`.
+#[derive(Debug, thiserror::Error)]
+pub enum HttpApiError {
+    #[error("{0}")]
+    NotFound(String),
+
+    #[error("{0}")]
+    BadRequest(String),
+
+    #[error("{0}")]
+    Unauthorized(String),
+
+    #[error("{0}")]
+    Forbidden(String),
+
+    #[error("{0}")]
+    Internal(String),
+}
+
+/// Body of the responses to requests that failed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct HttpErrorBody {
+    pub error: String,
+}
+
+impl HttpApiError {
+    pub fn status(&self) -> StatusCode {
+        match self {
+            HttpApiError::NotFound(_) => StatusCode::NOT_FOUND,
+            HttpApiError::BadRequest(_) => StatusCode::BAD_REQUEST,
+            HttpApiError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            HttpApiError::Forbidden(_) => StatusCode::FORBIDDEN,
+            HttpApiError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl From<RpcAccessError> for HttpApiError {
+    fn from(err: RpcAccessError) -> Self {
+        match err {
+            RpcAccessError::Unauthenticated(_) => HttpApiError::Unauthorized(err.to_string()),
+            RpcAccessError::UnknownCaller(_) | RpcAccessError::NotLocalhost(_) => {
+                HttpApiError::Forbidden(err.to_string())
+            }
+        }
+    }
+}
+
+impl IntoResponse for HttpApiError {
+    fn into_response(self) -> Response {
+        if let HttpApiError::Internal(err) = &self {
+            telemetry::error!("HTTP API error: {err}");
+        }
+
+        let body = HttpErrorBody {
+            error: self.to_string(),
+        };
+
+        (self.status(), Json(body)).into_response()
+    }
+}
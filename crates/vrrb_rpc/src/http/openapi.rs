@@ -0,0 +1,98 @@
+use std::sync::Arc;
+
+use axum::{Extension, Json};
+use utoipa::{
+    openapi::{
+        security::{Http, HttpAuthScheme, SecurityScheme},
+        OpenApi as OpenApiObject,
+    },
+    Modify, OpenApi,
+};
+
+use crate::http::{
+    routes::{accounts, blocks, claims, health, mempool, transactions},
+    HttpApiRouterConfig, HttpErrorBody,
+};
+
+/// Version of the OpenAPI specification the document follows
+pub const OPENAPI_VERSION: &str = "3.0.3";
+
+/// Name of the security scheme of the admin routes, which accept the same
+/// bearer tokens as the admin methods of the JSON-RPC API
+pub const BEARER_TOKEN_SECURITY_SCHEME: &str = "bearer_token";
+
+/// Operations of the HTTP API, derived from the routes they document so the
+/// OpenAPI document never drifts from them
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        health::health_check,
+        get_openapi_document,
+        accounts::create_account,
+        accounts::get_account,
+        accounts::update_account,
+        accounts::get_account_transactions,
+        accounts::get_transaction_count,
+        accounts::get_claims,
+        transactions::create_txn,
+        transactions::get_transaction,
+        blocks::get_blocks_range,
+        blocks::get_block_by_hash,
+        blocks::get_block_by_height,
+        claims::get_claims,
+        claims::get_claim,
+        mempool::get_full_mempool,
+    ),
+    components(schemas(HttpErrorBody)),
+    modifiers(&BearerToken),
+    tags(
+        (name = "meta", description = "The API itself"),
+        (name = "accounts", description = "Accounts and their history"),
+        (name = "transactions", description = "Confirmed and pending transactions"),
+        (name = "blocks", description = "Genesis and convergence blocks"),
+        (name = "claims", description = "Registered claims"),
+        (name = "mempool", description = "Transactions waiting to be confirmed"),
+    )
+)]
+pub struct ApiDoc;
+
+struct BearerToken;
+
+impl Modify for BearerToken {
+    fn modify(&self, openapi: &mut OpenApiObject) {
+        if let Some(components) = openapi.components.as_mut() {
+            components.add_security_scheme(
+                BEARER_TOKEN_SECURITY_SCHEME,
+                SecurityScheme::Http(Http::new(HttpAuthScheme::Bearer)),
+            );
+        }
+    }
+}
+
+/// The OpenAPI document served by the API, generated once when the router
+/// is created
+#[derive(Debug, Clone)]
+pub struct OpenApiDocument(pub Arc<OpenApiObject>);
+
+/// Generates the OpenAPI document describing every operation of the API
+pub fn openapi_document(config: &HttpApiRouterConfig) -> OpenApiObject {
+    let mut document = ApiDoc::openapi();
+    document.info.title = config.api_title.clone();
+    document.info.version = config.api_version.clone();
+
+    document
+}
+
+/// OpenAPI document describing this API
+#[utoipa::path(
+    get,
+    path = "/openapi.json",
+    operation_id = "getOpenApiDocument",
+    tag = "meta",
+    responses((status = 200, description = "OpenAPI document", body = Object))
+)]
+pub async fn get_openapi_document(
+    Extension(document): Extension<OpenApiDocument>,
+) -> Json<OpenApiObject> {
+    Json(document.0.as_ref().clone())
+}
@@ -1,18 +1,33 @@
-use axum::{routing::get, Router};
+use std::sync::Arc;
+
+use axum::{routing::get, Extension, Router};
 use tower::ServiceBuilder;
 use tower_http::trace::TraceLayer;
 
 use crate::http::{
-    routes::{accounts, health},
-    HttpApiRouterConfig,
+    get_openapi_document, openapi_document,
+    routes::{accounts, blocks, claims, health, mempool, transactions},
+    HttpApiRouterConfig, HttpApiState, OpenApiDocument,
 };
 
-pub fn create_router(_config: &HttpApiRouterConfig) -> Router {
+pub fn create_router(config: &HttpApiRouterConfig) -> Router {
+    let document = OpenApiDocument(Arc::new(openapi_document(config)));
+
     Router::new()
         .route("/", get(|| async { "index" }))
         .route("/health", get(health::health_check))
+        .route("/openapi.json", get(get_openapi_document))
         .nest("/accounts", accounts::create_account_router())
-        .layer(ServiceBuilder::new().layer(TraceLayer::new_for_http()))
+        .nest("/transactions", transactions::create_transaction_router())
+        .nest("/blocks", blocks::create_block_router())
+        .nest("/claims", claims::create_claim_router())
+        .nest("/mempool", mempool::create_mempool_router())
+        .layer(
+            ServiceBuilder::new()
+                .layer(TraceLayer::new_for_http())
+                .layer(Extension(HttpApiState::from(config)))
+                .layer(Extension(document)),
+        )
 }
 
 #[cfg(test)]
@@ -24,25 +39,32 @@ mod tests {
         http::{Request, StatusCode},
     };
     use tower::{Service, ServiceExt};
+    use utoipa::openapi::PathItemType;
 
     use super::*;
+    use crate::http::HttpApiServerConfig;
 
-    #[tokio::test]
-    async fn index_should_exist() {
+    fn router_config() -> HttpApiRouterConfig {
         let listener = std::net::TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], 0))).unwrap();
         let address = listener.local_addr().unwrap();
 
-        let api_title = "Node HTTP API".to_string();
-        let api_version = "1.0".to_string();
+        let server_config = HttpApiServerConfig::default();
 
-        let config = HttpApiRouterConfig {
+        HttpApiRouterConfig {
             address,
-            api_title,
-            api_version,
+            api_title: server_config.api_title,
+            api_version: server_config.api_version,
             server_timeout: None,
-        };
+            vrrbdb_read_handle: server_config.vrrbdb_read_handle,
+            mempool_read_handle_factory: server_config.mempool_read_handle_factory,
+            events_tx: server_config.events_tx,
+            access: server_config.access,
+        }
+    }
 
-        let mut router = create_router(&config);
+    #[tokio::test]
+    async fn index_should_exist() {
+        let mut router = create_router(&router_config());
 
         let request = Request::builder()
             .uri("/")
@@ -53,4 +75,45 @@ mod tests {
         let response = router.ready().await.unwrap().call(request).await.unwrap();
         assert_eq!(response.status(), StatusCode::OK);
     }
+
+    #[tokio::test]
+    async fn documented_operations_are_routed() {
+        let config = router_config();
+        let mut router = create_router(&config);
+        let document = openapi_document(&config);
+
+        for (path, item) in &document.paths.paths {
+            if !item.operations.contains_key(&PathItemType::Get) {
+                continue;
+            }
+
+            let uri = path
+                .replace("{address}", &primitives::Address::default().to_string())
+                .replace("{height}", "0")
+                .replace("{hash}", "0x0")
+                .replace("{digest}", "00");
+
+            let request = Request::builder()
+                .uri(uri)
+                .method("GET")
+                .body(Body::empty())
+                .unwrap();
+
+            let response = router.ready().await.unwrap().call(request).await.unwrap();
+            let status = response.status();
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+
+            // Lookups of missing resources are answered with an error body,
+            // unlike requests to unknown routes
+            assert!(
+                status == StatusCode::OK
+                    || (status == StatusCode::NOT_FOUND && body.starts_with(b"{\"error\"")),
+                "{path} responded with {status}",
+            );
+        }
+
+        let document = serde_json::to_value(&document).unwrap();
+        assert_eq!(document["info"]["title"], config.api_title);
+        assert!(document["paths"]["/accounts/{address}/transactions"]["get"].is_object());
+    }
 }
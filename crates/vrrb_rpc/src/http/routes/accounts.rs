@@ -1,58 +1,238 @@
+use std::net::SocketAddr;
+
 use axum::{
-    routing::{get, post, put},
+    extract::{ConnectInfo, Path, Query},
+    routing::{get, post},
     Extension, Json, Router,
 };
-use serde_json::{json, Value};
+use events::Event;
+use hyper::HeaderMap;
+use primitives::Address;
+use serde::{Deserialize, Serialize};
+use storage::vrrbdb::{Claims, TransactionDirection};
+use vrrb_core::{account::Account, serde_helpers::encode_to_binary};
+
+use crate::{
+    http::{HttpApiError, HttpApiState, HttpErrorBody, HttpResult},
+    rpc::{api::RpcAccountTransactionPage, RpcAdminMethod},
+};
+
+/// Number of transactions returned by `GET /accounts/:address/transactions`
+/// when no limit is given
+pub const DEFAULT_TRANSACTIONS_LIMIT: usize = 50;
 
 pub fn create_account_router() -> Router {
     Router::new()
-        .route("/:id", get(get_account))
-        .route("/:id", put(update_account))
         .route("/", post(create_account))
-        .layer(Extension(String::from("account route")))
+        .route("/:address", get(get_account).put(update_account))
+        .route("/:address/transactions", get(get_account_transactions))
+        .route("/:address/transactions/count", get(get_transaction_count))
+        .route("/:address/claims", get(get_claims))
 }
 
-async fn get_account(Extension(state): Extension<String>) -> Json<Value> {
-    Json(json!({
-        "account": "dummy_account_status",
-        "state": state,
-    }))
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateAccountRequest {
+    pub address: Address,
+    pub account: Account,
 }
 
-async fn create_account() {
-    todo!()
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AccountTransactionsQuery {
+    pub direction: Option<TransactionDirection>,
+    #[serde(default)]
+    pub from_round: u64,
+    pub limit: Option<usize>,
+    pub cursor: Option<String>,
 }
 
-async fn update_account() {
-    todo!()
+/// Account at the latest state
+#[utoipa::path(
+    get,
+    path = "/accounts/{address}",
+    operation_id = "getAccount",
+    tag = "accounts",
+    params(("address" = String, Path, description = "Account address")),
+    responses(
+        (status = 200, description = "The account", body = Object),
+        (status = 404, description = "No account has the address", body = inline(HttpErrorBody)),
+    )
+)]
+async fn get_account(
+    Extension(state): Extension<HttpApiState>,
+    Path(address): Path<Address>,
+) -> HttpResult<Json<Account>> {
+    state
+        .vrrbdb_read_handle
+        .get_account_by_address(&address)
+        .map(Json)
+        .map_err(|_| HttpApiError::NotFound(format!("unable to find account {address}")))
 }
 
-async fn _create_key_router() {
-    todo!()
-}
+/// Request the creation of an account
+///
+/// Subject to the access policy of `createAccount` in the JSON-RPC API.
+#[utoipa::path(
+    post,
+    path = "/accounts",
+    operation_id = "createAccount",
+    tag = "accounts",
+    request_body = Object,
+    security((), ("bearer_token" = [])),
+    responses(
+        (status = 200, description = "Address of the requested account", body = String),
+        (status = 401, description = "The bearer token is missing or invalid", body = inline(HttpErrorBody)),
+        (status = 403, description = "The caller is not allowed to create accounts", body = inline(HttpErrorBody)),
+    )
+)]
+async fn create_account(
+    Extension(state): Extension<HttpApiState>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    Json(request): Json<CreateAccountRequest>,
+) -> HttpResult<Json<Address>> {
+    state.authorize(
+        RpcAdminMethod::CreateAccount,
+        connect_info.map(|ConnectInfo(addr)| addr),
+        &headers,
+    )?;
 
-async fn _create_token_router() {
-    todo!()
+    let account_bytes = encode_to_binary(&request.account)
+        .map_err(|err| HttpApiError::BadRequest(err.to_string()))?;
+
+    let event = Event::CreateAccountRequested((request.address.clone(), account_bytes));
+    send_event(&state, event).await?;
+
+    Ok(Json(request.address))
 }
 
-async fn _get_key() {
-    todo!()
+/// Request an update of an account
+///
+/// Subject to the access policy of `updateAccount` in the JSON-RPC API.
+#[utoipa::path(
+    put,
+    path = "/accounts/{address}",
+    operation_id = "updateAccount",
+    tag = "accounts",
+    params(("address" = String, Path, description = "Account address")),
+    request_body = Object,
+    security((), ("bearer_token" = [])),
+    responses(
+        (status = 200, description = "Address of the updated account", body = String),
+        (status = 401, description = "The bearer token is missing or invalid", body = inline(HttpErrorBody)),
+        (status = 403, description = "The caller is not allowed to update accounts", body = inline(HttpErrorBody)),
+    )
+)]
+async fn update_account(
+    Extension(state): Extension<HttpApiState>,
+    Path(address): Path<Address>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    Json(account): Json<Account>,
+) -> HttpResult<Json<Address>> {
+    state.authorize(
+        RpcAdminMethod::UpdateAccount,
+        connect_info.map(|ConnectInfo(addr)| addr),
+        &headers,
+    )?;
+
+    let account_bytes =
+        encode_to_binary(&account).map_err(|err| HttpApiError::BadRequest(err.to_string()))?;
+
+    let event = Event::AccountUpdateRequested((address.clone(), account_bytes));
+    send_event(&state, event).await?;
+
+    Ok(Json(address))
 }
 
-async fn _create_token() {
-    todo!()
+/// Page of the confirmed transactions an account took part in, by round
+#[utoipa::path(
+    get,
+    path = "/accounts/{address}/transactions",
+    operation_id = "getAccountTransactions",
+    tag = "accounts",
+    params(
+        ("address" = String, Path, description = "Account address"),
+        ("direction" = Option<String>, Query, description = "Only return sent, received or stake transactions"),
+        ("from_round" = Option<u64>, Query, description = "Round the first page starts at"),
+        ("limit" = Option<usize>, Query, description = "Most transactions returned"),
+        ("cursor" = Option<String>, Query, description = "next_cursor of the previous page"),
+    ),
+    responses(
+        (status = 200, description = "Page of transactions", body = Object),
+        (status = 400, description = "The query is invalid", body = inline(HttpErrorBody)),
+    )
+)]
+async fn get_account_transactions(
+    Extension(state): Extension<HttpApiState>,
+    Path(address): Path<Address>,
+    Query(query): Query<AccountTransactionsQuery>,
+) -> HttpResult<Json<RpcAccountTransactionPage>> {
+    let read_handle = &state.vrrbdb_read_handle;
+    let page = read_handle
+        .get_account_transactions(
+            &address,
+            query.direction,
+            query.from_round as u128,
+            query.limit.unwrap_or(DEFAULT_TRANSACTIONS_LIMIT),
+            query.cursor.as_deref(),
+        )
+        .map_err(|err| HttpApiError::BadRequest(err.to_string()))?;
+
+    RpcAccountTransactionPage::read(read_handle, page)
+        .map(Json)
+        .map_err(|err| HttpApiError::Internal(err.to_string()))
 }
 
-async fn _update_token() {
-    todo!()
+/// Number of confirmed transactions an account took part in
+#[utoipa::path(
+    get,
+    path = "/accounts/{address}/transactions/count",
+    operation_id = "getTransactionCount",
+    tag = "accounts",
+    params(("address" = String, Path, description = "Account address")),
+    responses((status = 200, description = "Number of transactions", body = usize))
+)]
+async fn get_transaction_count(
+    Extension(state): Extension<HttpApiState>,
+    Path(address): Path<Address>,
+) -> HttpResult<Json<usize>> {
+    state
+        .vrrbdb_read_handle
+        .count_account_transactions(&address, None)
+        .map(Json)
+        .map_err(|err| HttpApiError::Internal(err.to_string()))
 }
 
-async fn _get_token() {
-    todo!()
+/// Claims owned by an account
+#[utoipa::path(
+    get,
+    path = "/accounts/{address}/claims",
+    operation_id = "getClaimsByAccountId",
+    tag = "accounts",
+    params(("address" = String, Path, description = "Account address")),
+    responses((status = 200, description = "Claims of the account", body = [Object]))
+)]
+async fn get_claims(
+    Extension(state): Extension<HttpApiState>,
+    Path(address): Path<Address>,
+) -> HttpResult<Json<Claims>> {
+    let claims = state
+        .vrrbdb_read_handle
+        .claim_store_values()
+        .map_err(|err| HttpApiError::Internal(err.to_string()))?
+        .into_values()
+        .filter(|claim| claim.address == address)
+        .collect();
+
+    Ok(Json(claims))
 }
 
-async fn _delete_token() {
-    todo!()
+async fn send_event(state: &HttpApiState, event: Event) -> HttpResult<()> {
+    state
+        .events_tx
+        .send(event.into())
+        .await
+        .map_err(|err| HttpApiError::Internal(format!("unable to publish event: {err}")))
 }
 
 #[cfg(test)]
@@ -61,22 +241,110 @@ mod tests {
         body::Body,
         http::{Request, StatusCode},
     };
+    use events::DEFAULT_BUFFER;
+    use tokio::sync::mpsc::channel;
     use tower::{Service, ServiceExt};
 
     use super::*;
+    use crate::{http::HttpApiServerConfig, rpc::RpcAccessConfig};
+
+    fn state() -> HttpApiState {
+        let config = HttpApiServerConfig::default();
+
+        HttpApiState {
+            vrrbdb_read_handle: config.vrrbdb_read_handle,
+            mempool_read_handle_factory: config.mempool_read_handle_factory,
+            events_tx: config.events_tx,
+            access: RpcAccessConfig::default().with_bearer_token("secret"),
+        }
+    }
+
+    fn router() -> Router {
+        create_account_router().layer(Extension(state()))
+    }
+
+    fn create_account_request(bearer_token: Option<&str>) -> Request<Body> {
+        let address = Address::default();
+        let body = serde_json::to_vec(&CreateAccountRequest {
+            address: address.clone(),
+            account: Account::new(address),
+        })
+        .unwrap();
+
+        let mut request = Request::builder()
+            .uri("/")
+            .method("POST")
+            .header("content-type", "application/json");
+        if let Some(bearer_token) = bearer_token {
+            request = request.header("authorization", format!("Bearer {bearer_token}"));
+        }
+
+        request.body(Body::from(body)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn get_account_returns_not_found_for_unknown_accounts() {
+        let mut router = router();
+        let address = Address::default();
+
+        let request = Request::builder()
+            .uri(format!("/{address}"))
+            .method("GET")
+            .body(Body::empty())
+            .unwrap();
+
+        let response = router.ready().await.unwrap().call(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
 
     #[tokio::test]
-    async fn get_account_returns_available_accounts() {
-        let mut router = create_account_router();
+    async fn get_transaction_count_counts_confirmed_transactions() {
+        let mut router = router();
+        let address = Address::default();
 
         let request = Request::builder()
-            .uri("/:id")
+            .uri(format!("/{address}/transactions/count"))
             .method("GET")
             .body(Body::empty())
             .unwrap();
 
         let response = router.ready().await.unwrap().call(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        assert_eq!(&body[..], b"0");
+    }
 
+    #[tokio::test]
+    async fn create_account_follows_the_access_policy_of_the_rpc_api() {
+        let (events_tx, mut events_rx) = channel(DEFAULT_BUFFER);
+        let mut router = create_account_router().layer(Extension(HttpApiState {
+            events_tx,
+            ..state()
+        }));
+
+        for bearer_token in [None, Some("other")] {
+            let response = router
+                .ready()
+                .await
+                .unwrap()
+                .call(create_account_request(bearer_token))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        }
+
+        let response = router
+            .ready()
+            .await
+            .unwrap()
+            .call(create_account_request(Some("secret")))
+            .await
+            .unwrap();
         assert_eq!(response.status(), StatusCode::OK);
+
+        let event: Event = events_rx.recv().await.unwrap().into();
+        assert!(matches!(event, Event::CreateAccountRequested(_)));
     }
 }
@@ -0,0 +1,98 @@
+use axum::{
+    extract::{Path, Query},
+    routing::get,
+    Extension, Json, Router,
+};
+use block::Block;
+use serde::{Deserialize, Serialize};
+use storage::vrrbdb::{BlockPage, MAX_BLOCKS_PER_PAGE};
+
+use crate::http::{HttpApiError, HttpApiState, HttpErrorBody, HttpResult};
+
+pub fn create_block_router() -> Router {
+    Router::new()
+        .route("/", get(get_blocks_range))
+        .route("/:hash", get(get_block_by_hash))
+        .route("/height/:height", get(get_block_by_height))
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BlocksRangeQuery {
+    #[serde(default)]
+    pub from_height: u64,
+    pub limit: Option<usize>,
+}
+
+/// Page of genesis and convergence blocks, by height
+#[utoipa::path(
+    get,
+    path = "/blocks",
+    operation_id = "getBlocksRange",
+    tag = "blocks",
+    params(
+        ("from_height" = Option<u64>, Query, description = "Height the page starts at"),
+        ("limit" = Option<usize>, Query, description = "Most blocks returned"),
+    ),
+    responses((status = 200, description = "Page of blocks", body = Object))
+)]
+async fn get_blocks_range(
+    Extension(state): Extension<HttpApiState>,
+    Query(query): Query<BlocksRangeQuery>,
+) -> HttpResult<Json<BlockPage>> {
+    state
+        .vrrbdb_read_handle
+        .get_blocks_range(
+            query.from_height as u128,
+            query.limit.unwrap_or(MAX_BLOCKS_PER_PAGE),
+        )
+        .map(Json)
+        .map_err(|err| HttpApiError::Internal(err.to_string()))
+}
+
+/// Block with the given hash
+#[utoipa::path(
+    get,
+    path = "/blocks/{hash}",
+    operation_id = "getBlockByHash",
+    tag = "blocks",
+    params(("hash" = String, Path, description = "Block hash")),
+    responses(
+        (status = 200, description = "The block", body = Object),
+        (status = 404, description = "No block has the hash", body = inline(HttpErrorBody)),
+    )
+)]
+async fn get_block_by_hash(
+    Extension(state): Extension<HttpApiState>,
+    Path(hash): Path<String>,
+) -> HttpResult<Json<Block>> {
+    state
+        .vrrbdb_read_handle
+        .get_block_by_hash(&hash)
+        .map_err(|err| HttpApiError::Internal(err.to_string()))?
+        .map(Json)
+        .ok_or_else(|| HttpApiError::NotFound(format!("unable to find block {hash}")))
+}
+
+/// Genesis or convergence block at the given height
+#[utoipa::path(
+    get,
+    path = "/blocks/height/{height}",
+    operation_id = "getBlockByHeight",
+    tag = "blocks",
+    params(("height" = u64, Path, description = "Block height")),
+    responses(
+        (status = 200, description = "The block", body = Object),
+        (status = 404, description = "No block is at the height", body = inline(HttpErrorBody)),
+    )
+)]
+async fn get_block_by_height(
+    Extension(state): Extension<HttpApiState>,
+    Path(height): Path<u64>,
+) -> HttpResult<Json<Block>> {
+    state
+        .vrrbdb_read_handle
+        .get_block_by_height(height as u128)
+        .map_err(|err| HttpApiError::Internal(err.to_string()))?
+        .map(Json)
+        .ok_or_else(|| HttpApiError::NotFound(format!("unable to find block at height {height}")))
+}
@@ -0,0 +1,57 @@
+use axum::{extract::Path, routing::get, Extension, Json, Router};
+use block::ClaimHash;
+use storage::vrrbdb::Claims;
+use vrrb_core::claim::Claim;
+
+use crate::http::{HttpApiError, HttpApiState, HttpErrorBody, HttpResult};
+
+pub fn create_claim_router() -> Router {
+    Router::new()
+        .route("/", get(get_claims))
+        .route("/:hash", get(get_claim))
+}
+
+/// Every registered claim
+#[utoipa::path(
+    get,
+    path = "/claims",
+    operation_id = "getClaims",
+    tag = "claims",
+    responses((status = 200, description = "Registered claims", body = [Object]))
+)]
+async fn get_claims(Extension(state): Extension<HttpApiState>) -> HttpResult<Json<Claims>> {
+    let claims = state
+        .vrrbdb_read_handle
+        .claim_store_values()
+        .map_err(|err| HttpApiError::Internal(err.to_string()))?
+        .into_values()
+        .collect();
+
+    Ok(Json(claims))
+}
+
+/// Claim with the given hash
+#[utoipa::path(
+    get,
+    path = "/claims/{hash}",
+    operation_id = "getClaim",
+    tag = "claims",
+    params(("hash" = String, Path, description = "0x prefixed hex encoded claim hash")),
+    responses(
+        (status = 200, description = "The claim", body = Object),
+        (status = 404, description = "No claim has the hash", body = inline(HttpErrorBody)),
+    )
+)]
+async fn get_claim(
+    Extension(state): Extension<HttpApiState>,
+    Path(hash): Path<ClaimHash>,
+) -> HttpResult<Json<Claim>> {
+    state
+        .vrrbdb_read_handle
+        .claim_store_values()
+        .map_err(|err| HttpApiError::Internal(err.to_string()))?
+        .into_values()
+        .find(|claim| claim.hash == hash)
+        .map(Json)
+        .ok_or_else(|| HttpApiError::NotFound(format!("unable to find claim {hash:#x}")))
+}
@@ -1,6 +1,14 @@
 use axum::Json;
 use serde_json::{json, Value};
 
+/// Whether the API is up
+#[utoipa::path(
+    get,
+    path = "/health",
+    operation_id = "healthCheck",
+    tag = "meta",
+    responses((status = 200, description = "The API is up", body = Object))
+)]
 pub async fn health_check() -> Json<Value> {
     Json(json!({
         "status": "ok",
@@ -0,0 +1,29 @@
+use axum::{routing::get, Extension, Json, Router};
+
+use crate::{
+    http::HttpApiState,
+    rpc::api::{FullMempoolSnapshot, RpcTransactionRecord},
+};
+
+pub fn create_mempool_router() -> Router {
+    Router::new().route("/", get(get_full_mempool))
+}
+
+/// Transactions waiting to be confirmed
+#[utoipa::path(
+    get,
+    path = "/mempool",
+    operation_id = "getFullMempool",
+    tag = "mempool",
+    responses((status = 200, description = "Pending transactions", body = [Object]))
+)]
+async fn get_full_mempool(Extension(state): Extension<HttpApiState>) -> Json<FullMempoolSnapshot> {
+    Json(
+        state
+            .mempool_read_handle_factory
+            .values()
+            .into_iter()
+            .map(RpcTransactionRecord::from)
+            .collect(),
+    )
+}
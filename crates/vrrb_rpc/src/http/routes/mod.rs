@@ -1,2 +1,6 @@
 pub mod accounts;
+pub mod blocks;
+pub mod claims;
 pub mod health;
+pub mod mempool;
+pub mod transactions;
@@ -0,0 +1,77 @@
+use axum::{
+    extract::Path,
+    routing::{get, post},
+    Extension, Json, Router,
+};
+use events::Event;
+use vrrb_core::transactions::{TransactionDigest, TransactionKind};
+
+use crate::{
+    http::{HttpApiError, HttpApiState, HttpErrorBody, HttpResult},
+    rpc::api::RpcTransactionRecord,
+};
+
+pub fn create_transaction_router() -> Router {
+    Router::new()
+        .route("/", post(create_txn))
+        .route("/:digest", get(get_transaction))
+}
+
+/// Submit a transaction to the mempool
+#[utoipa::path(
+    post,
+    path = "/transactions",
+    operation_id = "createTxn",
+    tag = "transactions",
+    request_body = Object,
+    responses((status = 200, description = "The submitted transaction", body = Object))
+)]
+async fn create_txn(
+    Extension(state): Extension<HttpApiState>,
+    Json(txn): Json<TransactionKind>,
+) -> HttpResult<Json<RpcTransactionRecord>> {
+    let event = Event::NewTxnCreated(txn.clone());
+
+    state
+        .events_tx
+        .send(event.into())
+        .await
+        .map_err(|err| HttpApiError::Internal(format!("could not queue transaction: {err}")))?;
+
+    Ok(Json(RpcTransactionRecord::from(txn)))
+}
+
+/// Confirmed or pending transaction
+///
+/// Returns a confirmed transaction, or a transaction still waiting in the
+/// mempool.
+#[utoipa::path(
+    get,
+    path = "/transactions/{digest}",
+    operation_id = "getTransaction",
+    tag = "transactions",
+    params(("digest" = String, Path, description = "Hex encoded transaction digest")),
+    responses(
+        (status = 200, description = "The transaction", body = Object),
+        (status = 400, description = "The digest is invalid", body = inline(HttpErrorBody)),
+        (status = 404, description = "No transaction has the digest", body = inline(HttpErrorBody)),
+    )
+)]
+async fn get_transaction(
+    Extension(state): Extension<HttpApiState>,
+    Path(digest): Path<String>,
+) -> HttpResult<Json<RpcTransactionRecord>> {
+    let digest = digest
+        .parse::<TransactionDigest>()
+        .map_err(|_| HttpApiError::BadRequest(format!("invalid transaction digest {digest}")))?;
+
+    if let Ok(txn) = state.vrrbdb_read_handle.get_transaction(&digest) {
+        return Ok(Json(RpcTransactionRecord::from(txn)));
+    }
+
+    state
+        .mempool_read_handle_factory
+        .get(&digest)
+        .map(|record| Json(RpcTransactionRecord::from(record.txn)))
+        .ok_or_else(|| HttpApiError::NotFound(format!("unable to find transaction {digest}")))
+}
@@ -14,7 +14,8 @@ use crate::{
     ApiError, Result,
 };
 
-/// A JSON-RPC API layer for VRRB nodes.
+/// A REST API layer for VRRB nodes, serving the same accounts, transactions,
+/// blocks, claims and mempool resources as the JSON-RPC API.
 pub struct HttpApiServer {
     router: Router,
     listener: TcpListener,
@@ -38,6 +39,10 @@ impl HttpApiServer {
             api_title: config.api_title.clone(),
             api_version: config.api_version.clone(),
             server_timeout: config.server_timeout,
+            vrrbdb_read_handle: config.vrrbdb_read_handle.clone(),
+            mempool_read_handle_factory: config.mempool_read_handle_factory.clone(),
+            events_tx: config.events_tx.clone(),
+            access: config.access.clone(),
         };
 
         let tls_config = config.tls_config;
@@ -69,7 +74,10 @@ impl HttpApiServer {
 
             let tls_server = axum_server::from_tcp_rustls(self.listener, tls_config)
                 .handle(handle.clone())
-                .serve(self.router.into_make_service());
+                .serve(
+                    self.router
+                        .into_make_service_with_connect_info::<SocketAddr>(),
+                );
 
             let server_handle = tokio::spawn(async move {
                 if let Err(err) = tls_server.await {
@@ -97,7 +105,10 @@ impl HttpApiServer {
             .map_err(|err| ApiError::Other(format!("unable to bind to listener: {err}")))?;
 
         let graceful = server
-            .serve(self.router.into_make_service())
+            .serve(
+                self.router
+                    .into_make_service_with_connect_info::<SocketAddr>(),
+            )
             .with_graceful_shutdown(async {
                 if let Err(err) = ctrl_rx.recv().await {
                     telemetry::error!("failed to listen for shutdown signal: {err}");
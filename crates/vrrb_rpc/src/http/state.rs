@@ -0,0 +1,51 @@
+use std::net::SocketAddr;
+
+use events::EventPublisher;
+use hyper::HeaderMap;
+use mempool::MempoolReadHandleFactory;
+use storage::vrrbdb::VrrbDbReadHandle;
+
+use crate::{
+    http::{HttpApiRouterConfig, HttpResult},
+    rpc::{audit_admin_call, bearer_token, RpcAccessConfig, RpcAdminMethod, RpcCaller},
+};
+
+/// Node handles shared by every route of the HTTP API
+#[derive(Debug, Clone)]
+pub struct HttpApiState {
+    pub vrrbdb_read_handle: VrrbDbReadHandle,
+    pub mempool_read_handle_factory: MempoolReadHandleFactory,
+    pub events_tx: EventPublisher,
+    pub access: RpcAccessConfig,
+}
+
+impl HttpApiState {
+    /// Checks that the client that sent a request from `remote_addr` with
+    /// `headers` may call `method`, like the JSON-RPC API does
+    pub(crate) fn authorize(
+        &self,
+        method: RpcAdminMethod,
+        remote_addr: Option<SocketAddr>,
+        headers: &HeaderMap,
+    ) -> HttpResult<()> {
+        let caller = RpcCaller {
+            remote_addr,
+            bearer_token: bearer_token(headers),
+        };
+
+        audit_admin_call(&self.access, method, Some(&caller))?;
+
+        Ok(())
+    }
+}
+
+impl From<&HttpApiRouterConfig> for HttpApiState {
+    fn from(config: &HttpApiRouterConfig) -> Self {
+        Self {
+            vrrbdb_read_handle: config.vrrbdb_read_handle.clone(),
+            mempool_read_handle_factory: config.mempool_read_handle_factory.clone(),
+            events_tx: config.events_tx.clone(),
+            access: config.access.clone(),
+        }
+    }
+}
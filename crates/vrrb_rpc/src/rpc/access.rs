@@ -0,0 +1,347 @@
+use std::{collections::HashMap, fmt, net::SocketAddr};
+
+use jsonrpsee::{
+    core::Error as RpseeError,
+    types::{error::CallError, ErrorObject},
+};
+use serde::{Deserialize, Serialize};
+use service_config::ServiceConfig;
+use subtle::{Choice, ConstantTimeEq};
+use telemetry::{info, warn};
+
+/// Error code admin calls are rejected with when the caller is not allowed to
+/// make them
+pub const UNAUTHORIZED_CODE: i32 = -32002;
+
+tokio::task_local! {
+    /// Caller of the HTTP request being served, set by `RpcAccessLayer`
+    static RPC_CALLER: RpcCaller;
+}
+
+/// Who may call a JSON-RPC method
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RpcAccessPolicy {
+    /// Anyone can call the method
+    Public,
+    /// Callers must send one of the configured bearer tokens, or the
+    /// pre-shared key, in the `Authorization` header
+    Authenticated,
+    /// Only callers connecting from a loopback address can call the method
+    LocalhostOnly,
+}
+
+/// Methods of the JSON-RPC API that act on behalf of the node operator and
+/// are subject to an access policy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RpcAdminMethod {
+    CreateAccount,
+    UpdateAccount,
+    SignTransaction,
+    DeployProgram,
+}
+
+impl RpcAdminMethod {
+    pub fn name(&self) -> &'static str {
+        match self {
+            RpcAdminMethod::CreateAccount => "createAccount",
+            RpcAdminMethod::UpdateAccount => "updateAccount",
+            RpcAdminMethod::SignTransaction => "signTransaction",
+            RpcAdminMethod::DeployProgram => "deployProgram",
+        }
+    }
+}
+
+impl fmt::Display for RpcAdminMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The client an HTTP request to the JSON-RPC server came from
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RpcCaller {
+    pub remote_addr: Option<SocketAddr>,
+    pub bearer_token: Option<String>,
+}
+
+impl RpcCaller {
+    /// Returns the caller of the HTTP request being served, if any. Calls
+    /// made over WebSocket have no caller.
+    pub fn current() -> Option<RpcCaller> {
+        RPC_CALLER.try_with(Clone::clone).ok()
+    }
+
+    pub(crate) fn scope<F: std::future::Future>(
+        self,
+        future: F,
+    ) -> tokio::task::futures::TaskLocalFuture<RpcCaller, F> {
+        RPC_CALLER.scope(self, future)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum RpcAccessError {
+    #[error("{0} must be called over HTTP")]
+    UnknownCaller(RpcAdminMethod),
+
+    #[error("{0} requires a valid bearer token")]
+    Unauthenticated(RpcAdminMethod),
+
+    #[error("{0} can only be called from localhost")]
+    NotLocalhost(RpcAdminMethod),
+}
+
+/// Access policies of the admin methods of the JSON-RPC API and the
+/// credentials authenticated callers are checked against
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RpcAccessConfig {
+    pub policies: HashMap<RpcAdminMethod, RpcAccessPolicy>,
+    /// Tokens accepted from authenticated callers
+    pub bearer_tokens: Vec<String>,
+    /// Key accepted from authenticated callers in addition to the bearer
+    /// tokens, usually `ServiceConfig::pre_shared_key`
+    pub pre_shared_key: Option<String>,
+}
+
+impl Default for RpcAccessConfig {
+    fn default() -> Self {
+        let policies = HashMap::from([
+            (
+                RpcAdminMethod::CreateAccount,
+                RpcAccessPolicy::Authenticated,
+            ),
+            (
+                RpcAdminMethod::UpdateAccount,
+                RpcAccessPolicy::Authenticated,
+            ),
+            (
+                RpcAdminMethod::SignTransaction,
+                RpcAccessPolicy::LocalhostOnly,
+            ),
+            (
+                RpcAdminMethod::DeployProgram,
+                RpcAccessPolicy::Authenticated,
+            ),
+        ]);
+
+        Self {
+            policies,
+            bearer_tokens: vec![],
+            pre_shared_key: None,
+        }
+    }
+}
+
+impl RpcAccessConfig {
+    pub fn with_policy(mut self, method: RpcAdminMethod, policy: RpcAccessPolicy) -> Self {
+        self.policies.insert(method, policy);
+        self
+    }
+
+    pub fn with_bearer_token(mut self, token: impl Into<String>) -> Self {
+        self.bearer_tokens.push(token.into());
+        self
+    }
+
+    /// Accepts the pre-shared key of `service_config` from authenticated
+    /// callers
+    pub fn with_service_config(mut self, service_config: &ServiceConfig) -> Self {
+        if !service_config.pre_shared_key.is_empty() {
+            self.pre_shared_key = Some(service_config.pre_shared_key.clone());
+        }
+        self
+    }
+
+    /// Returns the policy of `method`. Methods without a policy are public.
+    pub fn policy(&self, method: RpcAdminMethod) -> RpcAccessPolicy {
+        self.policies
+            .get(&method)
+            .copied()
+            .unwrap_or(RpcAccessPolicy::Public)
+    }
+
+    /// Checks whether `caller` may call `method`
+    pub fn authorize(
+        &self,
+        method: RpcAdminMethod,
+        caller: Option<&RpcCaller>,
+    ) -> Result<(), RpcAccessError> {
+        let policy = self.policy(method);
+        if policy == RpcAccessPolicy::Public {
+            return Ok(());
+        }
+
+        let caller = caller.ok_or(RpcAccessError::UnknownCaller(method))?;
+
+        match policy {
+            RpcAccessPolicy::Public => Ok(()),
+            RpcAccessPolicy::Authenticated => {
+                let authenticated = caller
+                    .bearer_token
+                    .as_deref()
+                    .map_or(false, |token| self.accepts_token(token));
+
+                if authenticated {
+                    Ok(())
+                } else {
+                    Err(RpcAccessError::Unauthenticated(method))
+                }
+            }
+            RpcAccessPolicy::LocalhostOnly => {
+                let is_localhost = caller
+                    .remote_addr
+                    .map_or(false, |addr| addr.ip().is_loopback());
+
+                if is_localhost {
+                    Ok(())
+                } else {
+                    Err(RpcAccessError::NotLocalhost(method))
+                }
+            }
+        }
+    }
+
+    /// Checks `token` against every accepted token in constant time, so that
+    /// response times do not reveal how much of a token a caller guessed
+    fn accepts_token(&self, token: &str) -> bool {
+        if token.is_empty() {
+            return false;
+        }
+
+        self.bearer_tokens
+            .iter()
+            .map(String::as_str)
+            .chain(self.pre_shared_key.as_deref())
+            .fold(Choice::from(0), |accepted, candidate| {
+                accepted | candidate.as_bytes().ct_eq(token.as_bytes())
+            })
+            .into()
+    }
+}
+
+/// Checks that the caller of the request being served may call `method` and
+/// records the outcome in the audit log
+pub(crate) fn authorize_admin_call(
+    config: &RpcAccessConfig,
+    method: RpcAdminMethod,
+) -> Result<(), RpseeError> {
+    audit_admin_call(config, method, RpcCaller::current().as_ref()).map_err(|err| {
+        RpseeError::Call(CallError::Custom(ErrorObject::owned(
+            UNAUTHORIZED_CODE,
+            err.to_string(),
+            None::<()>,
+        )))
+    })
+}
+
+/// Checks that `caller` may call `method` and records the outcome in the
+/// audit log
+pub(crate) fn audit_admin_call(
+    config: &RpcAccessConfig,
+    method: RpcAdminMethod,
+    caller: Option<&RpcCaller>,
+) -> Result<(), RpcAccessError> {
+    let remote_addr = caller
+        .and_then(|caller| caller.remote_addr)
+        .map_or_else(|| "unknown".to_string(), |addr| addr.to_string());
+
+    match config.authorize(method, caller) {
+        Ok(()) => {
+            info!("audit: {method} called by {remote_addr} was allowed");
+            Ok(())
+        }
+        Err(err) => {
+            warn!("audit: {method} called by {remote_addr} was denied: {err}");
+            Err(err)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn caller(remote_addr: &str, bearer_token: Option<&str>) -> RpcCaller {
+        RpcCaller {
+            remote_addr: Some(remote_addr.parse().unwrap()),
+            bearer_token: bearer_token.map(String::from),
+        }
+    }
+
+    #[test]
+    fn authenticated_methods_accept_bearer_tokens_and_the_pre_shared_key() {
+        let config = RpcAccessConfig {
+            pre_shared_key: Some("psk".to_string()),
+            ..Default::default()
+        }
+        .with_bearer_token("token");
+        let method = RpcAdminMethod::CreateAccount;
+
+        assert!(config
+            .authorize(method, Some(&caller("10.0.0.1:1000", Some("token"))))
+            .is_ok());
+        assert!(config
+            .authorize(method, Some(&caller("10.0.0.1:1000", Some("psk"))))
+            .is_ok());
+        assert_eq!(
+            config.authorize(method, Some(&caller("127.0.0.1:1000", Some("other")))),
+            Err(RpcAccessError::Unauthenticated(method))
+        );
+        assert_eq!(
+            config.authorize(method, Some(&caller("127.0.0.1:1000", None))),
+            Err(RpcAccessError::Unauthenticated(method))
+        );
+        assert_eq!(
+            config.authorize(method, None),
+            Err(RpcAccessError::UnknownCaller(method))
+        );
+    }
+
+    #[test]
+    fn localhost_only_methods_reject_remote_callers() {
+        let config = RpcAccessConfig::default();
+        let method = RpcAdminMethod::SignTransaction;
+
+        assert!(config
+            .authorize(method, Some(&caller("127.0.0.1:1000", None)))
+            .is_ok());
+        assert!(config
+            .authorize(method, Some(&caller("[::1]:1000", None)))
+            .is_ok());
+        assert_eq!(
+            config.authorize(method, Some(&caller("10.0.0.1:1000", None))),
+            Err(RpcAccessError::NotLocalhost(method))
+        );
+    }
+
+    #[test]
+    fn public_methods_accept_every_caller() {
+        let config = RpcAccessConfig::default()
+            .with_policy(RpcAdminMethod::UpdateAccount, RpcAccessPolicy::Public);
+
+        assert!(config
+            .authorize(RpcAdminMethod::UpdateAccount, None)
+            .is_ok());
+    }
+
+    #[test]
+    fn programs_can_only_be_deployed_by_authenticated_callers() {
+        let config = RpcAccessConfig::default().with_bearer_token("token");
+        let method = RpcAdminMethod::DeployProgram;
+
+        assert_eq!(config.policy(method), RpcAccessPolicy::Authenticated);
+        assert!(config
+            .authorize(method, Some(&caller("10.0.0.1:1000", Some("token"))))
+            .is_ok());
+        assert_eq!(
+            config.authorize(method, Some(&caller("127.0.0.1:1000", None))),
+            Err(RpcAccessError::Unauthenticated(method))
+        );
+        assert_eq!(
+            config.authorize(method, Some(&caller("127.0.0.1:1000", Some("toke")))),
+            Err(RpcAccessError::Unauthenticated(method))
+        );
+    }
+}
@@ -2,17 +2,26 @@ use std::collections::HashMap;
 
 use block::block::Block;
 use block::ClaimHash;
-use jsonrpsee::{core::Error as RpseeError, proc_macros::rpc};
-use primitives::{Address, NodeType, Round};
+use jsonrpsee::{core::Error as RpseeError, proc_macros::rpc, server::SubscriptionCloseResponse};
+use primitives::{Address, Epoch, NodeType, Round};
 use secp256k1::PublicKey;
 use serde::{Deserialize, Serialize};
-use storage::vrrbdb::Claims;
+use storage::{
+    storage_utils::Result as StorageResult,
+    vrrbdb::{
+        AccountTransactionPage, BlockHeight, BlockPage, Claims, EpochSummary, MerkleProof,
+        TransactionDirection, VrrbDbReadHandle,
+    },
+};
 use vrrb_config::QuorumMembershipConfig;
 use vrrb_core::account::Account;
 use vrrb_core::node_health_report::NodeHealthReport;
+use vrrb_core::staking::{Delegation, UnbondingEntry};
 use vrrb_core::transactions::{
-    RpcTransactionDigest, Token, Transaction, TransactionKind, TxAmount, TxNonce, TxTimestamp,
+    RpcTransactionDigest, Token, Transaction, TransactionKind, TxAmount, TxFee, TxNonce,
+    TxTimestamp,
 };
+use web3_pkg::web3_pkg::Web3Package;
 
 use crate::rpc::SignOpts;
 
@@ -29,15 +38,41 @@ pub struct FullMempoolSnapshotResponse {
     data: Vec<TransactionRecord>,
 }
 
+/// The kind of transaction a `RpcTransactionRecord` was built from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RpcTransactionKind {
+    #[default]
+    Transfer,
+    Stake,
+    RegisterClaim,
+    CallProgram,
+}
+
+impl From<&TransactionKind> for RpcTransactionKind {
+    fn from(txn: &TransactionKind) -> Self {
+        match txn {
+            TransactionKind::Transfer(_) => RpcTransactionKind::Transfer,
+            TransactionKind::Stake(_) => RpcTransactionKind::Stake,
+            TransactionKind::RegisterClaim(_) => RpcTransactionKind::RegisterClaim,
+            TransactionKind::CallProgram(_) => RpcTransactionKind::CallProgram,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RpcTransactionRecord {
     pub id: RpcTransactionDigest,
+    /// Records of nodes predating transaction kinds are transfers
+    #[serde(default)]
+    pub kind: RpcTransactionKind,
     pub timestamp: TxTimestamp,
     pub sender_address: Address,
     pub sender_public_key: PublicKey,
     pub receiver_address: Address,
     pub token: Token,
     pub amount: TxAmount,
+    pub fee: TxFee,
     pub signature: String,
     pub validators: HashMap<String, bool>,
     pub nonce: TxNonce,
@@ -47,12 +82,14 @@ impl From<TransactionKind> for RpcTransactionRecord {
     fn from(txn: TransactionKind) -> Self {
         Self {
             id: txn.id().digest_string(),
+            kind: RpcTransactionKind::from(&txn),
             timestamp: txn.timestamp(),
             sender_address: txn.sender_address(),
             sender_public_key: txn.sender_public_key(),
             receiver_address: txn.receiver_address(),
             token: txn.token(),
             amount: txn.amount(),
+            fee: txn.fee(),
             signature: txn.signature().to_string(),
             validators: txn.validators().unwrap_or_default(),
             nonce: txn.nonce(),
@@ -60,6 +97,88 @@ impl From<TransactionKind> for RpcTransactionRecord {
     }
 }
 
+/// A confirmed transaction an account took part in
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcAccountTransaction {
+    /// Round of the convergence block that applied the transaction
+    pub round: Round,
+    pub direction: TransactionDirection,
+    pub transaction: RpcTransactionRecord,
+}
+
+/// A page of the transactions returned by `getAccountTransactions`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RpcAccountTransactionPage {
+    pub transactions: Vec<RpcAccountTransaction>,
+    /// Cursor to request the next page with, if there are more transactions
+    pub next_cursor: Option<String>,
+}
+
+impl RpcAccountTransactionPage {
+    /// Reads the transactions of a page of the account transaction index
+    pub fn read(
+        read_handle: &VrrbDbReadHandle,
+        page: AccountTransactionPage,
+    ) -> StorageResult<Self> {
+        let mut transactions = Vec::with_capacity(page.entries.len());
+        for entry in page.entries {
+            let txn = read_handle.get_transaction(&entry.digest)?;
+
+            transactions.push(RpcAccountTransaction {
+                round: entry.round,
+                direction: entry.direction,
+                transaction: RpcTransactionRecord::from(txn),
+            });
+        }
+
+        Ok(Self {
+            transactions,
+            next_cursor: page.next_cursor,
+        })
+    }
+}
+
+/// Selects a past state either by block height or by hex encoded state root
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RpcHeightOrRoot {
+    Height(u128),
+    Root(String),
+}
+
+/// Input of a program run by `callProgram`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RpcProgramCall {
+    /// Written to the program's stdin, usually JSON
+    pub input: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// Most gas the program may use, capped at `MAX_PROGRAM_GAS_LIMIT`
+    pub gas_limit: u64,
+}
+
+/// Output of a program run by `callProgram`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RpcProgramOutput {
+    pub stdout: String,
+    pub stderr: String,
+    pub gas_used: u64,
+}
+
+/// Status of a transaction reported to `subscribeTxnStatus` subscribers
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RpcTxnStatus {
+    /// The transaction is waiting in the mempool
+    Pending,
+    /// The transaction was validated by the node's quorum
+    Validated,
+    /// The transaction was included in an appended convergence block
+    Confirmed { block_hash: String },
+}
+
 #[rpc(server, client, namespace = "state")]
 #[async_trait]
 pub trait RpcApi {
@@ -93,6 +212,21 @@ pub trait RpcApi {
         digests: Vec<RpcTransactionDigest>,
     ) -> Result<HashMap<RpcTransactionDigest, RpcTransactionRecord>, RpseeError>;
 
+    /// Returns up to `limit` confirmed transactions the account took part
+    /// in, ordered by round. Only the transactions of `direction` are
+    /// returned if it is given. The first page starts at `from_round`, the
+    /// following ones are requested with the `next_cursor` of the previous
+    /// page.
+    #[method(name = "getAccountTransactions")]
+    async fn get_account_transactions(
+        &self,
+        address: Address,
+        direction: Option<TransactionDirection>,
+        from_round: Round,
+        limit: usize,
+        cursor: Option<String>,
+    ) -> Result<RpcAccountTransactionPage, RpseeError>;
+
     #[method(name = "createAccount")]
     async fn create_account(&self, address: Address, account: Account) -> Result<(), RpseeError>;
 
@@ -102,6 +236,35 @@ pub trait RpcApi {
     #[method(name = "getAccount")]
     async fn get_account(&self, address: Address) -> Result<Account, RpseeError>;
 
+    /// Returns an account as it was at a past block height or state root
+    #[method(name = "getAccountAt")]
+    async fn get_account_at(
+        &self,
+        address: Address,
+        height_or_root: RpcHeightOrRoot,
+    ) -> Result<Account, RpseeError>;
+
+    /// Returns a Merkle proof of an account's inclusion in the state trie.
+    /// Proofs requested at a block height verify against the state root in
+    /// the header of that block, otherwise against the latest state root.
+    #[method(name = "getAccountProof")]
+    async fn get_account_proof(
+        &self,
+        address: Address,
+        block_height: Option<BlockHeight>,
+    ) -> Result<MerkleProof, RpseeError>;
+
+    /// Returns a Merkle proof of a transaction's inclusion in the transaction
+    /// trie. Proofs requested at a block height verify against the
+    /// transactions root in the header of that block, otherwise against the
+    /// latest transactions root.
+    #[method(name = "getTransactionProof")]
+    async fn get_transaction_proof(
+        &self,
+        transaction_digest: RpcTransactionDigest,
+        block_height: Option<BlockHeight>,
+    ) -> Result<MerkleProof, RpseeError>;
+
     #[method(name = "faucetDrip")]
     async fn faucet_drip(&self, address: Address) -> Result<(), RpseeError>;
 
@@ -111,15 +274,50 @@ pub trait RpcApi {
     #[method(name = "getRound")]
     async fn get_round(&self) -> Result<Round, RpseeError>;
 
+    /// Returns the most recent genesis and convergence blocks, up to
+    /// `MAX_BLOCKS_PER_PAGE` of them, ordered by height
     #[method(name = "getBlocks")]
     async fn get_blocks(&self) -> Result<Vec<Block>, RpseeError>;
 
+    #[method(name = "getBlockByHash")]
+    async fn get_block_by_hash(&self, block_hash: String) -> Result<Option<Block>, RpseeError>;
+
+    /// Returns the genesis or convergence block at the given height
+    #[method(name = "getBlockByHeight")]
+    async fn get_block_by_height(&self, height: BlockHeight) -> Result<Option<Block>, RpseeError>;
+
+    /// Returns up to `limit` genesis and convergence blocks starting at
+    /// `from_height`, along with the height the next page starts at
+    #[method(name = "getBlocksRange")]
+    async fn get_blocks_range(
+        &self,
+        from_height: BlockHeight,
+        limit: usize,
+    ) -> Result<BlockPage, RpseeError>;
+
+    /// Registers a program along with its hex encoded WASM module and returns
+    /// the content ID it can be called by
+    #[method(name = "deployProgram")]
+    async fn deploy_program(
+        &self,
+        package: Web3Package,
+        wasm: String,
+    ) -> Result<String, RpseeError>;
+
+    /// Returns the package of the program registered under `content_id`
     #[method(name = "getProgram")]
-    async fn get_program(&self) -> Result<(), RpseeError>;
+    async fn get_program(&self, content_id: String) -> Result<Option<Web3Package>, RpseeError>;
 
+    /// Runs the program registered under `content_id` and returns what it
+    /// wrote to stdout and stderr along with the gas it used
     #[method(name = "callProgram")]
-    async fn call_program(&self) -> Result<(), RpseeError>;
+    async fn call_program(
+        &self,
+        content_id: String,
+        call: RpcProgramCall,
+    ) -> Result<RpcProgramOutput, RpseeError>;
 
+    /// Returns the number of confirmed transactions the account took part in
     #[method(name = "getTransactionCount")]
     async fn get_transaction_count(&self, account: Address) -> Result<usize, RpseeError>;
 
@@ -135,9 +333,68 @@ pub trait RpcApi {
     #[method(name = "getClaims")]
     async fn get_claims(&self, claim_hashes: Vec<ClaimHash>) -> Result<Claims, RpseeError>;
 
+    /// Returns the stake withdrawn by `address` that is still unbonding, along
+    /// with the epoch each entry is released at
+    #[method(name = "getUnbondingEntries")]
+    async fn get_unbonding_entries(
+        &self,
+        address: Address,
+    ) -> Result<Vec<UnbondingEntry>, RpseeError>;
+
+    /// Returns the delegators of the claim owned by `address` along with the
+    /// stake each of them delegated to it
+    #[method(name = "getDelegators")]
+    async fn get_delegators(&self, address: Address) -> Result<Vec<Delegation>, RpseeError>;
+
+    /// Returns the participants, issued rewards and burned fees of `epoch`,
+    /// once the epoch is over
+    #[method(name = "getEpochSummary")]
+    async fn get_epoch_summary(&self, epoch: Epoch) -> Result<Option<EpochSummary>, RpseeError>;
+
+    /// Returns the summary of the most recent epoch that is over
+    #[method(name = "getLatestEpochSummary")]
+    async fn get_latest_epoch_summary(&self) -> Result<Option<EpochSummary>, RpseeError>;
+
     #[method(name = "getMembershipConfig")]
     async fn get_membership_config(&self) -> Result<QuorumMembershipConfig, RpseeError>;
 
     #[method(name = "getLastBlock")]
     async fn get_last_block(&self) -> Result<Option<Block>, RpseeError>;
+
+    /// Notifies subscribers of every convergence block appended by the node
+    #[subscription(
+        name = "subscribeNewBlocks" => "newBlock",
+        unsubscribe = "unsubscribeNewBlocks",
+        item = Block
+    )]
+    async fn subscribe_new_blocks(&self) -> SubscriptionCloseResponse;
+
+    /// Notifies subscribers of the status changes of a transaction until it is
+    /// confirmed
+    #[subscription(
+        name = "subscribeTxnStatus" => "txnStatus",
+        unsubscribe = "unsubscribeTxnStatus",
+        item = RpcTxnStatus
+    )]
+    async fn subscribe_txn_status(
+        &self,
+        transaction_digest: RpcTransactionDigest,
+    ) -> SubscriptionCloseResponse;
+
+    /// Notifies subscribers of every transaction added to the mempool
+    #[subscription(
+        name = "subscribeMempool" => "mempoolTxn",
+        unsubscribe = "unsubscribeMempool",
+        item = RpcTransactionRecord
+    )]
+    async fn subscribe_mempool(&self) -> SubscriptionCloseResponse;
+
+    /// Notifies subscribers of the current state of an account and of every
+    /// change appended blocks make to it
+    #[subscription(
+        name = "subscribeAccount" => "accountUpdate",
+        unsubscribe = "unsubscribeAccount",
+        item = Account
+    )]
+    async fn subscribe_account(&self, address: Address) -> SubscriptionCloseResponse;
 }
@@ -1,8 +1,18 @@
 use std::net::SocketAddr;
 
-use jsonrpsee::{core::client::Client, ws_client::WsClientBuilder};
+use block::block::Block;
+use jsonrpsee::{
+    core::client::{Client, Subscription},
+    http_client::{HeaderMap, HeaderValue, HttpClient, HttpClientBuilder},
+    ws_client::WsClientBuilder,
+};
+use primitives::Address;
+use vrrb_core::{account::Account, transactions::RpcTransactionDigest};
 
-use crate::ApiError;
+use crate::{
+    rpc::api::{RpcApiClient, RpcTransactionRecord, RpcTxnStatus},
+    ApiError,
+};
 
 pub async fn create_client(server_url: SocketAddr) -> crate::Result<Client> {
     let jsonrpc_url = format!("ws://{server_url}");
@@ -14,3 +24,73 @@ pub async fn create_client(server_url: SocketAddr) -> crate::Result<Client> {
 
     Ok(client)
 }
+
+/// Creates an HTTP client, the transport admin methods are served over,
+/// authenticating its requests with `bearer_token` if given
+pub async fn create_http_client(
+    server_url: SocketAddr,
+    bearer_token: Option<&str>,
+) -> crate::Result<HttpClient> {
+    let jsonrpc_url = format!("http://{server_url}");
+
+    let mut headers = HeaderMap::new();
+    if let Some(bearer_token) = bearer_token {
+        let value = HeaderValue::from_str(&format!("Bearer {bearer_token}"))
+            .map_err(|err| ApiError::Other(format!("invalid bearer token: {err}")))?;
+        headers.insert("authorization", value);
+    }
+
+    let client = HttpClientBuilder::default()
+        .set_headers(headers)
+        .build(jsonrpc_url)
+        .map_err(|err| ApiError::Other(format!("unable to create JSON-RPC client: {err}")))?;
+
+    Ok(client)
+}
+
+/// Subscribes to the convergence blocks appended by the node
+pub async fn subscribe_new_blocks(client: &Client) -> crate::Result<Subscription<Block>> {
+    Ok(client.subscribe_new_blocks().await?)
+}
+
+/// Subscribes to the status changes of a transaction
+pub async fn subscribe_txn_status(
+    client: &Client,
+    transaction_digest: RpcTransactionDigest,
+) -> crate::Result<Subscription<RpcTxnStatus>> {
+    Ok(client.subscribe_txn_status(transaction_digest).await?)
+}
+
+/// Subscribes to the transactions added to the node's mempool
+pub async fn subscribe_mempool(
+    client: &Client,
+) -> crate::Result<Subscription<RpcTransactionRecord>> {
+    Ok(client.subscribe_mempool().await?)
+}
+
+/// Subscribes to the state of an account
+pub async fn subscribe_account(
+    client: &Client,
+    address: Address,
+) -> crate::Result<Subscription<Account>> {
+    Ok(client.subscribe_account(address).await?)
+}
+
+/// Waits until a transaction is included in a convergence block and returns
+/// the hash of that block
+pub async fn wait_for_txn_confirmation(
+    client: &Client,
+    transaction_digest: RpcTransactionDigest,
+) -> crate::Result<String> {
+    let mut subscription = subscribe_txn_status(client, transaction_digest).await?;
+
+    while let Some(status) = subscription.next().await {
+        if let RpcTxnStatus::Confirmed { block_hash } = status? {
+            return Ok(block_hash);
+        }
+    }
+
+    Err(ApiError::Other(
+        "subscription ended before the transaction was confirmed".to_string(),
+    ))
+}
@@ -0,0 +1,128 @@
+use std::{
+    cell::Cell,
+    future::Future,
+    net::SocketAddr,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use hyper::{header, Body, HeaderMap, Request, Response, StatusCode};
+use jsonrpsee::server::logger::{HttpRequest, Logger, MethodKind, Params, TransportProtocol};
+use telemetry::warn;
+use tower::{Layer, Service};
+
+use crate::rpc::{RateLimiter, RpcCaller};
+
+tokio::task_local! {
+    /// Address of the client whose request `RpcAccessService` is handing to
+    /// the server, set by `RemoteAddrLogger`
+    static REMOTE_ADDR: Cell<Option<SocketAddr>>;
+}
+
+/// Reports the address of every client to `RpcAccessService`.
+///
+/// The server does not expose client addresses to its middleware, only to its
+/// logger, which it calls while `RpcAccessService` hands it a request.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RemoteAddrLogger;
+
+impl Logger for RemoteAddrLogger {
+    type Instant = ();
+
+    fn on_connect(&self, remote_addr: SocketAddr, _: &HttpRequest, _: TransportProtocol) {
+        let _ = REMOTE_ADDR.try_with(|slot| slot.set(Some(remote_addr)));
+    }
+
+    fn on_request(&self, _: TransportProtocol) -> Self::Instant {}
+
+    fn on_call(&self, _: &str, _: Params, _: MethodKind, _: TransportProtocol) {}
+
+    fn on_result(&self, _: &str, _: bool, _: Self::Instant, _: TransportProtocol) {}
+
+    fn on_response(&self, _: &str, _: Self::Instant, _: TransportProtocol) {}
+
+    fn on_disconnect(&self, _: SocketAddr, _: TransportProtocol) {}
+}
+
+/// Rate limits the clients of the JSON-RPC server by address and makes the
+/// caller of every HTTP request available to the methods it calls
+#[derive(Debug, Clone)]
+pub struct RpcAccessLayer {
+    rate_limiter: Option<RateLimiter>,
+}
+
+impl RpcAccessLayer {
+    pub fn new(rate_limiter: Option<RateLimiter>) -> Self {
+        Self { rate_limiter }
+    }
+}
+
+impl<S> Layer<S> for RpcAccessLayer {
+    type Service = RpcAccessService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcAccessService {
+            inner,
+            rate_limiter: self.rate_limiter.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RpcAccessService<S> {
+    inner: S,
+    rate_limiter: Option<RateLimiter>,
+}
+
+impl<S> Service<Request<Body>> for RpcAccessService<S>
+where
+    S: Service<Request<Body>, Response = Response<Body>>,
+    S::Future: Send + 'static,
+    S::Error: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<Body>) -> Self::Future {
+        let bearer_token = bearer_token(request.headers());
+
+        let (response, remote_addr) = REMOTE_ADDR.sync_scope(Cell::new(None), || {
+            let response = self.inner.call(request);
+            (response, REMOTE_ADDR.with(Cell::get))
+        });
+
+        if let (Some(rate_limiter), Some(remote_addr)) = (&self.rate_limiter, remote_addr) {
+            if !rate_limiter.try_acquire(remote_addr.ip()) {
+                warn!("rate limited JSON-RPC request from {remote_addr}");
+                return Box::pin(async { Ok(too_many_requests()) });
+            }
+        }
+
+        let caller = RpcCaller {
+            remote_addr,
+            bearer_token,
+        };
+
+        Box::pin(caller.scope(response))
+    }
+}
+
+pub(crate) fn bearer_token(headers: &HeaderMap) -> Option<String> {
+    headers
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+        .map(|token| token.trim().to_string())
+}
+
+fn too_many_requests() -> Response<Body> {
+    let mut response = Response::new(Body::from("too many requests"));
+    *response.status_mut() = StatusCode::TOO_MANY_REQUESTS;
+    response
+}
@@ -1,11 +1,21 @@
+mod access;
 pub mod api;
 pub mod client;
+mod middleware;
+mod programs;
+mod rate_limit;
 mod server;
 mod server_impl;
+mod subscriptions;
+pub use access::*;
+pub use middleware::*;
+pub use programs::*;
+pub use rate_limit::*;
 use serde::{Deserialize, Serialize};
 pub use server::*;
 pub use server_impl::*;
-use vrrb_core::transactions::Token;
+pub use subscriptions::*;
+use vrrb_core::transactions::{Token, TxFee};
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq, Hash)]
 pub struct SignOpts {
@@ -16,5 +26,8 @@ pub struct SignOpts {
     pub amount: u128,
     pub token: Token,
     pub nonce: u128,
+    /// Fee paid by the transfer, `BASE_FEE` if not given
+    #[serde(default)]
+    pub fee: Option<TxFee>,
     pub private_key: String,
 }
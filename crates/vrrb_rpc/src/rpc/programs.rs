@@ -0,0 +1,147 @@
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::anyhow;
+use lru_time_cache::LruCache;
+use storage::vrrbdb::ProgramRecord;
+use tokio::sync::Semaphore;
+use wasm_runtime::{
+    metering::{cost_function, MeteringConfig},
+    wasm_runtime::WasmRuntime,
+};
+use wasmer::{Cranelift, Engine, Module, Target};
+
+use super::api::{RpcProgramCall, RpcProgramOutput};
+
+/// Most gas a single `callProgram` request can make a program use
+pub const MAX_PROGRAM_GAS_LIMIT: u64 = 100_000_000;
+
+/// Number of compiled programs kept for the calls that follow
+pub const COMPILED_PROGRAMS_CACHE_SIZE: usize = 64;
+
+/// Most programs run at once, calls beyond it wait for a running program to
+/// finish
+pub const MAX_CONCURRENT_PROGRAMS: usize = 4;
+
+/// Longest a `callProgram` request waits for its program to run
+pub const PROGRAM_CALL_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Runs the programs called by `callProgram`, compiling every program once.
+/// Clones share the same compiled programs and limits.
+#[derive(Clone)]
+pub struct ProgramRunner {
+    engine: Engine,
+    compiled: Arc<Mutex<LruCache<String, Module>>>,
+    running: Arc<Semaphore>,
+}
+
+impl ProgramRunner {
+    pub fn new() -> Self {
+        let engine = WasmRuntime::engine::<Cranelift>(
+            &Target::default(),
+            MeteringConfig::new(MAX_PROGRAM_GAS_LIMIT, cost_function),
+        );
+
+        Self {
+            engine,
+            compiled: Arc::new(Mutex::new(LruCache::with_capacity(
+                COMPILED_PROGRAMS_CACHE_SIZE,
+            ))),
+            running: Arc::new(Semaphore::new(MAX_CONCURRENT_PROGRAMS)),
+        }
+    }
+
+    /// Runs a program on a blocking thread, metered by the gas limit of the
+    /// call, which is capped at `MAX_PROGRAM_GAS_LIMIT`.
+    ///
+    /// Calls fail once they waited `PROGRAM_CALL_TIMEOUT` for their program.
+    /// A program that is still running by then keeps its slot until it is done
+    /// or out of gas.
+    pub(crate) async fn run(
+        &self,
+        program: ProgramRecord,
+        call: RpcProgramCall,
+    ) -> anyhow::Result<RpcProgramOutput> {
+        let runner = self.clone();
+
+        let run = async move {
+            let permit = runner.running.clone().acquire_owned().await?;
+
+            tokio::task::spawn_blocking(move || {
+                let _permit = permit;
+                runner.execute(&program, &call)
+            })
+            .await?
+        };
+
+        tokio::time::timeout(PROGRAM_CALL_TIMEOUT, run)
+            .await
+            .map_err(|_| anyhow!("program did not finish within {PROGRAM_CALL_TIMEOUT:?}"))?
+    }
+
+    fn execute(
+        &self,
+        program: &ProgramRecord,
+        call: &RpcProgramCall,
+    ) -> anyhow::Result<RpcProgramOutput> {
+        let gas_limit = call.gas_limit.min(MAX_PROGRAM_GAS_LIMIT);
+
+        let mut runtime = WasmRuntime::from_module(&self.engine, self.module(program)?, gas_limit)
+            .stdin(call.input.as_bytes())?
+            .env(&call.env)?
+            .args(&call.args)?;
+
+        if let Err(err) = runtime.execute() {
+            return Err(anyhow!(
+                "program failed after using {} of {gas_limit} gas: {err}",
+                runtime.gas_used()
+            ));
+        }
+
+        Ok(RpcProgramOutput {
+            stdout: runtime.stdout(),
+            stderr: runtime.stderr(),
+            gas_used: runtime.gas_used(),
+        })
+    }
+
+    /// Returns the compiled module of a program, compiling it if it is not
+    /// cached
+    fn module(&self, program: &ProgramRecord) -> anyhow::Result<Module> {
+        let content_id = program.content_id();
+
+        if let Some(module) = self.compiled().get(&content_id) {
+            return Ok(module.clone());
+        }
+
+        let module = WasmRuntime::compile(&self.engine, &program.wasm)?;
+        self.compiled().insert(content_id, module.clone());
+
+        Ok(module)
+    }
+
+    fn compiled(&self) -> std::sync::MutexGuard<'_, LruCache<String, Module>> {
+        match self.compiled.lock() {
+            Ok(compiled) => compiled,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+impl Default for ProgramRunner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for ProgramRunner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProgramRunner")
+            .field("engine", &self.engine)
+            .field("available_permits", &self.running.available_permits())
+            .finish()
+    }
+}
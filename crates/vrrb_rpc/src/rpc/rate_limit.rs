@@ -0,0 +1,150 @@
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use serde::{Deserialize, Serialize};
+
+/// Most addresses the rate limiter tracks. Once it tracks that many, it
+/// forgets the ones whose buckets have refilled, and refuses requests from new
+/// addresses if none have.
+pub const MAX_RATE_LIMITED_ADDRESSES: usize = 10_000;
+
+/// Token bucket every client address of the JSON-RPC server is limited by.
+/// Every HTTP request and every WebSocket connection takes one token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimitConfig {
+    /// Most requests an address can send at once
+    pub burst: u32,
+    /// Tokens added back to the bucket of an address every second
+    pub requests_per_second: u32,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            burst: 200,
+            requests_per_second: 100,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TokenBucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+/// Per-address token buckets. Clones share the same buckets.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    buckets: Arc<Mutex<HashMap<IpAddr, TokenBucket>>>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            config,
+            buckets: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Takes a token from the bucket of `addr`, returning false if it is empty
+    /// or if `addr` has no bucket and there is no room for a new one
+    pub fn try_acquire(&self, addr: IpAddr) -> bool {
+        self.try_acquire_at(addr, Instant::now())
+    }
+
+    fn try_acquire_at(&self, addr: IpAddr, now: Instant) -> bool {
+        let burst = self.config.burst as f64;
+        let refill_rate = self.config.requests_per_second as f64;
+
+        let mut buckets = match self.buckets.lock() {
+            Ok(buckets) => buckets,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        if buckets.len() >= MAX_RATE_LIMITED_ADDRESSES && !buckets.contains_key(&addr) {
+            buckets.retain(|_, bucket| {
+                let elapsed = now.saturating_duration_since(bucket.updated_at);
+                bucket.tokens + elapsed.as_secs_f64() * refill_rate < burst
+            });
+
+            if buckets.len() >= MAX_RATE_LIMITED_ADDRESSES {
+                return false;
+            }
+        }
+
+        let bucket = buckets.entry(addr).or_insert(TokenBucket {
+            tokens: burst,
+            updated_at: now,
+        });
+
+        let elapsed = now.saturating_duration_since(bucket.updated_at);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * refill_rate).min(burst);
+        bucket.updated_at = now;
+
+        if bucket.tokens < 1.0 {
+            return false;
+        }
+
+        bucket.tokens -= 1.0;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        net::{Ipv4Addr, Ipv6Addr},
+        time::Duration,
+    };
+
+    use super::*;
+
+    #[test]
+    fn buckets_are_drained_and_refilled_per_address() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            burst: 2,
+            requests_per_second: 1,
+        });
+        let now = Instant::now();
+        let addr = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        let other_addr = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2));
+
+        assert!(limiter.try_acquire_at(addr, now));
+        assert!(limiter.try_acquire_at(addr, now));
+        assert!(!limiter.try_acquire_at(addr, now));
+        assert!(limiter.try_acquire_at(other_addr, now));
+
+        assert!(limiter.try_acquire_at(addr, now + Duration::from_secs(1)));
+        assert!(!limiter.try_acquire_at(addr, now + Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn new_addresses_are_refused_while_every_tracked_bucket_is_in_use() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            burst: 1,
+            requests_per_second: 1,
+        });
+        let now = Instant::now();
+
+        for index in 0..MAX_RATE_LIMITED_ADDRESSES as u32 {
+            assert!(limiter.try_acquire_at(IpAddr::V4(Ipv4Addr::from(index)), now));
+        }
+
+        let new_addr = IpAddr::V6(Ipv6Addr::LOCALHOST);
+        assert!(!limiter.try_acquire_at(new_addr, now));
+        assert_eq!(
+            limiter.buckets.lock().unwrap().len(),
+            MAX_RATE_LIMITED_ADDRESSES
+        );
+
+        // Buckets that refilled are forgotten to make room for new addresses
+        assert!(limiter.try_acquire_at(new_addr, now + Duration::from_secs(1)));
+        assert_eq!(limiter.buckets.lock().unwrap().len(), 1);
+    }
+}
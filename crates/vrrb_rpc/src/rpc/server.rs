@@ -6,8 +6,13 @@ use mempool::{LeftRightMempool, MempoolReadHandleFactory};
 use primitives::NodeType;
 use storage::vrrbdb::{VrrbDb, VrrbDbConfig, VrrbDbReadHandle};
 use tokio::sync::mpsc::channel;
+use tower::ServiceBuilder;
 
-use crate::rpc::{api::RpcApiServer, server_impl::RpcServerImpl};
+use crate::rpc::{
+    api::RpcApiServer, server_impl::RpcServerImpl, ProgramRunner, RateLimitConfig, RateLimiter,
+    RemoteAddrLogger, RpcAccessConfig, RpcAccessLayer, RpcSubscriptions,
+    MAX_BUFFERED_MESSAGES_PER_CONNECTION, MAX_SUBSCRIPTIONS_PER_CONNECTION,
+};
 
 #[derive(Debug, Clone)]
 pub struct JsonRpcServerConfig {
@@ -16,6 +21,13 @@ pub struct JsonRpcServerConfig {
     pub mempool_read_handle_factory: MempoolReadHandleFactory,
     pub node_type: NodeType,
     pub events_tx: EventPublisher,
+    /// Node events subscriptions opened over WebSocket are notified of
+    pub subscriptions: RpcSubscriptions,
+    /// Who may call the admin methods of the API
+    pub access: RpcAccessConfig,
+    /// Per-address rate limit of HTTP requests and WebSocket connections,
+    /// disabled if `None`
+    pub rate_limit: Option<RateLimitConfig>,
 }
 
 #[derive(Debug)]
@@ -23,13 +35,24 @@ pub struct JsonRpcServer;
 
 impl JsonRpcServer {
     pub async fn run(config: &JsonRpcServerConfig) -> anyhow::Result<(ServerHandle, SocketAddr)> {
-        let server = ServerBuilder::default().build(config.address).await?;
+        let rate_limiter = config.rate_limit.map(RateLimiter::new);
+
+        let server = ServerBuilder::default()
+            .max_subscriptions_per_connection(MAX_SUBSCRIPTIONS_PER_CONNECTION)
+            .set_message_buffer_capacity(MAX_BUFFERED_MESSAGES_PER_CONNECTION)
+            .set_logger(RemoteAddrLogger)
+            .set_middleware(ServiceBuilder::new().layer(RpcAccessLayer::new(rate_limiter)))
+            .build(config.address)
+            .await?;
 
         let server_impl = RpcServerImpl {
             node_type: config.node_type,
             events_tx: config.events_tx.clone(),
             vrrbdb_read_handle: config.vrrbdb_read_handle.clone(),
             mempool_read_handle_factory: config.mempool_read_handle_factory.clone(),
+            subscriptions: config.subscriptions.clone(),
+            access: config.access.clone(),
+            programs: ProgramRunner::new(),
         };
 
         let addr = server.local_addr()?;
@@ -52,7 +75,7 @@ impl Default for JsonRpcServerConfig {
 
         vrrbdb_config.path = db_path;
 
-        let vrrbdb = VrrbDb::new(vrrbdb_config);
+        let vrrbdb = VrrbDb::new(vrrbdb_config).expect("failed to open a temporary database");
         let vrrbdb_read_handle = vrrbdb.read_handle();
 
         let mempool = LeftRightMempool::default();
@@ -67,6 +90,9 @@ impl Default for JsonRpcServerConfig {
             mempool_read_handle_factory,
             node_type,
             events_tx,
+            subscriptions: RpcSubscriptions::default(),
+            access: RpcAccessConfig::default(),
+            rate_limit: Some(RateLimitConfig::default()),
         }
     }
 }
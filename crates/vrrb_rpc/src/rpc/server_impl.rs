@@ -4,25 +4,42 @@ use async_trait::async_trait;
 use block::block::Block;
 use block::ClaimHash;
 use events::{Event, EventPublisher};
-use jsonrpsee::core::Error as RpseeError;
+use jsonrpsee::{
+    core::Error as RpseeError,
+    server::{PendingSubscriptionSink, SubscriptionCloseResponse},
+    types::{error::INVALID_PARAMS_CODE, ErrorObject},
+};
 use mempool::MempoolReadHandleFactory;
-use primitives::{Address, NodeType, Round};
+use primitives::{Address, Epoch, NodeType, Round};
 use secp256k1::{Message, SecretKey};
 use sha2::{Digest, Sha256};
-use storage::vrrbdb::{Claims, VrrbDbReadHandle};
+use storage::vrrbdb::{
+    BlockHeight, BlockPage, Claims, EpochSummary, HeightOrRoot, MerkleProof, ProgramRecord,
+    TransactionDirection, VrrbDbReadHandle, MAX_BLOCKS_PER_PAGE,
+};
 use telemetry::{debug, error};
 use vrrb_config::QuorumMembershipConfig;
 use vrrb_core::node_health_report::NodeHealthReport;
 use vrrb_core::transactions::{
-    RpcTransactionDigest, Transaction, TransactionDigest, TransactionKind,
+    RpcTransactionDigest, Transaction, TransactionDigest, TransactionKind, BASE_FEE,
 };
-use vrrb_core::{account::Account, serde_helpers::encode_to_binary};
+use vrrb_core::{
+    account::Account,
+    serde_helpers::encode_to_binary,
+    staking::{Delegation, UnbondingEntry},
+};
+use web3_pkg::web3_pkg::Web3Package;
 
 use super::{
-    api::{FullMempoolSnapshot, RpcApiServer},
-    SignOpts,
+    access::authorize_admin_call,
+    api::{FullMempoolSnapshot, RpcApiServer, RpcProgramCall, RpcProgramOutput},
+    programs::ProgramRunner,
+    run_subscription, Notification, RpcAccessConfig, RpcAdminMethod, RpcSubscriptions, SignOpts,
+};
+use crate::rpc::api::{
+    FullStateSnapshot, RpcAccountTransactionPage, RpcHeightOrRoot, RpcTransactionRecord,
+    RpcTxnStatus,
 };
-use crate::rpc::api::{FullStateSnapshot, RpcTransactionRecord};
 
 #[derive(Debug, Clone)]
 pub struct RpcServerImpl {
@@ -30,6 +47,9 @@ pub struct RpcServerImpl {
     pub vrrbdb_read_handle: VrrbDbReadHandle,
     pub mempool_read_handle_factory: MempoolReadHandleFactory,
     pub events_tx: EventPublisher,
+    pub subscriptions: RpcSubscriptions,
+    pub access: RpcAccessConfig,
+    pub programs: ProgramRunner,
 }
 
 #[async_trait]
@@ -136,7 +156,28 @@ impl RpcApiServer for RpcServerImpl {
         Ok(values)
     }
 
+    async fn get_account_transactions(
+        &self,
+        address: Address,
+        direction: Option<TransactionDirection>,
+        from_round: Round,
+        limit: usize,
+        cursor: Option<String>,
+    ) -> Result<RpcAccountTransactionPage, RpseeError> {
+        debug!("Received a getAccountTransactions RPC request");
+
+        let page = self
+            .vrrbdb_read_handle
+            .get_account_transactions(&address, direction, from_round, limit, cursor.as_deref())
+            .map_err(|err| RpseeError::Custom(err.to_string()))?;
+
+        RpcAccountTransactionPage::read(&self.vrrbdb_read_handle, page)
+            .map_err(|err| RpseeError::Custom(err.to_string()))
+    }
+
     async fn create_account(&self, address: Address, account: Account) -> Result<(), RpseeError> {
+        authorize_admin_call(&self.access, RpcAdminMethod::CreateAccount)?;
+
         let account_bytes =
             encode_to_binary(&account).map_err(|err| RpseeError::Custom(err.to_string()))?;
 
@@ -159,6 +200,7 @@ impl RpcApiServer for RpcServerImpl {
 
     async fn update_account(&self, account: Account) -> Result<(), RpseeError> {
         debug!("Received an updateAccount RPC request");
+        authorize_admin_call(&self.access, RpcAdminMethod::UpdateAccount)?;
 
         let account_bytes =
             encode_to_binary(&account).map_err(|err| RpseeError::Custom(err.to_string()))?;
@@ -194,12 +236,70 @@ impl RpcApiServer for RpcServerImpl {
         }
     }
 
+    async fn get_account_at(
+        &self,
+        address: Address,
+        height_or_root: RpcHeightOrRoot,
+    ) -> Result<Account, RpseeError> {
+        debug!("Received getAccountAt RPC Request: {address} at {height_or_root:?}");
+
+        let at = match height_or_root {
+            RpcHeightOrRoot::Height(height) => HeightOrRoot::Height(height),
+            RpcHeightOrRoot::Root(root_hash) => HeightOrRoot::root_from_hex(&root_hash)
+                .map_err(|err| RpseeError::Custom(err.to_string()))?,
+        };
+
+        self.vrrbdb_read_handle
+            .get_account_at(&address, at)
+            .map_err(|err| RpseeError::Custom(err.to_string()))
+    }
+
+    async fn get_account_proof(
+        &self,
+        address: Address,
+        block_height: Option<BlockHeight>,
+    ) -> Result<MerkleProof, RpseeError> {
+        debug!("Received getAccountProof RPC Request: {address}");
+
+        match block_height {
+            Some(block_height) => self
+                .vrrbdb_read_handle
+                .get_account_proof_at(&address, block_height),
+            None => self.vrrbdb_read_handle.get_account_proof(&address),
+        }
+        .map_err(|err| RpseeError::Custom(err.to_string()))
+    }
+
+    async fn get_transaction_proof(
+        &self,
+        transaction_digest: RpcTransactionDigest,
+        block_height: Option<BlockHeight>,
+    ) -> Result<MerkleProof, RpseeError> {
+        debug!("Received getTransactionProof RPC Request: {transaction_digest}");
+
+        let parsed_digest = transaction_digest
+            .parse::<TransactionDigest>()
+            .map_err(|_err| RpseeError::Custom("unable to parse transaction digest".to_string()))?;
+
+        match block_height {
+            Some(block_height) => self
+                .vrrbdb_read_handle
+                .get_transaction_proof_at(&parsed_digest, block_height),
+            None => self
+                .vrrbdb_read_handle
+                .get_transaction_proof(&parsed_digest),
+        }
+        .map_err(|err| RpseeError::Custom(err.to_string()))
+    }
+
     async fn faucet_drip(&self, _address: Address) -> Result<(), RpseeError> {
         todo!()
     }
 
     async fn sign_transaction(&self, sign_opts: SignOpts) -> Result<String, RpseeError> {
-        let payload = format!(
+        authorize_admin_call(&self.access, RpcAdminMethod::SignTransaction)?;
+
+        let mut payload = format!(
             "{},{},{},{},{},{:?},{}",
             &sign_opts.timestamp,
             &sign_opts.sender_address,
@@ -210,6 +310,13 @@ impl RpcApiServer for RpcServerImpl {
             &sign_opts.nonce
         );
 
+        // Same as transfer digests, transfers paying the base fee sign the
+        // payload they signed before fees became configurable
+        let fee = sign_opts.fee.unwrap_or(BASE_FEE);
+        if fee != BASE_FEE {
+            payload.push_str(&format!(",{fee}"));
+        }
+
         let mut hasher = Sha256::new();
         hasher.update(payload.as_bytes());
         let payload_hash = hasher.finalize();
@@ -232,23 +339,118 @@ impl RpcApiServer for RpcServerImpl {
     }
 
     async fn get_blocks(&self) -> Result<Vec<Block>, RpseeError> {
-        error!("getBlocks is not implemented");
-        Ok(Vec::new())
+        debug!("Received getBlocks RPC Request");
+
+        self.vrrbdb_read_handle
+            .get_latest_blocks(MAX_BLOCKS_PER_PAGE)
+            .map_err(|err| RpseeError::Custom(err.to_string()))
     }
 
-    async fn get_program(&self) -> Result<(), RpseeError> {
-        error!("getProgram is not implemented");
-        Ok(())
+    async fn get_block_by_hash(&self, block_hash: String) -> Result<Option<Block>, RpseeError> {
+        debug!("Received getBlockByHash RPC Request: {block_hash}");
+
+        self.vrrbdb_read_handle
+            .get_block_by_hash(&block_hash)
+            .map_err(|err| RpseeError::Custom(err.to_string()))
     }
 
-    async fn call_program(&self) -> Result<(), RpseeError> {
-        error!("callProgram is not implemented");
-        Ok(())
+    async fn get_block_by_height(&self, height: BlockHeight) -> Result<Option<Block>, RpseeError> {
+        debug!("Received getBlockByHeight RPC Request: {height}");
+
+        self.vrrbdb_read_handle
+            .get_block_by_height(height)
+            .map_err(|err| RpseeError::Custom(err.to_string()))
+    }
+
+    async fn get_blocks_range(
+        &self,
+        from_height: BlockHeight,
+        limit: usize,
+    ) -> Result<BlockPage, RpseeError> {
+        debug!("Received getBlocksRange RPC Request: from {from_height}, limit {limit}");
+
+        self.vrrbdb_read_handle
+            .get_blocks_range(from_height, limit)
+            .map_err(|err| RpseeError::Custom(err.to_string()))
+    }
+
+    async fn deploy_program(
+        &self,
+        package: Web3Package,
+        wasm: String,
+    ) -> Result<String, RpseeError> {
+        debug!("Received a deployProgram RPC request");
+        authorize_admin_call(&self.access, RpcAdminMethod::DeployProgram)?;
+
+        let wasm = hex::decode(wasm.trim_start_matches("0x"))
+            .map_err(|err| RpseeError::Custom(format!("unable to decode wasm module: {err}")))?;
+
+        let program = ProgramRecord::new(package, wasm);
+
+        let content_id = program
+            .verify()
+            .map_err(|err| RpseeError::Custom(err.to_string()))?;
+
+        let registered = self
+            .vrrbdb_read_handle
+            .get_program(&content_id)
+            .map_err(|err| RpseeError::Custom(err.to_string()))?;
+
+        if registered.is_some() {
+            return Err(RpseeError::Custom(format!(
+                "program {content_id} is already registered"
+            )));
+        }
+
+        let program_bytes =
+            encode_to_binary(&program).map_err(|err| RpseeError::Custom(err.to_string()))?;
+
+        let event = Event::ProgramDeployRequested(program_bytes);
+
+        self.events_tx.send(event.into()).await.map_err(|err| {
+            error!("could not deploy program: {err}");
+            RpseeError::Custom(err.to_string())
+        })?;
+
+        Ok(content_id)
+    }
+
+    async fn get_program(&self, content_id: String) -> Result<Option<Web3Package>, RpseeError> {
+        debug!("Received getProgram RPC Request: {content_id}");
+
+        let program = self
+            .vrrbdb_read_handle
+            .get_program(&content_id)
+            .map_err(|err| RpseeError::Custom(err.to_string()))?;
+
+        Ok(program.map(|program| program.package))
+    }
+
+    async fn call_program(
+        &self,
+        content_id: String,
+        call: RpcProgramCall,
+    ) -> Result<RpcProgramOutput, RpseeError> {
+        debug!("Received callProgram RPC Request: {content_id}");
+
+        let program = self
+            .vrrbdb_read_handle
+            .get_program(&content_id)
+            .map_err(|err| RpseeError::Custom(err.to_string()))?
+            .ok_or_else(|| RpseeError::Custom(format!("unable to find program {content_id}")))?;
+
+        self.programs
+            .run(program, call)
+            .await
+            .map_err(|err| RpseeError::Custom(err.to_string()))
     }
 
-    async fn get_transaction_count(&self, _account: Address) -> Result<usize, RpseeError> {
-        error!("getTransactionCount is not implemented");
-        Ok(0)
+    async fn get_transaction_count(&self, account: Address) -> Result<usize, RpseeError> {
+        debug!("Received a getTransactionCount RPC request");
+
+        self.vrrbdb_read_handle
+            .count_account_transactions(&account, None)
+            .map_err(|err| RpseeError::Custom(err.to_string()))
     }
 
     async fn get_node_health(&self) -> Result<NodeHealthReport, RpseeError> {
@@ -297,6 +499,41 @@ impl RpcApiServer for RpcServerImpl {
         Ok(claims)
     }
 
+    async fn get_unbonding_entries(
+        &self,
+        address: Address,
+    ) -> Result<Vec<UnbondingEntry>, RpseeError> {
+        debug!("Received getUnbondingEntries RPC Request: {address}");
+
+        self.vrrbdb_read_handle
+            .get_unbonding_entries(&address)
+            .map_err(|err| RpseeError::Custom(err.to_string()))
+    }
+
+    async fn get_delegators(&self, address: Address) -> Result<Vec<Delegation>, RpseeError> {
+        debug!("Received getDelegators RPC Request: {address}");
+
+        self.vrrbdb_read_handle
+            .get_delegations(&address)
+            .map_err(|err| RpseeError::Custom(err.to_string()))
+    }
+
+    async fn get_epoch_summary(&self, epoch: Epoch) -> Result<Option<EpochSummary>, RpseeError> {
+        debug!("Received getEpochSummary RPC Request: {epoch}");
+
+        self.vrrbdb_read_handle
+            .get_epoch_summary(epoch)
+            .map_err(|err| RpseeError::Custom(err.to_string()))
+    }
+
+    async fn get_latest_epoch_summary(&self) -> Result<Option<EpochSummary>, RpseeError> {
+        debug!("Received getLatestEpochSummary RPC Request");
+
+        self.vrrbdb_read_handle
+            .latest_epoch_summary()
+            .map_err(|err| RpseeError::Custom(err.to_string()))
+    }
+
     async fn get_membership_config(&self) -> Result<QuorumMembershipConfig, RpseeError> {
         error!("getMembershipConfig is not implemented");
         Ok(Default::default())
@@ -306,4 +543,129 @@ impl RpcApiServer for RpcServerImpl {
         error!("getLastBlock is not implemented");
         Ok(None)
     }
+
+    async fn subscribe_new_blocks(
+        &self,
+        pending: PendingSubscriptionSink,
+    ) -> SubscriptionCloseResponse {
+        let events_rx = self.subscriptions.subscribe();
+        let vrrbdb_read_handle = self.vrrbdb_read_handle.clone();
+
+        run_subscription(pending, events_rx, None, move |event| match event {
+            Some(Event::BlockAppended(block_hash)) => {
+                match vrrbdb_read_handle.get_block_by_hash(&block_hash) {
+                    Ok(Some(block)) if block.is_convergence() => Notification::Send(block),
+                    _ => Notification::Skip,
+                }
+            }
+            Some(_) => Notification::Skip,
+            None => Notification::Lagged,
+        })
+        .await
+    }
+
+    async fn subscribe_txn_status(
+        &self,
+        pending: PendingSubscriptionSink,
+        transaction_digest: RpcTransactionDigest,
+    ) -> SubscriptionCloseResponse {
+        let digest = match transaction_digest.parse::<TransactionDigest>() {
+            Ok(digest) => digest,
+            Err(_) => {
+                pending
+                    .reject(ErrorObject::owned(
+                        INVALID_PARAMS_CODE,
+                        "unable to parse transaction digest",
+                        None::<()>,
+                    ))
+                    .await;
+                return SubscriptionCloseResponse::None;
+            }
+        };
+
+        let events_rx = self.subscriptions.subscribe();
+        let pending_status = self
+            .mempool_read_handle_factory
+            .get(&digest)
+            .map(|_| RpcTxnStatus::Pending);
+
+        let vrrbdb_read_handle = self.vrrbdb_read_handle.clone();
+
+        run_subscription(
+            pending,
+            events_rx,
+            pending_status,
+            move |event| match event {
+                Some(Event::TxnAddedToMempool(txn_id)) if txn_id == digest => {
+                    Notification::Send(RpcTxnStatus::Pending)
+                }
+                Some(Event::TxnValidated(txn)) if txn.id() == digest => {
+                    Notification::Send(RpcTxnStatus::Validated)
+                }
+                Some(Event::BlockAppended(block_hash)) => {
+                    match vrrbdb_read_handle.get_block_by_hash(&block_hash) {
+                        Ok(Some(Block::Convergence { block }))
+                            if block.txn_id_set().contains(&&digest) =>
+                        {
+                            Notification::Last(RpcTxnStatus::Confirmed { block_hash })
+                        }
+                        _ => Notification::Skip,
+                    }
+                }
+                Some(_) => Notification::Skip,
+                None => Notification::Lagged,
+            },
+        )
+        .await
+    }
+
+    async fn subscribe_mempool(
+        &self,
+        pending: PendingSubscriptionSink,
+    ) -> SubscriptionCloseResponse {
+        let events_rx = self.subscriptions.subscribe();
+        let mempool_read_handle_factory = self.mempool_read_handle_factory.clone();
+
+        run_subscription(pending, events_rx, None, move |event| match event {
+            Some(Event::TxnAddedToMempool(digest)) => {
+                match mempool_read_handle_factory.get(&digest) {
+                    Some(record) => Notification::Send(RpcTransactionRecord::from(record.txn)),
+                    None => Notification::Skip,
+                }
+            }
+            Some(_) => Notification::Skip,
+            None => Notification::Lagged,
+        })
+        .await
+    }
+
+    async fn subscribe_account(
+        &self,
+        pending: PendingSubscriptionSink,
+        address: Address,
+    ) -> SubscriptionCloseResponse {
+        let events_rx = self.subscriptions.subscribe();
+        let vrrbdb_read_handle = self.vrrbdb_read_handle.clone();
+        let mut current = vrrbdb_read_handle.get_account_by_address(&address).ok();
+
+        run_subscription(
+            pending,
+            events_rx,
+            current.clone(),
+            move |event| match event {
+                // Missed events are made up for by reading the account again
+                Some(Event::BlockAppended(_)) | None => {
+                    match vrrbdb_read_handle.get_account_by_address(&address) {
+                        Ok(account) if current.as_ref() != Some(&account) => {
+                            current = Some(account.clone());
+                            Notification::Send(account)
+                        }
+                        _ => Notification::Skip,
+                    }
+                }
+                Some(_) => Notification::Skip,
+            },
+        )
+        .await
+    }
 }
@@ -0,0 +1,166 @@
+use events::Event;
+use jsonrpsee::{
+    server::{
+        PendingSubscriptionSink, SubscriptionCloseResponse, SubscriptionMessage, SubscriptionSink,
+        TrySendError,
+    },
+    types::ErrorObject,
+};
+use serde::Serialize;
+use telemetry::{debug, error};
+use tokio::sync::broadcast::{self, error::RecvError, Receiver, Sender};
+
+/// Number of events buffered for each subscription before its subscriber is
+/// considered too slow to keep up
+pub const DEFAULT_SUBSCRIPTION_BUFFER: usize = 256;
+
+/// Maximum number of subscriptions a single connection can keep open
+pub const MAX_SUBSCRIPTIONS_PER_CONNECTION: u32 = 64;
+
+/// Maximum number of messages queued for a single connection before the
+/// server stops answering it, and the subscriptions it opened are closed,
+/// until its client reads them
+pub const MAX_BUFFERED_MESSAGES_PER_CONNECTION: u32 = 1024;
+
+/// Error code subscriptions are closed with when their subscriber falls
+/// behind by more than the subscription buffer, or leaves the messages of its
+/// connection unread
+pub const SUBSCRIBER_LAGGED_CODE: i32 = -32001;
+
+/// Fans node events out to the open JSON-RPC subscriptions.
+///
+/// Every subscription reads events from its own bounded buffer, so a slow
+/// subscriber only ever holds back its own notifications.
+#[derive(Debug, Clone)]
+pub struct RpcSubscriptions {
+    events_tx: Sender<Event>,
+}
+
+impl RpcSubscriptions {
+    pub fn new(buffer: usize) -> Self {
+        let (events_tx, _) = broadcast::channel(buffer.max(1));
+
+        Self { events_tx }
+    }
+
+    /// Forwards an event to the open subscriptions. Events subscribers cannot
+    /// be notified of are ignored.
+    pub fn publish(&self, event: &Event) {
+        if matches!(
+            event,
+            Event::BlockAppended(_) | Event::TxnAddedToMempool(_) | Event::TxnValidated(_)
+        ) {
+            // Sending only fails when there are no open subscriptions
+            let _ = self.events_tx.send(event.clone());
+        }
+    }
+
+    pub(crate) fn subscribe(&self) -> Receiver<Event> {
+        self.events_tx.subscribe()
+    }
+}
+
+impl Default for RpcSubscriptions {
+    fn default() -> Self {
+        Self::new(DEFAULT_SUBSCRIPTION_BUFFER)
+    }
+}
+
+/// What a subscription does about an event, or about the events it missed
+/// when it is handed `None`
+pub(crate) enum Notification<T> {
+    /// Nothing to notify the subscriber of
+    Skip,
+    /// Notify the subscriber and keep the subscription open
+    Send(T),
+    /// Notify the subscriber and end the subscription
+    Last(T),
+    /// End the subscription, the events the subscriber missed cannot be made
+    /// up for
+    Lagged,
+}
+
+/// Accepts a subscription and feeds it with `first`, then with the
+/// notifications `notify` derives from node events, until either side ends it.
+pub(crate) async fn run_subscription<T, F>(
+    pending: PendingSubscriptionSink,
+    mut events_rx: Receiver<Event>,
+    first: Option<T>,
+    mut notify: F,
+) -> SubscriptionCloseResponse
+where
+    T: Serialize,
+    F: FnMut(Option<Event>) -> Notification<T>,
+{
+    let mut sink = match pending.accept().await {
+        Ok(sink) => sink,
+        Err(_) => return SubscriptionCloseResponse::None,
+    };
+
+    if let Some(item) = first {
+        if let Err(close) = send_notification(&mut sink, &item) {
+            return close;
+        }
+    }
+
+    loop {
+        let (event, skipped) = tokio::select! {
+            _ = sink.closed() => return SubscriptionCloseResponse::None,
+            event = events_rx.recv() => match event {
+                Ok(event) => (Some(event), 0),
+                Err(RecvError::Lagged(skipped)) => (None, skipped),
+                Err(RecvError::Closed) => return SubscriptionCloseResponse::None,
+            },
+        };
+
+        let (item, last) = match notify(event) {
+            Notification::Skip => continue,
+            Notification::Send(item) => (item, false),
+            Notification::Last(item) => (item, true),
+            Notification::Lagged => {
+                debug!("closing subscription that fell {skipped} events behind");
+                return lagged(format!("subscriber fell {skipped} events behind"));
+            }
+        };
+
+        if let Err(close) = send_notification(&mut sink, &item) {
+            return close;
+        }
+
+        if last {
+            return SubscriptionCloseResponse::None;
+        }
+    }
+}
+
+/// Queues a notification without waiting for the connection of the
+/// subscriber to make room for it
+fn send_notification<T: Serialize>(
+    sink: &mut SubscriptionSink,
+    item: &T,
+) -> Result<(), SubscriptionCloseResponse> {
+    let message = SubscriptionMessage::from_json(item).map_err(|err| {
+        error!("failed to serialize subscription notification: {err}");
+        SubscriptionCloseResponse::None
+    })?;
+
+    match sink.try_send(message) {
+        Ok(()) => Ok(()),
+        Err(TrySendError::Closed(_)) => Err(SubscriptionCloseResponse::None),
+        Err(TrySendError::Full(_)) => {
+            debug!("closing subscription whose connection has too many unread messages");
+            Err(lagged(
+                "subscriber left too many messages unread".to_string(),
+            ))
+        }
+    }
+}
+
+fn lagged(message: String) -> SubscriptionCloseResponse {
+    let error = ErrorObject::owned(SUBSCRIBER_LAGGED_CODE, message, None::<()>);
+
+    match SubscriptionMessage::from_json(&error) {
+        Ok(message) => SubscriptionCloseResponse::NotifErr(message),
+        Err(_) => SubscriptionCloseResponse::None,
+    }
+}
@@ -15,6 +15,7 @@ async fn server_starts_and_stops() {
         api_version: "1.0".into(),
         server_timeout: None,
         tls_config: None,
+        ..Default::default()
     };
 
     let api = HttpApiServer::new(config).unwrap();
@@ -44,3 +45,48 @@ async fn server_starts_and_stops() {
     ctrl_tx.send(Event::Stop).unwrap();
     server_handle.await.unwrap();
 }
+
+#[tokio::test]
+async fn server_publishes_its_openapi_document() {
+    let api = HttpApiServer::new(HttpApiServerConfig::default()).unwrap();
+
+    let (ctrl_tx, mut ctrl_rx) = channel(1);
+
+    let addr = api.address().unwrap();
+
+    let server_handle = tokio::spawn(async move {
+        api.start(&mut ctrl_rx).await.unwrap();
+    });
+
+    let response = Client::new()
+        .request(
+            Request::builder()
+                .uri(format!("http://{addr}/openapi.json"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+    let document: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(document["openapi"], OPENAPI_VERSION);
+    for path in [
+        "/accounts/{address}",
+        "/transactions/{digest}",
+        "/blocks",
+        "/claims",
+        "/mempool",
+    ] {
+        assert!(
+            document["paths"][path].is_object(),
+            "{path} is not documented"
+        );
+    }
+
+    ctrl_tx.send(Event::Stop).unwrap();
+    server_handle.await.unwrap();
+}
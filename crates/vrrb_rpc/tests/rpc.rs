@@ -1,16 +1,24 @@
 use std::{collections::HashMap, net::SocketAddr};
 
-use events::{EventMessage, DEFAULT_BUFFER};
+use events::{Event, EventMessage, DEFAULT_BUFFER};
+use mempool::LeftRightMempool;
 use primitives::{generate_mock_account_keypair, Address};
 use secp256k1::Message;
-use storage::storage_utils::remove_vrrb_data_dir;
+use storage::{
+    storage_utils::remove_vrrb_data_dir,
+    vrrbdb::{ProgramRecord, VrrbDb, VrrbDbConfig},
+};
 use tokio::sync::mpsc::channel;
-use vrrb_core::transactions::{generate_transfer_digest_vec, Token, Transaction, TransactionKind};
+use vrrb_core::{
+    account::Account,
+    transactions::{generate_transfer_digest_vec, Token, Transaction, TransactionKind, BASE_FEE},
+};
 use vrrb_rpc::rpc::{
-    api::{RpcApiClient, RpcTransactionRecord},
-    client::create_client,
+    api::{RpcApiClient, RpcProgramCall, RpcTransactionKind, RpcTransactionRecord},
+    client::{create_client, create_http_client, subscribe_mempool},
     *,
 };
+use web3_pkg::web3_pkg::{Web3ContentId, Web3Package, Web3PackageArchitecture, Web3PackageObject};
 
 mod common;
 
@@ -78,12 +86,14 @@ async fn server_can_publish_transactions_to_be_created() {
 
     let mock_record = RpcTransactionRecord {
         id: mock_digest,
+        kind: RpcTransactionKind::Transfer,
         timestamp: 0,
         sender_address: address.clone(),
         sender_public_key: public_key,
         receiver_address: recv_address.clone(),
         token: Token::default(),
         amount: 10,
+        fee: BASE_FEE,
         signature: signature.to_string().clone(),
         validators: HashMap::new(),
         nonce: 0,
@@ -96,3 +106,204 @@ async fn server_can_publish_transactions_to_be_created() {
 
     handle.stop().expect("Unable to stop server");
 }
+
+#[tokio::test]
+async fn subscribers_are_notified_of_transactions_added_to_the_mempool() {
+    remove_vrrb_data_dir();
+
+    let mut mempool = LeftRightMempool::default();
+    let subscriptions = RpcSubscriptions::default();
+
+    let json_rpc_server_config = JsonRpcServerConfig {
+        address: "127.0.0.1:0".parse().unwrap(),
+        mempool_read_handle_factory: mempool.factory(),
+        subscriptions: subscriptions.clone(),
+        ..Default::default()
+    };
+
+    let (handle, rpc_server_address) = JsonRpcServer::run(&json_rpc_server_config).await.unwrap();
+
+    let client = create_client(rpc_server_address).await.unwrap();
+    let mut subscription = subscribe_mempool(&client).await.unwrap();
+
+    let (secret_key, public_key) = generate_mock_account_keypair();
+    let (_, recv_public_key) = generate_mock_account_keypair();
+
+    let address = Address::new(public_key);
+    let recv_address = Address::new(recv_public_key);
+
+    let digest = generate_transfer_digest_vec(
+        0,
+        address.to_string(),
+        public_key,
+        recv_address.to_string(),
+        Token::default(),
+        10,
+        0,
+    );
+
+    type H = secp256k1::hashes::sha256::Hash;
+    let msg = Message::from_hashed_data::<H>(&digest);
+    let signature = secret_key.sign_ecdsa(msg);
+
+    let txn = TransactionKind::transfer_builder()
+        .timestamp(0)
+        .sender_address(address)
+        .sender_public_key(public_key)
+        .receiver_address(recv_address)
+        .amount(10)
+        .signature(signature)
+        .nonce(0)
+        .build_kind()
+        .expect("failed to build transfer transaction");
+
+    mempool.insert(txn.clone()).unwrap();
+
+    // Events nobody can be notified of are not forwarded to subscribers
+    subscriptions.publish(&Event::NoOp);
+    subscriptions.publish(&Event::TxnAddedToMempool(txn.id()));
+
+    let record = subscription.next().await.unwrap().unwrap();
+
+    assert_eq!(record.id, txn.id().digest_string());
+    assert_eq!(record.amount, 10);
+
+    handle.stop().expect("Unable to stop server");
+}
+
+const TEST_TX_ID: &str = "81b067ac-8693-483a-8354-d7de15ab6f2c";
+
+#[tokio::test]
+async fn registered_programs_can_be_called() {
+    let db_path = std::env::temp_dir().join(vrrb_core::helpers::generate_random_string());
+    let vrrbdb = VrrbDb::new(VrrbDbConfig::default().with_path(db_path)).unwrap();
+
+    let wasm = std::fs::read("../wasm_runtime/test_data/wasm_test.wasm").unwrap();
+    let package = Web3Package {
+        pkg_objects: vec![Web3PackageObject {
+            object_arch: Web3PackageArchitecture::Wasm32Wasi,
+            object_cid: Web3ContentId::of_raw_block(&wasm),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let content_id = vrrbdb
+        .insert_program(&ProgramRecord::new(package, wasm))
+        .unwrap();
+
+    let json_rpc_server_config = JsonRpcServerConfig {
+        address: "127.0.0.1:0".parse().unwrap(),
+        vrrbdb_read_handle: vrrbdb.read_handle(),
+        ..Default::default()
+    };
+
+    let (handle, rpc_server_address) = JsonRpcServer::run(&json_rpc_server_config).await.unwrap();
+
+    let client = create_client(rpc_server_address).await.unwrap();
+
+    let package = client.get_program(content_id.clone()).await.unwrap();
+    assert!(package.unwrap().wasm_object().is_some());
+    assert!(client
+        .get_program("QmMissing".to_string())
+        .await
+        .unwrap()
+        .is_none());
+
+    let input =
+        std::fs::read_to_string("../wasm_runtime/test_data/wasm_test_oneline.json").unwrap();
+    let call = RpcProgramCall {
+        input,
+        gas_limit: 1_000_000,
+        ..Default::default()
+    };
+
+    let output = client
+        .call_program(content_id.clone(), call.clone())
+        .await
+        .unwrap();
+    assert!(output.stdout.contains(TEST_TX_ID));
+    assert!(output.gas_used > 0);
+
+    // The program runs out of gas long before it is done
+    let call = RpcProgramCall {
+        gas_limit: 10,
+        ..call
+    };
+    assert!(client.call_program(content_id, call).await.is_err());
+
+    handle.stop().expect("Unable to stop server");
+}
+
+#[tokio::test]
+async fn admin_calls_require_a_bearer_token_over_http() {
+    let (events_tx, mut events_rx) = channel::<EventMessage>(DEFAULT_BUFFER);
+
+    let json_rpc_server_config = JsonRpcServerConfig {
+        address: "127.0.0.1:0".parse().unwrap(),
+        events_tx,
+        access: RpcAccessConfig::default().with_bearer_token("secret"),
+        ..Default::default()
+    };
+
+    let (handle, rpc_server_address) = JsonRpcServer::run(&json_rpc_server_config).await.unwrap();
+
+    let (_, public_key) = generate_mock_account_keypair();
+    let address = Address::new(public_key);
+    let account = Account::new(address.clone());
+
+    let anonymous_client = create_http_client(rpc_server_address, None).await.unwrap();
+    assert!(anonymous_client
+        .create_account(address.clone(), account.clone())
+        .await
+        .is_err());
+
+    let ws_client = create_client(rpc_server_address).await.unwrap();
+    assert!(ws_client
+        .create_account(address.clone(), account.clone())
+        .await
+        .is_err());
+
+    let client = create_http_client(rpc_server_address, Some("secret"))
+        .await
+        .unwrap();
+    client
+        .create_account(address.clone(), account)
+        .await
+        .unwrap();
+
+    let event: Event = events_rx.recv().await.unwrap().into();
+    assert!(matches!(event, Event::CreateAccountRequested((requested, _)) if requested == address));
+
+    // Localhost can sign transactions without a token
+    let sign_opts = SignOpts {
+        private_key: generate_mock_account_keypair()
+            .0
+            .display_secret()
+            .to_string(),
+        ..Default::default()
+    };
+    assert!(anonymous_client.sign_transaction(sign_opts).await.is_ok());
+
+    handle.stop().expect("Unable to stop server");
+}
+
+#[tokio::test]
+async fn clients_are_rate_limited_by_address() {
+    let json_rpc_server_config = JsonRpcServerConfig {
+        address: "127.0.0.1:0".parse().unwrap(),
+        rate_limit: Some(RateLimitConfig {
+            burst: 2,
+            requests_per_second: 0,
+        }),
+        ..Default::default()
+    };
+
+    let (handle, rpc_server_address) = JsonRpcServer::run(&json_rpc_server_config).await.unwrap();
+
+    let client = create_http_client(rpc_server_address, None).await.unwrap();
+    assert!(client.get_node_type().await.is_ok());
+    assert!(client.get_node_type().await.is_ok());
+    assert!(client.get_node_type().await.is_err());
+
+    handle.stop().expect("Unable to stop server");
+}
@@ -10,10 +10,13 @@ use secp256k1::{ecdsa::Signature, Message, PublicKey, Secp256k1, SecretKey};
 use serde::{Deserialize, Serialize};
 use telemetry::error;
 use thiserror::Error;
+use vrrb_config::DEFAULT_REPLACEMENT_FEE_BUMP_PERCENT;
 use vrrb_core::account::Account;
-use vrrb_core::transactions::{Transaction, TransactionKind, Token, RpcTransactionDigest};
+use vrrb_core::transactions::{
+    RpcTransactionDigest, Token, Transaction, TransactionKind, TransferBuilder,
+};
 use vrrb_rpc::rpc::{
-    api::{RpcApiClient, RpcTransactionRecord},
+    api::{RpcApiClient, RpcTransactionKind, RpcTransactionRecord},
     client::create_client,
 };
 
@@ -44,6 +47,7 @@ pub struct Wallet {
     // pub addresses: HashMap<AddressAlias, Address>,
     // pub accounts: HashMap<Address, Account>,
     pub nonce: u128,
+    min_fee_bump_percent: u64,
 }
 
 #[derive(Debug)]
@@ -51,6 +55,10 @@ pub struct WalletConfig {
     pub rpc_server_address: SocketAddr,
     pub secret_key: SecretKey,
     pub public_key: PublicKey,
+    /// Smallest fee bump, in percent, the node accepts to replace a pending
+    /// transfer. Should match the `replacement_fee_bump_percent` of its
+    /// mempool.
+    pub min_fee_bump_percent: u64,
     // TODO: revise this when we reimplement hierarchical deterministic wallets
     // pub accounts: HashMap<Address, Account>,
     // pub addresses: HashMap<AddressAlias, Address>,
@@ -82,6 +90,7 @@ impl Default for WalletConfig {
             rpc_server_address,
             secret_key,
             public_key,
+            min_fee_bump_percent: DEFAULT_REPLACEMENT_FEE_BUMP_PERCENT,
             // accounts,
             // addresses,
         }
@@ -119,6 +128,7 @@ impl Wallet {
             // addresses,
             // accounts,
             nonce: 0,
+            min_fee_bump_percent: config.min_fee_bump_percent,
         };
 
         Ok(wallet)
@@ -173,6 +183,62 @@ impl Wallet {
             .validators(HashMap::new())
             .nonce(self.nonce);
 
+        self.sign_and_submit(transfer_builder).await
+    }
+
+    /// Resubmits a pending transfer sent by this wallet with its fee raised by
+    /// `bump_percent`, so it replaces the original transfer in the mempool.
+    /// Bumps smaller than the configured `min_fee_bump_percent` are raised
+    /// to it. Only
+    /// transfers can be bumped. Returns the digest of the replacement transfer.
+    pub async fn bump_fee(
+        &mut self,
+        transaction_digest: RpcTransactionDigest,
+        bump_percent: u64,
+    ) -> Result<RpcTransactionDigest, WalletError> {
+        let pending = self
+            .get_mempool()
+            .await?
+            .into_iter()
+            .find(|record| record.id == transaction_digest)
+            .ok_or_else(|| {
+                WalletError::Custom(format!("transaction {transaction_digest} is not pending"))
+            })?;
+
+        if pending.sender_address != self.address {
+            return Err(WalletError::Custom(format!(
+                "transaction {transaction_digest} was not sent by this wallet"
+            )));
+        }
+
+        if pending.kind != RpcTransactionKind::Transfer {
+            return Err(WalletError::Custom(format!(
+                "transaction {transaction_digest} is not a transfer and can't have its fee bumped"
+            )));
+        }
+
+        let bump_percent = bump_percent.max(self.min_fee_bump_percent);
+        let bump = pending.fee.saturating_mul(bump_percent as u128) / 100;
+        let fee = pending.fee.saturating_add(bump.max(1));
+
+        let transfer_builder = TransactionKind::transfer_builder()
+            .timestamp(pending.timestamp)
+            .sender_address(pending.sender_address)
+            .sender_public_key(self.public_key)
+            .receiver_address(pending.receiver_address)
+            .token(pending.token)
+            .amount(pending.amount)
+            .fee(fee)
+            .validators(HashMap::new())
+            .nonce(pending.nonce);
+
+        self.sign_and_submit(transfer_builder).await
+    }
+
+    async fn sign_and_submit(
+        &mut self,
+        transfer_builder: TransferBuilder,
+    ) -> Result<RpcTransactionDigest, WalletError> {
         let signature = self.sign_transaction(transfer_builder.build_payload().as_bytes());
 
         let transfer = transfer_builder.signature(signature).build_kind()
@@ -1,11 +1,18 @@
-use std::net::SocketAddr;
+use std::{collections::HashMap, net::SocketAddr};
 
+use events::{Event, EventMessage, DEFAULT_BUFFER};
+use jsonrpsee::server::ServerHandle;
+use mempool::LeftRightMempool;
 use primitives::Address;
-use secp256k1::{generate_keypair, PublicKey, Secp256k1, SecretKey};
+use secp256k1::{generate_keypair, Message, PublicKey, Secp256k1, SecretKey};
 use serial_test::serial;
 use storage::storage_utils::remove_vrrb_data_dir;
-use tokio::sync::mpsc::channel;
-use vrrb_core::transactions::Token;
+use tokio::sync::mpsc::{channel, Receiver};
+use vrrb_config::MempoolConfig;
+use vrrb_core::{
+    staking::{Stake, StakeUpdate},
+    transactions::{StakeTransaction, Token, Transaction, TransactionKind, BASE_FEE},
+};
 use vrrb_rpc::rpc::{JsonRpcServer, JsonRpcServerConfig};
 use wallet::v2::{Wallet, WalletConfig};
 
@@ -119,3 +126,185 @@ pub async fn wallet_sends_txn_to_rpc_server() {
 //
 //     wallet.create_account(1, public_key).await.unwrap();
 // }
+
+type H = secp256k1::hashes::sha256::Hash;
+
+/// Starts a JSON-RPC server serving `mempool` and a wallet connected to it,
+/// returning the receiver of the events the server publishes
+async fn wallet_with_mempool(
+    mempool: &LeftRightMempool,
+) -> (ServerHandle, Wallet, Receiver<EventMessage>) {
+    remove_vrrb_data_dir();
+
+    let (events_tx, events_rx) = channel(DEFAULT_BUFFER);
+
+    let json_rpc_server_config = JsonRpcServerConfig {
+        address: "127.0.0.1:0".parse().unwrap(),
+        mempool_read_handle_factory: mempool.factory(),
+        events_tx,
+        ..Default::default()
+    };
+
+    let (handle, socket_addr) = JsonRpcServer::run(&json_rpc_server_config).await.unwrap();
+
+    let wallet_config = WalletConfig {
+        rpc_server_address: socket_addr,
+        min_fee_bump_percent: mempool.config().replacement_fee_bump_percent,
+        ..Default::default()
+    };
+
+    let wallet = Wallet::new(wallet_config).await.unwrap();
+
+    (handle, wallet, events_rx)
+}
+
+fn pending_transfer(wallet: &Wallet, nonce: u128, fee: u128) -> TransactionKind {
+    let secret_key = WalletConfig::default().secret_key;
+    let recv_sk = SecretKey::from_hashed_data::<H>(b"recv_vrrb");
+    let recv_pk = PublicKey::from_secret_key(&Secp256k1::new(), &recv_sk);
+
+    TransactionKind::transfer_builder()
+        .timestamp(0)
+        .sender_address(wallet.address.clone())
+        .sender_public_key(wallet.public_key)
+        .receiver_address(Address::new(recv_pk))
+        .token(Token::default())
+        .amount(10)
+        .fee(fee)
+        .validators(HashMap::new())
+        .nonce(nonce)
+        .signature(secret_key.sign_ecdsa(Message::from_hashed_data::<H>(b"vrrb")))
+        .build_kind()
+        .unwrap()
+}
+
+async fn submitted_txn(events_rx: &mut Receiver<EventMessage>) -> TransactionKind {
+    let event: Event = events_rx.recv().await.unwrap().into();
+
+    match event {
+        Event::NewTxnCreated(txn) => txn,
+        event => panic!("unexpected event {event:?}"),
+    }
+}
+
+#[tokio::test]
+#[serial]
+pub async fn bump_fee_replaces_pending_transfers() {
+    let mut mempool = LeftRightMempool::default();
+    let (handle, mut wallet, mut events_rx) = wallet_with_mempool(&mempool).await;
+
+    let pending = pending_transfer(&wallet, 5, BASE_FEE);
+    mempool.insert(pending.clone()).unwrap();
+
+    let digest = wallet
+        .bump_fee(pending.id().digest_string(), 50)
+        .await
+        .unwrap();
+
+    let replacement = submitted_txn(&mut events_rx).await;
+
+    assert_eq!(replacement.id().digest_string(), digest);
+    assert_ne!(replacement.id(), pending.id());
+    assert_eq!(replacement.fee(), BASE_FEE + BASE_FEE / 2);
+    assert_eq!(replacement.receiver_address(), pending.receiver_address());
+    assert_eq!(replacement.amount(), pending.amount());
+
+    mempool.insert(replacement.clone()).unwrap();
+
+    assert_eq!(mempool.size(), 1);
+    assert!(mempool.get_txn(&pending.id()).is_none());
+    assert!(mempool.get_txn(&replacement.id()).is_some());
+
+    handle.stop().unwrap();
+}
+
+#[tokio::test]
+#[serial]
+pub async fn bump_fee_reuses_the_nonce_of_the_pending_transfer() {
+    let mut mempool = LeftRightMempool::default();
+    let (handle, mut wallet, mut events_rx) = wallet_with_mempool(&mempool).await;
+
+    let first = pending_transfer(&wallet, 1, BASE_FEE);
+    let second = pending_transfer(&wallet, 2, BASE_FEE);
+    mempool.insert(first.clone()).unwrap();
+    mempool.insert(second.clone()).unwrap();
+
+    wallet
+        .bump_fee(second.id().digest_string(), 20)
+        .await
+        .unwrap();
+
+    let replacement = submitted_txn(&mut events_rx).await;
+    assert_eq!(replacement.nonce(), second.nonce());
+
+    mempool.insert(replacement.clone()).unwrap();
+
+    assert_eq!(mempool.size(), 2);
+    assert!(mempool.get_txn(&first.id()).is_some());
+    assert!(mempool.get_txn(&second.id()).is_none());
+
+    handle.stop().unwrap();
+}
+
+#[tokio::test]
+#[serial]
+pub async fn bump_fee_raises_small_bumps_to_the_minimum() {
+    let mut mempool = LeftRightMempool::with_config(MempoolConfig {
+        replacement_fee_bump_percent: 25,
+        ..Default::default()
+    });
+    let (handle, mut wallet, mut events_rx) = wallet_with_mempool(&mempool).await;
+
+    let pending = pending_transfer(&wallet, 1, BASE_FEE);
+    mempool.insert(pending.clone()).unwrap();
+
+    wallet
+        .bump_fee(pending.id().digest_string(), 1)
+        .await
+        .unwrap();
+
+    let replacement = submitted_txn(&mut events_rx).await;
+    let min_fee = BASE_FEE + BASE_FEE / 4;
+
+    assert_eq!(replacement.fee(), min_fee);
+    assert_eq!(mempool.min_replacement_fee(pending.fee()), min_fee);
+    assert!(mempool.insert(replacement).is_ok());
+
+    handle.stop().unwrap();
+}
+
+#[tokio::test]
+#[serial]
+pub async fn bump_fee_rejects_pending_stakes() {
+    let mut mempool = LeftRightMempool::default();
+    let (handle, mut wallet, mut events_rx) = wallet_with_mempool(&mempool).await;
+
+    let secret_key = WalletConfig::default().secret_key;
+    let stake = Stake::new(
+        StakeUpdate::Add(Stake::MIN),
+        secret_key,
+        wallet.public_key,
+        wallet.address.clone(),
+        None,
+    )
+    .unwrap();
+
+    let pending = StakeTransaction::builder()
+        .timestamp(0)
+        .sender_address(wallet.address.clone())
+        .sender_public_key(wallet.public_key)
+        .stake(stake)
+        .signature(secret_key.sign_ecdsa(Message::from_hashed_data::<H>(b"vrrb")))
+        .nonce(1)
+        .build_kind()
+        .unwrap();
+    mempool.insert(pending.clone()).unwrap();
+
+    assert!(wallet
+        .bump_fee(pending.id().digest_string(), 50)
+        .await
+        .is_err());
+    assert!(events_rx.try_recv().is_err());
+
+    handle.stop().unwrap();
+}
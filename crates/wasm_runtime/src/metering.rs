@@ -33,6 +33,12 @@ where
             cost_function,
         }
     }
+
+    /// Returns the number of points the metered module starts with
+    pub fn initial_limit(&self) -> u64 {
+        self.initial_limit
+    }
+
     pub(crate) fn into_metering(self) -> Metering<F> {
         Metering::new(self.initial_limit, self.cost_function)
     }
@@ -161,3 +161,65 @@ fn test_failed_execution() {
 
     let _out: TestOutput = serde_json::from_str(&runtime.stdout()).unwrap();
 }
+
+/// This test checks that the metering points used by a WASM module are
+/// reported once it is done executing.
+#[test]
+fn test_gas_used() {
+    let wasm_bytes = std::fs::read("test_data/wasm_test.wasm").unwrap();
+    let json_data = std::fs::read("test_data/wasm_test_oneline.json").unwrap();
+    let target = Target::default();
+    let mut runtime = create_test_wasm_runtime(&target, &wasm_bytes)
+        .unwrap()
+        .stdin(&json_data)
+        .unwrap();
+    assert_eq!(runtime.gas_used(), 0);
+
+    runtime.execute().unwrap();
+
+    assert!(runtime.gas_used() > 0);
+    assert!(runtime.gas_used() < TEST_SPENDING_LIMIT);
+}
+
+/// This test checks that a WASM module running out of metering points fails
+/// to execute and is reported as having used all of them.
+#[test]
+fn test_gas_exhausted() {
+    let wasm_bytes = std::fs::read("test_data/wasm_test.wasm").unwrap();
+    let json_data = std::fs::read("test_data/wasm_test_oneline.json").unwrap();
+    let target = Target::default();
+    let mut runtime =
+        WasmRuntime::new::<Cranelift>(&target, &wasm_bytes, MeteringConfig::new(10, cost_function))
+            .unwrap()
+            .stdin(&json_data)
+            .unwrap();
+
+    assert!(runtime.execute().is_err());
+    assert_eq!(runtime.gas_used(), 10);
+}
+
+/// This test checks that a module compiled once can be run many times, each
+/// run being metered by its own limit.
+#[test]
+fn test_compiled_module_reuse() {
+    let wasm_bytes = std::fs::read("test_data/wasm_test.wasm").unwrap();
+    let json_data = std::fs::read("test_data/wasm_test_oneline.json").unwrap();
+    let target = Target::default();
+    let engine = WasmRuntime::engine::<Cranelift>(
+        &target,
+        MeteringConfig::new(TEST_SPENDING_LIMIT, cost_function),
+    );
+    let module = WasmRuntime::compile(&engine, &wasm_bytes).unwrap();
+
+    let mut runtime = WasmRuntime::from_module(&engine, module.clone(), TEST_SPENDING_LIMIT)
+        .stdin(&json_data)
+        .unwrap();
+    runtime.execute().unwrap();
+    assert!(runtime.gas_used() > 0);
+
+    let mut runtime = WasmRuntime::from_module(&engine, module, 10)
+        .stdin(&json_data)
+        .unwrap();
+    assert!(runtime.execute().is_err());
+    assert_eq!(runtime.gas_used(), 10);
+}
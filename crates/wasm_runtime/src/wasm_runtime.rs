@@ -21,7 +21,7 @@ use wasmer::{
     wasmparser::Operator, BaseTunables, CompilerConfig, Engine, Instance, Module, NativeEngineExt,
     Store, Target,
 };
-use wasmer_middlewares::metering::get_remaining_points;
+use wasmer_middlewares::metering::{get_remaining_points, set_remaining_points, MeteringPoints};
 use wasmer_wasix::{Pipe, WasiEnv};
 
 /// This is the first command line argument, traditionally reserved for the
@@ -36,6 +36,8 @@ pub struct WasmRuntime {
     stderr: String,
     args: Vec<String>,
     env: HashMap<String, String>,
+    gas_limit: u64,
+    gas_used: u64,
 }
 
 impl WasmRuntime {
@@ -48,6 +50,24 @@ impl WasmRuntime {
         wasm_bytes: &[u8],
         metering_config: MeteringConfig<impl Fn(&Operator<'_>) -> u64 + Send + Sync + 'static>,
     ) -> Result<Self>
+    where
+        C: Default + Into<Engine> + CompilerConfig,
+    {
+        let gas_limit = metering_config.initial_limit();
+        let engine = Self::engine::<C>(target, metering_config);
+        let module = Self::compile(&engine, wasm_bytes)?;
+
+        Ok(Self::from_module(&engine, module, gas_limit))
+    }
+
+    /// Creates an engine compiling WASM modules metered by `metering_config`,
+    /// which the modules it compiles can be run by many runtimes with.
+    ///
+    /// C represents the compiler to use, which at the time of writing is Cranelift.
+    pub fn engine<C>(
+        target: &Target,
+        metering_config: MeteringConfig<impl Fn(&Operator<'_>) -> u64 + Send + Sync + 'static>,
+    ) -> Engine
     where
         C: Default + Into<Engine> + CompilerConfig,
     {
@@ -58,15 +78,24 @@ impl WasmRuntime {
         let tunables = LimitingTunables::new(base, DEFAULT_PAGE_LIMIT);
         let mut engine: Engine = compiler.into();
         engine.set_tunables(tunables);
-        // Create an in-memory store for everything required to compile and run a WASM
-        // module
-        let store = Store::new(engine);
+        engine
+    }
 
+    /// Compiles a WASM binary with `engine`
+    pub fn compile(engine: &Engine, wasm_bytes: &[u8]) -> Result<Module> {
         debug!("Compiling {} bytes of WASM", wasm_bytes.len());
 
-        // Compile module into in-memory store
-        let module = Module::new(&store, wasm_bytes)?;
-        Ok(Self {
+        Ok(Module::new(engine, wasm_bytes)?)
+    }
+
+    /// Creates a new WasmRuntime environment to execute a module compiled by
+    /// `engine`, which may use `gas_limit` metering points.
+    pub fn from_module(engine: &Engine, module: Module, gas_limit: u64) -> Self {
+        // Create an in-memory store for everything required to run a WASM
+        // module
+        let store = Store::new(engine.clone());
+
+        Self {
             store,
             module,
             stdin: vec![],
@@ -74,7 +103,9 @@ impl WasmRuntime {
             stderr: String::new(),
             args: vec![],
             env: HashMap::new(),
-        })
+            gas_limit,
+            gas_used: 0,
+        }
     }
 
     /// Adds a set of command line arguments to the WASM module's execution
@@ -107,6 +138,12 @@ impl WasmRuntime {
         self.stderr.clone()
     }
 
+    /// Returns the number of metering points used by the last execution of
+    /// the WASM module. A module that ran out of points used all of them.
+    pub fn gas_used(&self) -> u64 {
+        self.gas_used
+    }
+
     /// Execute the compiled WASM module and retrieve the result.
     pub fn execute(&mut self) -> Result<()> {
         let (mut stdin, in_wasm) = Pipe::channel();
@@ -138,18 +175,23 @@ impl WasmRuntime {
 
         let import_obj = wasi_fn_env.import_object(store, module)?;
         let instance = Instance::new(store, module, &import_obj)?;
+        set_remaining_points(store, &instance, self.gas_limit);
 
         let mem_view = instance.exports.get_memory("memory")?.view(store);
         telemetry::info!("Memory: {:?}", mem_view.size());
 
         wasi_fn_env.initialize(store, instance.clone())?;
         let start = instance.exports.get_function("_start")?;
-        start.call(store, &[])?;
+        let result = start.call(store, &[]);
+
+        let remaining_points = get_remaining_points(store, &instance);
+        telemetry::info!("MeteringPoints::{:?}", remaining_points);
 
-        telemetry::info!(
-            "MeteringPoints::{:?}",
-            get_remaining_points(store, &instance)
-        );
+        self.gas_used = match remaining_points {
+            MeteringPoints::Remaining(remaining) => self.gas_limit.saturating_sub(remaining),
+            MeteringPoints::Exhausted => self.gas_limit,
+        };
+        result?;
 
         Ok(wasi_fn_env.cleanup(store, None))
     }
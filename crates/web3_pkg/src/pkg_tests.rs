@@ -140,3 +140,32 @@ async fn write_test() {
     let cid = store.write_dag(json.into()).await.unwrap();
     eprintln!("DAG write of root (package) returned CID: {}", cid);
 }
+
+/// This test checks that the WASM object of a package is found among its other objects.
+#[test]
+fn wasm_object_test() {
+    let pkg: Web3Package =
+        serde_json::from_slice(&std::fs::read("test_data/pkg.json").unwrap()).unwrap();
+
+    let obj = pkg.wasm_object().unwrap();
+    assert_eq!(obj.object_path, "wasm_test-opt.wasm");
+    assert_eq!(
+        obj.object_cid.cid,
+        "QmYSjeNygNwKP4E2vhZUs888UtdFXdpdZfkq7L4rH9nFgn"
+    );
+
+    assert!(Web3Package::default().wasm_object().is_none());
+}
+
+/// This test checks that the content IDs of raw blocks match the ones IPFS computes for them.
+#[test]
+fn raw_block_content_id_test() {
+    assert_eq!(
+        Web3ContentId::of_raw_block(b"").cid,
+        "bafkreihdwdcefgh4dqkjv67uzcmw7ojee6xedzdetojuzjevtenxquvyku"
+    );
+    assert_eq!(
+        Web3ContentId::of_raw_block(b"hello world").cid,
+        "bafkreifzjut3te2nhyekklss27nh3k72ysco7y32koao5eei66wof36n5e"
+    );
+}
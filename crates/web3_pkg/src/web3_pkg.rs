@@ -1,6 +1,12 @@
 use clap::clap_derive::ArgEnum;
+use data_encoding::BASE32_NOPAD;
 use derive_builder::Builder;
 use serde_derive::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Prefix of the CIDv1 of a raw block hashed with SHA2-256: the CID version, the raw multicodec,
+/// the sha2-256 multihash code and the length of the digest.
+const RAW_SHA256_CID_PREFIX: [u8; 4] = [0x01, 0x55, 0x12, 0x20];
 
 /// An enum representing different flavours of package payload. In some cases, a package might
 /// contain a smart contract (or potentially multiple smart contracts), in other cases it could be
@@ -47,6 +53,19 @@ pub struct Web3ContentId {
     pub cid: String,
 }
 
+impl Web3ContentId {
+    /// Computes the content ID of an object stored as a single raw block, which is the base32
+    /// CIDv1 IPFS gives it when it is added with raw leaves.
+    pub fn of_raw_block(bytes: &[u8]) -> Self {
+        let mut cid = RAW_SHA256_CID_PREFIX.to_vec();
+        cid.extend_from_slice(&Sha256::digest(bytes));
+
+        Self {
+            cid: format!("b{}", BASE32_NOPAD.encode(&cid).to_lowercase()),
+        }
+    }
+}
+
 /// An enum representing the type of object within the package. This is only as accurate as the
 /// package publisher makes it.
 #[derive(Debug, Default, Serialize, Deserialize, Clone, ArgEnum)]
@@ -103,3 +122,13 @@ pub struct Web3Package {
     /// DAG when there's a long history.
     pub pkg_replaces: Vec<Web3ContentId>,
 }
+
+impl Web3Package {
+    /// Returns the first object of the package targetted to the wasm32-wasi architecture, if
+    /// any. This is the object the compute stack runs for smart contract packages.
+    pub fn wasm_object(&self) -> Option<&Web3PackageObject> {
+        self.pkg_objects
+            .iter()
+            .find(|object| matches!(object.object_arch, Web3PackageArchitecture::Wasm32Wasi))
+    }
+}